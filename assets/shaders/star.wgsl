@@ -0,0 +1,130 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) points: f32,
+    @location(8) radius: f32,
+    @location(9) inner_ratio: f32,
+};
+
+// PER_OBJECT_BUFFER_BATCH_SIZE is defined by the pipeline on platforms without storage buffers
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) thickness: f32,
+    @location(3) half_angle: f32,
+    @location(4) inner_ratio: f32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    let matrix = mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    );
+
+    // The shared vertex data handles alignment, thickness types and anti-aliasing padding
+    var vertex_data = core::get_vertex_data(matrix, vertex.xy * shape.radius, shape.thickness, shape.flags);
+
+    out.clip_position = vertex_data.clip_pos;
+    out.uv = vertex.xy * vertex_data.uv_ratio;
+    out.thickness = core::calculate_thickness(vertex_data.thickness_data, shape.radius, shape.flags);
+
+    // Half of the angle between two adjacent points
+    out.half_angle = PI / max(shape.points, 2.0);
+    out.inner_ratio = shape.inner_ratio;
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex.xy);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) thickness: f32,
+    @location(3) half_angle: f32,
+    @location(4) inner_ratio: f32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+};
+
+// Signed distance to a star with a point facing up, negative within the star
+fn star_sdf(position: vec2<f32>, half_angle: f32, inner_ratio: f32) -> f32 {
+    // Fold our point into the half of a single point between an inner corner and the tip
+    var angle = atan2(position.x, position.y) + half_angle;
+    angle = angle - 2.0 * half_angle * floor(angle / (2.0 * half_angle));
+    var folded = abs(angle - half_angle);
+    var pos = vec2<f32>(cos(folded), sin(folded)) * length(position);
+
+    // The edge runs from the tip along the x axis to the inner corner
+    var tip = vec2<f32>(1.0, 0.0);
+    var inner = vec2<f32>(cos(half_angle), sin(half_angle)) * inner_ratio;
+    var edge = inner - tip;
+    var nearest = tip + edge * saturate(dot(pos - tip, edge) / dot(edge, edge));
+
+    // Points on the same side of the edge as the center are inside
+    var side = edge.x * (pos.y - tip.y) - edge.y * (pos.x - tip.x);
+    return length(pos - nearest) * -sign(side);
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Cut off points outside the shape or within the hollow area
+    var dist = star_sdf(f.uv, f.half_angle, f.inner_ratio);
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
@@ -0,0 +1,291 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape { 
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) start: vec3<f32>,
+    @location(8) end: vec3<f32>,
+    @location(9) dash: vec2<f32>,
+    @location(10) dash_pattern: u32,
+};
+
+// Bound by the Wind material extension in examples/material_extension.rs
+struct Wind {
+    direction: vec2<f32>,
+    strength: f32,
+    time: f32,
+};
+
+#ifdef SHAPE_EXTENSION
+@group(#{SHAPE_EXTENSION_GROUP}) @binding(0) var<uniform> wind: Wind;
+#endif
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif 
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) caps: vec2<u32>,
+    @location(3) dash: vec4<f32>,
+    @location(4) dash_pattern: u32,
+    @location(5) line: vec2<f32>,
+#ifdef TEXTURED
+    @location(6) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    );
+
+    // Vector from start -> end
+    var line_vec = shape.end - shape.start;
+
+    // Center of line in world space
+    var center = line_vec / 2.0;
+
+    // Line length in local space
+    var line_length = length(line_vec);
+
+    // Get our start and end in world space
+    var world_start = (matrix * vec4<f32>(shape.start, 1.0)).xyz;
+    var world_end = (matrix * vec4<f32>(shape.end, 1.0)).xyz;
+
+#ifdef SHAPE_EXTENSION
+    // Sway the end of the line in the wind while the start stays rooted, offset by position so lines move out of step
+    var gust = sin(wind.time * 2.0 + world_start.x * 0.02) * 0.5 + 0.5;
+    world_end += vec3<f32>(wind.direction * wind.strength * gust, 0.0);
+    line_length = length(world_end - world_start) / length(matrix[1].xyz);
+#endif
+
+    // The y basis is the normalized vector along the line
+    var y_basis = normalize(world_start - world_end);
+
+    // Choose which point we will work in reference to based on our y position
+    var origin = select(world_end, world_start, vertex.y < 0.0);
+
+    // Calculate the remainder of our basis vectors
+    var alignment = core::f_alignment(shape.flags) << 1u;
+    var basis_vectors = core::get_basis_vectors_from_up(matrix, origin, y_basis, alignment);
+
+    // Calculate thickness data
+    var thickness_type = core::f_thickness_type(shape.flags);
+    var thickness_data = core::get_thickness_data(shape.thickness, thickness_type, origin, basis_vectors[1]);
+
+    let scale = vec3<f32>(length(matrix[0].xyz), length(matrix[1].xyz), length(matrix[2].xyz));
+
+    // If our thickness in pixels is less than 1, clamp to 1 and reduce the alpha instead
+    var out_color = shape.color;
+    if thickness_data.thickness_p * max(scale.x, scale.y) < 1.0 {
+        out_color.a = out_color.a * thickness_data.thickness_p * max(scale.x, scale.y);
+        thickness_data.thickness_p = 1.;
+    }
+
+    // Calculate thickness and radius in world units
+    var thickness = thickness_data.thickness_p / thickness_data.pixels_per_u;
+    var radius = thickness / 2.0;
+
+    // Each end of the line may have a different cap type
+    out.caps = vec2<u32>(core::f_cap(shape.flags), core::f_end_cap(shape.flags));
+
+    // If we have square or round caps increase the cap length at that end to our radius, arrows end at the end point
+    var start_cap_length = select(0.0, radius, out.caps.x == 1u || out.caps.x == 2u);
+    var end_cap_length = select(0.0, radius, out.caps.y == 1u || out.caps.y == 2u);
+    var cap_length = select(end_cap_length, start_cap_length, vertex.y < 0.0);
+
+    // Widen the quad to fit arrowheads
+    var half_width = radius * select(1.0, ARROW_WIDTH, out.caps.x == 3u || out.caps.y == 3u);
+
+    // Calculate the vertex position with scaling
+    var local_pos = vertex.xy * vec2<f32>(half_width, cap_length + line_length / 2.0) * scale.xy;
+
+    // Scale our padding to world space and match direction of our vertex
+    var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
+    var aa_padding = sign(vertex.xy) * aa_padding_u;
+
+    // Pad our position and determine the ratio by which to scale uv such that uvs ignore the padding
+    var padded_pos = local_pos + aa_padding;
+    var uv_ratio = padded_pos / local_pos;
+
+    // Calculate the offset from our origin point
+    var local_offset = vertex.xy * (vec2<f32>(half_width, cap_length) * scale.xy + aa_padding_u);
+
+    // Determine final world position by offsetting by the origin we chose and rotating by our basis vectors
+    var world_pos = origin + local_offset.x * basis_vectors[0] + local_offset.y * basis_vectors[1];
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+    out.uv = vertex.xy * uv_ratio;
+
+    // Keep our x uv in terms of our radius
+    out.uv.x *= half_width / radius;
+
+    // Store our distance along the line from the start, dash and gap lengths and cap length for dashing
+    // The quad is offset from the nearest end so the distance is measured from that end, including padding
+    var beyond_end = cap_length + aa_padding_u / scale.y;
+    var dash_pos = select(line_length + beyond_end, -beyond_end, vertex.y < 0.0);
+    out.dash = vec4<f32>(dash_pos, shape.dash, start_cap_length);
+    out.dash_pattern = shape.dash_pattern;
+    out.line = vec2<f32>(line_length, radius);
+
+    out.color = out_color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex.xy);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) caps: vec2<u32>,
+    @location(3) dash: vec4<f32>,
+    @location(4) dash_pattern: u32,
+    @location(5) line: vec2<f32>,
+#ifdef TEXTURED
+    @location(6) texture_uv: vec2<f32>,
+#endif
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    var in_shape = f.color.a;
+
+    // Distance beyond the nearest end of the line in radii, negative within the body of the line
+    var along = f.dash.x;
+    var beyond = max(-along, along - f.line.x) / f.line.y;
+    var cap = select(f.caps.y, f.caps.x, along < f.line.x / 2.0);
+
+    // Our x value already represents the distance to our line in radii
+    var x = abs(f.uv.x);
+
+    // Mask our caps, arrowheads are masked along with the body of the line up to their base
+    if cap == 3u {
+        in_shape = min(in_shape, arrow_mask(x, beyond));
+    } else if cap == 2u {
+        // We now have the shortest vector from our point to the line so take the distance
+        var dist = length(vec2<f32>(x, max(0., beyond)));
+
+        // Mask out corners
+        in_shape = min(in_shape, core::step_aa(dist, 1.));
+    } else {
+        // Simple rectangle sdf for no caps or square caps, square caps extend the line by it's radius
+        var cap_length = select(0.0, 1.0, cap == 1u);
+        in_shape = min(in_shape, core::step_aa(x, 1.) * core::step_aa(beyond, cap_length));
+    }
+
+    // Mask out the gaps between dashes, leaving arrowheads whole
+    var in_arrow = cap == 3u && beyond > -ARROW_LENGTH;
+    if (f.dash_pattern > 0u || f.dash.y > 0.0) && !in_arrow {
+        in_shape = min(in_shape, dash_mask(f));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+    
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+
+// Length and half width of arrowheads in radii
+const ARROW_LENGTH: f32 = 6.0;
+const ARROW_WIDTH: f32 = 3.0;
+
+// Mask of an arrowhead with it's tip at the end of the line, joined to the body of the line at it's base
+fn arrow_mask(x: f32, beyond: f32) -> f32 {
+    var body = core::step_aa(x, 1.) * core::step_aa(beyond, -ARROW_LENGTH);
+
+    // Signed distance from the sloped side of the arrowhead, negative within it
+    var side = (x * ARROW_LENGTH + beyond * ARROW_WIDTH) / length(vec2<f32>(ARROW_LENGTH, ARROW_WIDTH));
+    var head = core::step_aa(-ARROW_LENGTH, beyond) * core::step_aa(side, 0.);
+
+    return max(body, head);
+}
+
+// Each dash is treated as a short line with the same caps as the start of the line
+fn dash_mask(f: FragmentInput) -> f32 {
+    var cap_length = f.dash.w;
+
+    // Signed distance along the line from the body of the nearest dash, negative when within it
+    var along: f32;
+    if f.dash_pattern > 0u {
+        along = dash_pattern_distance(f.dash.x, f.dash_pattern);
+    } else {
+        var dash_length = f.dash.y;
+        var period = dash_length + f.dash.z;
+        var offset = f.dash.x - dash_length / 2.0;
+        along = abs(offset - round(offset / period) * period) - dash_length / 2.0;
+    }
+
+    // Round caps have a length equal to our radius
+    if f.caps.x == 2u {
+        var dist = length(vec2<f32>(f.uv.x * cap_length, max(0., along))) / cap_length;
+        return core::step_aa(dist, 1.);
+    }
+
+    return core::step_aa(along, cap_length);
+}
+
+// Signed distance from the nearest dash of a pattern of alternating dash and gap lengths
+fn dash_pattern_distance(pos: f32, pattern: u32) -> f32 {
+    var offset = pattern >> 16u;
+    var len = pattern & 0xFFFFu;
+
+    var period = 0.0;
+    for (var i = 0u; i < len; i++) {
+        period += core::dash_pattern_value(offset + i);
+    }
+    var p = pos - floor(pos / period) * period;
+
+    // Find the segment containing our position, even segments are dashes and odd are gaps
+    var start = 0.0;
+    for (var i = 0u; i < len; i++) {
+        var end = start + core::dash_pattern_value(offset + i);
+        if p <= end || i == len - 1u {
+            var dist = min(p - start, end - p);
+            return select(dist, -dist, i % 2u == 0u);
+        }
+        start = end;
+    }
+    return 0.0;
+}
+#endif
\ No newline at end of file
@@ -38,12 +38,14 @@ fn draw_circles(painter: &mut ShapePainter, radius: f32) {
     painter.circle(radius);
 
     painter.rotate_z(-TAU / 3.0);
-    painter.translate(Vec3::Y * radius * 1.2 + Vec3::Z * 0.0001);
+    painter.translate(Vec3::Y * radius * 1.2);
+    painter.sort_bias = 1.0;
     painter.color = Color::srgba(0.0, 1.0, 0.0, 0.5);
     painter.circle(radius);
 
     painter.rotate_z(-TAU / 3.0);
-    painter.translate(Vec3::Y * radius * 1.2 + Vec3::Z * 0.0001);
+    painter.translate(Vec3::Y * radius * 1.2);
+    painter.sort_bias = 2.0;
     painter.color = Color::srgba(0.0, 0.0, 1.0, 0.5);
     painter.circle(radius);
 }
@@ -0,0 +1,52 @@
+// Demonstrates hard edged shapes that write depth while keeping multisampled edges with alpha to coverage
+// The intersecting cards sort as a whole when blended, press space to switch them to alpha to coverage
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .insert_resource(Mode(ShapeAlphaMode::Blend))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_mode, draw))
+        .run();
+}
+
+#[derive(Resource)]
+struct Mode(ShapeAlphaMode);
+
+fn setup(mut commands: Commands) {
+    // Alpha to coverage requires MSAA, without it shapes are blended
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Sample4,
+    ));
+}
+
+fn toggle_mode(keys: Res<ButtonInput<KeyCode>>, mut mode: ResMut<Mode>) {
+    if keys.just_pressed(KeyCode::Space) {
+        mode.0 = match mode.0 {
+            ShapeAlphaMode::Blend => ShapeAlphaMode::AlphaToCoverage,
+            _ => ShapeAlphaMode::Blend,
+        };
+        info!("Alpha mode: {:?}", mode.0);
+    }
+}
+
+fn draw(time: Res<Time>, mode: Res<Mode>, mut painter: ShapePainter) {
+    painter.alpha_mode = mode.0;
+    painter.corner_radii = Vec4::splat(0.3);
+    painter.rotate_y(time.elapsed_secs() * 0.5);
+
+    // Cards passing through one another at different angles
+    for (i, color) in [CRIMSON, GOLD, TEAL].into_iter().enumerate() {
+        painter.rotate_y(std::f32::consts::TAU / 3.0);
+        painter.rotate_x(0.3 * i as f32);
+        painter.set_color(color);
+        painter.rect(Vec2::new(3.0, 2.0));
+    }
+}
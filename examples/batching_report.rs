@@ -0,0 +1,53 @@
+// Demonstrates logging why shapes were split into separate batches, run with `--features stats`
+// Press space to toggle between alternating the alpha mode of every circle and drawing each alpha mode together
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::{prelude::*, render::ShapeBatchReportPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_plugins(ShapeBatchReportPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .insert_resource(Grouped(false))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_grouping, draw))
+        .run();
+}
+
+#[derive(Resource)]
+struct Grouped(bool);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn toggle_grouping(keys: Res<ButtonInput<KeyCode>>, mut grouped: ResMut<Grouped>) {
+    if keys.just_pressed(KeyCode::Space) {
+        grouped.0 = !grouped.0;
+        info!("Grouped by alpha mode: {}", grouped.0);
+    }
+}
+
+fn draw(grouped: Res<Grouped>, mut painter: ShapePainter) {
+    for i in 0..200 {
+        let additive = i % 2 == 0;
+        // Shapes are sorted by z in 2D, so offsetting one alpha mode in z draws each alpha mode together
+        let z = if grouped.0 && additive { 1.0 } else { 0.0 };
+        let position = Vec2::new(
+            (i % 20) as f32 * 40.0 - 380.0,
+            (i / 20) as f32 * 40.0 - 180.0,
+        );
+
+        painter.set_translation(position.extend(z + i as f32 * 0.001));
+        if additive {
+            painter.alpha_mode = ShapeAlphaMode::Add;
+            painter.set_color(ORANGE.with_alpha(0.5));
+        } else {
+            painter.alpha_mode = ShapeAlphaMode::Blend;
+            painter.set_color(SKY_BLUE);
+        }
+        painter.circle(16.0);
+    }
+}
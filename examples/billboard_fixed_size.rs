@@ -0,0 +1,56 @@
+// Demonstrates markers that face the camera and keep the same size in pixels however far away they are
+// The flat rings on the ground shrink into the distance while the markers above them do not
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera3d::default(), Msaa::Off));
+}
+
+fn draw(
+    time: Res<Time>,
+    mut painter: ShapePainter,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+) {
+    // Dolly the camera along the row of markers
+    let distance = 12.0 + 8.0 * time.elapsed_secs().sin();
+    for mut tf in &mut cameras {
+        *tf =
+            Transform::from_xyz(6.0, 4.0, distance).looking_at(Vec3::new(0.0, 0.0, -20.0), Vec3::Y);
+    }
+
+    for i in 0..6 {
+        let position = Vec3::new(0.0, 0.0, -8.0 * i as f32);
+
+        // Rings on the ground are sized in world units
+        painter.reset();
+        painter.set_translation(position);
+        painter.rotate_x(-std::f32::consts::FRAC_PI_2);
+        painter.hollow = true;
+        painter.thickness = 0.1;
+        painter.set_color(SILVER);
+        painter.circle(1.0);
+
+        // Markers above them are sized in pixels
+        painter.reset();
+        painter.alignment = Alignment::BillboardFixedSize;
+        painter.set_translation(position + Vec3::Y * 2.0);
+        painter.corner_radii = Vec4::splat(6.0);
+        painter.set_color(MIDNIGHT_BLUE);
+        painter.rect(Vec2::new(48.0, 32.0));
+        painter.set_color(GOLD);
+        painter.circle(8.0);
+        painter.line(Vec3::new(0.0, -16.0, 0.0), Vec3::new(0.0, -40.0, 0.0));
+    }
+}
@@ -0,0 +1,64 @@
+// Demonstrates health bars that turn to face the camera around their y axis but stay upright as the camera looks down
+// The bars on the left billboard fully and tilt with the camera, the bars on the right only rotate around the y axis
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera3d::default(), Msaa::Off));
+}
+
+fn draw(
+    time: Res<Time>,
+    mut painter: ShapePainter,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+) {
+    // Orbit the camera while it rises to look down on the characters
+    let t = time.elapsed_secs();
+    let height = 2.0 + 8.0 * (t * 0.5).sin().abs();
+    for mut tf in &mut cameras {
+        *tf = Transform::from_translation(
+            Quat::from_rotation_y(t * 0.3) * Vec3::new(0.0, height, 12.0),
+        )
+        .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+
+    for (i, alignment) in [Alignment::Billboard, Alignment::BillboardY]
+        .into_iter()
+        .enumerate()
+    {
+        for j in 0..3 {
+            let position = Vec3::new(i as f32 * 6.0 - 3.0, 0.0, j as f32 * 3.0 - 3.0);
+            let hp = ((t + j as f32).sin() + 1.0) / 2.0;
+
+            // Characters stand on the ground
+            painter.reset();
+            painter.set_translation(position);
+            painter.set_color(GRAY);
+            painter.thickness = 0.6;
+            painter.cap = Cap::Round;
+            painter.line(Vec3::ZERO, Vec3::Y * 1.5);
+
+            // Bars above them billboard with the given alignment
+            painter.alignment = alignment;
+            painter.set_translation(position + Vec3::Y * 2.0);
+            painter.corner_radii = Vec4::splat(0.1);
+            painter.set_color(GREEN * hp + RED * (1.0 - hp));
+            painter.rect(Vec2::new(0.2 + 1.3 * hp, 0.25));
+            painter.hollow = true;
+            painter.thickness = 0.03;
+            painter.set_color(WHITE);
+            painter.rect(Vec2::new(1.6, 0.35));
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Demonstrates combining primitives into a single shape with boolean operations
+
+use std::f32::consts::PI;
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    let seconds = time.elapsed_secs();
+    let start = painter.transform;
+
+    // A frame with a circular cutout, e.g. for a minimap or portrait
+    painter.translate(Vec3::new(-300.0, 120.0, 0.0));
+    painter.set_color(SEA_GREEN);
+    painter.boolean(
+        BooleanOp::Subtraction,
+        BooleanPrimitive::Rect {
+            size: Vec2::splat(160.0),
+            roundness: 16.0,
+        },
+        BooleanPrimitive::circle(60.0),
+    );
+
+    // Two circles blended smoothly together as they move apart
+    painter.transform = start;
+    painter.translate(Vec3::new(0.0, 120.0, 0.0));
+    painter.set_color(CORNFLOWER_BLUE);
+    let offset = Vec2::X * (seconds.sin() * 50.0 + 50.0);
+    painter.smooth_boolean(
+        BooleanOp::Union,
+        BooleanPrimitive::circle(50.0).at(-offset),
+        BooleanPrimitive::circle(50.0).at(offset),
+        40.0,
+    );
+
+    // The overlap between a rotating hexagon and a square
+    painter.transform = start;
+    painter.translate(Vec3::new(300.0, 120.0, 0.0));
+    painter.set_color(GOLD);
+    painter.boolean(
+        BooleanOp::Intersection,
+        BooleanPrimitive::ngon(6.0, 90.0)
+            .at(Vec2::ZERO)
+            .rotated(seconds),
+        BooleanPrimitive::rect(Vec2::splat(140.0)),
+    );
+
+    // Outlines follow the edge of the combined shape
+    painter.transform = start;
+    painter.translate(Vec3::new(0.0, -150.0, 0.0));
+    painter.hollow = true;
+    painter.thickness = 6.0;
+    painter.set_color(TOMATO);
+    painter.boolean(
+        BooleanOp::Subtraction,
+        BooleanPrimitive::circle(90.0),
+        BooleanPrimitive::rect(Vec2::new(200.0, 40.0))
+            .at(Vec2::ZERO)
+            .rotated(PI / 4.0),
+    );
+}
@@ -0,0 +1,75 @@
+// Demonstrates measuring the bounds of shapes before drawing them, to size a frame around them and hit-test the cursor
+
+use bevy::{color::palettes::css::*, math::bounding::Aabb3d, prelude::*, window::PrimaryWindow};
+use bevy_vector_shapes::{prelude::*, render::ShapeData};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(
+    time: Res<Time>,
+    mut painter: ShapePainter,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+) {
+    let (camera, camera_tf) = camera.single();
+    let cursor = window
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tf, cursor).ok());
+
+    let seconds = time.elapsed_secs();
+    let rotation = Quat::from_rotation_z(seconds * 0.5);
+    painter.thickness = 12.0;
+
+    // Each shape is measured with the config it is about to be drawn with
+    painter.set_rotation(rotation);
+    let size = Vec2::new(200.0, 100.0);
+    let bounds = RectData::new(painter.config(), size).world_bounds();
+    highlight(&mut painter, bounds, cursor);
+    painter.rect(size);
+
+    painter.set_translation(Vec3::X * -250.0);
+    let bounds = DiscData::circle(painter.config(), 80.0).world_bounds();
+    highlight(&mut painter, bounds, cursor);
+    painter.circle(80.0);
+
+    painter.set_translation(Vec3::X * 250.0);
+    let start = Vec3::new(-60.0, -60.0, 0.0);
+    let end = Vec3::new(60.0, 60.0 * seconds.sin(), 0.0);
+    let bounds = LineData::new(painter.config(), start, end).world_bounds();
+    highlight(&mut painter, bounds, cursor);
+    painter.line(start, end);
+}
+
+// Frame the bounds with a hollow rect, and color the shape drawn next by whether the cursor is within them
+fn highlight(painter: &mut ShapePainter, bounds: Option<Aabb3d>, cursor: Option<Vec2>) {
+    let Some(bounds) = bounds else {
+        return;
+    };
+    let min = Vec3::from(bounds.min).truncate();
+    let max = Vec3::from(bounds.max).truncate();
+
+    painter.with_children(|painter| {
+        painter.set_translation(((min + max) / 2.0).extend(0.0));
+        painter.set_rotation(Quat::IDENTITY);
+        painter.hollow = true;
+        painter.thickness = 1.0;
+        painter.set_color(WHITE);
+        painter.rect(max - min);
+    });
+
+    let hovered = cursor.is_some_and(|cursor| cursor.cmpge(min).all() && cursor.cmple(max).all());
+    painter.set_color(if hovered { GOLD } else { CORNFLOWER_BLUE });
+}
@@ -0,0 +1,55 @@
+// Demonstrates retained shapes targeting a canvas by label, spawned before the canvas exists as they would be by a scene
+// The shapes appear once the canvas labeled "badge" is spawned a second later, press space to respawn the canvas
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (spawn_canvas, draw_canvas))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn(Camera2d);
+
+    // Shapes reference the canvas symbolically rather than by entity
+    shapes.set_canvas_label("badge");
+    shapes.set_color(MIDNIGHT_BLUE);
+    shapes.circle(240.0);
+    shapes.hollow = true;
+    shapes.thickness = 24.0;
+    shapes.set_color(GOLD);
+    shapes.circle(200.0);
+    shapes.hollow = false;
+    shapes.ngon(5.0, 100.0);
+}
+
+fn spawn_canvas(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    canvases: Query<Entity, With<CanvasLabel>>,
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        for canvas in &canvases {
+            commands.entity(canvas).despawn_recursive();
+        }
+    } else if !canvases.is_empty() || time.elapsed_secs() < 1.0 {
+        return;
+    }
+
+    let (_, mut canvas) = commands.spawn_canvas(images.as_mut(), CanvasConfig::new(512, 512));
+    canvas.insert(CanvasLabel::new("badge"));
+}
+
+fn draw_canvas(mut painter: ShapePainter, canvases: Query<&Canvas, With<CanvasLabel>>) {
+    for canvas in &canvases {
+        painter.image(canvas.image.clone(), Vec2::splat(400.0));
+    }
+}
@@ -0,0 +1,57 @@
+// Demonstrates displaying a canvas on a 3D mesh with a material created from the canvas
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+mod gallery_3d;
+use gallery_3d::gallery;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_shapes, rotate_panel))
+        .run();
+}
+
+#[derive(Component)]
+struct Panel;
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // A transparent canvas, only the shapes drawn to it are visible on the panel
+    let mut config = CanvasConfig::new(1024, 1024);
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    let (image, _) = commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn((
+        Mesh3d(meshes.add(Rectangle::new(10.0, 10.0))),
+        MeshMaterial3d(materials.add(StandardMaterial::from_canvas(image))),
+        Panel,
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0., 0., 16.).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+    ));
+}
+
+fn draw_shapes(time: Res<Time>, mut painter: ShapePainter, canvas: Query<Entity, With<Canvas>>) {
+    painter.set_canvas(canvas.single());
+    painter.set_scale(Vec3::ONE * 48.0);
+
+    gallery(painter, time.elapsed_secs(), 0..15);
+}
+
+fn rotate_panel(time: Res<Time>, mut panel: Query<&mut Transform, With<Panel>>) {
+    for mut tf in &mut panel {
+        tf.rotation = Quat::from_rotation_y(time.elapsed_secs().sin() * 0.6);
+    }
+}
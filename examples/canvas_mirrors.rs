@@ -0,0 +1,51 @@
+// Demonstrates drawing a canvas at full size alongside thumbnails from it's mirrors without redrawing the shapes
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+mod gallery_3d;
+use gallery_3d::gallery;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw_shapes)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let mut config = CanvasConfig::new(1024, 1024);
+    // Maintain half and quarter size copies of the canvas
+    config.mirror_levels = 2;
+    commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0., 0., 16.).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+    ));
+}
+
+fn draw_shapes(time: Res<Time>, mut painter: ShapePainter, canvas: Query<(Entity, &Canvas)>) {
+    let (canvas_e, canvas) = canvas.single();
+    painter.translate(Vec3::X * -3.0);
+    painter.image(canvas.image.clone(), Vec2::splat(12.));
+
+    // Each mirror is drawn at the size it was downscaled to relative to the canvas
+    let mut offset = Vec3::new(9.0, 3.0, 0.0);
+    for (level, mirror) in canvas.mirrors().iter().enumerate() {
+        let size = 12.0 / 2.0f32.powi(level as i32 + 1);
+        painter.set_translation(offset);
+        painter.image(mirror.clone(), Vec2::splat(size));
+        offset.y -= size / 2.0 + 0.5 + size / 4.0;
+    }
+
+    painter.reset();
+    painter.set_canvas(canvas_e);
+    painter.set_scale(Vec3::ONE * 48.0);
+
+    gallery(painter, time.elapsed_secs(), 0..15);
+}
@@ -0,0 +1,104 @@
+// Demonstrates painting onto a persistent canvas with the erase alpha mode used to remove strokes
+// The canvas is cleared to a grid drawn as it's background
+// Hold the left mouse button to paint, the right mouse button to erase and press C to clear the canvas
+
+use bevy::{color::palettes::css::*, prelude::*, window::PrimaryWindow};
+use bevy_vector_shapes::prelude::*;
+
+const CANVAS_SIZE: f32 = 1024.0;
+const DISPLAY_SIZE: f32 = 600.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (paint, draw_canvas))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut compounds: ResMut<ShapeCompounds>,
+) {
+    // A grid drawn beneath the strokes each time the canvas is cleared
+    compounds.insert(
+        "paper",
+        CompoundShape::new().part(Transform::IDENTITY, |painter| {
+            painter.set_color(WHITE.with_alpha(0.2));
+            painter.thickness = 2.0;
+            painter.grid(UVec2::splat(16), Vec2::splat(CANVAS_SIZE / 16.0));
+        }),
+    );
+
+    let mut config = CanvasConfig::new(CANVAS_SIZE as u32, CANVAS_SIZE as u32);
+    // Keep previous strokes until the canvas is explicitly redrawn
+    config.mode = CanvasMode::Persistent;
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    config.background = Some("paper".to_string());
+    commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn(Camera2d);
+}
+
+fn draw_canvas(mut painter: ShapePainter, canvas: Query<&Canvas>) {
+    let canvas = canvas.single();
+
+    // Backdrop behind the canvas so that erased areas are visible
+    painter.set_color(GRAY);
+    painter.rect(Vec2::splat(DISPLAY_SIZE));
+    painter.image(canvas.image.clone(), Vec2::splat(DISPLAY_SIZE));
+}
+
+fn paint(
+    mut painter: ShapePainter,
+    mut canvas: Query<(Entity, &mut Canvas)>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), Without<Canvas>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut last_position: Local<Option<Vec2>>,
+) {
+    let (canvas_e, mut canvas) = canvas.single_mut();
+    if keys.just_pressed(KeyCode::KeyC) {
+        canvas.redraw();
+    }
+
+    let (camera, camera_tf) = camera.single();
+    let cursor = window
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tf, cursor).ok());
+
+    let erase = buttons.pressed(MouseButton::Right);
+    let (Some(cursor), true) = (cursor, erase || buttons.pressed(MouseButton::Left)) else {
+        *last_position = None;
+        return;
+    };
+
+    // The canvas camera maps one unit to one pixel of the canvas
+    let position = cursor * CANVAS_SIZE / DISPLAY_SIZE;
+    let start = last_position.unwrap_or(position);
+    *last_position = Some(position);
+
+    painter.set_canvas(canvas_e);
+    painter.cap = Cap::Round;
+    if erase {
+        painter.alpha_mode = ShapeAlphaMode::Erase;
+        painter.thickness = 48.0;
+    } else {
+        painter.set_color(ORANGE_RED);
+        painter.thickness = 16.0;
+    }
+
+    if start == position {
+        // Lines need a direction so dab a circle until the cursor moves
+        let radius = painter.thickness / 2.0;
+        painter.translate(position.extend(0.0));
+        painter.circle(radius);
+    } else {
+        painter.line(start.extend(0.0), position.extend(0.0));
+    }
+}
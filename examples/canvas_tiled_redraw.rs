@@ -0,0 +1,64 @@
+// Demonstrates splitting the redraw of a large persistent canvas across frames, one tile at a time
+// Press Space to redraw the map and watch the progress bar fill as each tile is drawn
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+const MAP_SIZE: u32 = 4096;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_map, draw_view, redraw))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let mut config = CanvasConfig::new(MAP_SIZE, MAP_SIZE);
+    config.mode = CanvasMode::Persistent;
+    config.clear_color = ClearColorConfig::Custom(DARK_SLATE_GRAY.into());
+    config.redraw_tiles = UVec2::splat(8);
+    commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn(Camera2d);
+}
+
+fn redraw(keys: Res<ButtonInput<KeyCode>>, mut canvas: Query<&mut Canvas>) {
+    if keys.just_pressed(KeyCode::Space) {
+        canvas.single_mut().redraw();
+    }
+}
+
+// A stand in for an expensive map, only the current tile is rasterized while a redraw is in progress
+fn draw_map(time: Res<Time>, mut painter: ShapePainter, canvas: Query<Entity, With<Canvas>>) {
+    painter.set_canvas(canvas.single());
+    let half = MAP_SIZE as f32 / 2.0;
+    let cells = 64;
+    let step = MAP_SIZE as f32 / cells as f32;
+    let hue = time.elapsed_secs() * 20.0;
+    for x in 0..cells {
+        for y in 0..cells {
+            let position = Vec2::new(x as f32, y as f32) * step - half + step / 2.0;
+            painter.set_translation(position.extend(0.0));
+            painter.set_color(Color::hsl((hue + (x + y) as f32 * 4.0) % 360.0, 0.6, 0.5));
+            painter.circle(step * 0.4);
+        }
+    }
+}
+
+fn draw_view(mut painter: ShapePainter, canvas: Query<&Canvas>) {
+    let canvas = canvas.single();
+    painter.image(canvas.image.clone(), Vec2::splat(512.0));
+
+    if let Some(progress) = canvas.redraw_progress() {
+        painter.set_translation(Vec3::Y * -290.0);
+        painter.set_color(BLACK);
+        painter.rect(Vec2::new(512.0, 16.0));
+        painter.set_translation(Vec3::new(-256.0 + 256.0 * progress, -290.0, 0.0));
+        painter.set_color(LIME);
+        painter.rect(Vec2::new(512.0 * progress, 16.0));
+    }
+}
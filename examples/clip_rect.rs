@@ -0,0 +1,59 @@
+// Demonstrates clipping shapes to the bounds of a panel with a scrolling list of items
+// Scroll with the mouse wheel, items outside of the panel are clipped to it's edges
+
+use bevy::{color::palettes::css::*, input::mouse::MouseWheel, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+const ITEMS: usize = 20;
+const ITEM_HEIGHT: f32 = 60.0;
+const PANEL_SIZE: Vec2 = Vec2::new(300.0, 400.0);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .init_resource::<Scroll>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (scroll, draw_panel).chain())
+        .run();
+}
+
+#[derive(Resource, Default)]
+struct Scroll(f32);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn scroll(mut wheel: EventReader<MouseWheel>, mut scroll: ResMut<Scroll>) {
+    let max = ITEMS as f32 * ITEM_HEIGHT - PANEL_SIZE.y;
+    for event in wheel.read() {
+        scroll.0 = (scroll.0 - event.y * 20.0).clamp(0.0, max);
+    }
+}
+
+fn draw_panel(mut painter: ShapePainter, scroll: Res<Scroll>) {
+    // Panel background and border
+    painter.set_color(BLACK.with_alpha(0.6));
+    painter.corner_radii = Vec4::splat(10.0);
+    painter.rect(PANEL_SIZE);
+    painter.hollow = true;
+    painter.thickness = 2.0;
+    painter.set_color(WHITE);
+    painter.rect(PANEL_SIZE + 2.0);
+    painter.hollow = false;
+
+    // Items are clipped to the inside of the panel
+    painter.clip_rect = Some(Rect::from_center_size(Vec2::ZERO, PANEL_SIZE));
+    for i in 0..ITEMS {
+        let y = PANEL_SIZE.y / 2.0 - (i as f32 + 0.5) * ITEM_HEIGHT + scroll.0;
+        painter.set_translation(Vec3::Y * y);
+        painter.set_color(if i % 2 == 0 { CORNFLOWER_BLUE } else { TOMATO });
+        painter.corner_radii = Vec4::splat(6.0);
+        painter.rect(Vec2::new(PANEL_SIZE.x - 20.0, ITEM_HEIGHT - 8.0));
+        painter.set_color(GOLD);
+        painter.translate(Vec3::X * (PANEL_SIZE.x / 2.0 - 10.0));
+        painter.circle(16.0);
+    }
+}
@@ -0,0 +1,50 @@
+// A stress test drawing a large number of compact shapes
+
+use bevy::{
+    color::palettes::css::*,
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vector_shapes::prelude::*;
+
+const SHAPES_PER_AXIS: u32 = 1000;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_plugins(FrameTimeDiagnosticsPlugin)
+        .add_plugins(LogDiagnosticsPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw_particles)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2d, Msaa::Off));
+}
+
+fn draw_particles(time: Res<Time>, mut painter: ShapePainter) {
+    let half = SHAPES_PER_AXIS as f32 / 2.0;
+    for x in 0..SHAPES_PER_AXIS {
+        for y in 0..SHAPES_PER_AXIS {
+            let (x, y) = (x as f32, y as f32);
+            let offset = time.elapsed_secs() + x * 0.1 + y * 0.07;
+            painter.transform.translation = Vec3::new(
+                (x - half) * 1.2 + offset.sin(),
+                (y - half) * 1.2 + offset.cos(),
+                0.0,
+            );
+            painter.set_color(GOLD * offset.sin().abs() + TEAL * (1.0 - offset.sin().abs()));
+
+            if (x as u32 + y as u32).is_multiple_of(2) {
+                painter.compact_circle(0.5);
+            } else {
+                painter.transform.rotation = Quat::from_rotation_z(offset);
+                painter.compact_rect(Vec2::splat(0.8));
+                painter.transform.rotation = Quat::IDENTITY;
+            }
+        }
+    }
+}
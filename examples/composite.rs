@@ -0,0 +1,81 @@
+// Demonstrates compositing shapes drawn to a canvas over a camera's view with blend modes such as overlay and soft light
+// Press space to cycle through the blend modes
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+const MODES: [ShapeBlendMode; 12] = [
+    ShapeBlendMode::Normal,
+    ShapeBlendMode::Multiply,
+    ShapeBlendMode::Screen,
+    ShapeBlendMode::Overlay,
+    ShapeBlendMode::Darken,
+    ShapeBlendMode::Lighten,
+    ShapeBlendMode::ColorDodge,
+    ShapeBlendMode::ColorBurn,
+    ShapeBlendMode::HardLight,
+    ShapeBlendMode::SoftLight,
+    ShapeBlendMode::Difference,
+    ShapeBlendMode::Exclusion,
+];
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_backdrop, draw_layer, cycle_modes))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>, window: Query<&Window>) {
+    // The canvas covers the window, in the same units as the camera's world space
+    let size = window.single().size().as_uvec2();
+    let mut config = CanvasConfig::new(size.x, size.y);
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    let (image, _) = commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn((
+        Camera2d,
+        ShapeCompositeLayers(vec![ShapeCompositeLayer::new(image, MODES[0])]),
+    ));
+}
+
+fn draw_backdrop(mut painter: ShapePainter) {
+    // Stripes running from black to white, with a band of saturated colors across them
+    for i in 0..8 {
+        let value = i as f32 / 7.0;
+        painter.set_translation(Vec3::new(i as f32 * 100.0 - 350.0, 0.0, 0.0));
+        painter.set_color(Color::srgb(value, value, value));
+        painter.rect(Vec2::new(100.0, 600.0));
+    }
+    for (i, color) in [CRIMSON, GOLD, LIME, DEEP_SKY_BLUE].into_iter().enumerate() {
+        painter.set_translation(Vec3::new(i as f32 * 200.0 - 300.0, -200.0, 1.0));
+        painter.set_color(color);
+        painter.rect(Vec2::new(200.0, 100.0));
+    }
+}
+
+fn draw_layer(time: Res<Time>, mut painter: ShapePainter, canvas: Query<Entity, With<Canvas>>) {
+    painter.set_canvas(canvas.single());
+    painter.set_translation(Vec3::new(time.elapsed_secs().sin() * 150.0, 50.0, 0.0));
+    painter.set_color(ORANGE);
+    painter.circle(220.0);
+    painter.set_color(Color::srgb(0.5, 0.5, 0.5));
+    painter.circle(120.0);
+}
+
+fn cycle_modes(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut layers: Query<&mut ShapeCompositeLayers>,
+    mut index: Local<usize>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    *index = (*index + 1) % MODES.len();
+    info!("Blend mode: {:?}", MODES[*index]);
+    for mut layers in &mut layers {
+        layers.0[0].mode = MODES[*index];
+    }
+}
@@ -0,0 +1,49 @@
+// Demonstrates registering a group of shapes once and drawing it many times by name
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut compounds: ResMut<ShapeCompounds>) {
+    commands.spawn(Camera2d);
+
+    // Each part draws relative to it's transform, shapes are white unless given a color
+    // so that the whole compound is tinted by the color it is drawn with
+    let mut crosshair = CompoundShape::new().part(Transform::IDENTITY, |painter| {
+        painter.hollow = true;
+        painter.thickness = 2.0;
+        painter.circle(12.0);
+    });
+    for i in 0..4 {
+        let rotation = Quat::from_rotation_z(i as f32 * PI / 2.0);
+        crosshair = crosshair.part(Transform::from_rotation(rotation), |painter| {
+            painter.thickness = 2.0;
+            painter.line(Vec3::Y * 6.0, Vec3::Y * 18.0);
+        });
+    }
+    compounds.insert("crosshair", crosshair);
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    let seconds = time.elapsed_secs();
+
+    for x in -5..=5 {
+        for y in -3..=3 {
+            let offset = Vec2::new(x as f32, y as f32) * 60.0;
+            painter.set_translation(offset.extend(0.0));
+            painter.set_rotation(Quat::from_rotation_z(seconds + offset.length() / 100.0));
+            painter.set_color(Color::hsl((x + 5) as f32 * 30.0, 0.8, 0.6));
+            painter.compound("crosshair");
+        }
+    }
+}
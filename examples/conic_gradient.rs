@@ -0,0 +1,45 @@
+// Demonstrates conic gradients swept around discs, arcs and sectors
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+use std::f32::consts::TAU;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    // A color wheel in a single instance
+    painter.translate(Vec3::X * -300.0);
+    painter.set_color(RED);
+    painter.conic_gradient = Some(ConicGradient::wheel(RED));
+    painter.circle(120.0);
+
+    // A progress ring sweeping through the hues it has filled so far
+    let progress = (time.elapsed_secs() * 0.25).fract();
+    painter.translate(Vec3::X * 300.0);
+    painter.hollow = true;
+    painter.thickness = 24.0;
+    painter.cap = Cap::Round;
+    painter.set_color(RED);
+    painter.conic_gradient = Some(ConicGradient::Hue {
+        end_color: VIOLET.into(),
+        turns: 0.0,
+    });
+    painter.arc(120.0, 0.0, progress * TAU);
+
+    // A linear two color sector
+    painter.translate(Vec3::X * 300.0);
+    painter.set_color(DEEP_SKY_BLUE);
+    painter.conic_gradient = Some(ConicGradient::Linear(NAVY.into()));
+    painter.sector(120.0, -TAU / 3.0, TAU / 3.0);
+}
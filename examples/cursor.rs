@@ -0,0 +1,65 @@
+// Demonstrates replacing the OS cursor with a shape cursor that reacts to hovering over a button
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::{painter::ShapeCursorCamera, prelude::*};
+
+const BUTTON_SIZE: Vec2 = Vec2::new(200.0, 80.0);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_plugins(ShapeCursorPlugin)
+        .insert_resource(ShapeCursor {
+            draw: draw_crosshair,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw_button)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw_crosshair(painter: &mut ShapePainter, state: ShapeCursorState) {
+    painter.thickness = 2.0;
+    painter.thickness_type = ThicknessType::Pixels;
+    painter.set_color(match state {
+        ShapeCursorState::Idle => WHITE,
+        ShapeCursorState::Hover => GOLD,
+        ShapeCursorState::Pressed => ORANGE_RED,
+    });
+
+    let gap = if state == ShapeCursorState::Hover {
+        8.0
+    } else {
+        4.0
+    };
+    for dir in [Vec3::X, Vec3::Y, Vec3::NEG_X, Vec3::NEG_Y] {
+        painter.line(dir * gap, dir * (gap + 10.0));
+    }
+}
+
+// Hovering the button switches the cursor into it's hover state
+fn draw_button(
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), Without<ShapeCursorCamera>>,
+    mut cursor: ResMut<ShapeCursor>,
+    mut painter: ShapePainter,
+) {
+    let (camera, camera_tf) = cameras.single();
+    let hovered = windows
+        .single()
+        .cursor_position()
+        .and_then(|position| camera.viewport_to_world_2d(camera_tf, position).ok())
+        .is_some_and(|position| Rect::from_center_size(Vec2::ZERO, BUTTON_SIZE).contains(position));
+    if cursor.hovered != hovered {
+        cursor.hovered = hovered;
+    }
+
+    painter.corner_radii = Vec4::splat(12.0);
+    painter.set_color(if hovered { STEEL_BLUE } else { SLATE_GRAY });
+    painter.rect(BUTTON_SIZE);
+}
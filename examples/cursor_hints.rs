@@ -0,0 +1,94 @@
+// Demonstrates changing the OS cursor while hovering shape based buttons, requires the `picking` feature
+// Shapes aren't picked by bevy_picking's own backends, so a minimal backend testing the pointer against each button is included
+
+use bevy::{
+    color::palettes::css::*,
+    picking::{
+        backend::{HitData, PointerHits},
+        focus::PickingInteraction,
+        pointer::{PointerId, PointerLocation},
+        PickSet,
+    },
+    prelude::*,
+    window::SystemCursorIcon,
+};
+use bevy_vector_shapes::prelude::*;
+
+/// Size of a button to test the pointer against.
+#[derive(Component)]
+struct ButtonSize(Vec2);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_plugins(ShapeCursorHintPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(PreUpdate, pick_buttons.in_set(PickSet::Backend))
+        .add_systems(Update, color_buttons)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn(Camera2d);
+
+    let size = Vec2::new(200.0, 80.0);
+    shapes.corner_radii = Vec4::splat(16.0);
+    for (x, hint) in [
+        (-250.0, ShapeCursorHint::default()),
+        (
+            0.0,
+            ShapeCursorHint::new(SystemCursorIcon::Grab).with_pressed(SystemCursorIcon::Grabbing),
+        ),
+        (250.0, ShapeCursorHint::new(SystemCursorIcon::NotAllowed)),
+    ] {
+        shapes.set_translation(Vec3::new(x, 0.0, 0.0));
+        shapes.rect(size).insert((hint, ButtonSize(size)));
+    }
+}
+
+/// Reports the buttons under each pointer to bevy_picking.
+fn pick_buttons(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    buttons: Query<(Entity, &ButtonSize, &GlobalTransform)>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    let Ok((camera_entity, camera, camera_tf)) = cameras.get_single() else {
+        return;
+    };
+    for (pointer, location) in &pointers {
+        let Some(location) = location.location() else {
+            continue;
+        };
+        let Ok(position) = camera.viewport_to_world_2d(camera_tf, location.position) else {
+            continue;
+        };
+
+        let picks = buttons
+            .iter()
+            .filter(|(_, size, tf)| {
+                let local = tf.affine().inverse().transform_point3(position.extend(0.0));
+                local.truncate().abs().cmple(size.0 / 2.0).all()
+            })
+            .map(|(entity, _, tf)| {
+                let depth = camera_tf.translation().z - tf.translation().z;
+                let hit = HitData::new(camera_entity, depth, Some(position.extend(0.0)), None);
+                (entity, hit)
+            })
+            .collect();
+        hits.send(PointerHits::new(*pointer, picks, camera.order as f32));
+    }
+}
+
+fn color_buttons(
+    mut buttons: Query<(&PickingInteraction, &mut ShapeFill), Changed<PickingInteraction>>,
+) {
+    for (interaction, mut fill) in &mut buttons {
+        fill.color = match interaction {
+            PickingInteraction::Pressed => ORANGE_RED.into(),
+            PickingInteraction::Hovered => ORANGE.into(),
+            PickingInteraction::None => MIDNIGHT_BLUE.into(),
+        };
+    }
+}
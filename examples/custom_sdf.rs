@@ -0,0 +1,56 @@
+// Demonstrates drawing a shape from a distance function written in WGSL
+
+use bevy::prelude::*;
+use bevy_vector_shapes::prelude::*;
+
+// Each custom sdf shape is identified by a unique id
+const HEART: u32 = 0;
+
+// Distance to a heart shape, rounded by params.x
+const HEART_SDF: &str = r"
+fn dot2(v: vec2<f32>) -> f32 {
+    return dot(v, v);
+}
+
+fn sdf(pos: vec2<f32>, size: vec2<f32>, params: vec4<f32>) -> f32 {
+    let scale = min(size.x, size.y) - params.x * 2.0;
+    var p = pos / scale + vec2<f32>(0.0, 0.5);
+    p.x = abs(p.x);
+
+    var dist: f32;
+    if p.y + p.x > 1.0 {
+        dist = sqrt(dot2(p - vec2<f32>(0.25, 0.75))) - sqrt(2.0) / 4.0;
+    } else {
+        dist = sqrt(min(dot2(p - vec2<f32>(0.0, 1.0)), dot2(p - 0.5 * max(p.x + p.y, 0.0)))) * sign(p.x - p.y);
+    }
+    return dist * scale - params.x;
+}
+";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        // Add a plugin for each custom sdf shape after the shape plugin
+        .add_plugins(CustomSdfPlugin::<HEART>::from_wgsl(HEART_SDF))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    let pulse = (time.elapsed_secs() * 3.0).sin().abs();
+
+    painter.color = Color::srgb(0.9, 0.1, 0.3);
+    painter.translate(Vec3::X * -150.0);
+    painter.custom_sdf::<HEART>(Vec2::splat(200.0 + pulse * 20.0), Vec4::ZERO);
+
+    painter.hollow = true;
+    painter.thickness = 10.0;
+    painter.translate(Vec3::X * 300.0);
+    painter.custom_sdf::<HEART>(Vec2::splat(200.0), Vec4::new(pulse * 20.0, 0.0, 0.0, 0.0));
+}
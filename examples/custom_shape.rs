@@ -0,0 +1,207 @@
+// Demonstrates defining a shape type outside of bevy_vector_shapes, as an extension crate would.
+use bevy::{
+    prelude::*,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use bevy_vector_shapes::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, ShapeTypePlugin},
+};
+use wgpu::vertex_attr_array;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            Shape2dPlugin::default(),
+            // Must be added after the shape plugin
+            ShapeTypePlugin::<StarComponent>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+/// Component containing the data for drawing a star, required to spawn stars as entities.
+#[derive(Component, Reflect)]
+pub struct StarComponent {
+    pub alignment: Alignment,
+
+    /// Number of points of the star
+    pub points: u32,
+    /// Distance from the center to each point
+    pub radius: f32,
+    /// Distance from the center to the corners between points as a fraction of radius
+    pub inner_ratio: f32,
+}
+
+impl StarComponent {
+    pub fn new(config: &ShapeConfig, points: u32, radius: f32, inner_ratio: f32) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            points,
+            radius,
+            inner_ratio,
+        }
+    }
+}
+
+impl ShapeComponent for StarComponent {
+    type Data = StarData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> StarData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        StarData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            points: self.points as f32,
+            radius: self.radius,
+            inner_ratio: self.inner_ratio,
+
+            padding: default(),
+        }
+    }
+}
+
+/// Raw data sent to the star shader, the layout must match `Shape` in `assets/shaders/star.wgsl`.
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct StarData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    points: f32,
+    radius: f32,
+    inner_ratio: f32,
+
+    padding: [f32; 3],
+}
+
+impl StarData {
+    pub fn new(config: &ShapeConfig, points: u32, radius: f32, inner_ratio: f32) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_hollow(config.hollow as u32);
+
+        StarData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            points: points as f32,
+            radius,
+            inner_ratio,
+
+            padding: default(),
+        }
+    }
+}
+
+impl ShapeData for StarData {
+    type Component = StarComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+            7 => Float32,
+            8 => Float32,
+            9 => Float32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        "shaders/star.wgsl".into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw stars.
+pub trait StarPainter {
+    fn star(&mut self, points: u32, radius: f32, inner_ratio: f32) -> &mut Self;
+}
+
+impl<'w, 's> StarPainter for ShapePainter<'w, 's> {
+    fn star(&mut self, points: u32, radius: f32, inner_ratio: f32) -> &mut Self {
+        self.send(StarData::new(self.config(), points, radius, inner_ratio))
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of star entities.
+pub trait StarSpawner<'w> {
+    fn star(&mut self, points: u32, radius: f32, inner_ratio: f32) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> StarSpawner<'w> for T {
+    fn star(&mut self, points: u32, radius: f32, inner_ratio: f32) -> ShapeEntityCommands {
+        let star = StarComponent::new(self.config(), points, radius, inner_ratio);
+        self.spawn_shape(ShapeBundle::new(self.config(), star))
+    }
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn(Camera2d);
+
+    // Retained stars are spawned like any built in shape
+    shapes.color = Color::srgb(1.0, 0.8, 0.2);
+    shapes.transform.translation = Vec3::new(-250.0, 0.0, 0.0);
+    shapes.star(5, 100.0, 0.45);
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    // Immediate mode stars are sent through the painter
+    painter.color = Color::srgb(0.3, 0.7, 1.0);
+    painter.rotate_z(time.elapsed_secs());
+    painter.star(6, 100.0, 0.6);
+
+    painter.reset();
+    painter.hollow = true;
+    painter.thickness = 5.0;
+    painter.thickness_type = ThicknessType::Pixels;
+    painter.color = Color::srgb(1.0, 0.4, 0.6);
+    painter.translate(Vec3::X * 250.0);
+    painter.star(8, 100.0, 0.5 + 0.2 * time.elapsed_secs().sin());
+}
@@ -0,0 +1,126 @@
+// Demonstrates the same dash patterns along lines, polylines, curves, rings and the outlines of hollow shapes,
+// drawn with the painter and on retained shapes through their ShapeFill
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw, swap_patterns))
+        .run();
+}
+
+#[derive(Resource)]
+struct Patterns {
+    dashed: DashPattern,
+    dash_dot: DashPattern,
+    dotted: DashPattern,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut dash_patterns: ResMut<ShapeDashPatterns>,
+    mut shapes: ShapeCommands,
+) {
+    commands.spawn(Camera2d);
+
+    let patterns = Patterns {
+        dashed: dash_patterns.add(&[24.0, 12.0]).unwrap(),
+        dash_dot: dash_patterns.add(&[24.0, 8.0, 2.0, 8.0]).unwrap(),
+        // Dots fit exactly around the middle of the outline of a circle of radius 60 and thickness 6
+        dotted: dash_patterns
+            .add_evenly_spaced(std::f32::consts::TAU * 57.0, 16, 0.7)
+            .unwrap(),
+    };
+
+    // Retained shapes keep their pattern in their ShapeFill
+    shapes.hollow = true;
+    shapes.thickness = 6.0;
+    shapes.set_color(GOLD);
+    shapes.dash = Some(patterns.dashed);
+    shapes.transform.translation = Vec3::new(-300.0, -180.0, 0.0);
+    shapes.rect(Vec2::new(200.0, 140.0));
+    shapes.transform.translation = Vec3::new(0.0, -180.0, 0.0);
+    shapes.capsule(Vec3::NEG_X * 60.0, Vec3::X * 60.0, 60.0);
+    shapes.transform.translation = Vec3::new(300.0, -180.0, 0.0);
+    shapes.quad(
+        Vec2::new(-100.0, -60.0),
+        Vec2::new(80.0, -70.0),
+        Vec2::new(100.0, 70.0),
+        Vec2::new(-60.0, 50.0),
+    );
+
+    commands.insert_resource(patterns);
+}
+
+fn draw(mut painter: ShapePainter, patterns: Res<Patterns>, time: Res<Time>) {
+    let seconds = time.elapsed_secs();
+    painter.dash = Some(patterns.dash_dot);
+    painter.thickness = 6.0;
+    painter.set_color(DEEP_SKY_BLUE);
+
+    painter.translate(Vec3::new(-300.0, 260.0, 0.0));
+    painter.cap = Cap::None;
+    painter.line(Vec3::NEG_X * 100.0, Vec3::X * 100.0);
+
+    painter.translate(Vec3::X * 300.0);
+    painter.cap = Cap::Round;
+    let points: Vec<Vec2> = (0..6)
+        .map(|i| Vec2::new(i as f32 * 40.0 - 100.0, 30.0 * (seconds + i as f32).sin()))
+        .collect();
+    painter.polyline(&points);
+
+    // Hollow shapes are dashed around their outlines
+    painter.hollow = true;
+    painter.translate(Vec3::new(300.0, -20.0, 0.0));
+    painter.circle(70.0);
+
+    painter.cap = Cap::None;
+    painter.translate(Vec3::new(-600.0, -200.0, 0.0));
+    painter.ngon(6.0, 80.0);
+
+    painter.translate(Vec3::X * 300.0);
+    painter.triangle(
+        Vec2::new(0.0, 80.0),
+        Vec2::new(-80.0, -60.0),
+        Vec2::new(80.0, -60.0),
+    );
+
+    painter.translate(Vec3::X * 300.0);
+    painter.arc(70.0, 0.0, seconds.sin() + 3.0);
+
+    painter.cap = Cap::Round;
+    painter.translate(Vec3::X * 240.0);
+    painter.bezier(
+        Vec2::new(-70.0, -60.0),
+        Vec2::new(-70.0, 80.0),
+        Vec2::new(70.0, -80.0),
+        Vec2::new(70.0, 60.0),
+    );
+
+    painter.translate(Vec3::NEG_Y * 220.0);
+    painter.ring(40.0, 70.0);
+
+    painter.dash = Some(patterns.dotted);
+    painter.translate(Vec3::Y * 440.0);
+    painter.circle(60.0);
+}
+
+// The entity API swaps patterns by changing each shape's ShapeFill
+fn swap_patterns(
+    time: Res<Time>,
+    patterns: Res<Patterns>,
+    mut fills: Query<&mut ShapeFill, With<RectangleComponent>>,
+) {
+    let dashed = (time.elapsed_secs() as u32).is_multiple_of(2);
+    for mut fill in &mut fills {
+        fill.dash = Some(if dashed {
+            patterns.dashed
+        } else {
+            patterns.dash_dot
+        });
+    }
+}
@@ -0,0 +1,57 @@
+// Demonstrates splitting a large translucent plane into depth slices so that particles passing through it sort correctly
+// Press space to toggle slicing, unsliced the plane is sorted at it's center so particles pop in front of or behind it all at once
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .insert_resource(Slices(16))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_slices, draw))
+        .run();
+}
+
+#[derive(Resource)]
+struct Slices(u8);
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 14.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+    ));
+}
+
+fn toggle_slices(keys: Res<ButtonInput<KeyCode>>, mut slices: ResMut<Slices>) {
+    if keys.just_pressed(KeyCode::Space) {
+        slices.0 = if slices.0 > 1 { 0 } else { 16 };
+        info!("Depth slices: {}", slices.0);
+    }
+}
+
+fn draw(time: Res<Time>, slices: Res<Slices>, mut painter: ShapePainter) {
+    // A water plane stretching away from the camera
+    painter.rotate_x(-std::f32::consts::FRAC_PI_2);
+    painter.depth_slices = slices.0;
+    painter.set_color(DEEP_SKY_BLUE.with_alpha(0.5));
+    painter.rect(Vec2::new(12.0, 40.0));
+
+    // Translucent particles bobbing through it along it's length
+    painter.reset();
+    painter.alignment = Alignment::Billboard;
+    for i in 0..20 {
+        let t = time.elapsed_secs() + i as f32 * 0.7;
+        let position = Vec3::new(
+            (i as f32 * 2.3).sin() * 5.0,
+            t.sin() * 0.8,
+            10.0 - i as f32 * 2.5,
+        );
+        painter.set_translation(position);
+        painter.set_color(ORANGE.with_alpha(0.7));
+        painter.circle(0.5);
+    }
+}
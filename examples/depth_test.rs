@@ -0,0 +1,71 @@
+// Demonstrates debug overlays drawn over scene geometry by disabling the depth test of shapes
+// The axes and bounds of each cube are drawn through the cubes in front of them, the rings on the ground are hidden by the cubes
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(2.0, 3.0, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(3.0, 8.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let cube = meshes.add(Cuboid::default());
+    let material = materials.add(StandardMaterial::from_color(STEEL_BLUE));
+    for i in 0..3 {
+        commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(i as f32 - 1.0, 0.0, i as f32 * -2.0),
+        ));
+    }
+}
+
+fn draw(
+    time: Res<Time>,
+    mut painter: ShapePainter,
+    mut cubes: Query<&mut Transform, With<Mesh3d>>,
+) {
+    for mut tf in &mut cubes {
+        tf.rotation = Quat::from_rotation_y(time.elapsed_secs() * 0.5);
+
+        // Rings on the ground are hidden where the cubes are in front of them
+        painter.reset();
+        painter.set_translation(tf.translation - Vec3::Y * 0.5);
+        painter.rotate_x(-std::f32::consts::FRAC_PI_2);
+        painter.hollow = true;
+        painter.thickness = 0.05;
+        painter.set_color(SILVER);
+        painter.circle(1.0);
+
+        // Overlays are drawn over everything
+        painter.reset();
+        painter.depth_test = false;
+        painter.transform = *tf;
+        painter.thickness = 0.03;
+        for (axis, color) in [(Vec3::X, RED), (Vec3::Y, LIME), (Vec3::Z, BLUE)] {
+            painter.set_color(color);
+            painter.line(Vec3::ZERO, axis);
+        }
+        painter.hollow = true;
+        painter.set_color(GOLD);
+        painter.rect(Vec2::ONE);
+    }
+}
@@ -0,0 +1,50 @@
+// Demonstrates drawing shapes with both a fill and an outline in a single call
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn(Camera2d);
+
+    // Retained shapes keep their outline in their ShapeFill
+    shapes.set_translation(Vec3::new(0.0, -200.0, 0.0));
+    shapes.set_color(MIDNIGHT_BLUE);
+    shapes.set_stroke_color(GOLD);
+    shapes.thickness = 8.0;
+    shapes.thickness_type = ThicknessType::Pixels;
+    shapes.corner_radii = Vec4::splat(20.0);
+    shapes.rect(Vec2::new(400.0, 100.0));
+}
+
+fn draw(time: Res<Time>, mut painter: ShapePainter) {
+    painter.set_stroke_color(WHITE);
+    painter.thickness = 6.0;
+    painter.thickness_type = ThicknessType::Pixels;
+
+    painter.set_translation(Vec3::new(-250.0, 50.0, 0.0));
+    painter.set_color(CRIMSON);
+    painter.circle(80.0);
+
+    painter.set_translation(Vec3::new(0.0, 50.0, 0.0));
+    painter.set_rotation(Quat::from_rotation_z(time.elapsed_secs()));
+    painter.set_color(SEA_GREEN);
+    painter.ngon(6.0, 90.0);
+
+    painter.set_translation(Vec3::new(250.0, 50.0, 0.0));
+    painter.set_rotation(Quat::IDENTITY);
+    painter.set_color(ROYAL_BLUE);
+    painter.triangle(
+        Vec2::new(-80.0, -70.0),
+        Vec2::new(80.0, -70.0),
+        Vec2::new(0.0, 80.0),
+    );
+}
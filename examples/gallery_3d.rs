@@ -98,6 +98,7 @@ pub fn gallery(mut painter: ShapePainter, seconds: f32, entries: Range<i32>) {
 
                 painter.set_color(SEA_GREEN.pastel() * 1.2);
                 painter.corner_radii = Vec4::new(0.0, 0.2, 0.35, 0.0);
+                painter.corner_chamfers = BVec4::new(false, false, true, false);
                 painter.translate(-Vec3::Y);
                 painter.rect(Vec2::new(2.0, 0.7));
             }
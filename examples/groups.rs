@@ -0,0 +1,88 @@
+// Demonstrates drawing debug shapes into named groups that are shown, hidden and cleared together
+// G toggles the grid, which is drawn once and kept, P toggles the paths drawn every frame
+// R redraws the grid with a new spacing and C clears it
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .insert_resource(GridSpacing(40.0))
+        .add_systems(Startup, (setup, draw_grid))
+        .add_systems(
+            Update,
+            (
+                draw_paths,
+                update_groups,
+                draw_grid
+                    .run_if(|keys: Res<ButtonInput<KeyCode>>| keys.just_pressed(KeyCode::KeyR)),
+            ),
+        )
+        .run();
+}
+
+#[derive(Resource)]
+struct GridSpacing(f32);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+// Only drawn when requested, the group keeps the grid until it is redrawn or cleared
+fn draw_grid(mut painter: ShapePainter, mut spacing: ResMut<GridSpacing>) {
+    let spacing = &mut spacing.0;
+    *spacing = if *spacing >= 80.0 {
+        20.0
+    } else {
+        *spacing + 20.0
+    };
+
+    painter.group("grid");
+    painter.thickness = 1.0;
+    painter.set_color(WHITE.with_alpha(0.3));
+    let extent = 400.0;
+    let lines = (extent / *spacing) as i32;
+    for i in -lines..=lines {
+        let offset = i as f32 * *spacing;
+        painter.line(
+            Vec3::new(offset, -extent, 0.0),
+            Vec3::new(offset, extent, 0.0),
+        );
+        painter.line(
+            Vec3::new(-extent, offset, 0.0),
+            Vec3::new(extent, offset, 0.0),
+        );
+    }
+}
+
+// Drawn every frame like any other immediate mode shapes
+fn draw_paths(time: Res<Time>, mut painter: ShapePainter) {
+    painter.group("paths");
+    painter.thickness = 4.0;
+    painter.set_color(GOLD);
+    let seconds = time.elapsed_secs();
+    for i in 0..3 {
+        let phase = i as f32 * 2.0;
+        let from = Vec2::from_angle(seconds + phase) * 250.0;
+        let to = Vec2::from_angle(seconds * 0.5 - phase) * 120.0;
+        painter.line(from.extend(0.0), to.extend(0.0));
+        painter.set_translation(to.extend(0.0));
+        painter.circle(8.0);
+        painter.set_translation(Vec3::ZERO);
+    }
+}
+
+fn update_groups(keys: Res<ButtonInput<KeyCode>>, mut groups: ResMut<ShapeGroups>) {
+    if keys.just_pressed(KeyCode::KeyG) {
+        groups.toggle("grid");
+    }
+    if keys.just_pressed(KeyCode::KeyP) {
+        groups.toggle("paths");
+    }
+    if keys.just_pressed(KeyCode::KeyC) {
+        groups.clear("grid");
+    }
+}
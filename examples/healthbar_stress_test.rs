@@ -1,4 +1,6 @@
 // A stress test drawing a large number of shapes
+// Press space to toggle shape rendering and compare frame times
+// Press I to toggle drawing the identical spheres and frames as instances of a single shape
 
 use bevy::{
     color::palettes::css::*,
@@ -17,10 +19,14 @@ fn main() {
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_plugins(LogDiagnosticsPlugin::default())
         .add_systems(Startup, setup)
-        .add_systems(Update, draw_spheres)
+        .init_resource::<Instanced>()
+        .add_systems(Update, (draw_spheres, toggle_shapes))
         .run();
 }
 
+#[derive(Resource, Default)]
+struct Instanced(bool);
+
 fn setup(mut commands: Commands) {
     let shapes = SHAPES_PER_AXIS as f32;
     let center = Vec3::new(shapes, 0.0, shapes);
@@ -44,7 +50,12 @@ fn draw_health_bar(painter: &mut ShapePainter, hp: f32) {
     painter.rect(Vec2::new(1.06, 0.26));
 }
 
-fn draw_spheres(time: Res<Time>, mut painter: ShapePainter) {
+fn draw_spheres(time: Res<Time>, instanced: Res<Instanced>, mut painter: ShapePainter) {
+    if instanced.0 {
+        draw_instanced(&time, &mut painter);
+        return;
+    }
+
     for x in 0..SHAPES_PER_AXIS {
         for y in 0..SHAPES_PER_AXIS {
             let (x, y) = (x as f32, y as f32);
@@ -63,3 +74,55 @@ fn draw_spheres(time: Res<Time>, mut painter: ShapePainter) {
         }
     }
 }
+
+fn draw_instanced(time: &Time, painter: &mut ShapePainter) {
+    let positions: Vec<(Vec3, f32)> = (0..SHAPES_PER_AXIS)
+        .flat_map(|x| (0..SHAPES_PER_AXIS).map(move |y| (x as f32, y as f32)))
+        .map(|(x, y)| {
+            let offset = time.elapsed_secs() + x + 100. * y;
+            (Vec3::new(x * 2.0, offset.sin(), y * 2.0), offset)
+        })
+        .collect();
+    let transforms = || {
+        positions
+            .iter()
+            .map(|(p, _)| Transform::from_translation(*p))
+    };
+
+    // Shapes that are the same for every sphere are built once and placed at each position
+    painter.alignment = Alignment::Billboard;
+    painter.set_color(GRAY);
+    painter.corner_radii = Vec4::splat(1.0);
+    let sphere = RectData::new(painter.config(), Vec2::splat(1.0));
+    painter.instances(sphere, transforms());
+
+    painter.translate(Vec3::Y * 0.7);
+    painter.corner_radii = Vec4::splat(0.3);
+    painter.thickness = 0.02;
+    painter.hollow = true;
+    painter.color = Color::WHITE;
+    let frame = RectData::new(painter.config(), Vec2::new(1.06, 0.26));
+    painter.instances(frame, transforms());
+
+    painter.hollow = false;
+    for (position, offset) in &positions {
+        let hp = (offset.sin() + 1.) / 2.0;
+        painter.transform.translation = *position + Vec3::Y * 0.7;
+        painter.set_color(GREEN * hp + RED * (1. - hp));
+        painter.rect(Vec2::new(0.2 + 0.8 * hp, 0.2));
+    }
+}
+
+fn toggle_shapes(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut enabled: ResMut<ShapesEnabled>,
+    mut instanced: ResMut<Instanced>,
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        enabled.toggle();
+    }
+    if keys.just_pressed(KeyCode::KeyI) {
+        instanced.0 = !instanced.0;
+        info!("Instanced: {}", instanced.0);
+    }
+}
@@ -0,0 +1,54 @@
+// Demonstrates keeping mostly static immediate mode shapes, such as a HUD, as entities that are only updated when they change
+// Hover over the panels to highlight them, press space to log the number of keyed shape entities
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::{painter::RetainedShapeData, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_hud, log_entities))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw_hud(
+    window: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    mut painter: ShapePainter,
+) {
+    let (camera, transform) = camera.single();
+    let cursor = window
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(transform, cursor).ok());
+
+    // Only the panels whose highlight changes are updated, the rest keep their entities untouched
+    for y in 0..8 {
+        for x in 0..12 {
+            let position = Vec2::new(x as f32 - 5.5, y as f32 - 3.5) * 70.0;
+            let hovered =
+                cursor.is_some_and(|cursor| (cursor - position).abs().max_element() < 30.0);
+
+            painter.keyed((x, y));
+            painter.set_translation(position.extend(0.0));
+            painter.corner_radii = Vec4::splat(8.0);
+            painter.set_color(if hovered { GOLD } else { STEEL_BLUE });
+            painter.rect(Vec2::splat(60.0));
+        }
+    }
+}
+
+fn log_entities(
+    keys: Res<ButtonInput<KeyCode>>,
+    shapes: Query<(), With<RetainedShapeData<RectData>>>,
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        info!("Keyed shape entities: {}", shapes.iter().count());
+    }
+}
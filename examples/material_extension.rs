@@ -0,0 +1,70 @@
+// Demonstrates adding a global uniform to the line pipeline with a material extension to sway lines in the wind.
+use bevy::{
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+use bevy_vector_shapes::{
+    prelude::*,
+    render::{ShapeMaterialExtension, ShapeMaterialExtensionPlugin},
+};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            Shape2dPlugin::default(),
+            // Must be added after the shape plugin
+            ShapeMaterialExtensionPlugin::<LineComponent, Wind>::default(),
+        ))
+        .insert_resource(Wind {
+            direction: Vec2::X,
+            strength: 40.0,
+            time: 0.0,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (update_wind, draw))
+        .run();
+}
+
+/// Uniform shared by every line, the layout must match `Wind` in `assets/shaders/wind_line.wgsl`.
+#[derive(Resource, AsBindGroup, Clone)]
+struct Wind {
+    #[uniform(0)]
+    direction: Vec2,
+    #[uniform(0)]
+    strength: f32,
+    #[uniform(0)]
+    time: f32,
+}
+
+impl ShapeMaterialExtension for Wind {
+    fn shader() -> ShaderRef {
+        "shaders/wind_line.wgsl".into()
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn update_wind(mut wind: ResMut<Wind>, time: Res<Time>) {
+    wind.time = time.elapsed_secs();
+    // Slowly shift the wind back and forth
+    wind.direction = Vec2::new((time.elapsed_secs() * 0.2).sin(), 0.0);
+}
+
+fn draw(mut painter: ShapePainter) {
+    painter.thickness = 4.0;
+    painter.cap = Cap::Round;
+
+    // Blades of grass rooted along the bottom of the screen
+    for i in 0..80 {
+        let x = -400.0 + i as f32 * 10.0;
+        let height = 80.0 + 40.0 * (i as f32 * 1.7).sin().abs();
+        painter.color = Color::srgb(0.2, 0.5 + 0.3 * (i as f32 * 0.9).cos().abs(), 0.2);
+        painter.line(
+            Vec3::new(x, -200.0, 0.0),
+            Vec3::new(x, -200.0 + height, 0.0),
+        );
+    }
+}
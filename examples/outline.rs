@@ -0,0 +1,74 @@
+// Demonstrates drawing selection outlines around meshes in a 3D scene, press space to select the next cube
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (select_next, rotate_cubes))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut shapes: ShapeCommands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(3.0, 8.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let cube = meshes.add(Cuboid::default());
+    let material = materials.add(StandardMaterial::from_color(STEEL_BLUE));
+    for i in 0..3 {
+        let mut entity = commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(i as f32 * 2.0 - 2.0, 0.0, 0.0),
+        ));
+        if i == 0 {
+            entity.insert(Outlined::default());
+        }
+    }
+
+    // Outline every outlined entity seen by the camera
+    shapes.color = GOLD.into();
+    shapes.thickness = 4.0;
+    shapes.outline(camera);
+}
+
+fn select_next(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    cubes: Query<(Entity, Has<Outlined>), With<Mesh3d>>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    // Move the outline to the cube after the currently outlined one
+    let cubes: Vec<_> = cubes.iter().collect();
+    let Some(current) = cubes.iter().position(|(_, outlined)| *outlined) else {
+        return;
+    };
+    let next = cubes[(current + 1) % cubes.len()].0;
+    commands.entity(cubes[current].0).remove::<Outlined>();
+    commands.entity(next).insert(Outlined::default());
+}
+
+fn rotate_cubes(time: Res<Time>, mut cubes: Query<&mut Transform, With<Mesh3d>>) {
+    for mut transform in &mut cubes {
+        transform.rotate_y(time.delta_secs());
+    }
+}
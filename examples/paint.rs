@@ -0,0 +1,100 @@
+// Demonstrates recording pointer input into strokes on a persistent canvas, as a simple whiteboard
+// Draw with the left mouse button or a touch, press Z to undo, Y to redo, C to clear,
+// 1, 2 and 3 to change color, D to toggle a dashed brush and E to toggle the eraser
+// Press S to save the drawing as bytes and L to load the last saved drawing, as it would be sent to other players
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_plugins(ShapePaintPlugin)
+        .insert_resource(ClearColor(Color::srgb(0.95, 0.95, 0.9)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, handle_keys)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>, window: Query<&Window>) {
+    let size = window.single().size().as_uvec2();
+    let mut config = CanvasConfig::new(size.x, size.y);
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    config.mode = CanvasMode::Persistent;
+    let (image, canvas) = commands.spawn_canvas(images.as_mut(), config);
+    let canvas = canvas.id();
+
+    // The canvas is shown over the whole view of the camera that pointer input is mapped from
+    let camera = commands
+        .spawn((
+            Camera2d,
+            ShapeCompositeLayers(vec![ShapeCompositeLayer::new(
+                image,
+                ShapeBlendMode::Normal,
+            )]),
+        ))
+        .id();
+
+    let mut brush = ShapeConfig::default_2d();
+    brush.set_color(MIDNIGHT_BLUE);
+    brush.thickness = 6.0;
+    commands
+        .entity(canvas)
+        .insert(ShapePaint::new(camera, brush));
+}
+
+fn handle_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut paint: Query<(&mut ShapePaint, &Canvas)>,
+    mut patterns: ResMut<ShapeDashPatterns>,
+    mut saved: Local<Vec<u8>>,
+) {
+    let (mut paint, canvas) = paint.single_mut();
+    if keys.just_pressed(KeyCode::KeyZ) {
+        paint.undo();
+    }
+    if keys.just_pressed(KeyCode::KeyY) {
+        paint.redo();
+    }
+    if keys.just_pressed(KeyCode::KeyC) {
+        paint.clear();
+    }
+    if keys.just_pressed(KeyCode::KeyS) {
+        *saved = paint.export(canvas, &patterns).to_bytes();
+        info!("Saved drawing in {} bytes", saved.len());
+    }
+    if keys.just_pressed(KeyCode::KeyL) {
+        match PaintRecording::from_bytes(&saved) {
+            Ok(recording) => paint.import(&recording, canvas, &mut patterns),
+            Err(error) => warn!("Failed to load drawing: {error}"),
+        }
+    }
+
+    for (key, color) in [
+        (KeyCode::Digit1, MIDNIGHT_BLUE),
+        (KeyCode::Digit2, CRIMSON),
+        (KeyCode::Digit3, SEA_GREEN),
+    ] {
+        if keys.just_pressed(key) {
+            paint.brush.set_color(color);
+            paint.brush.alpha_mode = ShapeAlphaMode::Blend;
+            paint.brush.thickness = 6.0;
+        }
+    }
+    if keys.just_pressed(KeyCode::KeyD) {
+        paint.brush.dash = match paint.brush.dash {
+            Some(_) => None,
+            None => patterns.add(&[12.0, 12.0]),
+        };
+    }
+    if keys.just_pressed(KeyCode::KeyE) {
+        let erasing = paint.brush.alpha_mode == ShapeAlphaMode::Erase;
+        paint.brush.alpha_mode = if erasing {
+            ShapeAlphaMode::Blend
+        } else {
+            ShapeAlphaMode::Erase
+        };
+        paint.brush.thickness = if erasing { 6.0 } else { 30.0 };
+    }
+}
@@ -0,0 +1,76 @@
+// Demonstrates drawing immediate mode shapes relative to entities by anchoring the painter to them
+// Each wandering unit is drawn with a ring around it's feet and a bar above it without querying it's transform
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (wander, draw))
+        .run();
+}
+
+#[derive(Component)]
+struct Unit(f32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 8.0, 12.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(3.0, 8.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let mesh = meshes.add(Capsule3d::new(0.3, 0.8));
+    let material = materials.add(StandardMaterial::from_color(STEEL_BLUE));
+    for i in 0..5 {
+        commands.spawn((
+            Unit(i as f32 * 1.3),
+            PainterAnchor,
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+        ));
+    }
+}
+
+fn wander(time: Res<Time>, mut units: Query<(&Unit, &mut Transform)>) {
+    for (unit, mut tf) in &mut units {
+        let t = time.elapsed_secs() * 0.5 + unit.0;
+        tf.translation = Vec3::new((t * 1.1).sin() * 5.0, 0.7, (t * 0.7).cos() * 3.0);
+    }
+}
+
+fn draw(time: Res<Time>, mut painter: ShapePainter, units: Query<(Entity, &Unit)>) {
+    for (entity, unit) in &units {
+        // Shapes are drawn relative to the unit's transform
+        painter.reset();
+        painter.anchor_to(entity);
+
+        painter.translate(Vec3::Y * -0.65);
+        painter.rotate_x(-std::f32::consts::FRAC_PI_2);
+        painter.hollow = true;
+        painter.thickness = 0.05;
+        painter.set_color(GOLD);
+        painter.circle(0.6);
+
+        let hp = ((time.elapsed_secs() + unit.0).sin() + 1.0) / 2.0;
+        painter.reset();
+        painter.anchor_to(entity);
+        painter.translate(Vec3::Y * 1.0);
+        painter.alignment = Alignment::Billboard;
+        painter.set_color(GREEN * hp + RED * (1.0 - hp));
+        painter.rect(Vec2::new(0.2 + hp, 0.15));
+    }
+}
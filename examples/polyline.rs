@@ -0,0 +1,80 @@
+// Demonstrates drawing polylines and hollow polygons with each join type, a closed path and a retained polyline that grows every frame
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_polylines, extend_trace))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn((Camera2d, Msaa::Off));
+
+    shapes.thickness = 4.0;
+    shapes.set_color(GOLD);
+    shapes.transform.translation = Vec3::new(-250.0, -300.0, 0.0);
+    shapes.dynamic_polyline(&[]);
+}
+
+// Appends a point to the trace each frame, only the new point is uploaded to the gpu
+fn extend_trace(time: Res<Time>, mut traces: Query<&mut DynamicPolylineComponent>) {
+    let seconds = time.elapsed_secs();
+    for mut trace in &mut traces {
+        if trace.len() >= 1000 {
+            trace.clear();
+        }
+        let x = trace.len() as f32 * 0.5;
+        trace.push(Vec2::new(x, 30.0 * (seconds * 4.0).sin()));
+    }
+}
+
+fn draw_polylines(time: Res<Time>, mut painter: ShapePainter) {
+    let seconds = time.elapsed_secs();
+    let points: Vec<Vec2> = (0..8)
+        .map(|i| {
+            let x = i as f32 * 40.0 - 140.0;
+            let y = if i % 2 == 0 { -40.0 } else { 40.0 };
+            Vec2::new(x, y * (seconds + i as f32).sin())
+        })
+        .collect();
+
+    painter.thickness = 16.0;
+    painter.translate(Vec3::Y * 200.0);
+
+    for (join, color) in [
+        (Join::Round, MIDNIGHT_BLUE),
+        (Join::Miter, SEA_GREEN),
+        (Join::Bevel, CRIMSON),
+    ] {
+        painter.join = join;
+        painter.set_color(color);
+        painter.polyline(&points);
+
+        // Closed hollow polygons use the same joins at their outer corners
+        painter.hollow = true;
+        painter.translate(Vec3::X * -300.0);
+        painter.ngon(3.0, 60.0);
+        painter.translate(Vec3::X * 300.0);
+        painter.hollow = false;
+
+        painter.translate(Vec3::NEG_Y * 200.0);
+    }
+
+    // Closed paths join their last segment back to their first
+    let mut outline: Vec<Vec3> = (0..5)
+        .map(|i| {
+            let angle = seconds + i as f32 * std::f32::consts::TAU / 5.0;
+            Vec3::new(250.0 + 60.0 * angle.cos(), 260.0 + 60.0 * angle.sin(), 0.0)
+        })
+        .collect();
+    outline.push(outline[0]);
+    painter.join = Join::Miter;
+    painter.set_color(ORANGE_RED);
+    painter.path(&outline);
+}
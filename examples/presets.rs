@@ -0,0 +1,66 @@
+// Demonstrates the ShapeConfig presets for a debug overlay over a 3D scene and a HUD drawn by a 2D camera
+
+use bevy::{color::palettes::css::*, prelude::*, render::camera::ClearColorConfig, sprite::Anchor};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_debug, draw_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(3.0, 3.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(3.0, 8.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(materials.add(StandardMaterial::from_color(STEEL_BLUE))),
+    ));
+}
+
+fn draw_debug(time: Res<Time>, mut painter: ShapePainter) {
+    // Billboarded pixel wide lines drawn through the cube
+    painter.set_config(ShapeConfig::debug_3d());
+    painter.rotate_y(time.elapsed_secs());
+    for (axis, color) in [(Vec3::X, RED), (Vec3::Y, LIME), (Vec3::Z, BLUE)] {
+        painter.set_color(color);
+        painter.line(-axis, axis);
+    }
+    painter.set_color(GOLD);
+    painter.circle(4.0);
+}
+
+fn draw_hud(time: Res<Time>, mut painter: ShapePainter) {
+    // A progress bar along the bottom of the screen
+    painter.set_config(ShapeConfig::ui_2d());
+    painter.set_translation(Vec3::new(0.0, -300.0, 0.0));
+    painter.hollow = true;
+    painter.set_color(WHITE);
+    painter.rect(Vec2::new(404.0, 28.0));
+    painter.hollow = false;
+    painter.rect_anchor = Anchor::CenterLeft;
+    painter.translate(Vec3::X * -200.0);
+    painter.set_color(ORANGE);
+    painter.rect(Vec2::new(400.0 * (time.elapsed_secs() * 0.2).fract(), 24.0));
+}
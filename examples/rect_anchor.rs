@@ -0,0 +1,43 @@
+// Demonstrates anchoring rectangles at a corner or edge so they grow from it, e.g. for progress bars
+
+use bevy::{color::palettes::css::*, prelude::*, sprite::Anchor};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(time: Res<Time>, mut painter: ShapePainter) {
+    let progress = (time.elapsed_secs().sin() + 1.0) / 2.0;
+
+    // The bar's left edge stays at the painter's translation as it grows, no compensation needed
+    painter.set_translation(Vec3::new(-200.0, 100.0, 0.0));
+    painter.rect_anchor = Anchor::CenterLeft;
+    painter.set_color(DARK_SLATE_GRAY);
+    painter.rect(Vec2::new(400.0, 30.0));
+    painter.set_color(LIMEGREEN);
+    painter.rect(Vec2::new(400.0 * progress, 30.0));
+
+    // Anchored at a corner the rectangle also rotates and scales around that corner
+    painter.set_translation(Vec3::new(0.0, -100.0, 0.0));
+    painter.rotate_z(time.elapsed_secs());
+    painter.rect_anchor = Anchor::BottomLeft;
+    painter.set_color(ORANGE);
+    painter.rect(Vec2::new(120.0, 60.0));
+
+    painter.hollow = true;
+    painter.thickness = 2.0;
+    painter.thickness_type = ThicknessType::Pixels;
+    painter.set_color(WHITE);
+    painter.rect_anchor = Anchor::Center;
+    painter.circle(6.0);
+}
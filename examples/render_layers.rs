@@ -30,6 +30,7 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
+    mut shapes: ShapeCommands,
 ) {
     let image_handle =
         Canvas::create_image(images.as_mut(), 512, 512, ImageSampler::Default, false);
@@ -55,9 +56,23 @@ fn setup(
         },
         Transform::from_translation(Vec3::new(0.0, 0.0, 15.0)).looking_at(Vec3::ZERO, Vec3::Y),
         Msaa::Off,
-        first_pass_layer,
+        first_pass_layer.clone(),
     ));
 
+    // Retained shapes parented to an entity with ShapeInheritLayers are drawn to the same layer
+    shapes.hollow = true;
+    shapes.thickness = 0.1;
+    shapes.set_color(GOLD);
+    let ring = shapes.circle(5.0).id();
+    commands
+        .spawn((
+            Transform::default(),
+            Visibility::default(),
+            first_pass_layer,
+            ShapeInheritLayers,
+        ))
+        .add_child(ring);
+
     let cube_size = 4.0;
     let cube_handle = meshes.add(Mesh::from(Cuboid::new(cube_size, cube_size, cube_size)));
 
@@ -0,0 +1,64 @@
+// Demonstrates promoting shapes drawn in immediate mode to retained entities without rewriting them with ShapeCommands
+// Press space to promote the flower, after which it's petals are entities spun by a separate system
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .init_resource::<Promoted>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw, spin_petals))
+        .run();
+}
+
+#[derive(Resource, Default)]
+struct Promoted(Option<Vec<Entity>>);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw_flower(painter: &mut ShapePainter) {
+    for i in 0..8 {
+        let angle = i as f32 / 8.0 * std::f32::consts::TAU;
+        painter.set_translation(Vec3::new(angle.cos(), angle.sin(), 0.0) * 120.0);
+        painter.set_rotation(Quat::from_rotation_z(angle));
+        painter.set_color(HOT_PINK.mix(&ORCHID, i as f32 / 8.0));
+        painter.corner_radii = Vec4::splat(20.0);
+        painter.rect(Vec2::new(120.0, 50.0));
+    }
+    painter.set_translation(Vec3::Z);
+    painter.set_rotation(Quat::IDENTITY);
+    painter.set_color(GOLD);
+    painter.circle(60.0);
+}
+
+fn draw(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut promoted: ResMut<Promoted>,
+    mut painter: ShapePainter,
+) {
+    if promoted.0.is_some() {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Space) {
+        promoted.0 = Some(painter.retain(draw_flower));
+    } else {
+        draw_flower(&mut painter);
+    }
+}
+
+fn spin_petals(time: Res<Time>, promoted: Res<Promoted>, mut transforms: Query<&mut Transform>) {
+    let Some(entities) = &promoted.0 else {
+        return;
+    };
+    for &entity in entities {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.rotate_z(time.delta_secs());
+        }
+    }
+}
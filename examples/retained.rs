@@ -11,7 +11,7 @@ fn main() {
         .add_plugins(ShapePlugin::default())
         .insert_resource(ClearColor(DIM_GRAY.into()))
         .add_systems(Startup, setup)
-        .add_systems(Update, rotate_circle)
+        .add_systems(Update, (rotate_circle, shake_rect))
         .run();
 }
 
@@ -29,6 +29,14 @@ fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
             parent.line(Vec3::ZERO, Vec3::Y * 2.0);
         }
     });
+
+    // Shakes applied by a ShapeTransformModifier are only visual, the rect's transform is left untouched
+    shapes.hollow = true;
+    shapes.thickness = 0.1;
+    shapes.set_translation(Vec3::X * 5.0);
+    shapes
+        .rect(Vec2::splat(2.0))
+        .insert(ShapeTransformModifier::new(|tf| *tf));
 }
 
 fn rotate_circle(time: Res<Time>, mut circle: Query<&mut Transform, With<DiscComponent>>) {
@@ -36,3 +44,13 @@ fn rotate_circle(time: Res<Time>, mut circle: Query<&mut Transform, With<DiscCom
         .iter_mut()
         .for_each(|mut tf| tf.rotation *= Quat::from_rotation_z(time.delta_secs()))
 }
+
+fn shake_rect(time: Res<Time>, mut modifiers: Query<&mut ShapeTransformModifier>) {
+    let seconds = time.elapsed_secs();
+    let offset = Vec3::new((seconds * 37.0).sin(), (seconds * 29.0).cos(), 0.0) * 0.1;
+    for mut modifier in &mut modifiers {
+        *modifier = ShapeTransformModifier::new(move |tf| {
+            tf.mul_transform(Transform::from_translation(offset))
+        });
+    }
+}
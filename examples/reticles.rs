@@ -0,0 +1,54 @@
+// Demonstrates a set of crosshairs built from lines and discs, click to fire and watch their spread grow and recover
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .insert_resource(Spread(0.0))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (fire, draw).chain())
+        .run();
+}
+
+#[derive(Resource)]
+struct Spread(f32);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn fire(time: Res<Time>, buttons: Res<ButtonInput<MouseButton>>, mut spread: ResMut<Spread>) {
+    if buttons.just_pressed(MouseButton::Left) {
+        spread.0 = (spread.0 + 12.0).min(40.0);
+    }
+    spread.0 *= (-6.0 * time.delta_secs()).exp();
+}
+
+fn draw(spread: Res<Spread>, mut painter: ShapePainter) {
+    let reticles = [
+        Reticle::cross(6.0, 14.0),
+        Reticle::cross(6.0, 14.0).with_dot(2.0),
+        Reticle::dot(3.0).with_circle(18.0),
+        Reticle::cross(10.0, 12.0)
+            .with_rotation(FRAC_PI_4)
+            .with_circle(20.0)
+            .with_circle_gap(0.8),
+        Reticle::cross(8.0, 16.0)
+            .with_arms(3)
+            .with_rotation(-FRAC_PI_4 * 2.0),
+    ];
+
+    painter.thickness = 2.0;
+    painter.cap = Cap::None;
+    for (i, reticle) in reticles.iter().enumerate() {
+        painter.set_translation(Vec3::new(i as f32 * 150.0 - 300.0, 0.0, 0.0));
+        painter.set_color(WHITE);
+        painter.reticle(&reticle.clone().with_spread(spread.0));
+    }
+}
@@ -0,0 +1,81 @@
+// Demonstrates drawing a HUD in front of a moving 3D camera with translations measured in pixels from the corners of it's viewport
+
+use bevy::{color::palettes::css::*, prelude::*, window::PrimaryWindow};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ShapePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (orbit_camera, draw_scene, draw_hud).chain())
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+fn orbit_camera(time: Res<Time>, mut camera: Query<&mut Transform, With<Camera3d>>) {
+    let angle = time.elapsed_secs() * 0.3;
+    let mut transform = camera.single_mut();
+    *transform = Transform::from_xyz(angle.sin() * 8.0, 2.0, angle.cos() * 8.0)
+        .looking_at(Vec3::ZERO, Vec3::Y);
+}
+
+fn draw_scene(mut painter: ShapePainter) {
+    painter.set_3d();
+    painter.set_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2));
+    painter.hollow = true;
+    painter.thickness = 0.05;
+    for i in 1..6 {
+        painter.set_color(DEEP_SKY_BLUE.mix(&CRIMSON, i as f32 / 5.0));
+        painter.circle(i as f32);
+    }
+}
+
+fn draw_hud(
+    time: Res<Time>,
+    mut painter: ShapePainter,
+    camera: Query<Entity, With<Camera3d>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+) {
+    painter.set_3d();
+    painter.set_screen_space(camera.single());
+
+    // A health bar measured from the top left corner, sized in pixels
+    let health = time.elapsed_secs().sin() * 0.5 + 0.5;
+    painter.set_translation(Vec3::new(130.0, 30.0, 0.0));
+    painter.corner_radii = Vec4::splat(6.0);
+    painter.set_color(DIM_GRAY);
+    painter.rect(Vec2::new(220.0, 24.0));
+    painter.set_translation(Vec3::new(20.0 + health * 110.0, 30.0, 0.0));
+    painter.set_color(LIME);
+    painter.rect(Vec2::new(health * 220.0, 24.0));
+
+    // A compass measured from the bottom right corner, turning with the camera
+    painter.set_screen_corner(ScreenCorner::BottomRight);
+    painter.set_translation(Vec3::new(70.0, 70.0, 0.0));
+    painter.hollow = true;
+    painter.thickness = 4.0;
+    painter.set_color(WHITE);
+    painter.circle(50.0);
+    painter.rotate_z(time.elapsed_secs() * 0.3);
+    painter.hollow = false;
+    painter.set_color(ORANGE_RED);
+    painter.ngon(3.0, 12.0);
+
+    // A ring around the cursor, as window coordinates match translations from the top left corner
+    let Some(cursor) = window.single().cursor_position() else {
+        return;
+    };
+    painter.set_screen_corner(ScreenCorner::TopLeft);
+    painter.set_translation(cursor.extend(0.0));
+    painter.hollow = true;
+    painter.thickness = 2.0;
+    painter.set_color(GOLD);
+    painter.circle(16.0);
+}
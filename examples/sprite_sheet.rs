@@ -0,0 +1,105 @@
+// Demonstrates drawing single frames of a sprite sheet by setting the region of the texture drawn on a rect, stretched or tiled
+// The sheet is a canvas with a frame of a spinning shape drawn into each of it's cells
+// Retained rects step through the cells of a TextureAtlasLayout while sharing a single batch
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+const FRAMES: u32 = 8;
+const FRAME_SIZE: f32 = 128.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (draw_sheet, draw_sprites, animate_atlases))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut shapes: ShapeCommands,
+) {
+    let mut config = CanvasConfig::new(FRAMES * FRAME_SIZE as u32, FRAME_SIZE as u32);
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    let (image, _) = commands.spawn_canvas(images.as_mut(), config);
+
+    commands.spawn(Camera2d);
+
+    let layout = layouts.add(TextureAtlasLayout::from_grid(
+        UVec2::splat(FRAME_SIZE as u32),
+        FRAMES,
+        1,
+        None,
+        None,
+    ));
+    shapes.texture = Some(image);
+    for i in 0..FRAMES {
+        shapes.set_translation(Vec3::new(i as f32 * 70.0 - 245.0, 40.0, 0.0));
+        shapes
+            .rect(Vec2::splat(60.0))
+            .insert(ShapeTextureAtlas(TextureAtlas {
+                layout: layout.clone(),
+                index: i as usize,
+            }));
+    }
+}
+
+fn draw_sheet(mut painter: ShapePainter, canvas: Query<(Entity, &Canvas)>) {
+    let (canvas, _) = canvas.single();
+    painter.set_canvas(canvas);
+    painter.set_color(GOLD);
+    painter.corner_radii = Vec4::splat(8.0);
+
+    // Canvases are centered on the origin, so start from the center of the leftmost cell
+    let sheet_width = FRAMES as f32 * FRAME_SIZE;
+    for frame in 0..FRAMES {
+        painter.set_translation(Vec3::X * ((frame as f32 + 0.5) * FRAME_SIZE - sheet_width / 2.0));
+        painter.set_rotation(Quat::from_rotation_z(
+            frame as f32 / FRAMES as f32 * std::f32::consts::FRAC_PI_2,
+        ));
+        painter.rect(Vec2::splat(FRAME_SIZE * 0.6));
+    }
+}
+
+fn draw_sprites(time: Res<Time>, mut painter: ShapePainter, canvas: Query<&Canvas>) {
+    let canvas = canvas.single();
+    let image_size = Vec2::new(canvas.width as f32, canvas.height as f32);
+
+    // The whole sheet
+    painter.set_translation(Vec3::Y * 200.0);
+    painter.image(canvas.image.clone(), image_size * 0.8);
+
+    // A sprite animated by stepping through the frames of the sheet in pixels
+    let frame = (time.elapsed_secs() * 12.0) as u32 % FRAMES;
+    let min = Vec2::new(frame as f32 * FRAME_SIZE, 0.0);
+    painter.set_texture_region(Rect::from_corners(min, min + FRAME_SIZE), image_size);
+    painter.set_translation(Vec3::new(-150.0, -100.0, 0.0));
+    painter.image(canvas.image.clone(), Vec2::splat(200.0));
+
+    // The same frame flipped horizontally by a negative scale in uv coordinates
+    let frame_uv = FRAME_SIZE / image_size;
+    painter.set_texture_offset_scale(
+        Vec2::new((frame + 1) as f32 * frame_uv.x, 0.0),
+        Vec2::new(-frame_uv.x, 1.0),
+    );
+    painter.set_translation(Vec3::new(150.0, -100.0, 0.0));
+    painter.image(canvas.image.clone(), Vec2::splat(200.0));
+
+    // The frame repeated along a strip rather than stretched across it
+    painter.set_texture_region(Rect::from_corners(min, min + FRAME_SIZE), image_size);
+    painter.texture_tile = Some(Vec2::splat(50.0));
+    painter.set_translation(Vec3::Y * -275.0);
+    painter.image(canvas.image.clone(), Vec2::new(600.0, 50.0));
+}
+
+fn animate_atlases(time: Res<Time>, mut atlases: Query<&mut ShapeTextureAtlas>) {
+    let step = (time.elapsed_secs() * 12.0) as usize;
+    for (i, mut atlas) in atlases.iter_mut().enumerate() {
+        atlas.0.index = (step + i) % FRAMES as usize;
+    }
+}
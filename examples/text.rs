@@ -0,0 +1,67 @@
+// Demonstrates drawing text with a font registered with ShapeFonts, run with `--features text`
+
+use std::f32::consts::TAU;
+
+use bevy::{color::palettes::css::*, prelude::*, sprite::Anchor};
+use bevy_vector_shapes::prelude::*;
+
+// Any TrueType or OpenType font can be used, place one at this path to run the example
+const FONT_PATH: &str = "assets/fonts/FiraSans-Bold.ttf";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        // Numbers drawn with `painter.number` are grouped by thousands
+        .insert_resource(ShapeNumberFormat::grouped(",", "."))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut fonts: ResMut<ShapeFonts>, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera2d);
+
+    let data = std::fs::read(FONT_PATH).expect("font should exist at FONT_PATH");
+    fonts
+        .add(&mut images, data)
+        .expect("font should be a valid TrueType or OpenType font");
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    let seconds = time.elapsed_secs();
+
+    // Text takes the painter's color, transform and render layers like any other shape
+    painter.font_size = 64.0;
+    painter.set_color(WHITE);
+    painter.set_translation(Vec3::Y * 200.0);
+    painter.text("bevy_vector_shapes");
+
+    // Text stays crisp at any scale
+    painter.set_translation(Vec3::ZERO);
+    painter.set_scale(Vec3::splat(1.0 + (seconds * 2.0).sin() * 0.5));
+    painter.set_color(GOLD);
+    painter.text("Scaled\nmultiline text");
+
+    // A label anchored to the left of a health bar
+    painter.set_scale(Vec3::ONE);
+    painter.set_translation(Vec3::new(-200.0, -200.0, 0.0));
+    painter.font_size = 24.0;
+    painter.text_anchor = Anchor::CenterLeft;
+    painter.set_color(LIME);
+    let health = (seconds / 4.0 * TAU).cos() * 0.5 + 0.5;
+    painter.text(&format!("HP {:>3.0}%", health * 100.0));
+
+    // Rects are centered on their translation, so offset by half the bar's width
+    painter.translate(Vec3::X * (150.0 + 100.0 * health));
+    painter.corner_radii = Vec4::splat(4.0);
+    painter.rect(Vec2::new(200.0 * health, 20.0));
+
+    // A score counter formatted by the ShapeNumberFormat resource
+    painter.set_translation(Vec3::new(200.0, -200.0, 0.0));
+    painter.text_anchor = Anchor::CenterRight;
+    painter.set_color(WHITE);
+    painter.decimals = 1;
+    painter.number(seconds as f64 * 12345.6);
+}
@@ -0,0 +1,65 @@
+// Demonstrates text curved around a gauge and along a path, run with `--features text`
+
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use bevy::{color::palettes::css::*, prelude::*, sprite::Anchor};
+use bevy_vector_shapes::prelude::*;
+
+// Any TrueType or OpenType font can be used, place one at this path to run the example
+const FONT_PATH: &str = "assets/fonts/FiraSans-Bold.ttf";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut fonts: ResMut<ShapeFonts>, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera2d);
+
+    let data = std::fs::read(FONT_PATH).expect("font should exist at FONT_PATH");
+    fonts
+        .add(&mut images, data)
+        .expect("font should be a valid TrueType or OpenType font");
+}
+
+fn draw(mut painter: ShapePainter, time: Res<Time>) {
+    let seconds = time.elapsed_secs();
+
+    // A gauge with a title around it's top and a value around it's bottom
+    painter.set_translation(Vec3::X * -200.0);
+    painter.hollow = true;
+    painter.thickness = 16.0;
+    painter.set_color(MIDNIGHT_BLUE);
+    painter.circle(120.0);
+    let value = (seconds.sin() + 1.0) / 2.0;
+    painter.set_color(GOLD);
+    painter.arc(120.0, FRAC_PI_2 - value * 2.0 * PI, FRAC_PI_2);
+
+    painter.font_size = 28.0;
+    painter.set_color(WHITE);
+    painter.text_arc("ENGINE TEMPERATURE", 140.0, FRAC_PI_2);
+    painter.text_arc(&format!("{:.0} degrees", value * 120.0), -140.0, -FRAC_PI_2);
+
+    // A wave with text flowing along it from it's start
+    painter.reset();
+    let points: Vec<Vec2> = (0..=64)
+        .map(|i| {
+            let x = i as f32 * 5.0;
+            Vec2::new(x, (x / 40.0 + seconds * 2.0).sin() * 30.0)
+        })
+        .collect();
+    painter.set_translation(Vec3::new(80.0, 0.0, 0.0));
+    painter.thickness = 2.0;
+    painter.set_color(SILVER);
+    painter.polyline(&points);
+
+    painter.font_size = 24.0;
+    painter.text_anchor = Anchor::BottomLeft;
+    painter.set_color(LIME);
+    painter.text_path("Text flowing along a path", &points);
+}
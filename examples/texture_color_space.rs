@@ -0,0 +1,62 @@
+// Demonstrates sampling a data texture as linear though it's image is sRGB, as heatmaps and lookup tables should be
+// The left heatmap is gamma decoded when sampled and skews towards the low end, the right is sampled as it was written
+
+use bevy::{
+    color::palettes::css::*,
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .insert_resource(ClearColor(DIM_GRAY.into()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+#[derive(Resource)]
+struct Heatmap(Handle<Image>);
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera2d);
+
+    // A linear ramp of values written into an sRGB image, as an image loaded from disk would be
+    let size = 64;
+    let data = (0..size * size)
+        .flat_map(|i| {
+            let value = ((i % size) * 255 / (size - 1)) as u8;
+            [value, value, value, 255]
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    ShapeTextureColorSpace::allow_views(&mut image);
+    commands.insert_resource(Heatmap(images.add(image)));
+}
+
+fn draw(mut painter: ShapePainter, heatmap: Res<Heatmap>) {
+    for (x, color_space) in [
+        (-220.0, ShapeTextureColorSpace::Image),
+        (220.0, ShapeTextureColorSpace::Linear),
+    ] {
+        painter.set_translation(Vec3::new(x, 0.0, 0.0));
+        painter.texture_color_space = color_space;
+        painter.image(heatmap.0.clone(), Vec2::splat(400.0));
+    }
+}
@@ -0,0 +1,69 @@
+// Demonstrates clipping a bevy_ui image to shapes drawn to a canvas, requires the `ui` feature
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw_mask)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut masks: ResMut<Assets<ShapeUiMask>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn(Camera2d);
+
+    // The mask is stretched over the node, so the canvas matches the node's aspect ratio
+    let mut config = CanvasConfig::new(512, 512);
+    config.clear_color = ClearColorConfig::Custom(Color::NONE);
+    let (mask, _) = commands.spawn_canvas(images.as_mut(), config);
+
+    let material = ShapeUiMask::new(mask).with_image(asset_server.load("shapes_alpha_modes.png"));
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(40.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(400.0),
+                    height: Val::Px(400.0),
+                    ..default()
+                },
+                MaterialNode(masks.add(material.clone())),
+            ));
+            parent.spawn((
+                Node {
+                    width: Val::Px(200.0),
+                    height: Val::Px(200.0),
+                    ..default()
+                },
+                MaterialNode(masks.add(material.with_color(GOLD))),
+            ));
+        });
+}
+
+fn draw_mask(time: Res<Time>, mut painter: ShapePainter, canvas: Query<Entity, With<Canvas>>) {
+    painter.set_canvas(canvas.single());
+
+    // A rounded square with a hole erased from it that drifts from side to side
+    painter.corner_radii = Vec4::splat(80.0);
+    painter.rect(Vec2::splat(480.0));
+    painter.corner_radii = Vec4::ZERO;
+
+    painter.set_translation(Vec3::new(time.elapsed_secs().sin() * 120.0, 0.0, 1.0));
+    painter.alpha_mode = ShapeAlphaMode::Erase;
+    painter.circle(80.0);
+}
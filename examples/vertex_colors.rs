@@ -0,0 +1,39 @@
+// Demonstrates colors interpolated between the corners of rectangles and the vertices of triangles
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn draw(mut painter: ShapePainter) {
+    // A vertical ramp, corner colors are multiplied with the painter's color so keep it white
+    painter.translate(Vec3::X * -300.0);
+    painter.corner_colors = Some([WHITE.into(), WHITE.into(), NAVY.into(), NAVY.into()]);
+    painter.rect(Vec2::new(200.0, 240.0));
+
+    // A different color in each corner, the rounded corners still clip the colors
+    painter.translate(Vec3::X * 300.0);
+    painter.corner_radii = Vec4::splat(30.0);
+    painter.corner_colors = Some([RED.into(), LIME.into(), BLUE.into(), YELLOW.into()]);
+    painter.rect(Vec2::splat(240.0));
+
+    // The classic rgb triangle
+    painter.translate(Vec3::X * 300.0);
+    painter.vertex_colors = Some([RED.into(), LIME.into(), BLUE.into()]);
+    painter.triangle(
+        Vec2::new(0.0, 140.0),
+        Vec2::new(-120.0, -100.0),
+        Vec2::new(120.0, -100.0),
+    );
+}
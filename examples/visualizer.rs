@@ -0,0 +1,85 @@
+// Demonstrates updating thousands of retained shapes each frame, as an audio visualizer would,
+// using bulk updates that only flag the shapes whose values actually changed
+
+use bevy::{color::palettes::css::*, prelude::*};
+use bevy_vector_shapes::prelude::*;
+
+const BARS: usize = 256;
+const DOTS: usize = 64;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(Shape2dPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (update_bars, update_dots))
+        .run();
+}
+
+#[derive(Resource)]
+struct Visualizer {
+    bars: Vec<Entity>,
+    dots: Vec<Entity>,
+}
+
+fn setup(mut commands: Commands, mut shapes: ShapeCommands) {
+    commands.spawn(Camera2d);
+
+    let bars = (0..BARS)
+        .map(|i| {
+            shapes.transform.translation = Vec3::new(i as f32 * 4.0 - 512.0, -100.0, 0.0);
+            shapes.set_color(DEEP_SKY_BLUE);
+            shapes.rect(Vec2::new(3.0, 1.0)).id()
+        })
+        .collect();
+
+    let dots = (0..DOTS)
+        .map(|i| {
+            shapes.transform.translation = Vec3::new(i as f32 * 16.0 - 504.0, 200.0, 0.0);
+            shapes.set_color(GOLD);
+            shapes.circle(6.0).id()
+        })
+        .collect();
+
+    commands.insert_resource(Visualizer { bars, dots });
+}
+
+// A stand in for the magnitude of a frequency band, quantized as levels from a real analyzer would be
+fn level(band: usize, seconds: f32) -> f32 {
+    let t = band as f32 * 0.05;
+    let level = ((seconds * 3.0 + t).sin() * (seconds * 1.3 - t * 2.0).cos()).abs();
+    (level * 16.0).round() / 16.0
+}
+
+// Resize every bar from a query, bars whose level didn't change are not marked as changed
+fn update_bars(
+    time: Res<Time>,
+    visualizer: Res<Visualizer>,
+    mut bars: Query<&mut RectangleComponent>,
+) {
+    let seconds = time.elapsed_secs();
+    bars.update_many(
+        visualizer
+            .bars
+            .iter()
+            .enumerate()
+            .map(|(band, &entity)| (entity, level(band, seconds))),
+        |rect, level| rect.size.y = 1.0 + level * 200.0,
+    );
+}
+
+// Recolor every dot with a single queued command
+fn update_dots(time: Res<Time>, visualizer: Res<Visualizer>, mut shapes: ShapeCommands) {
+    let seconds = time.elapsed_secs();
+    let fills: Vec<_> = visualizer
+        .dots
+        .iter()
+        .enumerate()
+        .map(|(band, &entity)| {
+            let mut fill = ShapeFill::new(&shapes);
+            fill.color = GOLD.mix(&CRIMSON, level(band * 4, seconds)).into();
+            (entity, fill)
+        })
+        .collect();
+    shapes.set_many(fills);
+}
@@ -34,6 +34,14 @@
 //! }
 //! ```
 //!
+//! ## Custom shapes
+//! Shape types can be defined outside of this crate by implementing [`ShapeData`](render::ShapeData) and [`ShapeComponent`](render::ShapeComponent)
+//! and adding a [`ShapeTypePlugin`](render::ShapeTypePlugin) for the component, see the `custom_shape` example.
+//!
+//! ## Sorting in 2D
+//! 2D shapes are drawn in bevy's transparent 2D phase alongside `Sprite`s and `Mesh2d`s, all sorted by their z translation.
+//! Shapes at the same z as a sprite or mesh are drawn in an unspecified order relative to it unless the
+//! [`Shape2dSortBias`](shapes::Shape2dSortBias) resource is set to place them above or below, or to offset every shape.
 
 use bevy::prelude::*;
 
@@ -52,8 +60,21 @@ use painter::*;
 /// `use bevy_vector_shapes::prelude::*` to import commonly used items.
 pub mod prelude {
     pub use crate::painter::{
-        BuildShapeChildren, Canvas, CanvasCommands, CanvasConfig, CanvasMode, ShapeChildBuilder,
-        ShapeCommands, ShapeConfig, ShapeEntityCommands, ShapePainter, ShapeSpawner,
+        BuildShapeChildren, Canvas, CanvasCommands, CanvasConfig, CanvasLabel, CanvasMaterial,
+        CanvasMode, CanvasResizeMode, CompoundShape, DashPattern, Follows, Outlined,
+        PaintRecording, PaintStroke, PainterAnchor, PainterAnchors, RecordedStroke, ScreenCorner,
+        ScreenSpace, SetMany, ShapeChildBuilder, ShapeCommands, ShapeCompounds, ShapeConfig,
+        ShapeCursor, ShapeCursorPlugin, ShapeCursorState, ShapeDashPatterns, ShapeEntityCommands,
+        ShapeGroupConfig, ShapeGroups, ShapeHierarchyOrder, ShapeInheritLayers, ShapeKey,
+        ShapePaint, ShapePaintPlugin, ShapePainter, ShapePolylinePoints, ShapeQueryExt,
+        ShapeSortKey, ShapeSpawner, ShapeTextureAtlas, UvSpace, WrittenCanvases,
+    };
+    #[cfg(feature = "picking")]
+    pub use crate::painter::{ShapeCursorHint, ShapeCursorHintPlugin};
+    #[cfg(feature = "ui")]
+    pub use crate::render::ShapeUiMask;
+    pub use crate::render::{
+        ShapeBlendMode, ShapeCompositeLayer, ShapeCompositeLayers, ShapesEnabled,
     };
     pub use crate::{shapes::*, BaseShapeConfig, Shape2dPlugin, ShapePlugin};
 }
@@ -64,6 +85,31 @@ pub mod prelude {
 #[derive(Resource, Clone)]
 pub struct BaseShapeConfig(pub ShapeConfig);
 
+/// Invokes the given macro with each built in shape component, so every plugin that registers them stays in sync.
+macro_rules! for_each_shape_type {
+    ($add:ident) => {
+        $add!(LineComponent);
+        $add!(PolylineComponent);
+        $add!(DynamicPolylineComponent);
+        $add!(DiscComponent);
+        $add!(RectangleComponent);
+        $add!(RegularPolygonComponent);
+        $add!(TriangleComponent);
+        $add!(QuadComponent);
+        $add!(MetaballComponent);
+        $add!(BezierComponent);
+        $add!(BooleanComponent);
+        $add!(CapsuleComponent);
+        $add!(GridComponent);
+        $add!(RingComponent);
+        $add!(OutlineComponent);
+        $add!(CompactComponent);
+        #[cfg(feature = "text")]
+        $add!(GlyphComponent);
+    };
+}
+pub(crate) use for_each_shape_type;
+
 /// Plugin that contains all necessary functionality to draw shapes with a 2D camera.
 pub struct Shape2dPlugin {
     /// Default config that will be used for all [`ShapePainter`]s.
@@ -90,12 +136,14 @@ impl Plugin for Shape2dPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(BaseShapeConfig(self.base_config.clone()))
             .add_plugins(PainterPlugin)
-            .add_plugins(ShapeRenderPlugin)
-            .add_plugins(ShapeTypePlugin::<LineComponent>::default())
-            .add_plugins(ShapeTypePlugin::<DiscComponent>::default())
-            .add_plugins(ShapeTypePlugin::<RectangleComponent>::default())
-            .add_plugins(ShapeTypePlugin::<RegularPolygonComponent>::default())
-            .add_plugins(ShapeTypePlugin::<TriangleComponent>::default());
+            .add_plugins(ShapeRenderPlugin);
+
+        macro_rules! add {
+            ($shape:ty) => {
+                app.add_plugins(ShapeTypePlugin::<$shape>::default());
+            };
+        }
+        for_each_shape_type!(add);
     }
 }
 
@@ -134,10 +182,11 @@ impl Plugin for ShapePlugin {
         if !self.exclude_2d {
             app.add_plugins(Shape2dPlugin::new(self.base_config.clone()));
         }
-        app.add_plugins(ShapeType3dPlugin::<LineComponent>::default())
-            .add_plugins(ShapeType3dPlugin::<DiscComponent>::default())
-            .add_plugins(ShapeType3dPlugin::<RectangleComponent>::default())
-            .add_plugins(ShapeType3dPlugin::<RegularPolygonComponent>::default())
-            .add_plugins(ShapeType3dPlugin::<TriangleComponent>::default());
+        macro_rules! add {
+            ($shape:ty) => {
+                app.add_plugins(ShapeType3dPlugin::<$shape>::default());
+            };
+        }
+        for_each_shape_type!(add);
     }
 }
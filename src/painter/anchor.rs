@@ -0,0 +1,32 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+/// Marker component for entities that painters can draw relative to with [`ShapePainter::anchor_to`](crate::prelude::ShapePainter::anchor_to).
+///
+/// The [`GlobalTransform`] of each marked entity is cached in [`PainterAnchors`] after transforms are propagated.
+#[derive(Component, Default, Clone, Copy, Debug, Reflect)]
+pub struct PainterAnchor;
+
+/// Resource caching the [`GlobalTransform`] of each entity with a [`PainterAnchor`], updated in [`PostUpdate`].
+///
+/// Painters drawing during [`Update`] see the transforms of the previous frame, as they would by querying them directly.
+/// As every [`ShapePainter`](crate::prelude::ShapePainter) reads this resource it can't be modified from systems that also use a painter.
+#[derive(Resource, Default, Debug)]
+pub struct PainterAnchors(EntityHashMap<GlobalTransform>);
+
+impl PainterAnchors {
+    /// Cached transform of the given entity, `None` if it has no [`PainterAnchor`] or was spawned this frame.
+    pub fn get(&self, entity: Entity) -> Option<&GlobalTransform> {
+        self.0.get(&entity)
+    }
+}
+
+/// Caches the transforms of each [`PainterAnchor`] in [`PainterAnchors`].
+pub fn update_painter_anchors(
+    anchors: Query<(Entity, &GlobalTransform), With<PainterAnchor>>,
+    mut cache: ResMut<PainterAnchors>,
+) {
+    cache.0.clear();
+    cache
+        .0
+        .extend(anchors.iter().map(|(entity, tf)| (entity, *tf)));
+}
@@ -0,0 +1,60 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Command that sets a component on many existing entities at once, queued by [`ShapeCommands::set_many`](crate::prelude::ShapeCommands::set_many).
+///
+/// Entities without the component are skipped rather than given it, so no entity changes archetype,
+/// and components already equal to their new value are left untouched so they aren't flagged as changed.
+pub struct SetMany<C: Component> {
+    pub values: Vec<(Entity, C)>,
+}
+
+impl<C: Component + PartialEq> Command for SetMany<C> {
+    fn apply(self, world: &mut World) {
+        for (entity, value) in self.values {
+            if let Some(mut component) = world.get_mut::<C>(entity) {
+                component.set_if_neq(value);
+            }
+        }
+    }
+}
+
+/// Extension trait for queries of retained shape components, e.g. `Query<&mut DiscComponent>`,
+/// to update thousands of shapes each frame while only flagging those whose value changed.
+pub trait ShapeQueryExt<C> {
+    /// Set the component of each listed entity matched by the query, entities not matched are skipped.
+    fn set_many(&mut self, values: impl IntoIterator<Item = (Entity, C)>);
+
+    /// Update the component of each listed entity matched by the query with `f` and the entity's value,
+    /// entities not matched are skipped.
+    fn update_many<V>(
+        &mut self,
+        values: impl IntoIterator<Item = (Entity, V)>,
+        f: impl FnMut(&mut C, V),
+    );
+}
+
+impl<C: Component + Clone + PartialEq, F: QueryFilter> ShapeQueryExt<C>
+    for Query<'_, '_, &mut C, F>
+{
+    fn set_many(&mut self, values: impl IntoIterator<Item = (Entity, C)>) {
+        for (entity, value) in values {
+            if let Ok(mut component) = self.get_mut(entity) {
+                component.set_if_neq(value);
+            }
+        }
+    }
+
+    fn update_many<V>(
+        &mut self,
+        values: impl IntoIterator<Item = (Entity, V)>,
+        mut f: impl FnMut(&mut C, V),
+    ) {
+        for (entity, value) in values {
+            if let Ok(mut component) = self.get_mut(entity) {
+                let mut updated = component.clone();
+                f(&mut updated, value);
+                component.set_if_neq(updated);
+            }
+        }
+    }
+}
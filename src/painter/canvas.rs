@@ -1,23 +1,125 @@
+use std::borrow::Cow;
+
 use bevy::{
-    ecs::system::EntityCommands,
+    ecs::{entity::EntityHashSet, system::EntityCommands},
     image::ImageSampler,
     prelude::*,
     render::{
-        camera::RenderTarget,
+        camera::{RenderTarget, Viewport},
         view::{RenderLayers, ViewTarget},
     },
+    sprite::AlphaMode2d,
 };
 use wgpu::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
 
+use crate::{
+    painter::{ShapePainter, ShapeStorage},
+    shapes::ShapeMaterial,
+};
+
+/// Component naming a [`Canvas`] so shapes can target it with [`ShapeConfig::canvas_label`](crate::painter::ShapeConfig::canvas_label)
+/// instead of it's entity, e.g. shapes in scenes or prefabs authored before the canvas is spawned.
+///
+/// Labels should be unique, if several canvases share a label shapes are drawn to one of them.
+#[derive(Component, Reflect, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct CanvasLabel(pub Cow<'static, str>);
+
+impl CanvasLabel {
+    pub fn new(label: impl Into<Cow<'static, str>>) -> Self {
+        Self(label.into())
+    }
+}
+
+impl From<&'static str> for CanvasLabel {
+    fn from(label: &'static str) -> Self {
+        Self::new(label)
+    }
+}
+
+impl From<String> for CanvasLabel {
+    fn from(label: String) -> Self {
+        Self::new(label)
+    }
+}
+
+/// Resolves the [`ShapeMaterial::canvas`] of retained shapes targeting a [`CanvasLabel`].
+///
+/// Shapes whose label doesn't match any canvas aren't drawn until a canvas with that label is spawned.
+pub fn resolve_canvas_labels(
+    canvases: Query<(Entity, &CanvasLabel), With<Canvas>>,
+    mut shapes: Query<&mut ShapeMaterial>,
+) {
+    for mut material in &mut shapes {
+        let Some(label) = &material.canvas_label else {
+            continue;
+        };
+        let canvas = canvases
+            .iter()
+            .find(|(_, canvas_label)| *canvas_label == label)
+            .map_or(Entity::PLACEHOLDER, |(entity, _)| entity);
+        if material.canvas != Some(canvas) {
+            material.canvas = Some(canvas);
+        }
+    }
+}
+
+/// Resource containing the canvases that shapes were drawn to this frame.
+///
+/// Includes both immediate mode shapes and visible retained shapes, updated in [`PostUpdate`].
+#[derive(Resource, Default, Debug)]
+pub struct WrittenCanvases(EntityHashSet);
+
+impl WrittenCanvases {
+    /// Whether any shapes were drawn to the given canvas this frame.
+    pub fn contains(&self, canvas: Entity) -> bool {
+        self.0.contains(&canvas)
+    }
+
+    /// Iterate over the canvases that shapes were drawn to this frame.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Collects the canvases targeted by shapes this frame into [`WrittenCanvases`].
+///
+/// Runs before cameras are updated, so retained shapes use their visibility as of the previous frame.
+pub fn update_written_canvases(
+    storage: Res<ShapeStorage>,
+    shapes: Query<(&ShapeMaterial, &InheritedVisibility)>,
+    mut written: ResMut<WrittenCanvases>,
+) {
+    written.0.clear();
+    written.0.extend(storage.canvases());
+    written.0.extend(
+        shapes
+            .iter()
+            .filter(|(_, visibility)| visibility.get())
+            .filter_map(|(material, _)| material.canvas),
+    );
+}
+
 /// Prepares the camera associated with each canvas.
 ///
-/// Replaces the image handle when the canvas is resized and applies [`CanvasMode`] behaviours.
+/// Replaces the image handle when the canvas is resized, creates any missing mirrors and applies [`CanvasMode`] behaviours.
 pub fn update_canvases(
-    mut canvases: Query<(&mut Canvas, &mut Camera, &mut OrthographicProjection)>,
+    mut canvases: Query<(
+        Entity,
+        &mut Canvas,
+        &mut Camera,
+        &mut OrthographicProjection,
+        &mut Transform,
+    )>,
+    written: Res<WrittenCanvases>,
+    mut images: ResMut<Assets<Image>>,
 ) {
-    canvases
-        .iter_mut()
-        .for_each(|(mut canvas, mut camera, mut projection)| {
+    canvases.iter_mut().for_each(
+        |(entity, mut canvas, mut camera, mut projection, mut transform)| {
+            if canvas.mirrors.len() != canvas.mirror_levels as usize {
+                canvas.create_mirrors(&mut images);
+            }
+
             if let RenderTarget::Image(camera_handle) = &camera.target {
                 if camera_handle != &canvas.image {
                     camera.target = RenderTarget::Image(canvas.image.clone());
@@ -27,15 +129,20 @@ pub fn update_canvases(
 
             match canvas.mode {
                 CanvasMode::Continuous => {
+                    // Idle canvases only need to be cleared once after the last frame they were drawn to
+                    let is_written = written.contains(entity);
                     camera.clear_color = canvas.clear_color;
-                    camera.is_active = true;
+                    camera.is_active = is_written || canvas.was_written || canvas.redraw;
+                    canvas.was_written = is_written;
                 }
                 CanvasMode::Persistent => {
                     if canvas.redraw {
                         camera.clear_color = canvas.clear_color;
+                        canvas.redraw_tile = (canvas.redraw_tile_count() > 1).then_some(0);
                     } else {
                         camera.clear_color = ClearColorConfig::None;
                     }
+                    canvas.update_redraw_tile(&mut camera, &mut transform);
                 }
                 CanvasMode::OnDemand => {
                     camera.is_active = canvas.redraw;
@@ -43,13 +150,34 @@ pub fn update_canvases(
             }
 
             canvas.redraw = false;
-        })
+        },
+    )
+}
+
+/// Distance the 2D sort keys of canvas backgrounds are offset by, beyond the depth of a [`Camera2d`]'s default projection.
+pub(crate) const CANVAS_BACKGROUND_DEPTH: f32 = 2000.0;
+
+/// Draws the background of each canvas that is cleared this frame, see [`Canvas::background`].
+pub fn draw_canvas_backgrounds(
+    mut painter: ShapePainter,
+    canvases: Query<(Entity, &Canvas, &Camera)>,
+) {
+    for (entity, canvas, camera) in &canvases {
+        let Some(background) = &canvas.background else {
+            continue;
+        };
+        if camera.is_active && !matches!(camera.clear_color, ClearColorConfig::None) {
+            painter.canvas_background(entity, background);
+        }
+    }
 }
 
 /// Enum that determines when canvases are cleared and redrawn.
 #[derive(Default, Reflect)]
 pub enum CanvasMode {
-    /// Always clear and draw each frame
+    /// Clear and draw each frame that shapes are drawn to the canvas, see [`WrittenCanvases`]
+    ///
+    /// The canvas is left untouched while idle, call Canvas::redraw to draw it regardless
     #[default]
     Continuous,
     /// Always draw but don't clear until a call to Canvas::redraw
@@ -58,6 +186,26 @@ pub enum CanvasMode {
     OnDemand,
 }
 
+/// Enum that determines what happens to the existing contents of a canvas when it is resized.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum CanvasResizeMode {
+    /// Discard the existing contents, the canvas will need to be redrawn
+    #[default]
+    Discard,
+    /// Copy the existing contents into the resized texture without scaling, positioned by the given anchor.
+    /// `Vec2::ZERO` keeps the top left corner in place and `Vec2::ONE` keeps the bottom right corner in place.
+    Anchored(Vec2),
+    /// Stretch the existing contents to fill the resized texture
+    Scaled,
+}
+
+/// Pending copy of a canvas' contents from it's texture before a resize to the texture after.
+pub(crate) struct CanvasBlit {
+    pub source: Handle<Image>,
+    pub target: Handle<Image>,
+    pub mode: CanvasResizeMode,
+}
+
 /// Component containing data and methods for a given canvas.
 ///
 /// Can be spawned as part of a [`CanvasBundle`] with [`CanvasCommands::spawn_canvas`].
@@ -73,7 +221,29 @@ pub struct Canvas {
     pub mode: CanvasMode,
     /// Clear mode to revert to for [`CanvasMode::OnDemand`].
     pub clear_color: ClearColorConfig,
+    /// Determines what happens to the canvas' contents when it is resized, see [`CanvasResizeMode`].
+    pub resize_mode: CanvasResizeMode,
+    /// Number of downscaled copies of the canvas to maintain, see [`Canvas::mirrors`].
+    pub mirror_levels: u32,
+    /// Name of a [`CompoundShape`] registered with [`ShapeCompounds`] drawn each time the canvas is cleared,
+    /// behind every other shape drawn to it regardless of their z.
+    ///
+    /// The compound is drawn centered on the canvas with it's own colors. As it is only drawn when the canvas is cleared,
+    /// it is kept beneath the contents of persistent canvases until they are redrawn.
+    pub background: Option<String>,
+    /// Number of tiles along each axis that redraws of a [`CanvasMode::Persistent`] canvas are split into,
+    /// drawing one tile each frame so redrawing a very large canvas doesn't stall a single frame.
+    ///
+    /// The canvas is cleared as the first tile is drawn, and shapes outside of the current tile are discarded
+    /// until every tile is drawn, see [`Canvas::redraw_progress`]. While redrawing the canvas' camera has it's viewport
+    /// and translation set to the current tile.
+    pub redraw_tiles: UVec2,
+    mirrors: Vec<Handle<Image>>,
     redraw: bool,
+    redraw_tile: Option<u32>,
+    was_written: bool,
+    #[reflect(ignore)]
+    pending_blit: Option<CanvasBlit>,
 }
 
 impl Canvas {
@@ -104,6 +274,7 @@ impl Canvas {
                 mip_level_count: 1,
                 sample_count: 1,
                 usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC
                     | TextureUsages::COPY_DST
                     | TextureUsages::RENDER_ATTACHMENT,
                 view_formats: &[],
@@ -119,6 +290,8 @@ impl Canvas {
     /// Resize a canvas returning the new [`Handle<Image>`].
     ///
     /// Unfortunately due to a quirk in the bevy renderer you cannot re-use an image handle as a render target once it has been resized.
+    ///
+    /// The existing contents of the canvas are carried over to the new image according to [`Canvas::resize_mode`].
     pub fn resize(&mut self, assets: &mut Assets<Image>, width: u32, height: u32) -> Handle<Image> {
         self.width = width;
         self.height = height;
@@ -133,14 +306,162 @@ impl Canvas {
         let mut new_image = image.clone();
         new_image.resize(size);
         let handle = assets.add(new_image);
+
+        if self.resize_mode != CanvasResizeMode::Discard {
+            // If we've already been resized this frame the intermediate image was never drawn to
+            let source = match self.pending_blit.take() {
+                Some(blit) => blit.source,
+                None => self.image.clone(),
+            };
+            self.pending_blit = Some(CanvasBlit {
+                source,
+                target: handle.clone(),
+                mode: self.resize_mode,
+            });
+        }
+
         self.image = handle.clone();
+        // Mirrors are recreated at the new size the next time canvases are updated
+        self.mirrors.clear();
         handle
     }
 
+    /// Downscaled copies of the canvas, each half the size of the last starting from half the size of the canvas.
+    ///
+    /// Mirrors are updated each frame after the canvas is drawn to and recreated when it is resized,
+    /// so handles should be fetched each frame rather than stored.
+    pub fn mirrors(&self) -> &[Handle<Image>] {
+        &self.mirrors
+    }
+
+    /// Downscaled copy of the canvas at the given level, `1` being half size, `2` quarter size and so on.
+    ///
+    /// Level `0` is the canvas' own image.
+    pub fn mirror(&self, level: u32) -> Option<&Handle<Image>> {
+        match level {
+            0 => Some(&self.image),
+            _ => self.mirrors.get(level as usize - 1),
+        }
+    }
+
+    /// Replace the canvas' mirrors with new images matching [`Canvas::mirror_levels`].
+    fn create_mirrors(&mut self, assets: &mut Assets<Image>) {
+        let Some(image) = assets.get(&self.image).cloned() else {
+            return;
+        };
+
+        self.mirrors = (1..=self.mirror_levels)
+            .map(|level| {
+                let mut mirror = image.clone();
+                mirror.resize(Extent3d {
+                    width: (self.width >> level).max(1),
+                    height: (self.height >> level).max(1),
+                    ..default()
+                });
+                assets.add(mirror)
+            })
+            .collect();
+    }
+
+    /// Take the copy of the canvas' previous contents requested by the last resize, if any.
+    pub(crate) fn take_pending_blit(&mut self) -> Option<CanvasBlit> {
+        self.pending_blit.take()
+    }
+
+    /// Whether a copy of the canvas' previous contents is waiting to be applied.
+    pub(crate) fn has_pending_blit(&self) -> bool {
+        self.pending_blit.is_some()
+    }
+
     /// Mark this canvas to be redraw this frame, behaviour depends on [`CanvasMode`].
     pub fn redraw(&mut self) {
         self.redraw = true;
     }
+
+    /// Fraction of the tiles drawn so far by a redraw split across frames, see [`Canvas::redraw_tiles`].
+    ///
+    /// `None` if no such redraw is in progress.
+    pub fn redraw_progress(&self) -> Option<f32> {
+        self.redraw_tile
+            .map(|tile| tile as f32 / self.redraw_tile_count() as f32)
+    }
+
+    /// Tiles along each axis, limited so that every tile covers at least a pixel.
+    fn redraw_tile_grid(&self) -> UVec2 {
+        self.redraw_tiles
+            .max(UVec2::ONE)
+            .min(UVec2::new(self.width, self.height).max(UVec2::ONE))
+    }
+
+    fn redraw_tile_count(&self) -> u32 {
+        self.redraw_tile_grid().element_product()
+    }
+
+    /// Restrict the camera to the tile drawn this frame, or restore it to the whole canvas once every tile is drawn.
+    fn update_redraw_tile(&mut self, camera: &mut Camera, transform: &mut Transform) {
+        let Some(tile) = self.redraw_tile else {
+            return;
+        };
+        if tile >= self.redraw_tile_count() {
+            self.redraw_tile = None;
+            camera.viewport = None;
+            transform.translation = Vec2::ZERO.extend(transform.translation.z);
+            return;
+        }
+
+        let tiles = self.redraw_tile_grid();
+        let size = UVec2::new(self.width, self.height);
+        let tile_size = (size + tiles - 1) / tiles;
+        let min = UVec2::new(tile % tiles.x, tile / tiles.x) * tile_size;
+        let max = (min + tile_size).min(size);
+        camera.viewport = Some(Viewport {
+            physical_position: min,
+            physical_size: max - min,
+            ..default()
+        });
+
+        // Canvases are centered on the origin with y up, while pixels are from the top left with y down
+        let center = (min + max).as_vec2() / 2.0 - size.as_vec2() / 2.0;
+        transform.translation = Vec3::new(center.x, -center.y, transform.translation.z);
+        self.redraw_tile = Some(tile + 1);
+    }
+}
+
+/// Extension trait for materials to create them configured to display a [`Canvas`] with the colors it was drawn with.
+///
+/// Takes the canvas' image, as returned by [`CanvasCommands::spawn_canvas`] or [`Canvas::image`],
+/// the handle must be replaced when the canvas is resized.
+pub trait CanvasMaterial {
+    fn from_canvas(image: Handle<Image>) -> Self;
+}
+
+impl CanvasMaterial for StandardMaterial {
+    /// The material is unlit and unaffected by fog so the canvas isn't shaded by the scene.
+    ///
+    /// Shapes blended onto a transparent canvas leave it's color premultiplied by alpha, so the material uses
+    /// [`AlphaMode::Premultiplied`], which also displays opaque canvases correctly.
+    /// Canvas textures are sampled as linear color whether or not they are HDR, so no conversion is needed.
+    fn from_canvas(image: Handle<Image>) -> Self {
+        StandardMaterial {
+            base_color_texture: Some(image),
+            unlit: true,
+            fog_enabled: false,
+            alpha_mode: AlphaMode::Premultiplied,
+            ..default()
+        }
+    }
+}
+
+impl CanvasMaterial for ColorMaterial {
+    /// The material is alpha blended, as 2D materials have no premultiplied alpha mode the partially
+    /// transparent edges of shapes on a transparent canvas appear slightly darker than when drawn directly.
+    fn from_canvas(image: Handle<Image>) -> Self {
+        ColorMaterial {
+            texture: Some(image),
+            alpha_mode: AlphaMode2d::Blend,
+            ..default()
+        }
+    }
 }
 
 /// Configuration to be used when creating a [`CanvasBundle`]
@@ -160,6 +481,14 @@ pub struct CanvasConfig {
     pub sampler: ImageSampler,
     /// Whether to enable hdr for the associated camera and texture.
     pub hdr: bool,
+    /// Determines what happens to the canvas' contents when it is resized, see [`CanvasResizeMode`].
+    pub resize_mode: CanvasResizeMode,
+    /// Number of downscaled copies of the canvas to maintain, e.g. `2` for half and quarter size copies, see [`Canvas::mirrors`].
+    pub mirror_levels: u32,
+    /// Name of a [`CompoundShape`] drawn behind the canvas' contents each time it is cleared, see [`Canvas::background`].
+    pub background: Option<String>,
+    /// Number of tiles along each axis that redraws of a [`CanvasMode::Persistent`] canvas are split into, see [`Canvas::redraw_tiles`].
+    pub redraw_tiles: UVec2,
 }
 
 impl CanvasConfig {
@@ -172,6 +501,10 @@ impl CanvasConfig {
             order: -1,
             sampler: ImageSampler::Default,
             hdr: false,
+            resize_mode: CanvasResizeMode::default(),
+            mirror_levels: 0,
+            background: None,
+            redraw_tiles: UVec2::ONE,
         }
     }
 }
@@ -206,7 +539,15 @@ impl CanvasBundle {
 
                 mode: config.mode,
                 clear_color: config.clear_color,
+                resize_mode: config.resize_mode,
+                mirror_levels: config.mirror_levels,
+                background: config.background,
+                redraw_tiles: config.redraw_tiles,
+                mirrors: Vec::new(),
                 redraw: true,
+                redraw_tile: None,
+                was_written: false,
+                pending_blit: None,
             },
             render_layers: RenderLayers::none(),
         }
@@ -238,9 +579,9 @@ impl<'w, 's> CanvasCommands<'w> for Commands<'w, 's> {
             config.sampler.clone(),
             config.hdr,
         );
-        (
-            handle.clone(),
-            self.spawn(CanvasBundle::new(handle, config)),
-        )
+        let mut bundle = CanvasBundle::new(handle.clone(), config);
+        // Create mirrors up front so that their handles are available immediately
+        bundle.canvas.create_mirrors(assets);
+        (handle, self.spawn(bundle))
     }
 }
@@ -51,6 +51,15 @@ impl<'w, 's> ShapeEntityCommands<'w, 's> {
         self.commands().queue(children);
         self
     }
+
+    /// Inserts [`Follows`] so that this shape tracks the translation of `target` without becoming it's child.
+    ///
+    /// The config's translation is used as the offset from the target.
+    pub fn follow(&mut self, target: Entity) -> &mut Self {
+        let offset = self.config.transform.translation;
+        self.commands.insert(Follows { target, offset });
+        self
+    }
 }
 
 impl<'w, 's> Deref for ShapeEntityCommands<'w, 's> {
@@ -0,0 +1,157 @@
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{painter::ShapeStorage, prelude::*, render::ShapeData};
+
+/// Draws a part of a [`CompoundShape`], see [`CompoundShape::part`].
+pub type CompoundPartFn = Arc<dyn Fn(&mut ShapePainter) + Send + Sync>;
+
+/// A reusable group of shapes drawn as a single unit with [`ShapePainter::compound`].
+///
+/// Each part is a closure drawing shapes with a painter positioned relative to the compound's origin.
+/// The shapes are recorded the first time the compound is drawn and replayed from then on,
+/// so parts should draw the same shapes every time they are called.
+#[derive(Clone, Default)]
+pub struct CompoundShape {
+    parts: Vec<(Transform, CompoundPartFn)>,
+}
+
+impl CompoundShape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a part drawn by `draw`, offset from the compound's origin by `transform`.
+    ///
+    /// The painter's config is reset to the [`BaseShapeConfig`](crate::BaseShapeConfig) with a white color before each part,
+    /// when drawn each shape's color is multiplied by the color of the painter drawing the compound.
+    pub fn part(
+        mut self,
+        transform: Transform,
+        draw: impl Fn(&mut ShapePainter) + Send + Sync + 'static,
+    ) -> Self {
+        self.parts.push((transform, Arc::new(draw)));
+        self
+    }
+
+    pub(crate) fn parts(&self) -> &[(Transform, CompoundPartFn)] {
+        &self.parts
+    }
+}
+
+/// Resource containing the [`CompoundShape`]s that can be drawn by name with [`ShapePainter::compound`].
+///
+/// Compounds can be registered or replaced at any time, replacing a compound discards it's recorded shapes.
+/// As every [`ShapePainter`] reads this resource it can't be modified from systems that also use a painter.
+#[derive(Resource, Default, Clone)]
+pub struct ShapeCompounds {
+    compounds: HashMap<String, (u32, CompoundShape)>,
+    next_version: u32,
+}
+
+impl ShapeCompounds {
+    /// Register a compound under the given name, replacing any compound previously registered with it.
+    pub fn insert(&mut self, name: impl Into<String>, compound: CompoundShape) {
+        self.next_version = self.next_version.wrapping_add(1);
+        self.compounds
+            .insert(name.into(), (self.next_version, compound));
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<CompoundShape> {
+        self.compounds.remove(name).map(|(_, compound)| compound)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CompoundShape> {
+        self.compounds.get(name).map(|(_, compound)| compound)
+    }
+
+    /// Version of the compound registered under the given name, changes whenever the compound is replaced.
+    pub(crate) fn version(&self, name: &str) -> Option<u32> {
+        self.compounds.get(name).map(|(version, _)| *version)
+    }
+}
+
+trait RecordedShapes: Send + Sync {
+    fn replay(&self, storage: &mut ShapeStorage, config: &ShapeConfig);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct RecordedInstances<T: ShapeData>(Vec<T>);
+
+impl<T: ShapeData> RecordedShapes for RecordedInstances<T> {
+    fn replay(&self, storage: &mut ShapeStorage, config: &ShapeConfig) {
+        let transform = config.transform.compute_matrix();
+        let tint = config.color.to_linear().to_f32_array();
+        for data in &self.0 {
+            let mut data = data.clone();
+            data.set_transform(transform * data.transform());
+            let mut color = data.color();
+            for (channel, tint) in color.iter_mut().zip(tint) {
+                *channel *= tint;
+            }
+            data.set_color(color);
+            storage.send(config, data);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Shapes recorded in the local space of a [`CompoundShape`], grouped by type.
+#[derive(Default)]
+pub(crate) struct CompoundRecording {
+    shapes: HashMap<TypeId, Box<dyn RecordedShapes>>,
+}
+
+impl CompoundRecording {
+    pub(crate) fn push<T: ShapeData>(&mut self, data: T) {
+        let recorded = self
+            .shapes
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(RecordedInstances::<T>(Vec::new())));
+
+        // Entries are only inserted above with the type matching their key
+        recorded
+            .as_any_mut()
+            .downcast_mut::<RecordedInstances<T>>()
+            .unwrap()
+            .0
+            .push(data);
+    }
+
+    /// Recorded shapes of the given type.
+    pub(crate) fn get<T: ShapeData>(&self) -> &[T] {
+        self.shapes
+            .get(&TypeId::of::<T>())
+            .and_then(|recorded| recorded.as_any().downcast_ref::<RecordedInstances<T>>())
+            .map_or(&[], |recorded| &recorded.0)
+    }
+
+    /// Recorded shapes of the given type, mutably.
+    pub(crate) fn get_mut<T: ShapeData>(&mut self) -> &mut [T] {
+        self.shapes
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|recorded| recorded.as_any_mut().downcast_mut::<RecordedInstances<T>>())
+            .map_or(&mut [], |recorded| &mut recorded.0)
+    }
+
+    /// Send every recorded shape, placed and tinted by the given config.
+    pub(crate) fn replay(&self, storage: &mut ShapeStorage, config: &ShapeConfig) {
+        for recorded in self.shapes.values() {
+            recorded.replay(storage, config);
+        }
+    }
+}
@@ -3,8 +3,10 @@ use bevy::ecs::system::{SystemMeta, SystemParam};
 use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
+use bevy::sprite::Anchor;
 use bevy::utils::synccell::SyncCell;
 
+use super::atlas_texture_rect;
 use crate::prelude::*;
 use crate::render::ShapePipelineType;
 
@@ -27,12 +29,63 @@ pub struct ShapeConfig {
     /// If true spawned shape will have a [`ShapeFill`] with [`FillType::Stroke`], taking into account thickness and thickness_type.
     /// This doesn't use [`FillType`] directly in order to avoid unnecessary shuffling of thickness values when using both types of shape.
     pub hollow: bool,
+    /// Color of an outline `thickness` wide drawn over filled shapes by the same call, ignored while `hollow`.
+    ///
+    /// Drawn by the painter for discs, rectangles, regular polygons, triangles, quads and capsules,
+    /// and stored in the [`ShapeFill`] of retained shapes, see [`ShapeFill::stroke`].
+    pub stroke_color: Option<Color>,
     pub thickness: f32,
     pub thickness_type: ThicknessType,
 
     pub cap: Cap,
+    /// Overrides `cap` for the end of a [`LineComponent`], allowing abutted segments to avoid overlapping caps.
+    pub end_cap: Option<Cap>,
+    /// Join type used between connected segments of a [`PolylineComponent`] and at the corners of a hollow [`RegularPolygonComponent`].
+    pub join: Join,
+    /// Largest ratio of a [`Join::Miter`]'s length to the thickness before it is replaced by a bevel,
+    /// with the same meaning as `stroke-miterlimit` in SVG.
+    pub miter_limit: f32,
     pub roundness: f32,
     pub corner_radii: Vec4,
+    /// Corners of a [`RectangleComponent`] that are cut off diagonally by their corner radius instead of being rounded.
+    pub corner_chamfers: BVec4,
+    /// If true the rounded corners of a [`RectangleComponent`] have continuous curvature, avoiding the pinched look of circular corners on large rectangles.
+    pub smooth_corners: bool,
+    /// Point of a [`RectangleComponent`] placed at the config's transform, e.g. [`Anchor::CenterLeft`] for a progress bar
+    /// that grows to the right as it's size changes. Rotation and scale are also applied around the anchor.
+    pub rect_anchor: Anchor,
+    /// Colors at the top right, top left, bottom left and bottom right corners of a [`RectangleComponent`], interpolated across the rectangle and multiplied with `color`.
+    pub corner_colors: Option<[Color; 4]>,
+    /// Colors at each vertex of a [`TriangleComponent`], interpolated across the triangle and multiplied with `color`.
+    pub vertex_colors: Option<[Color; 3]>,
+    /// Angle in radians to rotate regular polygons by without rotating the transform, textures or children.
+    pub ngon_rotation: f32,
+    /// Dash pattern registered with [`ShapeDashPatterns`], stored in each shape's [`ShapeFill`].
+    ///
+    /// Dashes lines, polylines, bezier curves, rings and the outlines of hollow discs, rectangles, regular polygons, triangles, quads and capsules.
+    /// Dashes are capped according to `cap` along lines, polylines, bezier curves and discs and are cut square around other outlines,
+    /// lengths are measured in the shape's local space.
+    pub dash: Option<DashPattern>,
+    /// Color swept around a [`DiscComponent`] from `color`, see [`ConicGradient`].
+    pub conic_gradient: Option<ConicGradient>,
+    /// Every `grid_major_interval`th line of a [`GridComponent`] is drawn as a major line, zero to only draw minor lines.
+    pub grid_major_interval: u32,
+    /// Multiplier applied to the thickness of major lines of a [`GridComponent`].
+    pub grid_major_thickness: f32,
+    /// Number of straight segments each span between the points of a spline is drawn with.
+    pub spline_segments: u32,
+    /// Font registered with [`ShapeFonts`] that text is drawn with, the first font registered if `None`.
+    #[cfg(feature = "text")]
+    pub font: Option<ShapeFont>,
+    /// Height of each line of text, excluding the gap between lines.
+    #[cfg(feature = "text")]
+    pub font_size: f32,
+    /// Point of the text placed at the config's transform, lines are aligned horizontally by the same anchor.
+    #[cfg(feature = "text")]
+    pub text_anchor: Anchor,
+    /// Digits drawn after the decimal point by [`TextPainter::number`].
+    #[cfg(feature = "text")]
+    pub decimals: usize,
 
     #[reflect(ignore)]
     pub render_layers: Option<RenderLayers>,
@@ -41,8 +94,78 @@ pub struct ShapeConfig {
     pub disable_laa: bool,
     /// [`Canvas`] to draw the shape to.
     pub canvas: Option<Entity>,
+    /// [`CanvasLabel`] of the [`Canvas`] to draw the shape to when `canvas` is `None`, resolved each frame.
+    pub canvas_label: Option<CanvasLabel>,
     /// Texture to apply to the shape, color is determined as color * sample.
     pub texture: Option<Handle<Image>>,
+    /// Color space `texture` is sampled in, e.g. [`ShapeTextureColorSpace::Linear`] for heatmaps and lookup tables
+    /// that shouldn't be gamma decoded.
+    pub texture_color_space: ShapeTextureColorSpace,
+    /// Region of `texture` drawn on rectangles in uv coordinates, the whole texture if `None`.
+    ///
+    /// `min` is sampled at the top left of the rectangle and `max` at the bottom right, so swapping them flips the image.
+    pub texture_rect: Option<Rect>,
+    /// Size in world units of each repetition of `texture` tiled across rectangles, stretched across them if `None`.
+    pub texture_tile: Option<Vec2>,
+    /// Distances from the camera over which the shape fades, only supported by the 3D pipeline.
+    pub fade: ShapeFade,
+    /// Render phase the shape is queued into, see [`ShapeRenderPhase`].
+    pub render_phase: ShapeRenderPhase,
+    /// Visualizes the distance to the edge of spawned shapes instead of filling them, see [`ShapeSdfView`].
+    pub sdf_view: ShapeSdfView,
+    /// Width in pixels over which the edges of spawned shapes fade out, zero for standard anti-aliasing.
+    ///
+    /// Shapes grow by this width, so large glows can be drawn as soft oversized shapes.
+    /// Each distinct width is drawn with it's own pipeline.
+    pub edge_softness: u8,
+    /// Curve the edges of spawned shapes fade out with when `edge_softness` is set.
+    pub edge_falloff: EdgeFalloff,
+    /// Side of spawned shapes that is hidden, see [`ShapeCullMode`].
+    ///
+    /// Each cull mode is drawn with it's own pipeline.
+    pub cull_mode: ShapeCullMode,
+    /// Whether spawned shapes are hidden behind geometry nearer to the camera, disable to draw debug overlays
+    /// and markers over the scene without a second camera.
+    ///
+    /// Shapes drawn without the depth test are still sorted against other transparent items,
+    /// use `sort_bias` or [`ShapeRenderPhase`] to draw them over those too.
+    pub depth_test: bool,
+    /// Rect in world space on the xy plane that shapes are clipped to, e.g. the bounds of a scrollable panel.
+    ///
+    /// Shapes are clipped to the smallest region of the screen containing the rect, with a scissor rect per clip rect,
+    /// so shapes with different clip rects are drawn in separate batches.
+    pub clip_rect: Option<Rect>,
+    /// Offset added to the value shapes are sorted by, their z translation in 2D and their depth from the camera in 3D.
+    ///
+    /// Shapes with a greater bias are drawn over coincident shapes with a lesser one,
+    /// without nudging their translations apart. Doesn't affect the depth test.
+    pub sort_bias: f32,
+    /// Number of slices 3D shapes are split into along their local axis spanning the most depth from each view,
+    /// each sorted at it's own depth, zero or one to draw shapes whole.
+    ///
+    /// Reduces sorting artifacts where large translucent shapes such as water planes or force fields interpenetrate
+    /// other transparent items like particles, at the cost of an instance per slice. Only shapes drawn on a quad in their
+    /// plane are sliced: rectangles, discs, rings, regular polygons, quads, grids, boolean and custom SDF shapes.
+    pub depth_slices: u8,
+    /// Named group that shapes drawn with a [`ShapePainter`] are kept in, see [`ShapeGroups`](crate::painter::ShapeGroups).
+    ///
+    /// Ignored by [`ShapeCommands`], retained shapes can be shown and hidden through their [`Visibility`].
+    pub group: Option<String>,
+    /// Key that shapes drawn with a [`ShapePainter`] are kept under as entities, see [`ShapeKey`](crate::painter::ShapeKey).
+    ///
+    /// Ignored by [`ShapeCommands`], as are `group`, `screen_space` and `cull` for keyed shapes.
+    pub key: Option<ShapeKey>,
+    /// Skip shapes drawn with a [`ShapePainter`] that are outside the view of every camera as of the previous frame.
+    ///
+    /// Shapes sent to a canvas are only tested against the canvas' camera, even while it's inactive between redraws.
+    /// Only shapes with known bounds and strokes measured in world units are culled, see [`ShapeStorage::culled`](crate::painter::ShapeStorage::culled).
+    pub cull: bool,
+    /// Camera whose viewport translations are measured across in pixels, see [`ScreenSpace`].
+    ///
+    /// Ignored by [`ShapeCommands`], set with [`ShapeConfig::set_screen_space`].
+    pub screen_space: Option<ScreenSpace>,
+    /// Rect that positions given to [`ShapeConfig::set_uv`] are relative to, set with [`ShapeConfig::set_uv_space`].
+    pub uv_space: Option<UvSpace>,
     /// Set with set_2d, set_3d and set_canvas.
     pub pipeline: ShapePipelineType,
     /// Indicates whether or not the config will be reset after a system is run
@@ -95,6 +218,75 @@ impl ShapeConfig {
         self.transform.scale = scale;
     }
 
+    /// Helper method to treat a rect of the given size at the configs current transform as the space for [`ShapeConfig::set_uv`].
+    ///
+    /// Typically called within `with_children` after drawing a textured rect to annotate it's image.
+    pub fn set_uv_space(&mut self, size: Vec2) {
+        self.uv_space = Some(UvSpace {
+            origin: self.transform,
+            size,
+        });
+    }
+
+    /// Helper method to set the configs translation to the given uv coordinates of the current [`UvSpace`].
+    ///
+    /// Does nothing if no uv space has been set.
+    pub fn set_uv(&mut self, uv: Vec2) {
+        if let Some(uv_space) = self.uv_space {
+            self.transform.translation = uv_space.uv_to_local(uv);
+        }
+    }
+
+    /// Helper method to convert a size in uv coordinates of the current [`UvSpace`] to the configs local space.
+    ///
+    /// Returns `uv` unchanged if no uv space has been set.
+    pub fn uv_size(&self, uv: Vec2) -> Vec2 {
+        self.uv_space.map_or(uv, |uv_space| uv * uv_space.size)
+    }
+
+    /// Helper method to set the region of the texture drawn on rectangles from it's offset and scale in uv coordinates.
+    ///
+    /// A negative scale flips the image along that axis.
+    pub fn set_texture_offset_scale(&mut self, offset: Vec2, scale: Vec2) {
+        self.texture_rect = Some(Rect {
+            min: offset,
+            max: offset + scale,
+        });
+    }
+
+    /// Helper method to set the region of the texture drawn on rectangles in pixels from the image's top left,
+    /// e.g. a single frame of a sprite sheet drawn with `painter.image`.
+    pub fn set_texture_region(&mut self, region: Rect, image_size: Vec2) {
+        self.texture_rect = Some(Rect {
+            min: region.min / image_size,
+            max: region.max / image_size,
+        });
+    }
+
+    /// Helper method to set the region of the texture drawn on rectangles to a cell of a [`TextureAtlasLayout`].
+    ///
+    /// Does nothing if the index is out of range. Rectangles drawing different cells of the same texture are drawn in the same batch.
+    pub fn set_texture_atlas(&mut self, layout: &TextureAtlasLayout, index: usize) {
+        if let Some(rect) = atlas_texture_rect(layout, index) {
+            self.texture_rect = Some(rect);
+        }
+    }
+
+    /// Helper method to interpret translations as pixels measured from the top left corner of the camera's viewport,
+    /// e.g. to draw a HUD without projecting positions to the camera's world space, see [`ScreenSpace`].
+    pub fn set_screen_space(&mut self, camera: Entity) {
+        self.screen_space = Some(ScreenSpace::new(camera));
+    }
+
+    /// Helper method to change the corner of the viewport that screen space translations are measured from.
+    ///
+    /// Does nothing if no screen space has been set.
+    pub fn set_screen_corner(&mut self, corner: ScreenCorner) {
+        if let Some(screen_space) = &mut self.screen_space {
+            screen_space.corner = corner;
+        }
+    }
+
     /// Helper method to change shape render target to a canvas.
     ///
     /// Also sets pipeline to Shape2d.
@@ -103,6 +295,15 @@ impl ShapeConfig {
         self.canvas = Some(canvas);
     }
 
+    /// Helper method to change shape render target to the canvas with the given label.
+    ///
+    /// Also sets pipeline to Shape2d and clears `canvas` so the label is used.
+    pub fn set_canvas_label(&mut self, label: impl Into<CanvasLabel>) {
+        self.pipeline = ShapePipelineType::Shape2d;
+        self.canvas = None;
+        self.canvas_label = Some(label.into());
+    }
+
     /// Helper method to change the target pipeline to the 3d pipeline.
     pub fn set_3d(&mut self) {
         self.pipeline = ShapePipelineType::Shape3d;
@@ -124,6 +325,143 @@ impl ShapeConfig {
     pub fn set_color(&mut self, color: impl Into<Color>) {
         self.color = color.into();
     }
+
+    /// Helper method to outline the following filled shapes in the given color, see [`ShapeConfig::stroke_color`].
+    pub fn set_stroke_color(&mut self, color: impl Into<Color>) {
+        self.stroke_color = Some(color.into());
+    }
+
+    /// Config drawing the outline of a filled shape drawn with this config, if it has a `stroke_color`.
+    pub(crate) fn stroke_config(&self) -> Option<Self> {
+        let color = self.stroke_color.filter(|_| !self.hollow)?;
+        let mut config = self.clone();
+        config.color = color;
+        config.hollow = true;
+        config.stroke_color = None;
+        Some(config)
+    }
+
+    /// Helper method to add a layer to the render layers, starting from the default layer if none are set.
+    ///
+    /// Shapes in multiple layers are drawn as a single batch to every view that shares any of them.
+    pub fn add_layer(&mut self, layer: usize) {
+        self.render_layers = Some(self.render_layers.take().unwrap_or_default().with(layer));
+    }
+
+    /// Helper method to remove a layer from the render layers, starting from the default layer if none are set.
+    pub fn remove_layer(&mut self, layer: usize) {
+        self.render_layers = Some(self.render_layers.take().unwrap_or_default().without(layer));
+    }
+}
+
+/// Space spanning a rect centered on `origin`, with uv coordinates matching those of textures.
+///
+/// (0, 0) is the top left corner of the rect and (1, 1) the bottom right.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct UvSpace {
+    /// Transform of the rect.
+    pub origin: Transform,
+    /// Size of the rect.
+    pub size: Vec2,
+}
+
+impl UvSpace {
+    /// Convert uv coordinates to a position in the space of the config the uv space was created from.
+    pub fn uv_to_local(&self, uv: Vec2) -> Vec3 {
+        let offset = Vec3::new((uv.x - 0.5) * self.size.x, (0.5 - uv.y) * self.size.y, 0.0);
+        self.origin.transform_point(offset)
+    }
+}
+
+/// Corner of a camera's viewport that [`ScreenSpace`] translations are measured from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum ScreenCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ScreenCorner {
+    /// Position of the corner in pixels from the bottom left of a viewport of the given size,
+    /// and the directions translations from it are measured in.
+    fn origin(self, size: Vec2) -> (Vec2, Vec2) {
+        match self {
+            ScreenCorner::TopLeft => (Vec2::new(0.0, size.y), Vec2::new(1.0, -1.0)),
+            ScreenCorner::TopRight => (size, Vec2::NEG_ONE),
+            ScreenCorner::BottomLeft => (Vec2::ZERO, Vec2::ONE),
+            ScreenCorner::BottomRight => (Vec2::new(size.x, 0.0), Vec2::new(-1.0, 1.0)),
+        }
+    }
+}
+
+/// Space spanning the viewport of a camera, with translations in logical pixels measured into the viewport from a corner.
+///
+/// From the top left corner translations match window coordinates such as the cursor position.
+/// Only translations are measured from the corner, shapes keep their y axis pointing up and are sized in pixels,
+/// while z translations move shapes towards the camera.
+///
+/// Shapes are moved to the camera's world space as they are extracted for rendering, so they follow the camera
+/// without lagging behind it. Shapes are skipped if the camera doesn't exist or it's viewport size isn't known.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct ScreenSpace {
+    /// Camera the shapes are drawn in front of.
+    pub camera: Entity,
+    /// Corner translations are measured from.
+    pub corner: ScreenCorner,
+    /// Distance in world units in front of the camera's near plane that shapes without a z translation are placed at.
+    pub distance: f32,
+}
+
+impl ScreenSpace {
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            corner: default(),
+            distance: 1.0,
+        }
+    }
+
+    /// Convert the transform of a shape in this space to the world space of the given camera,
+    /// `None` if the camera's viewport size isn't known.
+    pub fn to_world(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        transform: Mat4,
+    ) -> Option<Mat4> {
+        let size = camera.logical_viewport_size()?;
+        let clip_from_view = camera.clip_from_view();
+        let view_from_clip = clip_from_view.inverse();
+
+        // Shapes are placed on a plane parallel to the near plane, which has a depth of 1 as bevy uses reversed z
+        let near = view_from_clip.project_point3(Vec3::Z).z;
+        let depth = clip_from_view
+            .project_point3(Vec3::Z * (near - self.distance))
+            .z;
+        let view_from_pixel =
+            |pixel: Vec2| view_from_clip.project_point3((pixel / size * 2.0 - 1.0).extend(depth));
+
+        let translation = transform.w_axis.truncate();
+        let (corner, direction) = self.corner.origin(size);
+        let pixel = corner + direction * translation.truncate();
+
+        let origin = view_from_pixel(pixel);
+        let x_axis = view_from_pixel(pixel + Vec2::X) - origin;
+        let y_axis = view_from_pixel(pixel + Vec2::Y) - origin;
+        let z_axis = Vec3::Z * x_axis.length();
+        let view_from_screen = Mat4::from_cols(
+            x_axis.extend(0.0),
+            y_axis.extend(0.0),
+            z_axis.extend(0.0),
+            (origin + z_axis * translation.z).extend(1.0),
+        );
+
+        let mut local = transform;
+        local.w_axis = Vec4::W;
+        Some(camera_transform.compute_matrix() * view_from_screen * local)
+    }
 }
 
 impl ShapeConfig {
@@ -138,15 +476,57 @@ impl ShapeConfig {
             thickness_type: default(),
             alignment: default(),
             hollow: false,
+            stroke_color: None,
             cap: default(),
+            end_cap: None,
+            join: default(),
+            miter_limit: 4.0,
             roundness: default(),
             corner_radii: default(),
+            corner_chamfers: BVec4::FALSE,
+            smooth_corners: false,
+            rect_anchor: Anchor::Center,
+            corner_colors: None,
+            vertex_colors: None,
+            ngon_rotation: 0.0,
+            dash: None,
+            conic_gradient: None,
+            grid_major_interval: 0,
+            grid_major_thickness: 2.0,
+            spline_segments: 8,
+            #[cfg(feature = "text")]
+            font: None,
+            #[cfg(feature = "text")]
+            font_size: 1.0,
+            #[cfg(feature = "text")]
+            text_anchor: Anchor::Center,
+            #[cfg(feature = "text")]
+            decimals: 0,
 
             render_layers: None,
             alpha_mode: ShapeAlphaMode::Blend,
             disable_laa: false,
             canvas: None,
+            canvas_label: None,
             texture: None,
+            texture_color_space: default(),
+            texture_rect: None,
+            texture_tile: None,
+            fade: default(),
+            render_phase: default(),
+            sdf_view: default(),
+            edge_softness: 0,
+            edge_falloff: default(),
+            cull_mode: default(),
+            depth_test: true,
+            clip_rect: None,
+            sort_bias: 0.0,
+            depth_slices: 0,
+            group: None,
+            key: None,
+            cull: false,
+            screen_space: None,
+            uv_space: None,
             pipeline: ShapePipelineType::Shape2d,
             reset: true,
         }
@@ -0,0 +1,172 @@
+use bevy::{prelude::*, render::view::RenderLayers, window::PrimaryWindow};
+
+use crate::prelude::*;
+
+/// Render layer that the cursor drawn by [`ShapeCursorPlugin`] is drawn on by default.
+pub const DEFAULT_CURSOR_LAYER: usize = 30;
+
+/// Interaction state of the cursor drawn by [`ShapeCursorPlugin`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum ShapeCursorState {
+    #[default]
+    Idle,
+    /// [`ShapeCursor::hovered`] is set.
+    Hover,
+    /// The left mouse button is held, takes priority over hovering.
+    Pressed,
+}
+
+/// Resource configuring the cursor drawn by [`ShapeCursorPlugin`].
+#[derive(Resource, Clone)]
+pub struct ShapeCursor {
+    /// Draw the cursor and hide the OS cursor over the primary window.
+    pub enabled: bool,
+    /// Set to draw the hover state, e.g. while the cursor is over something interactive.
+    pub hovered: bool,
+    /// Point of the cursor placed on the pointer, in pixels from the painter's origin with y up.
+    pub hotspot: Vec2,
+    /// Render layer of the cursor and it's camera, should not be rendered by any other camera.
+    pub layer: usize,
+    /// Order of the camera drawing the cursor, the cursor is drawn over cameras with a lower order.
+    pub order: isize,
+    /// Draws the cursor for the given state, the painter is positioned and set to the cursor's layer beforehand.
+    pub draw: fn(&mut ShapePainter, ShapeCursorState),
+}
+
+impl Default for ShapeCursor {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hovered: false,
+            hotspot: Vec2::ZERO,
+            layer: DEFAULT_CURSOR_LAYER,
+            order: 1000,
+            draw: draw_default_cursor,
+        }
+    }
+}
+
+/// Draws a ring around the pointer that grows when hovering and is filled in when pressed.
+pub fn draw_default_cursor(painter: &mut ShapePainter, state: ShapeCursorState) {
+    painter.thickness = 2.0;
+    painter.thickness_type = ThicknessType::Pixels;
+    match state {
+        ShapeCursorState::Idle => {
+            painter.hollow = true;
+            painter.circle(8.0);
+        }
+        ShapeCursorState::Hover => {
+            painter.hollow = true;
+            painter.circle(12.0);
+            painter.hollow = false;
+            painter.circle(2.0);
+        }
+        ShapeCursorState::Pressed => {
+            painter.hollow = false;
+            painter.circle(6.0);
+        }
+    }
+}
+
+/// Component attached to the camera that draws the cursor of [`ShapeCursorPlugin`].
+#[derive(Component, Debug)]
+pub struct ShapeCursorCamera;
+
+/// Plugin that hides the OS cursor over the primary window and draws a shape in it's place, configured by [`ShapeCursor`].
+///
+/// The cursor is drawn in screen space by a 2D camera above all other cameras, in pixels with y up.
+/// Must be added after [`Shape2dPlugin`] or [`ShapePlugin`].
+pub struct ShapeCursorPlugin;
+
+impl Plugin for ShapeCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapeCursor>().add_systems(
+            Update,
+            (
+                update_cursor_camera,
+                update_cursor_visibility,
+                draw_shape_cursor.after(update_cursor_camera),
+            ),
+        );
+    }
+}
+
+/// Spawns the camera drawing the cursor and keeps it in sync with [`ShapeCursor`].
+pub fn update_cursor_camera(
+    mut commands: Commands,
+    cursor: Res<ShapeCursor>,
+    mut cameras: Query<(&mut Camera, &mut RenderLayers), With<ShapeCursorCamera>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+
+    match cameras.get_single_mut() {
+        Ok((mut camera, mut layers)) => {
+            camera.order = cursor.order;
+            camera.is_active = cursor.enabled;
+            *layers = RenderLayers::layer(cursor.layer);
+        }
+        Err(_) => {
+            commands.spawn((
+                Camera2d,
+                Camera {
+                    order: cursor.order,
+                    is_active: cursor.enabled,
+                    clear_color: ClearColorConfig::None,
+                    ..default()
+                },
+                RenderLayers::layer(cursor.layer),
+                ShapeCursorCamera,
+            ));
+        }
+    }
+}
+
+/// Hides the OS cursor over the primary window while the shape cursor is enabled.
+pub fn update_cursor_visibility(
+    cursor: Res<ShapeCursor>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+    for mut window in &mut windows {
+        window.cursor_options.visible = !cursor.enabled;
+    }
+}
+
+/// Draws the cursor at the pointer's position over the primary window.
+pub fn draw_shape_cursor(
+    cursor: Res<ShapeCursor>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<ShapeCursorCamera>>,
+    mut painter: ShapePainter,
+) {
+    if !cursor.enabled {
+        return;
+    }
+    let Some(position) = windows.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Ok((camera, camera_tf)) = cameras.get_single() else {
+        return;
+    };
+    let Ok(position) = camera.viewport_to_world_2d(camera_tf, position) else {
+        return;
+    };
+
+    let state = if buttons.pressed(MouseButton::Left) {
+        ShapeCursorState::Pressed
+    } else if cursor.hovered {
+        ShapeCursorState::Hover
+    } else {
+        ShapeCursorState::Idle
+    };
+
+    painter.set_2d();
+    painter.render_layers = Some(RenderLayers::layer(cursor.layer));
+    painter.set_translation((position - cursor.hotspot).extend(0.0));
+    (cursor.draw)(&mut painter, state);
+}
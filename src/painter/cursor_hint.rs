@@ -0,0 +1,96 @@
+use bevy::{
+    picking::focus::PickingInteraction,
+    prelude::*,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
+};
+
+use crate::prelude::*;
+
+/// Component hinting the cursor to show while a shape is hovered, e.g. a pointer over shape based buttons,
+/// applied by [`ShapeCursorHintPlugin`], requires the `picking` feature.
+///
+/// Hovering is read from the entity's [`PickingInteraction`], so a picking backend that reports hits on the entity
+/// is needed, the shapes themselves are not picked.
+#[derive(Component, Clone, Debug, PartialEq)]
+#[require(PickingInteraction)]
+pub struct ShapeCursorHint {
+    /// Icon of the OS cursor while hovered, custom images and their hotspot require bevy's `custom_cursor` feature.
+    pub icon: CursorIcon,
+    /// Icon of the OS cursor while pressed, `icon` if `None`.
+    pub pressed: Option<CursorIcon>,
+}
+
+impl ShapeCursorHint {
+    pub fn new(icon: impl Into<CursorIcon>) -> Self {
+        Self {
+            icon: icon.into(),
+            pressed: None,
+        }
+    }
+
+    pub fn with_pressed(mut self, icon: impl Into<CursorIcon>) -> Self {
+        self.pressed = Some(icon.into());
+        self
+    }
+
+    /// The icon to show for the given interaction, `None` if the shape isn't interacted with.
+    pub fn icon(&self, interaction: PickingInteraction) -> Option<&CursorIcon> {
+        match interaction {
+            PickingInteraction::Pressed => Some(self.pressed.as_ref().unwrap_or(&self.icon)),
+            PickingInteraction::Hovered => Some(&self.icon),
+            PickingInteraction::None => None,
+        }
+    }
+}
+
+impl Default for ShapeCursorHint {
+    fn default() -> Self {
+        Self::new(SystemCursorIcon::Pointer)
+    }
+}
+
+/// Plugin that sets the cursor of the primary window to the [`ShapeCursorHint`] of the hovered shape,
+/// restoring the default cursor once no hinted shape is hovered, requires the `picking` feature.
+///
+/// While [`ShapeCursorPlugin`] hides the OS cursor the hint sets [`ShapeCursor::hovered`] instead.
+/// Pressed shapes take priority over hovered shapes.
+pub struct ShapeCursorHintPlugin;
+
+impl Plugin for ShapeCursorHintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_cursor_hints);
+    }
+}
+
+/// Applies the [`ShapeCursorHint`] of the hovered shape, only resetting cursors that were set by a hint.
+pub fn update_cursor_hints(
+    mut commands: Commands,
+    hints: Query<(&ShapeCursorHint, &PickingInteraction)>,
+    windows: Query<(Entity, Option<&CursorIcon>), With<PrimaryWindow>>,
+    shape_cursor: Option<ResMut<ShapeCursor>>,
+    mut hinted: Local<bool>,
+) {
+    let icon = hints
+        .iter()
+        .filter_map(|(hint, interaction)| Some((hint.icon(*interaction)?, *interaction as u8)))
+        .max_by_key(|(_, priority)| *priority)
+        .map(|(icon, _)| icon.clone());
+    if icon.is_none() && !*hinted {
+        return;
+    }
+    *hinted = icon.is_some();
+
+    if let Some(mut cursor) = shape_cursor {
+        if cursor.hovered != *hinted {
+            cursor.hovered = *hinted;
+        }
+    }
+
+    let icon = icon.unwrap_or_default();
+    for (window, current) in &windows {
+        if current != Some(&icon) {
+            commands.entity(window).insert(icon.clone());
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+/// Maximum number of values across all patterns in [`ShapeDashPatterns`].
+pub const MAX_DASH_PATTERN_VALUES: usize = 256;
+
+/// Reference to a dash pattern registered with [`ShapeDashPatterns`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub struct DashPattern {
+    offset: u16,
+    len: u16,
+}
+
+impl DashPattern {
+    /// Pattern packed into a single value for the shaders, offset in the upper 16 bits and length in the lower.
+    pub fn packed(self) -> u32 {
+        ((self.offset as u32) << 16) | self.len as u32
+    }
+}
+
+/// Resource containing arbitrary dash patterns, similar to SVG's `stroke-dasharray`, that shapes reference through [`ShapeConfig::dash`](super::ShapeConfig::dash).
+///
+/// All patterns are uploaded together as a single small uniform so shapes only need to store a [`DashPattern`].
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ShapeDashPatterns {
+    values: Vec<f32>,
+}
+
+impl ShapeDashPatterns {
+    /// Register a pattern of alternating dash and gap lengths, starting with a dash.
+    ///
+    /// Patterns with an odd number of values are repeated to make them even, as in SVG.
+    /// Identical patterns share storage, returns `None` if the pattern has no length or there is no room left.
+    pub fn add(&mut self, pattern: &[f32]) -> Option<DashPattern> {
+        if pattern.iter().any(|v| *v < 0.0) || pattern.iter().sum::<f32>() <= 0.0 {
+            return None;
+        }
+
+        let mut values = pattern.to_vec();
+        if values.len() % 2 == 1 {
+            values.extend_from_slice(pattern);
+        }
+
+        if let Some(offset) = self.values.windows(values.len()).position(|w| w == values) {
+            return Some(DashPattern {
+                offset: offset as u16,
+                len: values.len() as u16,
+            });
+        }
+
+        if self.values.len() + values.len() > MAX_DASH_PATTERN_VALUES {
+            return None;
+        }
+
+        let offset = self.values.len();
+        self.values.extend(values);
+        Some(DashPattern {
+            offset: offset as u16,
+            len: (self.values.len() - offset) as u16,
+        })
+    }
+
+    /// Register a pattern of `count` dashes that exactly fill a stroke of the given length, such as the outline of a disc,
+    /// with `spacing` being the fraction of the space allocated to each dash that is left empty.
+    ///
+    /// Around a disc dashes are measured along the middle of it's outline, which has a length of `TAU * (radius - thickness / 2.0)`.
+    pub fn add_evenly_spaced(
+        &mut self,
+        length: f32,
+        count: u32,
+        spacing: f32,
+    ) -> Option<DashPattern> {
+        if count == 0 {
+            return None;
+        }
+
+        let segment = length / count as f32;
+        let spacing = spacing.clamp(0.0, 1.0);
+        self.add(&[segment * (1.0 - spacing), segment * spacing])
+    }
+
+    /// Alternating dash and gap lengths of a registered pattern.
+    pub fn get(&self, pattern: DashPattern) -> &[f32] {
+        let start = pattern.offset as usize;
+        self.values
+            .get(start..start + pattern.len as usize)
+            .unwrap_or_default()
+    }
+
+    /// All registered values, each pattern stored contiguously.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+}
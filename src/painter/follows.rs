@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+/// Component that keeps a shape positioned relative to another entity without parenting it.
+///
+/// Each frame the shape's translation is set to the target's translation plus `offset`,
+/// leaving it's rotation and scale untouched by the target's transform, useful for labels and indicators.
+///
+/// Followers should not have a [`Parent`] as the translation is written in world space.
+/// If the target is despawned the shape remains where it was last placed.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct Follows {
+    /// Entity to follow.
+    pub target: Entity,
+    /// Offset from the target's translation in world space.
+    pub offset: Vec3,
+}
+
+impl Follows {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vec3::ZERO,
+        }
+    }
+}
+
+/// Copies the translation of each [`Follows`] target to it's followers.
+///
+/// Runs before transform propagation, so targets without a parent are followed exactly
+/// while nested targets use their [`GlobalTransform`] from the previous frame.
+pub fn update_follows(
+    mut followers: Query<(&Follows, &mut Transform)>,
+    targets: Query<(&Transform, &GlobalTransform, Has<Parent>), Without<Follows>>,
+) {
+    for (follows, mut transform) in &mut followers {
+        let Ok((target, global, has_parent)) = targets.get(follows.target) else {
+            continue;
+        };
+        let translation = if has_parent {
+            global.translation()
+        } else {
+            target.translation
+        };
+        transform.translation = translation + follows.offset;
+    }
+}
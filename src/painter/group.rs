@@ -0,0 +1,91 @@
+use std::any::TypeId;
+
+use any_vec::AnyVec;
+use bevy::{ecs::entity::EntityHashSet, prelude::*, utils::HashMap};
+
+use crate::{painter::ShapeStorage, render::ShapePipelineType};
+
+/// Settings of a named group of shapes, see [`ShapeGroups`].
+#[derive(Clone, Debug, Reflect)]
+pub struct ShapeGroupConfig {
+    /// Whether the group's shapes are drawn.
+    pub enabled: bool,
+}
+
+impl Default for ShapeGroupConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Resource controlling named groups of shapes drawn with [`ShapePainter::group`](crate::prelude::ShapePainter::group),
+/// e.g. large debug categories such as a navmesh that are shown or hidden together.
+///
+/// A group keeps the shapes drawn into it during the last frame anything was drawn into it, so a group can be drawn
+/// once and kept until it is cleared or redrawn, or drawn every frame like any other immediate mode shapes.
+/// Changes are applied at the end of each frame, groups without a config are enabled.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ShapeGroups {
+    configs: HashMap<String, ShapeGroupConfig>,
+    cleared: Vec<String>,
+}
+
+impl ShapeGroups {
+    pub fn config(&self, name: &str) -> Option<&ShapeGroupConfig> {
+        self.configs.get(name)
+    }
+
+    /// Config of the group with the given name, inserting the default config if it has none.
+    pub fn config_mut(&mut self, name: &str) -> &mut ShapeGroupConfig {
+        self.configs.entry(name.to_string()).or_default()
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.config(name).is_none_or(|config| config.enabled)
+    }
+
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        self.config_mut(name).enabled = enabled;
+    }
+
+    pub fn toggle(&mut self, name: &str) {
+        let config = self.config_mut(name);
+        config.enabled = !config.enabled;
+    }
+
+    /// Discard the shapes kept in the group with the given name at the end of the frame, including any drawn into it this frame.
+    pub fn clear(&mut self, name: impl Into<String>) {
+        self.cleared.push(name.into());
+    }
+}
+
+/// Shapes kept in a named group, stored as [`ShapeStorage`] stores the shapes drawn each frame.
+pub(crate) struct GroupStorage {
+    pub(crate) shapes: HashMap<(TypeId, ShapePipelineType), AnyVec<dyn Send + Sync>>,
+    pub(crate) canvases: EntityHashSet,
+    /// Frame the group was last drawn into.
+    pub(crate) frame: u32,
+    pub(crate) enabled: bool,
+}
+
+impl GroupStorage {
+    pub(crate) fn new(frame: u32) -> Self {
+        Self {
+            shapes: HashMap::new(),
+            canvases: default(),
+            frame,
+            enabled: true,
+        }
+    }
+}
+
+/// Applies the changes made to [`ShapeGroups`] to the groups kept in [`ShapeStorage`].
+pub fn update_shape_groups(mut groups: ResMut<ShapeGroups>, mut storage: ResMut<ShapeStorage>) {
+    let groups = groups.as_mut();
+    for name in groups.cleared.drain(..) {
+        storage.groups_mut().remove(&name);
+    }
+    for (name, group) in storage.groups_mut() {
+        group.enabled = groups.is_enabled(name);
+    }
+}
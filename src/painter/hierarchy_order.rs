@@ -0,0 +1,81 @@
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+
+use crate::prelude::*;
+
+/// Opt-in component that orders the 2D retained shapes in an entity's hierarchy by their position in the hierarchy rather than their z translation.
+///
+/// Shapes are drawn depth first, parents before their children and siblings in the order they appear in [`Children`],
+/// so nested shape widgets stack naturally without assigning z to each child.
+///
+/// The hierarchy is sorted against other 2D shapes as if all of it's shapes were placed between the z translation of the root and 1.0 unit in front of it.
+/// Markers on entities nested within another ordered hierarchy are ignored.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+pub struct ShapeHierarchyOrder;
+
+/// Overrides the value used to sort a 2D shape, which is otherwise it's z translation.
+///
+/// Inserted and kept up to date on shapes within a [`ShapeHierarchyOrder`] hierarchy, changes made to those keys will be overwritten.
+/// Keys added to other shapes are left untouched.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub struct ShapeSortKey(pub f32);
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_hierarchy_order(
+    mut commands: Commands,
+    roots: Query<(Entity, &GlobalTransform), With<ShapeHierarchyOrder>>,
+    ordered: Query<(), With<ShapeHierarchyOrder>>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    shapes: Query<(), With<ShapeFill>>,
+    mut keys: Query<&mut ShapeSortKey>,
+    mut visited: Local<EntityHashSet>,
+    // Shapes whose keys were set by us, so that only those keys are removed once the shape leaves the hierarchy
+    mut managed: Local<EntityHashSet>,
+    mut order: Local<Vec<Entity>>,
+    mut stack: Local<Vec<Entity>>,
+) {
+    visited.clear();
+
+    for (root, tf) in &roots {
+        if parents.iter_ancestors(root).any(|e| ordered.contains(e)) {
+            continue;
+        }
+
+        // Depth first traversal, children are pushed in reverse so that they are popped in order
+        order.clear();
+        stack.push(root);
+        while let Some(entity) = stack.pop() {
+            if shapes.contains(entity) {
+                order.push(entity);
+            }
+            if let Ok(children) = children.get(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        let base = tf.translation().z;
+        let step = 1.0 / order.len().max(1) as f32;
+        for (i, &entity) in order.iter().enumerate() {
+            let key = ShapeSortKey(base + i as f32 * step);
+            visited.insert(entity);
+            match keys.get_mut(entity) {
+                Ok(mut existing) => {
+                    existing.set_if_neq(key);
+                }
+                Err(_) => {
+                    commands.entity(entity).insert(key);
+                }
+            }
+        }
+    }
+
+    // Remove the keys we set on shapes that are no longer part of an ordered hierarchy
+    for &entity in managed.iter() {
+        if !visited.contains(&entity) {
+            if let Some(mut entity) = commands.get_entity(entity) {
+                entity.remove::<ShapeSortKey>();
+            }
+        }
+    }
+    managed.clone_from(&visited);
+}
@@ -0,0 +1,53 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+
+use crate::prelude::*;
+
+/// Opt-in component that copies an entity's [`RenderLayers`] to every retained shape in it's hierarchy.
+///
+/// Shapes spawned as children of entities that weren't spawned with [`ShapeCommands`], e.g. a scene root placed on a
+/// minimap camera's layer, otherwise stay on the default layer and are only drawn to cameras that render it.
+/// Entities without [`RenderLayers`] pass on the default layer, markers nested within the hierarchy pass on their own layers instead.
+///
+/// Layers are kept up to date each frame and any set on the shapes themselves are overwritten,
+/// shapes removed from the hierarchy keep the last layers they were given.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+pub struct ShapeInheritLayers;
+
+pub fn update_inherited_layers(
+    mut commands: Commands,
+    roots: Query<(Entity, Option<&RenderLayers>), With<ShapeInheritLayers>>,
+    inheriting: Query<Option<&RenderLayers>, With<ShapeInheritLayers>>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    mut shapes: Query<Option<&mut RenderLayers>, (With<ShapeFill>, Without<ShapeInheritLayers>)>,
+    mut stack: Local<Vec<(Entity, RenderLayers)>>,
+) {
+    for (root, layers) in &roots {
+        if parents.iter_ancestors(root).any(|e| inheriting.contains(e)) {
+            continue;
+        }
+
+        stack.push((root, layers.cloned().unwrap_or_default()));
+        while let Some((entity, layers)) = stack.pop() {
+            match shapes.get_mut(entity) {
+                Ok(Some(mut existing)) => {
+                    existing.set_if_neq(layers.clone());
+                }
+                Ok(None) => {
+                    commands.entity(entity).insert(layers.clone());
+                }
+                Err(_) => {}
+            }
+
+            if let Ok(children) = children.get(entity) {
+                for &child in children {
+                    let layers = match inheriting.get(child) {
+                        Ok(own) => own.cloned().unwrap_or_default(),
+                        Err(_) => layers.clone(),
+                    };
+                    stack.push((child, layers));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    any::TypeId,
+    hash::{BuildHasher, Hash},
+};
+
+use bevy::{prelude::*, render::render_resource::encase::StorageBuffer, utils::FixedState};
+
+use super::{
+    retain::{retain_shape, RetainedShape},
+    ShapeStorage,
+};
+use crate::{
+    prelude::*,
+    render::{ShapeData, ShapePipelineMaterial},
+};
+
+/// Identifies the shapes drawn with a [`ShapePainter`] under a key across frames, see [`ShapePainter::keyed`].
+///
+/// Shapes drawn under a key are kept as entities with [`RetainedShapeData`], matched in order to the shapes drawn
+/// under the same key in the previous frame. Entities are only updated when their shape changes and are despawned
+/// once their shape is no longer drawn, so mostly static shapes such as UI aren't rewritten every frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub struct ShapeKey(u64);
+
+impl ShapeKey {
+    pub fn new(id: impl Hash) -> Self {
+        Self(FixedState.hash_one(id))
+    }
+}
+
+/// Everything a keyed shape is drawn with, compared to the previous frame to find changed shapes.
+#[derive(PartialEq)]
+struct KeyedState {
+    type_id: TypeId,
+    data: Vec<u8>,
+    material: ShapePipelineMaterial,
+    fade: ShapeFade,
+    sort_bias: f32,
+    depth_slices: u8,
+    origin: Option<Vec3>,
+}
+
+impl KeyedState {
+    fn new<T: ShapeData>(config: &ShapeConfig, data: &T) -> Self {
+        let mut buffer = StorageBuffer::new(Vec::new());
+        buffer.write(data).unwrap();
+        Self {
+            type_id: TypeId::of::<T>(),
+            data: buffer.into_inner(),
+            material: ShapePipelineMaterial::from(config),
+            fade: config.fade.clone(),
+            sort_bias: config.sort_bias,
+            depth_slices: config.depth_slices,
+            origin: config.origin,
+        }
+    }
+}
+
+struct KeyedShape {
+    /// Entity the shape is kept as, `None` until it's first update is applied.
+    entity: Option<Entity>,
+    state: KeyedState,
+    update: Option<RetainedShape>,
+}
+
+/// Shapes drawn under a [`ShapeKey`], in the order they were drawn.
+#[derive(Default)]
+pub(crate) struct KeyedShapes {
+    frame: u32,
+    /// Number of shapes drawn under the key during `frame`.
+    drawn: usize,
+    shapes: Vec<KeyedShape>,
+    /// Entities of shapes replaced by a shape of another type.
+    stale: Vec<Entity>,
+}
+
+impl KeyedShapes {
+    pub(crate) fn push<T: ShapeData>(&mut self, frame: u32, config: &ShapeConfig, data: T) {
+        if self.frame != frame {
+            self.frame = frame;
+            self.drawn = 0;
+        }
+        let index = self.drawn;
+        self.drawn += 1;
+
+        let state = KeyedState::new(config, &data);
+        match self.shapes.get_mut(index) {
+            Some(shape) if shape.state == state => {}
+            Some(shape) => {
+                // Entities only hold the data of a single shape type so shapes that change type are respawned
+                if shape.state.type_id != state.type_id {
+                    self.stale.extend(shape.entity.take());
+                }
+                shape.state = state;
+                shape.update = Some(retain_shape(config, data));
+            }
+            None => self.shapes.push(KeyedShape {
+                entity: None,
+                state,
+                update: Some(retain_shape(config, data)),
+            }),
+        }
+    }
+}
+
+/// Applies the changes to shapes drawn under a [`ShapeKey`] this frame, despawning those that are no longer drawn.
+pub fn update_keyed_shapes(mut commands: Commands, mut storage: ResMut<ShapeStorage>) {
+    let frame = storage.frame();
+    storage.keyed_mut().retain(|_, keyed| {
+        if keyed.frame != frame {
+            keyed.drawn = 0;
+        }
+
+        let removed = keyed
+            .shapes
+            .drain(keyed.drawn..)
+            .filter_map(|shape| shape.entity);
+        for entity in keyed.stale.drain(..).chain(removed) {
+            if let Some(mut entity) = commands.get_entity(entity) {
+                entity.despawn();
+            }
+        }
+
+        for shape in &mut keyed.shapes {
+            let Some(update) = shape.update.take() else {
+                continue;
+            };
+            // Entities despawned elsewhere are spawned again once their shape changes
+            let mut entity = match shape.entity.and_then(|entity| commands.get_entity(entity)) {
+                Some(entity) => entity,
+                None => commands.spawn_empty(),
+            };
+            update(&mut entity);
+            shape.entity = Some(entity.id());
+        }
+
+        !keyed.shapes.is_empty()
+    });
+}
@@ -1,10 +1,15 @@
 use std::ops::DerefMut;
 
-use bevy::{prelude::*, render::camera::CameraUpdateSystem};
+use bevy::{
+    prelude::*,
+    render::{camera::CameraUpdateSystem, view::VisibilitySystems},
+};
 
 mod config;
 pub use config::*;
 
+mod presets;
+
 mod shape_commands;
 pub use shape_commands::*;
 
@@ -17,6 +22,59 @@ pub use shape_painter::*;
 mod canvas;
 pub use canvas::*;
 
+mod hierarchy_order;
+pub use hierarchy_order::*;
+
+mod dash_pattern;
+pub use dash_pattern::*;
+
+mod follows;
+pub use follows::*;
+
+mod anchor;
+pub use anchor::*;
+
+mod polyline_points;
+pub use polyline_points::*;
+
+mod outline;
+pub use outline::*;
+
+mod cursor;
+pub use cursor::*;
+
+#[cfg(feature = "picking")]
+mod cursor_hint;
+#[cfg(feature = "picking")]
+pub use cursor_hint::*;
+
+mod compound;
+pub use compound::*;
+
+mod bulk;
+pub use bulk::*;
+
+mod inherit_layers;
+pub use inherit_layers::*;
+
+mod texture_atlas;
+pub use texture_atlas::*;
+
+mod group;
+pub use group::*;
+
+mod retain;
+pub use retain::*;
+
+mod keyed;
+pub use keyed::*;
+
+mod paint;
+pub use paint::*;
+
+mod paint_recording;
+pub use paint_recording::*;
+
 /// Trait that contains logic for spawning shape entities by type.
 ///
 /// Implemented by [`ShapeCommands`] and [`ShapeChildBuilder`].
@@ -37,7 +95,67 @@ pub struct PainterPlugin;
 impl Plugin for PainterPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ShapeStorage>()
-            .add_systems(First, clear_storage)
-            .add_systems(PostUpdate, update_canvases.before(CameraUpdateSystem));
+            .init_resource::<ShapeDashPatterns>()
+            .init_resource::<WrittenCanvases>()
+            .init_resource::<ShapePolylinePoints>()
+            .init_resource::<ShapeCompounds>()
+            .init_resource::<ShapeGroups>()
+            .init_resource::<PainterAnchors>()
+            .register_type::<CanvasLabel>()
+            .add_systems(
+                First,
+                (
+                    clear_storage,
+                    clear_polyline_points,
+                    update_storage_canvas_labels,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, (update_shape_groups, release_polyline_points).chain())
+            .add_systems(PostUpdate, (update_dynamic_polylines, update_polylines))
+            .add_systems(
+                PostUpdate,
+                update_storage_frusta.after(VisibilitySystems::UpdateFrusta),
+            )
+            .add_systems(
+                PostUpdate,
+                (update_outlined, update_outline_masks).before(CameraUpdateSystem),
+            )
+            .add_systems(
+                PostUpdate,
+                update_follows.before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                update_keyed_shapes.before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    resolve_canvas_labels,
+                    update_written_canvases,
+                    update_canvases,
+                    draw_canvas_backgrounds,
+                )
+                    .chain()
+                    .before(CameraUpdateSystem),
+            )
+            .add_systems(
+                PostUpdate,
+                update_hierarchy_order.after(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                update_painter_anchors.after(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                update_inherited_layers.before(VisibilitySystems::CheckVisibility),
+            )
+            .add_systems(PostUpdate, update_texture_atlases);
+
+        #[cfg(feature = "text")]
+        app.init_resource::<crate::shapes::ShapeFonts>()
+            .init_resource::<crate::shapes::ShapeNumberFormat>();
     }
 }
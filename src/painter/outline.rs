@@ -0,0 +1,140 @@
+use bevy::{
+    ecs::entity::EntityHashMap,
+    image::ImageSampler,
+    prelude::*,
+    render::{camera::RenderTarget, view::RenderLayers},
+};
+
+use crate::prelude::*;
+
+/// Component that marks an entity to be outlined by each [`OutlineComponent`] using the same layer.
+///
+/// The entity is added to the given render layer so that it is drawn into the outline mask,
+/// the layer is read when the component is inserted and removed along with it.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub struct Outlined {
+    /// Render layer the entity is drawn to the mask on, see [`OutlineComponent::layer`].
+    pub layer: usize,
+}
+
+impl Default for Outlined {
+    fn default() -> Self {
+        Self {
+            layer: DEFAULT_OUTLINE_LAYER,
+        }
+    }
+}
+
+/// Component attached to the cameras that draw the mask for an [`OutlineComponent`], containing the outline entity.
+#[derive(Component, Debug)]
+pub struct OutlineMaskCamera(pub Entity);
+
+/// Adds [`Outlined`] entities to their mask's render layer, and removes them when they are no longer outlined.
+pub fn update_outlined(
+    mut commands: Commands,
+    added: Query<(Entity, &Outlined, Option<&RenderLayers>), Added<Outlined>>,
+    layers: Query<&RenderLayers>,
+    mut removed: RemovedComponents<Outlined>,
+    mut outlined: Local<EntityHashMap<usize>>,
+) {
+    for entity in removed.read() {
+        let Some(layer) = outlined.remove(&entity) else {
+            continue;
+        };
+        if let Ok(layers) = layers.get(entity) {
+            commands
+                .entity(entity)
+                .insert(layers.clone().without(layer));
+        }
+    }
+
+    for (entity, outline, layers) in &added {
+        let layers = layers.cloned().unwrap_or_default();
+        commands.entity(entity).insert(layers.with(outline.layer));
+        outlined.insert(entity, outline.layer);
+    }
+}
+
+/// Spawns and maintains the camera drawing the mask for each [`OutlineComponent`].
+///
+/// The mask is recreated whenever the size of the outlined camera's viewport changes.
+pub fn update_outline_masks(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut outlines: Query<(Entity, &mut OutlineComponent, &mut ShapeMaterial)>,
+    cameras: Query<(&Camera, Ref<Projection>), Without<OutlineMaskCamera>>,
+    mut masks: Query<(
+        Entity,
+        &OutlineMaskCamera,
+        &mut Camera,
+        &mut Projection,
+        &mut RenderLayers,
+    )>,
+) {
+    // Despawn the masks of outlines that no longer exist
+    for (entity, mask, ..) in &masks {
+        if !outlines.contains(mask.0) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for (entity, mut outline, mut material) in &mut outlines {
+        let Ok((camera, projection)) = cameras.get(outline.camera) else {
+            continue;
+        };
+        let Some(size) = camera.physical_viewport_size() else {
+            continue;
+        };
+
+        let resized = material
+            .texture
+            .as_ref()
+            .and_then(|texture| images.get(texture))
+            .is_none_or(|image| image.size() != size);
+        if resized {
+            material.texture = Some(Canvas::create_image(
+                &mut images,
+                size.x,
+                size.y,
+                ImageSampler::linear(),
+                false,
+            ));
+        }
+        let image = material.texture.clone().unwrap();
+
+        match outline.mask.and_then(|mask| masks.get_mut(mask).ok()) {
+            Some((_, _, mut mask_camera, mut mask_projection, mut layers)) => {
+                if resized {
+                    mask_camera.target = RenderTarget::Image(image);
+                }
+                if projection.is_changed() {
+                    *mask_projection = (*projection).clone();
+                }
+                if mask_camera.order != camera.order - 1 {
+                    mask_camera.order = camera.order - 1;
+                }
+                if !layers.intersects(&RenderLayers::layer(outline.layer)) {
+                    *layers = RenderLayers::layer(outline.layer);
+                }
+            }
+            None => {
+                let mask = commands
+                    .spawn((
+                        Camera3d::default(),
+                        Camera {
+                            order: camera.order - 1,
+                            target: RenderTarget::Image(image),
+                            clear_color: ClearColorConfig::Custom(Color::NONE),
+                            ..default()
+                        },
+                        (*projection).clone(),
+                        RenderLayers::layer(outline.layer),
+                        OutlineMaskCamera(entity),
+                    ))
+                    .set_parent(outline.camera)
+                    .id();
+                outline.mask = Some(mask);
+            }
+        }
+    }
+}
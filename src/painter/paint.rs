@@ -0,0 +1,339 @@
+use bevy::{
+    input::touch::{ForceTouch, Touch},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use super::PaintRecordingLoader;
+use crate::prelude::*;
+
+/// Stroke recorded by a [`ShapePaint`] canvas, in the canvas' space of pixels from it's center with y up.
+#[derive(Clone, Reflect)]
+pub struct PaintStroke {
+    /// Brush the stroke was drawn with, see [`ShapePaint::brush`].
+    pub config: ShapeConfig,
+    /// Points along the stroke with the thickness of the stroke at each point.
+    pub points: Vec<(Vec2, f32)>,
+}
+
+/// Component for canvases that records pointer input over a camera's view as strokes drawn to the canvas,
+/// turning it into a drawing surface such as an in-game whiteboard, requires [`ShapePaintPlugin`].
+///
+/// The canvas is treated as stretched over the camera's viewport, e.g. shown by a [`ShapeCompositeLayer`]
+/// or a UI image covering the window. Strokes are drawn with the left mouse button or a touch,
+/// touches that report a force scale the brush's thickness by their pressure.
+///
+/// Each stroke is drawn as a single polyline tapered to the thickness at each point. Only new points are drawn to
+/// [`CanvasMode::Persistent`] canvases, except while a translucent or dashed stroke is drawn as the pieces would overlap
+/// where they meet or restart their dashes, so the canvas is redrawn each frame instead. Canvases in other modes have
+/// every stroke drawn each frame.
+/// Strokes are kept so that they can be undone, which redraws the canvas.
+#[derive(Component, Clone)]
+pub struct ShapePaint {
+    /// Camera whose viewport pointer positions are mapped from, usually the camera the canvas is shown by.
+    pub camera: Entity,
+    /// Whether pointer input is recorded, the strokes recorded so far are drawn regardless.
+    pub enabled: bool,
+    /// Config new strokes are drawn with, including their color, thickness and alpha mode,
+    /// e.g. [`ShapeAlphaMode::Erase`] for an eraser. The transform and canvas are set by the plugin.
+    pub brush: ShapeConfig,
+    /// Minimum distance in canvas pixels between recorded points.
+    pub spacing: f32,
+    strokes: Vec<PaintStroke>,
+    undone: Vec<PaintStroke>,
+    /// Whether the last stroke is still being drawn.
+    active: bool,
+    /// Number of points of the last stroke already drawn to a persistent canvas.
+    drawn: usize,
+    redraw: bool,
+}
+
+impl ShapePaint {
+    pub fn new(camera: Entity, brush: ShapeConfig) -> Self {
+        Self {
+            camera,
+            enabled: true,
+            brush,
+            spacing: 2.0,
+            strokes: Vec::new(),
+            undone: Vec::new(),
+            active: false,
+            drawn: 0,
+            redraw: false,
+        }
+    }
+
+    /// Strokes recorded so far, oldest first, including any stroke still being drawn.
+    pub fn strokes(&self) -> &[PaintStroke] {
+        &self.strokes
+    }
+
+    /// Whether a stroke is currently being drawn.
+    pub fn is_painting(&self) -> bool {
+        self.active
+    }
+
+    /// Remove the last stroke so that it can be restored by [`ShapePaint::redo`], redrawing the canvas.
+    pub fn undo(&mut self) {
+        self.active = false;
+        if let Some(stroke) = self.strokes.pop() {
+            self.undone.push(stroke);
+            self.redraw = true;
+        }
+    }
+
+    /// Restore the last stroke removed by [`ShapePaint::undo`], strokes can't be redone once a new stroke is drawn.
+    pub fn redo(&mut self) {
+        if let Some(stroke) = self.undone.pop() {
+            self.active = false;
+            self.strokes.push(stroke);
+            self.redraw = true;
+        }
+    }
+
+    /// Remove every stroke, redrawing the canvas.
+    pub fn clear(&mut self) {
+        self.strokes.clear();
+        self.undone.clear();
+        self.active = false;
+        self.redraw = true;
+    }
+
+    /// Export the strokes recorded so far relative to the size of the given canvas, usually the canvas this
+    /// component is on, so that they can be saved or sent elsewhere and replayed on a canvas of any size.
+    ///
+    /// Dash patterns are looked up in `patterns` so that they can be registered again when imported.
+    pub fn export(&self, canvas: &Canvas, patterns: &ShapeDashPatterns) -> PaintRecording {
+        let scale = 1.0 / canvas.height.max(1) as f32;
+        PaintRecording {
+            strokes: self
+                .strokes
+                .iter()
+                .map(|stroke| RecordedStroke {
+                    color: stroke.config.color,
+                    alpha_mode: stroke.config.alpha_mode,
+                    dash: stroke.config.dash.map_or(Vec::new(), |dash| {
+                        patterns
+                            .get(dash)
+                            .iter()
+                            .map(|value| value * scale)
+                            .collect()
+                    }),
+                    conic_gradient: stroke.config.conic_gradient,
+                    points: stroke
+                        .points
+                        .iter()
+                        .map(|&(point, thickness)| point.extend(thickness) * scale)
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replace the recorded strokes with those of a recording scaled to the size of the given canvas, drawing them
+    /// with the current brush in each stroke's color, alpha mode, dash pattern and gradient and redrawing the canvas.
+    ///
+    /// Dash patterns are scaled to the canvas and registered with `patterns`, strokes whose pattern doesn't fit are drawn solid.
+    /// Patterns are never removed and [`ShapeDashPatterns`] holds at most [`MAX_DASH_PATTERN_VALUES`](super::MAX_DASH_PATTERN_VALUES)
+    /// values, identical patterns are shared so re-importing onto canvases of the same height adds nothing, but each new
+    /// height registers every dash pattern of the recording again.
+    pub fn import(
+        &mut self,
+        recording: &PaintRecording,
+        canvas: &Canvas,
+        patterns: &mut ShapeDashPatterns,
+    ) {
+        let scale = canvas.height as f32;
+        let mut brush = self.brush.clone();
+        brush.transform = Transform::IDENTITY;
+        self.strokes = recording
+            .strokes
+            .iter()
+            .map(|stroke| {
+                let mut config = brush.clone();
+                config.color = stroke.color;
+                config.alpha_mode = stroke.alpha_mode;
+                config.conic_gradient = stroke.conic_gradient;
+                let dash: Vec<f32> = stroke.dash.iter().map(|value| value * scale).collect();
+                config.dash = patterns.add(&dash);
+                PaintStroke {
+                    config,
+                    points: stroke
+                        .points
+                        .iter()
+                        .map(|point| (point.truncate() * scale, point.z * scale))
+                        .collect(),
+                }
+            })
+            .collect();
+        self.undone.clear();
+        self.active = false;
+        self.redraw = true;
+    }
+
+    /// Append a point to the current stroke, or start a new stroke if none is being drawn.
+    fn record(&mut self, point: Vec2, pressure: f32) {
+        let thickness = self.brush.thickness * pressure;
+        if !self.active {
+            let mut config = self.brush.clone();
+            config.transform = Transform::IDENTITY;
+            self.strokes.push(PaintStroke {
+                config,
+                points: vec![(point, thickness)],
+            });
+            self.undone.clear();
+            self.active = true;
+            self.drawn = 0;
+            return;
+        }
+
+        let Some(stroke) = self.strokes.last_mut() else {
+            return;
+        };
+        if stroke
+            .points
+            .last()
+            .is_none_or(|(last, _)| last.distance(point) >= self.spacing)
+        {
+            stroke.points.push((point, thickness));
+        }
+    }
+}
+
+/// Plugin that records pointer input into strokes on canvases with a [`ShapePaint`] component.
+///
+/// Also registers the [`PaintRecording`] asset along with a loader for `.strokes` files.
+///
+/// Must be added after [`Shape2dPlugin`] or [`ShapePlugin`].
+pub struct ShapePaintPlugin;
+
+impl Plugin for ShapePaintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PaintRecording>()
+            .init_asset_loader::<PaintRecordingLoader>()
+            .add_systems(Update, (record_paint_strokes, draw_paint_strokes).chain());
+    }
+}
+
+/// Pressure of a touch between zero and one, touches that don't report a force are at full pressure.
+fn touch_pressure(touch: &Touch) -> f32 {
+    match touch.force() {
+        Some(ForceTouch::Normalized(force)) => (force as f32).clamp(0.0, 1.0),
+        Some(ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        }) if max_possible_force > 0.0 => ((force / max_possible_force) as f32).clamp(0.0, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// Records the position of the pointer over each [`ShapePaint`] canvas' camera into it's current stroke.
+pub fn record_paint_strokes(
+    buttons: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<&Camera>,
+    mut canvases: Query<(&mut ShapePaint, &Canvas)>,
+) {
+    // Touches take priority over the mouse, as some platforms emulate mouse input from touches
+    let pointer = touches
+        .iter()
+        .next()
+        .map(|touch| (touch.position(), touch_pressure(touch)))
+        .or_else(|| {
+            let window = windows.get_single().ok()?;
+            let position = window.cursor_position()?;
+            buttons
+                .pressed(MouseButton::Left)
+                .then_some((position, 1.0))
+        });
+
+    for (mut paint, canvas) in &mut canvases {
+        let point = pointer.and_then(|(position, pressure)| {
+            let viewport = cameras.get(paint.camera).ok()?.logical_viewport_rect()?;
+            let uv = (position - viewport.min) / viewport.size();
+            // Strokes can only be started within the view but continue while the pointer strays outside
+            let inside = uv.cmpge(Vec2::ZERO).all() && uv.cmple(Vec2::ONE).all();
+            (inside || paint.active).then(|| {
+                let size = Vec2::new(canvas.width as f32, canvas.height as f32);
+                (Vec2::new(uv.x - 0.5, 0.5 - uv.y) * size, pressure)
+            })
+        });
+
+        match point {
+            Some((point, pressure)) if paint.enabled => paint.record(point, pressure),
+            _ => paint.active = false,
+        }
+    }
+}
+
+/// Draws the strokes of each [`ShapePaint`] canvas, only drawing new points to persistent canvases unless they are redrawn.
+pub fn draw_paint_strokes(
+    mut canvases: Query<(Entity, &mut ShapePaint, &mut Canvas)>,
+    mut painter: ShapePainter,
+) {
+    for (entity, mut paint, mut canvas) in &mut canvases {
+        let incremental =
+            matches!(canvas.mode, CanvasMode::Persistent) && canvas.redraw_progress().is_none();
+        // Translucent and dashed strokes can't be drawn a piece at a time
+        let whole = paint.active
+            && paint.strokes.last().is_some_and(|stroke| {
+                stroke.config.color.alpha() < 1.0 || stroke.config.dash.is_some()
+            });
+
+        if paint.redraw || (incremental && whole) {
+            canvas.redraw();
+            paint.redraw = false;
+        } else if incremental {
+            // Earlier strokes are already on the canvas, so only the new points of the last stroke are drawn
+            let from = paint.drawn;
+            if let Some(stroke) = paint.strokes.last() {
+                draw_stroke(&mut painter, entity, stroke, from);
+                paint.drawn = stroke.points.len();
+            }
+            continue;
+        }
+
+        for stroke in &paint.strokes {
+            draw_stroke(&mut painter, entity, stroke, 0);
+        }
+        paint.drawn = paint.strokes.last().map_or(0, |stroke| stroke.points.len());
+    }
+}
+
+/// Draws a stroke as a polyline tapered to the thickness at each point, starting from the point before the given index.
+fn draw_stroke(painter: &mut ShapePainter, canvas: Entity, stroke: &PaintStroke, from: usize) {
+    let Some(&(first, thickness)) = stroke.points.first() else {
+        return;
+    };
+    if from >= stroke.points.len() {
+        return;
+    }
+
+    painter.set_config(stroke.config.clone());
+    painter.set_canvas(canvas);
+    painter.cap = Cap::Round;
+    painter.join = Join::Round;
+
+    // A tap draws a single dot
+    if stroke.points.len() == 1 {
+        painter.hollow = false;
+        painter.set_translation(first.extend(0.0));
+        painter.circle(thickness / 2.0);
+        return;
+    }
+
+    let points = &stroke.points[from.saturating_sub(1)..];
+    let thickness = points
+        .iter()
+        .fold(0.0, |max, (_, thickness)| thickness.max(max));
+    if thickness <= 0.0 {
+        return;
+    }
+
+    let positions: Vec<Vec2> = points.iter().map(|(point, _)| *point).collect();
+    let scales: Vec<f32> = points.iter().map(|(_, t)| t / thickness).collect();
+    painter.thickness = thickness;
+    painter.tapered_polyline(&positions, &scales);
+}
@@ -0,0 +1,307 @@
+use std::io::{Error, ErrorKind, Read};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+
+use crate::prelude::*;
+
+/// Bytes every serialized [`PaintRecording`] starts with.
+const MAGIC: &[u8; 4] = b"VSPR";
+const VERSION: u32 = 1;
+
+/// Stroke of a [`PaintRecording`], measured relative to the height of the canvas it was drawn on.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct RecordedStroke {
+    pub color: Color,
+    /// Alpha mode of the brush, so that strokes of an eraser still erase when replayed.
+    pub alpha_mode: ShapeAlphaMode,
+    /// Alternating dash and gap lengths of the brush's dash pattern in fractions of the canvas' height,
+    /// empty for a solid stroke.
+    pub dash: Vec<f32>,
+    /// Gradient of the brush, swept around the dots drawn by taps.
+    pub conic_gradient: Option<ConicGradient>,
+    /// Points along the stroke from the center of the canvas with y up in x and y and the thickness at each point in z,
+    /// all in fractions of the canvas' height.
+    pub points: Vec<Vec3>,
+}
+
+/// Asset holding the strokes recorded by a [`ShapePaint`] canvas independent of the canvas' resolution,
+/// so that drawings can be saved, sent to other players and replayed on canvases of any size.
+///
+/// Created by [`ShapePaint::export`] and replayed by [`ShapePaint::import`]. Serialized with
+/// [`PaintRecording::to_bytes`], files with the `strokes` extension are loaded by [`ShapePaintPlugin`].
+/// Only the color, alpha mode, dash pattern and gradient of each stroke's brush are kept, the rest of the brush
+/// is taken from the [`ShapePaint`] it's imported into.
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq)]
+pub struct PaintRecording {
+    pub strokes: Vec<RecordedStroke>,
+}
+
+impl PaintRecording {
+    /// Serialize the recording into a compact little endian binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let points: usize = self.strokes.iter().map(|stroke| stroke.points.len()).sum();
+        let mut bytes = Vec::with_capacity(12 + self.strokes.len() * 32 + points * 12);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.strokes.len() as u32).to_le_bytes());
+
+        for stroke in &self.strokes {
+            write_color(&mut bytes, stroke.color);
+            bytes.extend_from_slice(&alpha_mode_index(stroke.alpha_mode).to_le_bytes());
+            bytes.extend_from_slice(&(stroke.dash.len() as u32).to_le_bytes());
+            for value in &stroke.dash {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            write_gradient(&mut bytes, stroke.conic_gradient);
+            bytes.extend_from_slice(&(stroke.points.len() as u32).to_le_bytes());
+            for point in &stroke.points {
+                for value in point.to_array() {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Deserialize a recording written by [`PaintRecording::to_bytes`].
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, Error> {
+        let mut magic = [0; 4];
+        bytes.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "not a paint recording"));
+        }
+        let version = read_u32(&mut bytes)?;
+        if version != VERSION {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unsupported paint recording version {version}"),
+            ));
+        }
+
+        let count = read_u32(&mut bytes)?;
+        let mut strokes = Vec::new();
+        for _ in 0..count {
+            let color = read_color(&mut bytes)?;
+            let alpha_mode = alpha_mode_from_index(read_u32(&mut bytes)?)?;
+            let dash = (0..read_u32(&mut bytes)?)
+                .map(|_| read_f32(&mut bytes))
+                .collect::<Result<_, Error>>()?;
+            let conic_gradient = read_gradient(&mut bytes)?;
+            let points = (0..read_u32(&mut bytes)?)
+                .map(|_| {
+                    Ok(Vec3::new(
+                        read_f32(&mut bytes)?,
+                        read_f32(&mut bytes)?,
+                        read_f32(&mut bytes)?,
+                    ))
+                })
+                .collect::<Result<_, Error>>()?;
+            strokes.push(RecordedStroke {
+                color,
+                alpha_mode,
+                dash,
+                conic_gradient,
+                points,
+            });
+        }
+
+        Ok(Self { strokes })
+    }
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, Error> {
+    let mut value = [0; 4];
+    bytes.read_exact(&mut value)?;
+    Ok(u32::from_le_bytes(value))
+}
+
+fn read_f32(bytes: &mut &[u8]) -> Result<f32, Error> {
+    read_u32(bytes).map(f32::from_bits)
+}
+
+fn read_color(bytes: &mut &[u8]) -> Result<Color, Error> {
+    Ok(LinearRgba::from_f32_array([
+        read_f32(bytes)?,
+        read_f32(bytes)?,
+        read_f32(bytes)?,
+        read_f32(bytes)?,
+    ])
+    .into())
+}
+
+fn write_color(bytes: &mut Vec<u8>, color: Color) {
+    for channel in color.to_linear().to_f32_array() {
+        bytes.extend_from_slice(&channel.to_le_bytes());
+    }
+}
+
+/// Gradients are written as their kind, zero for none, followed by their end color and turns of hue.
+fn write_gradient(bytes: &mut Vec<u8>, gradient: Option<ConicGradient>) {
+    match gradient {
+        None => bytes.extend_from_slice(&0u32.to_le_bytes()),
+        Some(ConicGradient::Linear(color)) => {
+            bytes.extend_from_slice(&1u32.to_le_bytes());
+            write_color(bytes, color);
+        }
+        Some(ConicGradient::Hue { end_color, turns }) => {
+            bytes.extend_from_slice(&2u32.to_le_bytes());
+            write_color(bytes, end_color);
+            bytes.extend_from_slice(&turns.to_le_bytes());
+        }
+    }
+}
+
+fn read_gradient(bytes: &mut &[u8]) -> Result<Option<ConicGradient>, Error> {
+    match read_u32(bytes)? {
+        0 => Ok(None),
+        1 => Ok(Some(ConicGradient::Linear(read_color(bytes)?))),
+        2 => Ok(Some(ConicGradient::Hue {
+            end_color: read_color(bytes)?,
+            turns: read_f32(bytes)?,
+        })),
+        _ => Err(Error::new(ErrorKind::InvalidData, "unknown gradient")),
+    }
+}
+
+fn alpha_mode_index(alpha_mode: ShapeAlphaMode) -> u32 {
+    match alpha_mode {
+        ShapeAlphaMode::Blend => 0,
+        ShapeAlphaMode::Add => 1,
+        ShapeAlphaMode::Multiply => 2,
+        ShapeAlphaMode::Erase => 3,
+        ShapeAlphaMode::AlphaToCoverage => 4,
+    }
+}
+
+fn alpha_mode_from_index(index: u32) -> Result<ShapeAlphaMode, Error> {
+    match index {
+        0 => Ok(ShapeAlphaMode::Blend),
+        1 => Ok(ShapeAlphaMode::Add),
+        2 => Ok(ShapeAlphaMode::Multiply),
+        3 => Ok(ShapeAlphaMode::Erase),
+        4 => Ok(ShapeAlphaMode::AlphaToCoverage),
+        _ => Err(Error::new(ErrorKind::InvalidData, "unknown alpha mode")),
+    }
+}
+
+/// Loads [`PaintRecording`]s from files with the `strokes` extension.
+#[derive(Default)]
+pub struct PaintRecordingLoader;
+
+impl AssetLoader for PaintRecordingLoader {
+    type Asset = PaintRecording;
+    type Settings = ();
+    type Error = Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<PaintRecording, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        PaintRecording::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strokes"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> PaintRecording {
+        PaintRecording {
+            strokes: vec![
+                RecordedStroke {
+                    color: LinearRgba::RED.into(),
+                    alpha_mode: ShapeAlphaMode::Blend,
+                    dash: Vec::new(),
+                    conic_gradient: None,
+                    points: vec![Vec3::new(-0.25, 0.5, 0.01), Vec3::new(0.25, -0.5, 0.02)],
+                },
+                RecordedStroke {
+                    color: LinearRgba::new(0.1, 0.2, 0.3, 0.4).into(),
+                    alpha_mode: ShapeAlphaMode::Erase,
+                    dash: vec![0.05, 0.025],
+                    conic_gradient: Some(ConicGradient::Linear(LinearRgba::BLUE.into())),
+                    points: vec![Vec3::new(0.0, 0.0, 0.03)],
+                },
+                RecordedStroke {
+                    color: LinearRgba::WHITE.into(),
+                    alpha_mode: ShapeAlphaMode::AlphaToCoverage,
+                    dash: vec![0.1],
+                    conic_gradient: Some(ConicGradient::Hue {
+                        end_color: LinearRgba::GREEN.into(),
+                        turns: 1.5,
+                    }),
+                    points: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    fn invalid_data(bytes: &[u8]) -> bool {
+        matches!(PaintRecording::from_bytes(bytes), Err(error) if error.kind() == ErrorKind::InvalidData)
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = recording();
+        assert_eq!(
+            PaintRecording::from_bytes(&recording.to_bytes()).unwrap(),
+            recording
+        );
+        assert_eq!(
+            PaintRecording::from_bytes(&PaintRecording::default().to_bytes()).unwrap(),
+            PaintRecording::default()
+        );
+    }
+
+    #[test]
+    fn bad_magic() {
+        let mut bytes = recording().to_bytes();
+        bytes[0] = b'X';
+        assert!(invalid_data(&bytes));
+    }
+
+    #[test]
+    fn bad_version() {
+        let mut bytes = recording().to_bytes();
+        bytes[4..8].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert!(invalid_data(&bytes));
+    }
+
+    #[test]
+    fn unknown_alpha_mode() {
+        let mut bytes = recording().to_bytes();
+        // Header followed by the first stroke's color
+        bytes[28..32].copy_from_slice(&5u32.to_le_bytes());
+        assert!(invalid_data(&bytes));
+    }
+
+    #[test]
+    fn unknown_gradient() {
+        let mut bytes = recording().to_bytes();
+        // First stroke's alpha mode followed by an empty dash pattern
+        bytes[36..40].copy_from_slice(&3u32.to_le_bytes());
+        assert!(invalid_data(&bytes));
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = recording().to_bytes();
+        for len in [0, 3, 8, 11, 20, bytes.len() - 1] {
+            assert!(matches!(
+                PaintRecording::from_bytes(&bytes[..len]),
+                Err(error) if error.kind() == ErrorKind::UnexpectedEof
+            ));
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Range,
+};
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    utils::{FixedState, HashMap, HashSet},
+};
+
+use super::{RetainedShapeData, ShapeStorage};
+use crate::{prelude::*, render::polyline_points::MAX_UNIFORM_POLYLINE_POINTS};
+
+/// Smallest block of points allocated for a [`DynamicPolylineComponent`].
+const MIN_BLOCK_SIZE: u32 = 16;
+
+/// Resource containing the points of every polyline, mirroring the buffer they are drawn from.
+///
+/// Each point is stored with the scale of the polyline's thickness at that point in z, w is unused.
+///
+/// Each [`DynamicPolylineComponent`] is assigned a block of points that doubles in size when outgrown.
+/// The points of other polylines never change once written, so they are shared by every polyline drawn through
+/// the same points and released once no polyline kept by [`ShapeStorage`] or the world refers to them.
+/// The ranges written each frame are tracked so that only they are uploaded.
+#[derive(Resource, Default, Debug)]
+pub struct ShapePolylinePoints {
+    points: Vec<Vec4>,
+    blocks: EntityHashMap<Range<u32>>,
+    /// Blocks holding the points of polylines other than [`DynamicPolylineComponent`]s, keyed by the hash of their points.
+    interned: HashMap<u64, Vec<Range<u32>>>,
+    free: Vec<Range<u32>>,
+    changed: Vec<Range<u32>>,
+}
+
+impl ShapePolylinePoints {
+    /// All points, each polyline stored contiguously within it's block.
+    pub fn values(&self) -> &[Vec4] {
+        &self.points
+    }
+
+    /// Ranges of [`Self::values`] that were written this frame.
+    pub fn changed(&self) -> &[Range<u32>] {
+        &self.changed
+    }
+
+    /// Offset of a block holding the given points, writing them to a new block if no polyline was already drawn through them.
+    ///
+    /// Each point's thickness is scaled by the value at the same index of `thickness_scales`, clamped between zero and one,
+    /// points without a scale are drawn at full thickness.
+    pub fn intern(&mut self, points: &[Vec2], thickness_scales: &[f32]) -> u32 {
+        if points.is_empty() {
+            return 0;
+        }
+
+        let point = |i: usize| {
+            let scale = thickness_scales.get(i).map_or(1.0, |s| s.clamp(0.0, 1.0));
+            points[i].extend(scale).extend(0.0)
+        };
+
+        let mut hasher = FixedState.build_hasher();
+        for i in 0..points.len() {
+            point(i).to_array().map(f32::to_bits).hash(&mut hasher);
+        }
+        let hash = hasher.finish();
+
+        let same = |block: &&Range<u32>| {
+            let interned = &self.points[block.start as usize..block.end as usize];
+            interned.len() == points.len()
+                && interned.iter().enumerate().all(|(i, interned)| {
+                    interned.to_array().map(f32::to_bits) == point(i).to_array().map(f32::to_bits)
+                })
+        };
+        if let Some(block) = self
+            .interned
+            .get(&hash)
+            .and_then(|blocks| blocks.iter().find(same))
+        {
+            return block.start;
+        }
+
+        let block = self.allocate(points.len() as u32);
+        for (i, stored) in self.points[block.start as usize..block.end as usize]
+            .iter_mut()
+            .enumerate()
+        {
+            *stored = point(i);
+        }
+        self.changed.push(block.clone());
+        self.interned.entry(hash).or_default().push(block.clone());
+        block.start
+    }
+
+    fn allocate(&mut self, size: u32) -> Range<u32> {
+        if let Some(i) = self
+            .free
+            .iter()
+            .position(|block| block.len() as u32 >= size)
+        {
+            let block = self.free.swap_remove(i);
+            if block.len() as u32 > size {
+                self.free.push(block.start + size..block.end);
+            }
+            return block.start..block.start + size;
+        }
+
+        let start = self.points.len() as u32;
+        self.points.resize((start + size) as usize, Vec4::ZERO);
+        start..start + size
+    }
+
+    fn release(&mut self, entity: Entity) {
+        if let Some(block) = self.blocks.remove(&entity) {
+            self.free.push(block);
+        }
+    }
+
+    /// Merge adjacent free blocks so that they can hold larger polylines.
+    fn merge_free(&mut self) {
+        self.free.sort_unstable_by_key(|block| block.start);
+        let mut merged: Vec<Range<u32>> = Vec::with_capacity(self.free.len());
+        for block in self.free.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.end == block.start => last.end = block.end,
+                _ => merged.push(block),
+            }
+        }
+        self.free = merged;
+    }
+
+    /// Whether enough points have been freed that moving the remaining blocks together is worthwhile.
+    ///
+    /// Without storage buffers only the first [`MAX_UNIFORM_POLYLINE_POINTS`] points can be drawn,
+    /// so any gap is closed as soon as the points extend past them.
+    fn needs_compaction(&self) -> bool {
+        let free: usize = self.free.iter().map(ExactSizeIterator::len).sum();
+        free > 0
+            && (free * 2 > self.points.len()
+                || self.points.len() > MAX_UNIFORM_POLYLINE_POINTS as usize)
+    }
+
+    /// Moves every block to the start of the points, returning the new offset of each block that moved.
+    fn compact(&mut self) -> HashMap<u32, u32> {
+        let ShapePolylinePoints {
+            points,
+            blocks,
+            interned,
+            free,
+            changed,
+        } = self;
+
+        let mut live: Vec<&mut Range<u32>> = blocks
+            .values_mut()
+            .chain(interned.values_mut().flatten())
+            .collect();
+        live.sort_unstable_by_key(|block| block.start);
+
+        let mut moved = HashMap::new();
+        let mut end = 0;
+        for block in live {
+            let len = block.len() as u32;
+            if block.start != end {
+                points.copy_within(block.start as usize..block.end as usize, end as usize);
+                moved.insert(block.start, end);
+                *block = end..end + len;
+                changed.push(block.clone());
+            }
+            end += len;
+        }
+
+        points.truncate(end as usize);
+        free.clear();
+        moved
+    }
+
+    fn write(&mut self, entity: Entity, polyline: &mut DynamicPolylineComponent) {
+        let len = polyline.len() as u32;
+        let mut changed = polyline.take_changed();
+
+        let block = match self.blocks.get(&entity) {
+            Some(block) if block.len() as u32 >= len => block.clone(),
+            _ => {
+                // Move to a larger block, all points need to be copied across
+                self.release(entity);
+                let block = self.allocate(len.max(MIN_BLOCK_SIZE).next_power_of_two());
+                self.blocks.insert(entity, block.clone());
+                changed = Some(0..len as usize);
+                block
+            }
+        };
+        polyline.set_offset(block.start);
+
+        let Some(range) = changed else {
+            return;
+        };
+        let range = range.start..range.end.min(len as usize);
+        if range.is_empty() {
+            return;
+        }
+
+        let start = block.start as usize + range.start;
+        let points = polyline.points()[range.clone()].iter();
+        for (stored, point) in self.points[start..start + range.len()]
+            .iter_mut()
+            .zip(points)
+        {
+            *stored = point.extend(1.0).extend(0.0);
+        }
+        self.changed
+            .push(start as u32..(start + range.len()) as u32);
+    }
+}
+
+/// Clears the ranges of [`ShapePolylinePoints`] written during the previous frame.
+pub fn clear_polyline_points(mut points: ResMut<ShapePolylinePoints>) {
+    points.bypass_change_detection().changed.clear();
+}
+
+/// Writes modified [`DynamicPolylineComponent`]s to [`ShapePolylinePoints`] and frees the points of removed ones.
+pub fn update_dynamic_polylines(
+    mut points: ResMut<ShapePolylinePoints>,
+    mut polylines: Query<
+        (Entity, &mut DynamicPolylineComponent),
+        Changed<DynamicPolylineComponent>,
+    >,
+    mut removed: RemovedComponents<DynamicPolylineComponent>,
+) {
+    for entity in removed.read() {
+        points.bypass_change_detection().release(entity);
+    }
+
+    for (entity, mut polyline) in &mut polylines {
+        points.write(entity, polyline.bypass_change_detection());
+    }
+}
+
+/// Writes the points of modified [`PolylineComponent`]s to [`ShapePolylinePoints`].
+pub fn update_polylines(
+    mut points: ResMut<ShapePolylinePoints>,
+    mut polylines: Query<&mut PolylineComponent, Changed<PolylineComponent>>,
+) {
+    for mut polyline in &mut polylines {
+        let offset = points.intern(&polyline.points, &polyline.thickness_scales);
+        polyline.bypass_change_detection().set_offset(offset);
+    }
+}
+
+/// Frees the points of polylines that are no longer kept by [`ShapeStorage`], retained shapes or [`PolylineComponent`]s,
+/// moving the remaining points together once enough have been freed.
+///
+/// Runs at the end of the frame so that every polyline drawn this frame is still held by [`ShapeStorage`].
+pub fn release_polyline_points(
+    mut points: ResMut<ShapePolylinePoints>,
+    mut storage: ResMut<ShapeStorage>,
+    mut retained: Query<&mut RetainedShapeData<PolylineData>>,
+    mut polylines: Query<&mut PolylineComponent>,
+    mut dynamic: Query<(Entity, &mut DynamicPolylineComponent)>,
+) {
+    let used: HashSet<u32> = storage
+        .stored::<PolylineData>()
+        .chain(retained.iter().map(|retained| &retained.0))
+        .map(PolylineData::offset)
+        .chain(polylines.iter().map(PolylineComponent::offset))
+        .collect();
+
+    let points = points.bypass_change_detection();
+    let mut released = false;
+    let ShapePolylinePoints { interned, free, .. } = &mut *points;
+    interned.retain(|_, blocks| {
+        blocks.retain(|block| {
+            let keep = used.contains(&block.start);
+            if !keep {
+                free.push(block.clone());
+                released = true;
+            }
+            keep
+        });
+        !blocks.is_empty()
+    });
+
+    if released {
+        points.merge_free();
+    }
+
+    if !points.needs_compaction() {
+        return;
+    }
+
+    let moved = points.compact();
+    let storage = storage.bypass_change_detection();
+    for data in storage.stored_mut::<PolylineData>().chain(
+        retained
+            .iter_mut()
+            .map(|retained| &mut retained.into_inner().0),
+    ) {
+        if let Some(offset) = moved.get(&data.offset()) {
+            data.set_offset(*offset);
+        }
+    }
+    for mut polyline in &mut polylines {
+        if let Some(offset) = moved.get(&polyline.offset()) {
+            polyline.bypass_change_detection().set_offset(*offset);
+        }
+    }
+    for (entity, mut polyline) in &mut dynamic {
+        if let Some(block) = points.blocks.get(&entity) {
+            polyline.bypass_change_detection().set_offset(block.start);
+        }
+    }
+}
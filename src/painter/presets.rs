@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::{prelude::*, render::ShapePipelineType};
+
+impl ShapeConfig {
+    /// Preset for HUDs and other 2D interfaces drawn by a 2D camera.
+    ///
+    /// Thicknesses are in pixels so strokes stay the same width as the camera zooms.
+    pub fn ui_2d() -> Self {
+        Self {
+            thickness: 2.0,
+            thickness_type: ThicknessType::Pixels,
+            ..Self::default_2d()
+        }
+    }
+
+    /// Preset for debug visualizations drawn over a 3D scene.
+    ///
+    /// Shapes billboard to face the camera with thicknesses in pixels, so lines are readable from any angle or distance,
+    /// and are drawn without the depth test so they aren't hidden by the geometry they describe.
+    /// Set `alignment` to [`Alignment::Flat`] to draw shapes that lie in a plane such as bounding rects.
+    pub fn debug_3d() -> Self {
+        Self {
+            alignment: Alignment::Billboard,
+            thickness: 2.0,
+            thickness_type: ThicknessType::Pixels,
+            depth_test: false,
+            ..Self::default_3d()
+        }
+    }
+
+    /// Preset for drawing hard edged pixel art to the given [`Canvas`], typically one with a nearest sampler
+    /// upscaled to the screen.
+    ///
+    /// Local anti-aliasing is disabled so edges aren't blended, and strokes are a single pixel wide ending exactly at their end points.
+    pub fn pixel_perfect(canvas: Entity) -> Self {
+        Self {
+            canvas: Some(canvas),
+            pipeline: ShapePipelineType::Shape2d,
+            disable_laa: true,
+            thickness: 1.0,
+            thickness_type: ThicknessType::Pixels,
+            cap: Cap::None,
+            ..Self::default_2d()
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::{ecs::system::EntityCommands, prelude::*, render::view::RenderLayers};
+
+use crate::{prelude::*, render::ShapeData, render::ShapePipelineType};
+
+/// Component holding the [`ShapeData`] of a shape promoted to a retained entity by [`ShapePainter::retain`].
+///
+/// The data is drawn at the entity's [`GlobalTransform`], so promoted shapes can be moved, hidden and despawned
+/// like any other entity, but their shape can only be changed through the data itself as they have no shape component.
+#[derive(Component, Clone)]
+pub struct RetainedShapeData<T: ShapeData>(pub T);
+
+/// Inserts a shape sent while retaining shapes into an entity, replacing the components of any shape of the same type it held.
+pub(crate) type RetainedShape = Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>;
+
+/// Moves the data sent with the given config into an entity to be spawned or updated later.
+pub(crate) fn retain_shape<T: ShapeData>(config: &ShapeConfig, mut data: T) -> RetainedShape {
+    let matrix = data.transform();
+    data.set_transform(Mat4::IDENTITY);
+
+    let material = ShapeMaterial::new(config);
+    let layers = config.render_layers.clone();
+    let is_3d = config.pipeline == ShapePipelineType::Shape3d;
+    let origin = config
+        .origin
+        .map(|origin| ShapeOrigin(matrix.inverse().transform_point3(origin)));
+
+    Box::new(move |entity| {
+        entity.insert((
+            RetainedShapeData(data),
+            material,
+            Transform::from_matrix(matrix),
+            Visibility::default(),
+        ));
+        match layers {
+            Some(layers) => entity.insert(layers),
+            None => entity.remove::<RenderLayers>(),
+        };
+        if is_3d {
+            entity.insert(Shape3d);
+        } else {
+            entity.remove::<Shape3d>();
+        }
+        match origin {
+            Some(origin) => entity.insert(origin),
+            None => entity.remove::<ShapeOrigin>(),
+        };
+    })
+}
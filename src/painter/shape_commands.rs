@@ -22,6 +22,16 @@ impl<'w, 's> ShapeCommands<'w, 's> {
     pub fn reset(&mut self) {
         *self.config = self.default_config.0.clone();
     }
+
+    /// Queue a [`SetMany`] command setting a component, e.g. [`DiscComponent`] or [`ShapeFill`], on many existing shapes at once.
+    pub fn set_many<C: Component + PartialEq>(
+        &mut self,
+        values: impl IntoIterator<Item = (Entity, C)>,
+    ) {
+        self.commands.queue(SetMany {
+            values: values.into_iter().collect(),
+        });
+    }
 }
 
 impl<'w, 's> ShapeSpawner<'w> for ShapeCommands<'w, 's> {
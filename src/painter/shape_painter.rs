@@ -1,13 +1,27 @@
 use std::{
     any::TypeId,
+    hash::Hash,
     ops::{Deref, DerefMut},
     slice::Iter,
 };
 
-use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use bevy::{
+    ecs::{
+        entity::{EntityHashMap, EntityHashSet},
+        system::{SystemName, SystemParam},
+    },
+    prelude::*,
+    render::primitives::{Frustum, Sphere},
+    utils::HashMap,
+};
 
 use any_vec::AnyVec;
 
+use super::{
+    canvas::CANVAS_BACKGROUND_DEPTH, compound::CompoundRecording, group::GroupStorage,
+    keyed::KeyedShapes, retain::retain_shape, Canvas, CanvasLabel, RetainedShape,
+};
+
 use crate::{
     prelude::*,
     render::{ShapeData, ShapeInstance, ShapePipelineMaterial, ShapePipelineType},
@@ -16,43 +30,369 @@ use crate::{
 /// A system param for type erased storage of [`ShapeInstance`].
 ///
 /// Generally should only be consumed as part of [`ShapePainter`] and not used directly.
+///
+/// Instances of any [`ShapeData`] can be submitted, including types defined outside this crate,
+/// they are extracted for rendering as long as a [`ShapeTypePlugin`](crate::render::ShapeTypePlugin)
+/// (and [`ShapeType3dPlugin`](crate::render::ShapeType3dPlugin) for the 3D pipeline) has been added for the type.
+/// See the `custom_shape` example.
 #[derive(Resource, Default)]
 pub struct ShapeStorage {
     shapes: HashMap<(TypeId, ShapePipelineType), AnyVec<dyn Send + Sync>>,
+    canvases: EntityHashSet,
+    /// Frusta of active cameras that aren't drawing to a [`Canvas`], used to cull shapes that don't target a canvas.
+    frusta: Vec<Frustum>,
+    /// Frusta of each canvas' camera whether or not it's active, used to cull shapes sent to that canvas.
+    canvas_frusta: EntityHashMap<Frustum>,
+    /// Canvases by their [`CanvasLabel`], used to resolve [`ShapeConfig::canvas_label`].
+    canvas_labels: HashMap<CanvasLabel, Entity>,
+    culled: u32,
+    recording: Option<CompoundRecording>,
+    compounds: HashMap<String, (u32, CompoundRecording)>,
+    /// Shapes sent within [`ShapePainter::retain`], spawned as entities once it's closure returns.
+    retained: Option<Vec<RetainedShape>>,
+    /// Offset added to the 2D sort keys of shapes as they are sent, used to draw canvas backgrounds behind everything else.
+    sort_offset: Option<f32>,
+    groups: HashMap<String, GroupStorage>,
+    keyed: HashMap<ShapeKey, KeyedShapes>,
+    frame: u32,
+    /// Total number of shapes sent this frame, used to attribute them to the system that sent them.
+    sent: u32,
+    /// Shapes sent this frame by each system while tracking is enabled, see [`ShapeStorage::set_tracking`].
+    sources: Option<HashMap<String, u32>>,
+    last_sources: HashMap<String, u32>,
 }
 
 impl ShapeStorage {
-    fn send<T: ShapeData>(&mut self, config: &ShapeConfig, data: T) {
-        let key = (TypeId::of::<T>(), config.pipeline);
-        let vec = self
-            .shapes
-            .entry(key)
-            .or_insert_with(AnyVec::new::<ShapeInstance<T>>);
+    /// Submits a shape to be drawn this frame with the material, origin and pipeline of the given config.
+    ///
+    /// The data is sent as is, so it should already reflect the config's transform and color.
+    pub fn send<T: ShapeData>(&mut self, config: &ShapeConfig, data: T) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(data);
+            return;
+        }
+
+        if let Some(retained) = &mut self.retained {
+            retained.push(retain_shape(config, data));
+            return;
+        }
+
+        self.sent += 1;
+        if let Some(key) = config.key {
+            let frame = self.frame;
+            self.keyed.entry(key).or_default().push(frame, config, data);
+            return;
+        }
+
+        let canvas = self.resolve_canvas(config);
+        if config.cull
+            && config.screen_space.is_none()
+            && config.alignment != Alignment::BillboardFixedSize
+            && self.is_culled(canvas, &data)
+        {
+            self.culled += 1;
+            return;
+        }
 
         let instance = ShapeInstance {
-            material: ShapePipelineMaterial::from(config),
+            material: ShapePipelineMaterial::from(config).with_canvas(canvas),
             origin: config.origin.unwrap_or(config.transform.translation),
+            fade: config.fade.clone(),
+            sort_key: self.sort_offset.map(|offset| data.distance() + offset),
+            sort_bias: config.sort_bias,
+            depth_slices: config.depth_slices,
+            screen_space: config.screen_space,
             data,
         };
+        self.push(config, canvas, [instance]);
+    }
+
+    /// Submits a copy of a shape for each transform, with the material, origin and pipeline of the given config.
+    ///
+    /// Each transform is applied on top of the shape's own transform, so a shape drawn at the origin is placed at each transform.
+    /// The material is built once for every copy, which avoids the overhead of sending each copy separately
+    /// when drawing many identical shapes, e.g. markers over thousands of units.
+    pub fn send_instances<T: ShapeData>(
+        &mut self,
+        config: &ShapeConfig,
+        data: T,
+        transforms: impl IntoIterator<Item = Transform>,
+    ) {
+        let copy = |transform: Transform| {
+            let mut copy = data.clone();
+            copy.set_transform(transform.compute_matrix() * data.transform());
+            copy
+        };
+
+        // Recorded, retained and keyed shapes are tracked one by one
+        if self.recording.is_some() || self.retained.is_some() || config.key.is_some() {
+            for transform in transforms {
+                self.send(config, copy(transform));
+            }
+            return;
+        }
+
+        let cull = config.cull
+            && config.screen_space.is_none()
+            && config.alignment != Alignment::BillboardFixedSize;
+        let canvas = self.resolve_canvas(config);
+        let material = ShapePipelineMaterial::from(config).with_canvas(canvas);
+        let origin = config.origin.unwrap_or(config.transform.translation);
+
+        let mut instances = Vec::new();
+        for transform in transforms {
+            let data = copy(transform);
+            self.sent += 1;
+            if cull && self.is_culled(canvas, &data) {
+                self.culled += 1;
+                continue;
+            }
+            instances.push(ShapeInstance {
+                material: material.clone(),
+                origin: transform.transform_point(origin),
+                fade: config.fade.clone(),
+                sort_key: self.sort_offset.map(|offset| data.distance() + offset),
+                sort_bias: config.sort_bias,
+                depth_slices: config.depth_slices,
+                screen_space: config.screen_space,
+                data,
+            });
+        }
+        self.push(config, canvas, instances);
+    }
+
+    /// Canvas targeted by the config, shapes targeting a label that doesn't match any canvas aren't drawn.
+    fn resolve_canvas(&self, config: &ShapeConfig) -> Option<Entity> {
+        config.canvas.or_else(|| {
+            let label = config.canvas_label.as_ref()?;
+            Some(
+                *self
+                    .canvas_labels
+                    .get(label)
+                    .unwrap_or(&Entity::PLACEHOLDER),
+            )
+        })
+    }
+
+    fn push<T: ShapeData>(
+        &mut self,
+        config: &ShapeConfig,
+        canvas: Option<Entity>,
+        instances: impl IntoIterator<Item = ShapeInstance<T>>,
+    ) {
+        // Shapes in a group replace those drawn into it in previous frames
+        let (shapes, canvases) = match &config.group {
+            Some(name) => {
+                let frame = self.frame;
+                let group = self
+                    .groups
+                    .entry(name.clone())
+                    .or_insert_with(|| GroupStorage::new(frame));
+                if group.frame != frame {
+                    *group = GroupStorage::new(frame);
+                }
+                (&mut group.shapes, &mut group.canvases)
+            }
+            None => (&mut self.shapes, &mut self.canvases),
+        };
+
+        let key = (TypeId::of::<T>(), config.pipeline);
+        if let Some(canvas) = canvas {
+            canvases.insert(canvas);
+        }
+        let vec = shapes
+            .entry(key)
+            .or_insert_with(AnyVec::new::<ShapeInstance<T>>);
 
         // SAFETY: we only insert entries in this function and only those that match the appropriate TypeId
-        unsafe {
-            vec.downcast_mut_unchecked().push(instance);
+        let mut vec = unsafe { vec.downcast_mut_unchecked() };
+        for instance in instances {
+            vec.push(instance);
         }
     }
 
+    /// Shapes of the given type sent this frame, not including those kept in [`ShapeGroups`], see [`Self::get_with_groups`].
     pub fn get<T: ShapeData>(
         &self,
         pipeline: ShapePipelineType,
     ) -> Option<Iter<'_, ShapeInstance<T>>> {
-        // SAFETY: we only insert entries in ShapeStorage::send and only those that match the appropriate TypeId
+        // SAFETY: we only insert entries in ShapeStorage::push and only those that match the appropriate TypeId
         self.shapes
             .get(&(TypeId::of::<T>(), pipeline))
             .map(|vec| unsafe { vec.downcast_ref_unchecked::<ShapeInstance<T>>().iter() })
     }
 
+    /// Shapes of the given type sent this frame, followed by those kept in enabled [`ShapeGroups`].
+    pub fn get_with_groups<T: ShapeData>(
+        &self,
+        pipeline: ShapePipelineType,
+    ) -> impl Iterator<Item = &ShapeInstance<T>> {
+        let key = (TypeId::of::<T>(), pipeline);
+        let groups = self
+            .enabled_groups()
+            .filter_map(move |group| group.shapes.get(&key));
+        // SAFETY: we only insert entries in ShapeStorage::push and only those that match the appropriate TypeId
+        self.shapes
+            .get(&key)
+            .into_iter()
+            .chain(groups)
+            .flat_map(|vec| unsafe { vec.downcast_ref_unchecked::<ShapeInstance<T>>().iter() })
+    }
+
+    /// Canvases that shapes have been sent to this frame, or that are drawn to by enabled [`ShapeGroups`].
+    pub fn canvases(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.enabled_groups()
+            .flat_map(|group| group.canvases.iter())
+            .chain(self.canvases.iter())
+            .copied()
+    }
+
+    /// Every shape of the given type kept in storage, including those in disabled groups and recorded [`CompoundShape`]s.
+    pub(crate) fn stored<T: ShapeData>(&self) -> impl Iterator<Item = &T> {
+        let type_id = TypeId::of::<T>();
+        let instances = self
+            .groups
+            .values()
+            .map(|group| &group.shapes)
+            .chain([&self.shapes])
+            .flat_map(move |shapes| shapes.iter().filter(move |((id, _), _)| *id == type_id))
+            // SAFETY: we only insert entries in ShapeStorage::push and only those that match the appropriate TypeId
+            .flat_map(|(_, vec)| unsafe { vec.downcast_ref_unchecked::<ShapeInstance<T>>().iter() })
+            .map(|instance| &instance.data);
+        let recorded = self
+            .compounds
+            .values()
+            .flat_map(|(_, recording)| recording.get::<T>());
+        instances.chain(recorded)
+    }
+
+    /// Mutable access to every shape returned by [`Self::stored`].
+    pub(crate) fn stored_mut<T: ShapeData>(&mut self) -> impl Iterator<Item = &mut T> {
+        let type_id = TypeId::of::<T>();
+        let instances = self
+            .groups
+            .values_mut()
+            .map(|group| &mut group.shapes)
+            .chain([&mut self.shapes])
+            .flat_map(move |shapes| shapes.iter_mut().filter(move |((id, _), _)| *id == type_id))
+            // SAFETY: we only insert entries in ShapeStorage::push and only those that match the appropriate TypeId
+            .flat_map(|(_, vec)| unsafe {
+                vec.downcast_mut_unchecked::<ShapeInstance<T>>().iter_mut()
+            })
+            .map(|instance| &mut instance.data);
+        let recorded = self
+            .compounds
+            .values_mut()
+            .flat_map(|(_, recording)| recording.get_mut::<T>());
+        instances.chain(recorded)
+    }
+
+    fn enabled_groups(&self) -> impl Iterator<Item = &GroupStorage> {
+        self.groups.values().filter(|group| group.enabled)
+    }
+
+    pub(crate) fn groups_mut(&mut self) -> &mut HashMap<String, GroupStorage> {
+        &mut self.groups
+    }
+
+    pub(crate) fn keyed_mut(&mut self) -> &mut HashMap<ShapeKey, KeyedShapes> {
+        &mut self.keyed
+    }
+
+    pub(crate) fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Number of shapes skipped this frame as they were outside the view of every camera, see [`ShapeConfig::cull`].
+    pub fn culled(&self) -> u32 {
+        self.culled
+    }
+
+    /// Enable or disable counting the shapes sent by each system, see [`ShapeStorage::sources`].
+    ///
+    /// Useful to find which debug system is responsible for an unexpected number of shapes.
+    pub fn set_tracking(&mut self, enabled: bool) {
+        if enabled != self.is_tracking() {
+            self.sources = enabled.then(HashMap::new);
+            self.last_sources.clear();
+        }
+    }
+
+    pub fn is_tracking(&self) -> bool {
+        self.sources.is_some()
+    }
+
+    /// Number of shapes sent during the previous frame by each system that sent any, keyed by system name.
+    ///
+    /// Only counted while tracking is enabled with [`ShapeStorage::set_tracking`]. Shapes are counted before they are culled,
+    /// and shapes kept in [`ShapeGroups`] are only counted in the frame they were drawn.
+    /// Shapes sent with [`ShapeStorage::send`] directly rather than through a [`ShapePainter`] aren't attributed to a system.
+    pub fn sources(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.last_sources
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
+    /// Attribute the shapes sent since `sent` was read to the given system while tracking is enabled.
+    fn attribute(&mut self, system: &str, sent: u32) {
+        let count = self.sent - sent;
+        let Some(sources) = &mut self.sources else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+        match sources.get_mut(system) {
+            Some(total) => *total += count,
+            None => {
+                sources.insert(system.to_string(), count);
+            }
+        }
+    }
+
+    /// Whether the shape is outside the view of every camera that could draw it, only the canvas' camera for shapes sent to a canvas.
+    fn is_culled<T: ShapeData>(&self, canvas: Option<Entity>, data: &T) -> bool {
+        let frusta = match canvas {
+            Some(canvas) => match self.canvas_frusta.get(&canvas) {
+                Some(frustum) => std::slice::from_ref(frustum),
+                None => return false,
+            },
+            None => &self.frusta,
+        };
+        // Nothing is culled until cameras have been cached from a previous frame
+        if frusta.is_empty() {
+            return false;
+        }
+        let Some(radius) = data.bounding_radius() else {
+            return false;
+        };
+
+        let transform = data.transform();
+        let scale = transform
+            .x_axis
+            .length()
+            .max(transform.y_axis.length())
+            .max(transform.z_axis.length());
+        let sphere = Sphere {
+            center: transform.w_axis.truncate().into(),
+            radius: radius * scale,
+        };
+
+        !frusta
+            .iter()
+            .any(|frustum| frustum.intersects_sphere(&sphere, true))
+    }
+
     fn clear(&mut self) {
         self.shapes = HashMap::new();
+        self.canvases.clear();
+        self.culled = 0;
+        self.sent = 0;
+        if let Some(sources) = &mut self.sources {
+            std::mem::swap(sources, &mut self.last_sources);
+            sources.clear();
+        }
+        self.frame = self.frame.wrapping_add(1);
     }
 }
 
@@ -61,6 +401,39 @@ pub fn clear_storage(mut storage: ResMut<ShapeStorage>) {
     storage.clear();
 }
 
+/// Caches the frusta of cameras in [`ShapeStorage`] for culling shapes drawn during the next frame.
+///
+/// Canvas cameras are cached even while inactive, so shapes drawn to a canvas that is only redrawn on demand
+/// are culled against it's own view rather than skipped because no other camera can see them.
+pub fn update_storage_frusta(
+    storage: ResMut<ShapeStorage>,
+    cameras: Query<(Entity, &Camera, &Frustum, Has<Canvas>)>,
+) {
+    let storage = storage.into_inner();
+    storage.frusta.clear();
+    storage.canvas_frusta.clear();
+    for (entity, camera, frustum, is_canvas) in &cameras {
+        if is_canvas {
+            storage.canvas_frusta.insert(entity, *frustum);
+        } else if camera.is_active {
+            storage.frusta.push(*frustum);
+        }
+    }
+}
+
+/// Caches the [`CanvasLabel`] of each canvas in [`ShapeStorage`] for resolving the labels of shapes drawn this frame.
+pub fn update_storage_canvas_labels(
+    mut storage: ResMut<ShapeStorage>,
+    canvases: Query<(Entity, &CanvasLabel), With<Canvas>>,
+) {
+    storage.canvas_labels.clear();
+    storage.canvas_labels.extend(
+        canvases
+            .iter()
+            .map(|(entity, label)| (label.clone(), entity)),
+    );
+}
+
 /// A system param that allows ergonomic drawing of immediate mode shapes.
 ///
 /// The [`ShapeConfig`] used is initially extracted from the [`BaseShapeConfig`] resource.
@@ -72,6 +445,15 @@ pub struct ShapePainter<'w, 's> {
     config: &'s mut ShapeConfig,
     shapes: ResMut<'w, ShapeStorage>,
     default_config: Res<'w, BaseShapeConfig>,
+    compounds: Res<'w, ShapeCompounds>,
+    anchors: Res<'w, PainterAnchors>,
+    polyline_points: ResMut<'w, ShapePolylinePoints>,
+    commands: Commands<'w, 's>,
+    system_name: SystemName<'s>,
+    #[cfg(feature = "text")]
+    fonts: Res<'w, ShapeFonts>,
+    #[cfg(feature = "text")]
+    number_format: Res<'w, ShapeNumberFormat>,
 }
 
 impl<'w, 's> ShapePainter<'w, 's> {
@@ -87,14 +469,53 @@ impl<'w, 's> ShapePainter<'w, 's> {
         let Self {
             config,
             shapes: event_writer,
+            system_name,
             ..
         } = self;
+        let sent = event_writer.sent;
         event_writer.send(config, data);
+        event_writer.attribute(system_name, sent);
+        self
+    }
+
+    /// Sends a copy of the data for each transform, applied on top of the data's own transform,
+    /// see [`ShapeStorage::send_instances`].
+    pub fn instances<T: ShapeData>(
+        &mut self,
+        data: T,
+        transforms: impl IntoIterator<Item = Transform>,
+    ) -> &mut Self {
+        let Self {
+            config,
+            shapes: event_writer,
+            system_name,
+            ..
+        } = self;
+        let sent = event_writer.sent;
+        event_writer.send_instances(config, data, transforms);
+        event_writer.attribute(system_name, sent);
         self
     }
 
     pub fn send_with_config<T: ShapeData>(&mut self, config: &ShapeConfig, data: T) -> &mut Self {
+        let sent = self.shapes.sent;
         self.shapes.send(config, data);
+        self.shapes.attribute(&self.system_name, sent);
+        self
+    }
+
+    /// Sends the data built from the painter's config, followed by it's outline if the config has a [`ShapeConfig::stroke_color`].
+    ///
+    /// The outline is built from the same config made hollow in the stroke color, and is sent directly after the fill
+    /// so that it's drawn over it without offsetting it in z.
+    pub fn send_filled<T: ShapeData>(&mut self, data: impl Fn(&ShapeConfig) -> T) -> &mut Self {
+        let stroke = self.config.stroke_config();
+        let fill = data(self.config);
+        self.send(fill);
+        if let Some(stroke) = stroke {
+            let outline = data(&stroke);
+            self.send_with_config(&stroke, outline);
+        }
         self
     }
 
@@ -109,6 +530,140 @@ impl<'w, 's> ShapePainter<'w, 's> {
         self
     }
 
+    /// Draws the [`CompoundShape`] registered in [`ShapeCompounds`] with the given name, e.g. `painter.compound("crosshair")`.
+    ///
+    /// The compound is placed by the painter's transform and tinted by it's color, all other config such as
+    /// render layers and canvas are taken from the painter. Does nothing if no compound has the given name.
+    pub fn compound(&mut self, name: &str) -> &mut Self {
+        let Some(version) = self.compounds.version(name) else {
+            warn_once!("No compound shape registered with the name {name}");
+            return self;
+        };
+
+        let cached =
+            matches!(self.shapes.compounds.get(name), Some((cached, _)) if *cached == version);
+        if !cached {
+            self.record_compound(name, version);
+        }
+
+        // Take the recording out while replaying so that it's shapes can be sent to the same storage
+        let Some((name, (version, recording))) = self.shapes.compounds.remove_entry(name) else {
+            return self;
+        };
+        let sent = self.shapes.sent;
+        recording.replay(&mut self.shapes, self.config);
+        self.shapes.attribute(&self.system_name, sent);
+        self.shapes.compounds.insert(name, (version, recording));
+        self
+    }
+
+    fn record_compound(&mut self, name: &str, version: u32) {
+        let Some(compound) = self.compounds.get(name).cloned() else {
+            return;
+        };
+
+        let mut base = self.default_config.0.clone();
+        base.transform = Transform::IDENTITY;
+        base.color = Color::WHITE;
+        let config = std::mem::replace(self.config, base.clone());
+
+        // Compounds may draw other compounds, so restore any recording already in progress afterwards
+        let outer = self.shapes.recording.replace(CompoundRecording::default());
+        for (transform, draw) in compound.parts() {
+            *self.config = base.clone();
+            self.config.transform = *transform;
+            draw(self);
+        }
+        let recording = std::mem::replace(&mut self.shapes.recording, outer).unwrap_or_default();
+
+        *self.config = config;
+        self.shapes
+            .compounds
+            .insert(name.to_string(), (version, recording));
+    }
+
+    /// Spawns the shapes drawn within the closure as retained entities rather than drawing them this frame,
+    /// returning the spawned entities in the order their shapes were drawn.
+    ///
+    /// Useful to promote shapes prototyped in immediate mode to retained mode without rewriting them with [`ShapeCommands`].
+    /// Each entity has the shape's transform and a [`RetainedShapeData`] holding the rest of the shape,
+    /// render layers and the 3D pipeline are kept while `group`, `screen_space` and `cull` are ignored.
+    /// Entities are spawned as the painter's commands are applied.
+    pub fn retain(&mut self, draw: impl FnOnce(&mut ShapePainter)) -> Vec<Entity> {
+        // Retain calls may be nested, so restore any shapes already being retained afterwards
+        let outer = self.shapes.retained.replace(Vec::new());
+        let config = self.config.clone();
+        draw(self);
+        *self.config = config;
+        let retained = std::mem::replace(&mut self.shapes.retained, outer).unwrap_or_default();
+
+        retained
+            .into_iter()
+            .map(|insert| {
+                let mut entity = self.commands.spawn_empty();
+                insert(&mut entity);
+                entity.id()
+            })
+            .collect()
+    }
+
+    /// Draws the compound with the given name to a canvas, sorted behind every other shape drawn to it, see [`Canvas::background`].
+    pub(crate) fn canvas_background(&mut self, canvas: Entity, name: &str) {
+        self.reset();
+        self.set_canvas(canvas);
+        self.shapes.sort_offset = Some(-CANVAS_BACKGROUND_DEPTH);
+        self.compound(name);
+        self.shapes.sort_offset = None;
+        self.reset();
+    }
+
+    /// Set the painter's transform to the [`GlobalTransform`] of an entity with a [`PainterAnchor`],
+    /// so the following shapes are drawn relative to it, e.g. indicators around a unit.
+    ///
+    /// Transforms are read from [`PainterAnchors`], if the entity isn't cached the transform is left unchanged.
+    pub fn anchor_to(&mut self, entity: Entity) -> &mut Self {
+        match self.anchors.get(entity) {
+            Some(tf) => self.config.transform = tf.compute_transform(),
+            None => warn_once!("Painter anchored to an entity without a PainterAnchor"),
+        }
+        self
+    }
+
+    /// The painter's config along with the points shared by polylines, used to build [`PolylineData`].
+    pub(crate) fn polyline_points(&mut self) -> (&ShapeConfig, &mut ShapePolylinePoints) {
+        (self.config, &mut self.polyline_points)
+    }
+
+    /// Fonts available to draw text with, requires the `text` feature.
+    #[cfg(feature = "text")]
+    pub fn fonts(&self) -> &ShapeFonts {
+        &self.fonts
+    }
+
+    /// Format used to draw numbers, requires the `text` feature.
+    #[cfg(feature = "text")]
+    pub fn number_format(&self) -> &ShapeNumberFormat {
+        &self.number_format
+    }
+
+    /// Draw the following shapes into the named group, see [`ShapeGroups`].
+    ///
+    /// The group is part of the painter's config, so it is unset by [`ShapePainter::reset`].
+    pub fn group(&mut self, name: impl Into<String>) -> &mut Self {
+        self.config.group = Some(name.into());
+        self
+    }
+
+    /// Keep the following shapes as entities that are only updated when they change, see [`ShapeKey`].
+    ///
+    /// Ids are scoped to the painter's system and, like the group, the key is part of the painter's config
+    /// so every shape drawn until it's reset is kept under it, e.g. `painter.keyed("health").rect(size)`.
+    /// Changes are applied before transform propagation, so keyed shapes should be drawn in or before [`PostUpdate`].
+    pub fn keyed(&mut self, id: impl Hash) -> &mut Self {
+        self.config.key = Some(ShapeKey::new((self.system_name.name(), id)));
+        self
+    }
+
     /// Set the painter's [`ShapeConfig`] to the current value of the [`BaseShapeConfig`] resource.
     pub fn reset(&mut self) {
         *self.config = self.default_config.0.clone();
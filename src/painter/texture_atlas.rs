@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Component that draws a single cell of a [`TextureAtlasLayout`] on a retained rectangle, e.g. a frame of a sprite sheet.
+///
+/// The cell is drawn by setting the rectangle's [`RectangleComponent::texture_rect`] each frame, so rectangles sharing
+/// a texture stay in the same batch whichever cell they draw. The texture itself is set through the [`ShapeMaterial`].
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct ShapeTextureAtlas(pub TextureAtlas);
+
+/// Texture rect in uv coordinates of a cell of a [`TextureAtlasLayout`], `None` if the index is out of range.
+pub fn atlas_texture_rect(layout: &TextureAtlasLayout, index: usize) -> Option<Rect> {
+    let cell = layout.textures.get(index)?.as_rect();
+    let size = layout.size.as_vec2();
+    Some(Rect {
+        min: cell.min / size,
+        max: cell.max / size,
+    })
+}
+
+pub fn update_texture_atlases(
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut shapes: Query<(&ShapeTextureAtlas, &mut RectangleComponent)>,
+) {
+    for (atlas, mut rect) in &mut shapes {
+        let Some(texture_rect) = layouts
+            .get(&atlas.0.layout)
+            .and_then(|layout| atlas_texture_rect(layout, atlas.0.index))
+        else {
+            continue;
+        };
+        if rect.texture_rect != Some(texture_rect) {
+            rect.texture_rect = Some(texture_rect);
+        }
+    }
+}
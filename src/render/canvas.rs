@@ -0,0 +1,296 @@
+use bevy::{
+    core_pipeline::blit::{BlitPipeline, BlitPipelineKey},
+    prelude::*,
+    render::{
+        render_asset::RenderAssets,
+        render_resource::*,
+        renderer::{RenderDevice, RenderQueue},
+        texture::GpuImage,
+        view::ViewTarget,
+        MainWorld,
+    },
+    utils::HashSet,
+};
+
+use crate::painter::{Canvas, CanvasBlit, CanvasResizeMode};
+
+/// Copies of canvas contents requested by resizes in the main world, applied before the canvases are drawn to.
+#[derive(Resource, Default)]
+pub struct ExtractedCanvasBlits {
+    /// Blits waiting to be applied, including those from previous frames whose pipeline was still compiling.
+    blits: Vec<CanvasBlit>,
+    /// Canvases drawn this frame along with their mirrors, see [`Canvas::mirrors`].
+    mirrors: Vec<(Handle<Image>, Vec<Handle<Image>>)>,
+    /// Texture formats of canvases that may require a scaled blit, used to prepare pipelines ahead of time.
+    scaled_formats: HashSet<TextureFormat>,
+}
+
+/// Sampler used when downscaling canvases into their mirrors.
+///
+/// Unlike the blit pipeline's own sampler this filters linearly so that each texel of a mirror averages the texels it covers.
+#[derive(Resource)]
+pub struct CanvasMirrorSampler(Sampler);
+
+impl FromWorld for CanvasMirrorSampler {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        Self(render_device.create_sampler(&SamplerDescriptor {
+            label: Some("canvas_mirror_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        }))
+    }
+}
+
+/// Takes any pending blits from canvases in the main world.
+///
+/// The source image handle is kept alive until the blit has been applied.
+pub fn extract_canvas_blits(
+    mut main_world: ResMut<MainWorld>,
+    mut extracted: ResMut<ExtractedCanvasBlits>,
+) {
+    extracted.mirrors.clear();
+    extracted.scaled_formats.clear();
+
+    let mut canvases = main_world.query::<(&mut Canvas, &Camera)>();
+    for (mut canvas, camera) in canvases.iter_mut(&mut main_world) {
+        if camera.is_active && !canvas.mirrors().is_empty() {
+            extracted
+                .mirrors
+                .push((canvas.image.clone(), canvas.mirrors().to_vec()));
+        }
+
+        if matches!(canvas.resize_mode, CanvasResizeMode::Scaled) || !canvas.mirrors().is_empty() {
+            extracted.scaled_formats.insert(if camera.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            });
+        }
+
+        // Avoid triggering change detection on every canvas
+        if canvas.has_pending_blit() {
+            extracted.blits.extend(canvas.take_pending_blit());
+        }
+    }
+}
+
+/// Copies the previous contents of each resized canvas into it's new texture.
+///
+/// Runs after the new textures have been prepared but before any cameras have drawn to them.
+/// Scaled blits are retried each frame until the blit pipeline has compiled, along with any later blits
+/// of the same canvas, anything drawn to the canvas in the meantime is replaced by it's old contents.
+pub fn prepare_canvas_blits(
+    mut extracted: ResMut<ExtractedCanvasBlits>,
+    images: Res<RenderAssets<GpuImage>>,
+    blit_pipeline: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let key = |texture_format| BlitPipelineKey {
+        texture_format,
+        blend_state: None,
+        samples: 1,
+    };
+
+    // Specialize ahead of time so that the pipeline has been compiled by the time a canvas is resized
+    for &format in &extracted.scaled_formats {
+        pipelines.specialize(&pipeline_cache, &blit_pipeline, key(format));
+    }
+
+    if extracted.blits.is_empty() {
+        return;
+    }
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("canvas_resize_encoder"),
+    });
+
+    // Textures whose contents are still waiting on a deferred blit
+    let mut deferred = HashSet::new();
+    let blits = std::mem::take(&mut extracted.blits);
+    for blit in blits {
+        if deferred.contains(&blit.source.id()) {
+            deferred.insert(blit.target.id());
+            extracted.blits.push(blit);
+            continue;
+        }
+
+        let (Some(source), Some(target)) = (images.get(&blit.source), images.get(&blit.target))
+        else {
+            continue;
+        };
+
+        match blit.mode {
+            CanvasResizeMode::Discard => {}
+            CanvasResizeMode::Anchored(anchor) => {
+                // Offset of the old contents within the new texture, negative when the canvas has shrunk
+                let offset = ((target.size.as_vec2() - source.size.as_vec2()) * anchor)
+                    .round()
+                    .as_ivec2();
+                let source_origin = (-offset).max(IVec2::ZERO).as_uvec2();
+                let target_origin = offset.max(IVec2::ZERO).as_uvec2();
+                let size = source
+                    .size
+                    .saturating_sub(source_origin)
+                    .min(target.size.saturating_sub(target_origin));
+                if size.x == 0 || size.y == 0 {
+                    continue;
+                }
+
+                encoder.copy_texture_to_texture(
+                    ImageCopyTexture {
+                        texture: &source.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: source_origin.x,
+                            y: source_origin.y,
+                            z: 0,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    ImageCopyTexture {
+                        texture: &target.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: target_origin.x,
+                            y: target_origin.y,
+                            z: 0,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    Extent3d {
+                        width: size.x,
+                        height: size.y,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+            CanvasResizeMode::Scaled => {
+                let id = pipelines.specialize(
+                    &pipeline_cache,
+                    &blit_pipeline,
+                    key(target.texture_format),
+                );
+                // The pipeline may still be compiling if this is the first resize, keep the old texture until it's ready
+                let Some(pipeline) = pipeline_cache.get_render_pipeline(id) else {
+                    deferred.insert(blit.target.id());
+                    extracted.blits.push(blit);
+                    continue;
+                };
+
+                blit_scaled(
+                    &mut encoder,
+                    &render_device,
+                    &blit_pipeline,
+                    pipeline,
+                    &blit_pipeline.sampler,
+                    source,
+                    target,
+                );
+            }
+        }
+    }
+
+    render_queue.submit([encoder.finish()]);
+}
+
+/// Downscales each canvas drawn this frame into it's mirrors, each mirror being downscaled from the previous.
+///
+/// Runs after the render graph so that the mirrors match what was drawn this frame.
+#[allow(clippy::too_many_arguments)]
+pub fn update_canvas_mirrors(
+    extracted: Res<ExtractedCanvasBlits>,
+    images: Res<RenderAssets<GpuImage>>,
+    blit_pipeline: Res<BlitPipeline>,
+    sampler: Res<CanvasMirrorSampler>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if extracted.mirrors.is_empty() {
+        return;
+    }
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("canvas_mirror_encoder"),
+    });
+
+    for (image, mirrors) in &extracted.mirrors {
+        let Some(mut source) = images.get(image) else {
+            continue;
+        };
+
+        for mirror in mirrors {
+            let Some(target) = images.get(mirror) else {
+                break;
+            };
+
+            let id = pipelines.specialize(
+                &pipeline_cache,
+                &blit_pipeline,
+                BlitPipelineKey {
+                    texture_format: target.texture_format,
+                    blend_state: None,
+                    samples: 1,
+                },
+            );
+            // The pipeline may still be compiling, in which case the mirrors keep their previous contents
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(id) else {
+                break;
+            };
+
+            blit_scaled(
+                &mut encoder,
+                &render_device,
+                &blit_pipeline,
+                pipeline,
+                &sampler.0,
+                source,
+                target,
+            );
+            source = target;
+        }
+    }
+
+    render_queue.submit([encoder.finish()]);
+}
+
+/// Stretch the contents of `source` to fill `target`.
+fn blit_scaled(
+    encoder: &mut CommandEncoder,
+    render_device: &RenderDevice,
+    blit_pipeline: &BlitPipeline,
+    pipeline: &RenderPipeline,
+    sampler: &Sampler,
+    source: &GpuImage,
+    target: &GpuImage,
+) {
+    let bind_group = render_device.create_bind_group(
+        "canvas_blit_bind_group",
+        &blit_pipeline.texture_bind_group,
+        &BindGroupEntries::sequential((&source.texture_view, sampler)),
+    );
+
+    let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+        label: Some("canvas_blit_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &target.texture_view,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(default()),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, &*bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
@@ -18,18 +18,22 @@ use bevy::{
         renderer::RenderDevice,
         texture::{FallbackImage, GpuImage},
         view::{ExtractedView, ViewUniformOffset, ViewUniforms},
+        Extract,
     },
     utils::HashMap,
 };
 
-use crate::render::*;
+use crate::render::{dash_pattern::DashPatternBuffer, polyline_points::PolylinePointBuffer, *};
 
 pub type DrawShape2dCommand<T> = (
     SetItemPipeline,
     SetShapeViewBindGroup<0>,
     SetShape2dBindGroup<T, 1>,
     SetShape2dTextureBindGroup<T, 2>,
+    SetShape2dExtensionBindGroup<T>,
+    SetShape2dScissor<T>,
     DrawShape<T>,
+    ResetShape2dScissor<T>,
 );
 
 pub type DrawShape3dCommand<T> = (
@@ -37,7 +41,10 @@ pub type DrawShape3dCommand<T> = (
     SetShapeViewBindGroup<0>,
     SetShape3dBindGroup<T, 1>,
     SetShape3dTextureBindGroup<T, 2>,
+    SetShape3dExtensionBindGroup<T>,
+    SetShape3dScissor<T>,
     DrawShape<T>,
+    ResetShape3dScissor<T>,
 );
 
 #[derive(Component, Debug)]
@@ -56,10 +63,13 @@ pub fn prepare_shape_view_bind_groups(
     tonemapping_luts: Res<TonemappingLuts>,
     images: Res<RenderAssets<GpuImage>>,
     fallback_image: Res<FallbackImage>,
+    dash_patterns: Res<DashPatternBuffer>,
+    polyline_points: Res<PolylinePointBuffer>,
 ) {
-    let (Some(view_binding), Some(globals)) = (
+    let (Some(view_binding), Some(globals), Some(dash_patterns)) = (
         view_uniforms.uniforms.binding(),
         globals_buffer.buffer.binding(),
+        dash_patterns.buffer.binding(),
     ) else {
         return;
     };
@@ -75,6 +85,8 @@ pub fn prepare_shape_view_bind_groups(
                 (1, globals.clone()),
                 (2, lut_bindings.0),
                 (3, lut_bindings.1),
+                (4, dash_patterns.clone()),
+                (5, polyline_points.buffer.as_entire_binding()),
             )),
         );
 
@@ -86,7 +98,74 @@ pub fn prepare_shape_view_bind_groups(
 
 #[derive(Resource, Default)]
 pub struct ShapeTextureBindGroups {
-    values: HashMap<Handle<Image>, BindGroup>,
+    values: HashMap<(Handle<Image>, ShapeTextureColorSpace), BindGroup>,
+}
+
+/// View formats of each image that lists any, used to check whether a texture can be sampled in another color space.
+#[derive(Resource, Default)]
+pub struct ShapeTextureViewFormats(HashMap<AssetId<Image>, &'static [TextureFormat]>);
+
+/// System that copies the view formats of images into the render world whenever images change.
+pub fn extract_texture_view_formats(
+    mut view_formats: ResMut<ShapeTextureViewFormats>,
+    images: Extract<Res<Assets<Image>>>,
+) {
+    if !images.is_changed() {
+        return;
+    }
+    view_formats.0.clear();
+    view_formats.0.extend(
+        images
+            .iter()
+            .filter(|(_, image)| !image.texture_descriptor.view_formats.is_empty())
+            .map(|(id, image)| (id, image.texture_descriptor.view_formats)),
+    );
+}
+
+fn prepare_texture_bind_group(
+    render_device: &RenderDevice,
+    shape_pipelines: &ShapePipelines,
+    gpu_images: &RenderAssets<GpuImage>,
+    view_formats: &ShapeTextureViewFormats,
+    image_bind_groups: &mut ShapeTextureBindGroups,
+    material: &ShapePipelineMaterial,
+) {
+    let Some(handle) = &material.texture else {
+        return;
+    };
+    let Some(gpu_image) = gpu_images.get(handle.id()) else {
+        return;
+    };
+    let color_space = material.texture_color_space;
+    image_bind_groups
+        .values
+        .entry((handle.clone_weak(), color_space))
+        .or_insert_with(|| {
+            let format = color_space.view_format(gpu_image.texture_format);
+            let view = if format == gpu_image.texture_format {
+                gpu_image.texture_view.clone()
+            } else if view_formats
+                .0
+                .get(&handle.id())
+                .is_some_and(|formats| formats.contains(&format))
+            {
+                gpu_image.texture.create_view(&TextureViewDescriptor {
+                    label: Some("shape_texture_color_space_view"),
+                    format: Some(format),
+                    ..default()
+                })
+            } else {
+                warn_once!(
+                    "Texture sampled as {color_space:?} doesn't list {format:?} in its view formats, see ShapeTextureColorSpace::allow_views"
+                );
+                gpu_image.texture_view.clone()
+            };
+            render_device.create_bind_group(
+                "shape_texture_bind_group",
+                &shape_pipelines.texture_layout,
+                &BindGroupEntries::sequential((&view, &gpu_image.sampler)),
+            )
+        });
 }
 
 pub fn prepare_shape_2d_texture_bind_groups<T: ShapeData>(
@@ -94,26 +173,18 @@ pub fn prepare_shape_2d_texture_bind_groups<T: ShapeData>(
     shape_pipelines: Res<ShapePipelines>,
     materials: ResMut<Shape2dMaterials<T>>,
     gpu_images: Res<RenderAssets<GpuImage>>,
+    view_formats: Res<ShapeTextureViewFormats>,
     mut image_bind_groups: ResMut<ShapeTextureBindGroups>,
 ) {
     for material in materials.keys() {
-        if let Some(handle) = &material.texture {
-            if let Some(gpu_image) = gpu_images.get(handle.id()) {
-                image_bind_groups
-                    .values
-                    .entry(handle.clone_weak())
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(
-                            "shape_texture_bind_group",
-                            &shape_pipelines.texture_layout,
-                            &BindGroupEntries::sequential((
-                                &gpu_image.texture_view,
-                                &gpu_image.sampler,
-                            )),
-                        )
-                    });
-            }
-        }
+        prepare_texture_bind_group(
+            &render_device,
+            &shape_pipelines,
+            &gpu_images,
+            &view_formats,
+            &mut image_bind_groups,
+            material,
+        );
     }
 }
 
@@ -122,26 +193,18 @@ pub fn prepare_shape_3d_texture_bind_groups<T: ShapeData>(
     shape_pipelines: Res<ShapePipelines>,
     materials: ResMut<Shape3dMaterials<T>>,
     gpu_images: Res<RenderAssets<GpuImage>>,
+    view_formats: Res<ShapeTextureViewFormats>,
     mut image_bind_groups: ResMut<ShapeTextureBindGroups>,
 ) {
     for material in materials.keys() {
-        if let Some(handle) = &material.texture {
-            if let Some(gpu_image) = gpu_images.get(handle.id()) {
-                image_bind_groups
-                    .values
-                    .entry(handle.clone_weak())
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(
-                            "shape_texture_bind_group",
-                            &shape_pipelines.texture_layout,
-                            &BindGroupEntries::sequential((
-                                &gpu_image.texture_view,
-                                &gpu_image.sampler,
-                            )),
-                        )
-                    });
-            }
-        }
+        prepare_texture_bind_group(
+            &render_device,
+            &shape_pipelines,
+            &gpu_images,
+            &view_formats,
+            &mut image_bind_groups,
+            material,
+        );
     }
 }
 
@@ -189,7 +252,10 @@ impl<const I: usize, T: ShapeData, P: PhaseItem> RenderCommand<P>
             let bind_groups = bind_groups.into_inner();
             pass.set_bind_group(
                 I,
-                bind_groups.values.get(&handle.clone_weak()).unwrap(),
+                bind_groups
+                    .values
+                    .get(&(handle.clone_weak(), material.texture_color_space))
+                    .unwrap(),
                 &[],
             );
         }
@@ -221,7 +287,10 @@ impl<const I: usize, T: ShapeData, P: PhaseItem> RenderCommand<P>
             let bind_groups = bind_groups.into_inner();
             pass.set_bind_group(
                 I,
-                bind_groups.values.get(&handle.clone_weak()).unwrap(),
+                bind_groups
+                    .values
+                    .get(&(handle.clone_weak(), material.texture_color_space))
+                    .unwrap(),
                 &[],
             );
         }
@@ -315,3 +384,161 @@ impl<P: PhaseItem, T: ShapeData> RenderCommand<P> for DrawShape<T> {
         RenderCommandResult::Success
     }
 }
+
+/// Region of the view's target in pixels covering the given rect in world space, `None` if it is entirely outside the viewport.
+fn clip_scissor(view: &ExtractedView, clip_rect: Rect) -> Option<URect> {
+    let clip_from_world = view
+        .clip_from_world
+        .unwrap_or_else(|| view.clip_from_view * view.world_from_view.compute_matrix().inverse());
+    let viewport = view.viewport.as_vec4();
+
+    let corners = [
+        clip_rect.min,
+        Vec2::new(clip_rect.max.x, clip_rect.min.y),
+        clip_rect.max,
+        Vec2::new(clip_rect.min.x, clip_rect.max.y),
+    ];
+    let pixels = corners.map(|corner| {
+        let ndc = clip_from_world
+            .project_point3(corner.extend(0.0))
+            .truncate();
+        // Pixels increase downwards from the top left of the target
+        viewport.xy() + (Vec2::new(ndc.x, -ndc.y) * 0.5 + 0.5) * viewport.zw()
+    });
+    let bounds = Rect::from_corners(pixels[0], pixels[2])
+        .union_point(pixels[1])
+        .union_point(pixels[3])
+        .intersect(Rect::from_corners(
+            viewport.xy(),
+            viewport.xy() + viewport.zw(),
+        ));
+    if bounds.is_empty() {
+        return None;
+    }
+
+    Some(URect::from_corners(
+        bounds.min.floor().as_uvec2(),
+        bounds.max.ceil().as_uvec2(),
+    ))
+}
+
+fn set_clip_scissor<'w>(
+    view: &ExtractedView,
+    clip_rect: Option<Rect>,
+    pass: &mut TrackedRenderPass<'w>,
+) -> RenderCommandResult {
+    let Some(clip_rect) = clip_rect else {
+        return RenderCommandResult::Success;
+    };
+    let Some(scissor) = clip_scissor(view, clip_rect) else {
+        return RenderCommandResult::Skip;
+    };
+    let size = scissor.size();
+    pass.set_scissor_rect(scissor.min.x, scissor.min.y, size.x, size.y);
+    RenderCommandResult::Success
+}
+
+fn reset_clip_scissor<'w>(
+    view: &ExtractedView,
+    clip_rect: Option<Rect>,
+    pass: &mut TrackedRenderPass<'w>,
+) -> RenderCommandResult {
+    // Restore the whole viewport so that later items in the phase aren't clipped
+    if clip_rect.is_some() {
+        let viewport = view.viewport;
+        pass.set_scissor_rect(viewport.x, viewport.y, viewport.z, viewport.w);
+    }
+    RenderCommandResult::Success
+}
+
+/// Clips 2D shapes to the screen space bounds of their [`ShapeConfig::clip_rect`], skipping them if it is off screen.
+pub struct SetShape2dScissor<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for SetShape2dScissor<T> {
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+    type Param = SRes<Shape2dInstances<T>>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _item_query: Option<()>,
+        instances: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let clip_rect = instances
+            .get(&item.entity())
+            .and_then(|i| i.material.clip_rect());
+        set_clip_scissor(view, clip_rect, pass)
+    }
+}
+
+/// Restores the scissor rect after drawing 2D shapes with a [`ShapeConfig::clip_rect`].
+pub struct ResetShape2dScissor<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for ResetShape2dScissor<T> {
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+    type Param = SRes<Shape2dInstances<T>>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _item_query: Option<()>,
+        instances: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let clip_rect = instances
+            .get(&item.entity())
+            .and_then(|i| i.material.clip_rect());
+        reset_clip_scissor(view, clip_rect, pass)
+    }
+}
+
+/// Clips 3D shapes to the screen space bounds of their [`ShapeConfig::clip_rect`], skipping them if it is off screen.
+pub struct SetShape3dScissor<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for SetShape3dScissor<T> {
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+    type Param = SRes<Shape3dInstances<T>>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _item_query: Option<()>,
+        instances: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let clip_rect = instances
+            .get(&item.entity())
+            .and_then(|i| i.material.clip_rect());
+        set_clip_scissor(view, clip_rect, pass)
+    }
+}
+
+/// Restores the scissor rect after drawing 3D shapes with a [`ShapeConfig::clip_rect`].
+pub struct ResetShape3dScissor<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for ResetShape3dScissor<T> {
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+    type Param = SRes<Shape3dInstances<T>>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _item_query: Option<()>,
+        instances: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let clip_rect = instances
+            .get(&item.entity())
+            .and_then(|i| i.material.clip_rect());
+        reset_clip_scissor(view, clip_rect, pass)
+    }
+}
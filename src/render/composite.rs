@@ -0,0 +1,295 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{
+        core_2d::graph::{Core2d, Node2d},
+        core_3d::graph::{Core3d, Node3d},
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    },
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        render_asset::RenderAssets,
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+        },
+        render_resource::{binding_types::*, *},
+        renderer::{RenderContext, RenderDevice},
+        texture::GpuImage,
+        view::{ExtractedView, ViewTarget},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+pub const COMPOSITE_HANDLE: Handle<Shader> = Handle::weak_from_u128(16873140592317740218);
+
+/// Photoshop style operator that combines a [`ShapeCompositeLayer`] with the view beneath it.
+///
+/// Modes follow the separable blend modes of the [W3C compositing spec](https://www.w3.org/TR/compositing-1/#blending),
+/// applied to gamma encoded colors as image editors do.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum ShapeBlendMode {
+    /// The layer is drawn over the view as usual.
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+}
+
+/// Canvas image composited over the view of a camera, see [`ShapeCompositeLayers`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct ShapeCompositeLayer {
+    /// Image of the [`Canvas`](crate::painter::Canvas) the layer's shapes are drawn to, stretched over the view.
+    pub image: Handle<Image>,
+    pub mode: ShapeBlendMode,
+}
+
+impl ShapeCompositeLayer {
+    pub fn new(image: Handle<Image>, mode: ShapeBlendMode) -> Self {
+        Self { image, mode }
+    }
+}
+
+/// Component for cameras that composites the shapes drawn to canvases over the camera's view with [`ShapeBlendMode`]s
+/// that can't be expressed as blend states, such as overlay or soft light, as they need to read the view beneath them.
+///
+/// Layers are applied in order after tonemapping, each reading a copy of the view as composited so far.
+/// Canvases should match the size of the camera's viewport and be cleared to transparent,
+/// and keep their last contents while nothing is drawn to them so layers that are no longer needed should be removed.
+#[derive(Component, Default, Debug, Clone, PartialEq, Reflect)]
+pub struct ShapeCompositeLayers(pub Vec<ShapeCompositeLayer>);
+
+impl ExtractComponent for ShapeCompositeLayers {
+    type QueryData = &'static Self;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(layers: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        (!layers.0.is_empty()).then(|| layers.clone())
+    }
+}
+
+/// Pipelines and images of the layers composited over a view.
+#[derive(Component)]
+pub struct ViewShapeCompositePipelines(Vec<(CachedRenderPipelineId, AssetId<Image>)>);
+
+#[derive(Resource)]
+pub struct ShapeCompositePipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for ShapeCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "shape_composite_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("shape_composite_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        Self { layout, sampler }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShapeCompositePipelineKey {
+    texture_format: TextureFormat,
+    mode: ShapeBlendMode,
+}
+
+impl SpecializedRenderPipeline for ShapeCompositePipeline {
+    type Key = ShapeCompositePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("shape_composite_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: COMPOSITE_HANDLE,
+                shader_defs: vec![ShaderDefVal::UInt("BLEND_MODE".into(), key.mode as u32)],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+pub fn prepare_composite_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ShapeCompositePipeline>>,
+    composite_pipeline: Res<ShapeCompositePipeline>,
+    views: Query<(Entity, &ExtractedView, &ShapeCompositeLayers)>,
+    removed: Query<
+        Entity,
+        (
+            With<ViewShapeCompositePipelines>,
+            Without<ShapeCompositeLayers>,
+        ),
+    >,
+) {
+    for entity in &removed {
+        commands
+            .entity(entity)
+            .remove::<ViewShapeCompositePipelines>();
+    }
+
+    for (entity, view, layers) in &views {
+        let texture_format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let layers = layers
+            .0
+            .iter()
+            .map(|layer| {
+                let key = ShapeCompositePipelineKey {
+                    texture_format,
+                    mode: layer.mode,
+                };
+                let id = pipelines.specialize(&pipeline_cache, &composite_pipeline, key);
+                (id, layer.image.id())
+            })
+            .collect();
+
+        commands
+            .entity(entity)
+            .insert(ViewShapeCompositePipelines(layers));
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct ShapeCompositeLabel;
+
+/// Render graph node compositing each [`ShapeCompositeLayer`] over the view in turn.
+#[derive(Default)]
+pub struct ShapeCompositeNode;
+
+impl ViewNode for ShapeCompositeNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewShapeCompositePipelines);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, layers): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let composite_pipeline = world.resource::<ShapeCompositePipeline>();
+        let images = world.resource::<RenderAssets<GpuImage>>();
+
+        for (id, image) in &layers.0 {
+            // Layers are skipped while their pipelines compile or their canvases are created
+            let (Some(pipeline), Some(image)) =
+                (pipeline_cache.get_render_pipeline(*id), images.get(*image))
+            else {
+                continue;
+            };
+
+            let post_process = target.post_process_write();
+            let bind_group = render_context.render_device().create_bind_group(
+                "shape_composite_bind_group",
+                &composite_pipeline.layout,
+                &BindGroupEntries::sequential((
+                    post_process.source,
+                    &image.texture_view,
+                    &composite_pipeline.sampler,
+                )),
+            );
+
+            let mut pass =
+                render_context
+                    .command_encoder()
+                    .begin_render_pass(&RenderPassDescriptor {
+                        label: Some("shape_composite_pass"),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: post_process.destination,
+                            resolve_target: None,
+                            ops: Operations::default(),
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn build_composite(app: &mut App) {
+    app.register_type::<ShapeCompositeLayers>()
+        .add_plugins(ExtractComponentPlugin::<ShapeCompositeLayers>::default());
+}
+
+pub(crate) fn setup_composite(app: &mut App) {
+    load_internal_asset!(
+        app,
+        COMPOSITE_HANDLE,
+        "shaders/composite.wgsl",
+        Shader::from_wgsl
+    );
+
+    app.sub_app_mut(RenderApp)
+        .init_resource::<ShapeCompositePipeline>()
+        .init_resource::<SpecializedRenderPipelines<ShapeCompositePipeline>>()
+        .add_systems(
+            Render,
+            prepare_composite_pipelines.in_set(RenderSet::Prepare),
+        )
+        .add_render_graph_node::<ViewNodeRunner<ShapeCompositeNode>>(Core2d, ShapeCompositeLabel)
+        .add_render_graph_edges(
+            Core2d,
+            (
+                Node2d::Tonemapping,
+                ShapeCompositeLabel,
+                Node2d::EndMainPassPostProcessing,
+            ),
+        )
+        .add_render_graph_node::<ViewNodeRunner<ShapeCompositeNode>>(Core3d, ShapeCompositeLabel)
+        .add_render_graph_edges(
+            Core3d,
+            (
+                Node3d::Tonemapping,
+                ShapeCompositeLabel,
+                Node3d::EndMainPassPostProcessing,
+            ),
+        );
+}
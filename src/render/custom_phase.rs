@@ -0,0 +1,228 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_phase::{
+            AddRenderCommand, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions,
+            SortedPhaseItem, ViewSortedRenderPhases,
+        },
+        render_resource::{CachedRenderPipelineId, PipelineCache},
+        view::{ExtractedView, RenderLayers},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use crate::render::*;
+
+/// Trait implemented by user defined sorted phase items that shapes can be queued into.
+///
+/// Shapes with [`ShapeRenderPhase::Custom`] matching [`ShapePhaseItem::ID`] are queued into the phase instead of the
+/// built in transparent phases once a [`ShapeCustomPhasePlugin`] has been added for the item.
+///
+/// The render pass drawing the phase must use attachments compatible with the shape's pipeline,
+/// i.e. the view target format and msaa samples, along with the depth texture for 3D shapes.
+pub trait ShapePhaseItem: CachedRenderPipelinePhaseItem + SortedPhaseItem {
+    /// Identifier matched against [`ShapeRenderPhase::Custom`].
+    const ID: u32;
+
+    /// Create a phase item for a single shape.
+    ///
+    /// `distance` is the z position, or [`ShapeSortKey`](crate::painter::ShapeSortKey), of 2D shapes and the view space depth of the origin of 3D shapes.
+    fn from_shape(
+        entity: Entity,
+        pipeline: CachedRenderPipelineId,
+        draw_function: DrawFunctionId,
+        distance: f32,
+    ) -> Self;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_shapes_custom_2d<P: ShapePhaseItem, T: ShapeData>(
+    draw_functions: Res<DrawFunctions<P>>,
+    pipeline: Res<Shape2dPipeline<T>>,
+    pipeline_cache: Res<PipelineCache>,
+    materials: Res<Shape2dMaterials<T>>,
+    instance_data: Res<Shape2dInstances<T>>,
+    mut shape_pipelines: ResMut<ShapePipelines>,
+    mut phases: ResMut<ViewSortedRenderPhases<P>>,
+    views: Query<(Entity, &ExtractedView, &Msaa, Option<&RenderLayers>)>,
+) {
+    let draw_function = draw_functions.read().id::<DrawShape2dCommand<T>>();
+
+    for (material, entities) in materials.iter() {
+        if material.render_phase != ShapeRenderPhase::Custom(P::ID) {
+            continue;
+        }
+
+        let key = ShapePipelineKey::from_material(material) | ShapePipelineKey::PIPELINE_2D;
+        for (view_entity, view, msaa) in visible_views(material, &views) {
+            let Some(phase) = phases.get_mut(&view_entity) else {
+                continue;
+            };
+
+            let view_key = view_key(material, key, view, msaa);
+            let pipeline = shape_pipelines.specialize(&pipeline_cache, pipeline.as_ref(), view_key);
+
+            for &entity in entities {
+                // SAFETY: we insert this alongside inserting into the vector we are currently iterating
+                let instance = unsafe { instance_data.get(&entity).unwrap_unchecked() };
+                phase.add(P::from_shape(
+                    entity,
+                    pipeline,
+                    draw_function,
+                    instance.distance_2d(),
+                ));
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_shapes_custom_3d<P: ShapePhaseItem, T: ShapeData>(
+    draw_functions: Res<DrawFunctions<P>>,
+    pipeline: Res<Shape3dPipeline<T>>,
+    pipeline_cache: Res<PipelineCache>,
+    materials: Res<Shape3dMaterials<T>>,
+    instance_data: Res<Shape3dInstances<T>>,
+    mut shape_pipelines: ResMut<ShapePipelines>,
+    mut phases: ResMut<ViewSortedRenderPhases<P>>,
+    views: Query<(Entity, &ExtractedView, &Msaa, Option<&RenderLayers>)>,
+) {
+    let draw_function = draw_functions.read().id::<DrawShape3dCommand<T>>();
+
+    for (material, entities) in materials.iter() {
+        if material.render_phase != ShapeRenderPhase::Custom(P::ID) {
+            continue;
+        }
+
+        let key = ShapePipelineKey::from_material(material);
+        for (view_entity, view, msaa) in visible_views(material, &views) {
+            let Some(phase) = phases.get_mut(&view_entity) else {
+                continue;
+            };
+
+            let view_key = view_key(material, key, view, msaa);
+            let pipeline = shape_pipelines.specialize(&pipeline_cache, pipeline.as_ref(), view_key);
+
+            let rangefinder = view.rangefinder3d();
+            let view_position = view.world_from_view.translation();
+            for &entity in entities {
+                // SAFETY: we insert this alongside inserting into the vector we are currently iterating
+                let instance = unsafe { instance_data.get(&entity).unwrap_unchecked() };
+                phase.add(P::from_shape(
+                    entity,
+                    pipeline,
+                    draw_function,
+                    instance.distance_3d(&rangefinder, view_position),
+                ));
+            }
+        }
+    }
+}
+
+/// Find the views that a given material should be drawn to, mirroring the built in phases.
+fn visible_views<'a>(
+    material: &ShapePipelineMaterial,
+    views: &'a Query<(Entity, &ExtractedView, &Msaa, Option<&RenderLayers>)>,
+) -> Vec<(Entity, &'a ExtractedView, &'a Msaa)> {
+    if let Some(canvas) = material.canvas {
+        views
+            .get(canvas)
+            .map(|(entity, view, msaa, _)| (entity, view, msaa))
+            .into_iter()
+            .collect()
+    } else {
+        views
+            .iter()
+            .filter(|(_, _, _, layers)| material.is_visible_in(*layers))
+            .map(|(entity, view, msaa, _)| (entity, view, msaa))
+            .collect()
+    }
+}
+
+fn view_key(
+    material: &ShapePipelineMaterial,
+    mut key: ShapePipelineKey,
+    view: &ExtractedView,
+    msaa: &Msaa,
+) -> ShapePipelineKey {
+    if !material.disable_laa {
+        key |= ShapePipelineKey::LOCAL_AA;
+    }
+    key |= ShapePipelineKey::from_msaa_samples(msaa.samples());
+    key |= ShapePipelineKey::from_hdr(view.hdr);
+    key
+}
+
+/// Plugin that queues shapes of the given type into the custom phase `P`, see [`ShapePhaseItem`].
+///
+/// Must be added after [`ShapeTypePlugin`] and, for 3D shapes, [`ShapeType3dPlugin`] of the same type.
+/// [`DrawFunctions<P>`] and [`ViewSortedRenderPhases<P>`] must be initialized in the render app.
+pub struct ShapeCustomPhaseTypePlugin<P: ShapePhaseItem, T: ShapeComponent>(PhantomData<(P, T)>);
+
+impl<P: ShapePhaseItem, T: ShapeComponent> Default for ShapeCustomPhaseTypePlugin<P, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: ShapePhaseItem, T: ShapeComponent> Plugin for ShapeCustomPhaseTypePlugin<P, T> {
+    fn build(&self, _app: &mut App) {}
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<P, DrawShape2dCommand<T::Data>>()
+            .add_systems(
+                Render,
+                (
+                    queue_shapes_custom_2d::<P, T::Data>.in_set(RenderSet::Queue),
+                    batch_and_prepare_render_phase::<P, Shape2dPipeline<T::Data>>
+                        .in_set(RenderSet::PrepareResources),
+                ),
+            );
+
+        if render_app
+            .world()
+            .contains_resource::<Shape3dPipeline<T::Data>>()
+        {
+            render_app
+                .add_render_command::<P, DrawShape3dCommand<T::Data>>()
+                .add_systems(
+                    Render,
+                    (
+                        queue_shapes_custom_3d::<P, T::Data>.in_set(RenderSet::Queue),
+                        batch_and_prepare_render_phase::<P, Shape3dPipeline<T::Data>>
+                            .in_set(RenderSet::PrepareResources),
+                    ),
+                );
+        }
+    }
+}
+
+/// Plugin that queues all built in shape types into the custom phase `P`, see [`ShapePhaseItem`].
+///
+/// Must be added after [`Shape2dPlugin`] or [`ShapePlugin`].
+/// Shape types defined outside this crate need their own [`ShapeCustomPhaseTypePlugin`].
+pub struct ShapeCustomPhasePlugin<P: ShapePhaseItem>(PhantomData<P>);
+
+impl<P: ShapePhaseItem> Default for ShapeCustomPhasePlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: ShapePhaseItem> Plugin for ShapeCustomPhasePlugin<P> {
+    fn build(&self, app: &mut App) {
+        macro_rules! add {
+            ($shape:ty) => {
+                app.add_plugins(ShapeCustomPhaseTypePlugin::<P, $shape>::default());
+            };
+        }
+        crate::for_each_shape_type!(add);
+    }
+}
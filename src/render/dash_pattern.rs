@@ -0,0 +1,67 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Extract,
+    },
+};
+
+use crate::painter::{ShapeDashPatterns, MAX_DASH_PATTERN_VALUES};
+
+/// Dash pattern values as laid out in the shaders.
+#[derive(ShaderType, Clone)]
+pub struct DashPatternsUniform {
+    values: [Vec4; MAX_DASH_PATTERN_VALUES / 4],
+}
+
+impl Default for DashPatternsUniform {
+    fn default() -> Self {
+        Self {
+            values: [Vec4::ZERO; MAX_DASH_PATTERN_VALUES / 4],
+        }
+    }
+}
+
+/// Uniform buffer containing the contents of [`ShapeDashPatterns`], bound alongside the view.
+#[derive(Resource)]
+pub struct DashPatternBuffer {
+    pub buffer: UniformBuffer<DashPatternsUniform>,
+    dirty: bool,
+}
+
+impl Default for DashPatternBuffer {
+    fn default() -> Self {
+        Self {
+            buffer: default(),
+            dirty: true,
+        }
+    }
+}
+
+pub fn extract_dash_patterns(
+    patterns: Extract<Res<ShapeDashPatterns>>,
+    mut buffer: ResMut<DashPatternBuffer>,
+) {
+    if !patterns.is_changed() {
+        return;
+    }
+
+    let uniform = buffer.buffer.get_mut();
+    *uniform = default();
+    for (i, value) in patterns.values().iter().enumerate() {
+        uniform.values[i / 4][i % 4] = *value;
+    }
+    buffer.dirty = true;
+}
+
+pub fn prepare_dash_patterns(
+    mut buffer: ResMut<DashPatternBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if buffer.dirty {
+        buffer.buffer.write_buffer(&render_device, &render_queue);
+        buffer.dirty = false;
+    }
+}
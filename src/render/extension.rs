@@ -0,0 +1,219 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::system::{lifetimeless::SRes, StaticSystemParam, SystemParamItem},
+    prelude::*,
+    render::{
+        render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
+        render_resource::{AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, ShaderRef},
+        renderer::RenderDevice,
+        Extract, Render, RenderApp, RenderSet,
+    },
+};
+
+use crate::render::*;
+
+/// Trait implemented by resources that add an extra bind group to the pipelines of a shape type, akin to bevy's `MaterialExtension`.
+///
+/// Once a [`ShapeMaterialExtensionPlugin`] has been added the resource is extracted whenever it changes and bound
+/// after the shape's instance and texture bind groups. As the texture bind group is only present for textured shapes
+/// shaders should declare their bindings with `@group(#{SHAPE_EXTENSION_GROUP})`, `SHAPE_EXTENSION` is also defined
+/// so that a shader can be shared with pipelines that aren't extended.
+///
+/// The bind group is recreated every time the resource changes, so values that change every frame should be kept small.
+pub trait ShapeMaterialExtension: AsBindGroup + Resource + Clone {
+    /// Shader used in place of the shape's own, [`ShaderRef::Default`] keeps the shape's shader.
+    ///
+    /// The shader must declare the same instance layout and entry points as the shader it replaces.
+    fn shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+}
+
+/// Layout and shader of a [`ShapeMaterialExtension`] used when specializing a shape's pipelines.
+#[derive(Clone)]
+pub struct ShapePipelineExtension {
+    pub layout: BindGroupLayout,
+    pub shader: Option<Handle<Shader>>,
+}
+
+/// Bind group of the [`ShapeMaterialExtension`] applied to a given shape type.
+#[derive(Resource)]
+pub struct ShapeExtensionBindGroup<T: ShapeData> {
+    pub layout: BindGroupLayout,
+    pub value: Option<BindGroup>,
+    dirty: bool,
+    _marker: PhantomData<T>,
+}
+
+pub fn extract_shape_extension<E: ShapeMaterialExtension>(
+    mut commands: Commands,
+    extension: Extract<Option<Res<E>>>,
+) {
+    if let Some(extension) = extension.as_ref() {
+        if extension.is_changed() {
+            commands.insert_resource(E::clone(extension));
+        }
+    }
+}
+
+pub fn prepare_shape_extension_bind_group<T: ShapeData, E: ShapeMaterialExtension>(
+    render_device: Res<RenderDevice>,
+    extension: Option<Res<E>>,
+    mut bind_group: ResMut<ShapeExtensionBindGroup<T>>,
+    param: StaticSystemParam<E::Param>,
+) {
+    let Some(extension) = extension else {
+        return;
+    };
+    if extension.is_changed() {
+        bind_group.dirty = true;
+    }
+    if !bind_group.dirty {
+        return;
+    }
+
+    let mut param = param.into_inner();
+    match extension.as_bind_group(&bind_group.layout, &render_device, &mut param) {
+        Ok(prepared) => {
+            bind_group.value = Some(prepared.bind_group);
+            bind_group.dirty = false;
+        }
+        // Resources used by the extension such as images may not be loaded yet
+        Err(AsBindGroupError::RetryNextUpdate) => {}
+        Err(err) => {
+            error!("Failed to prepare shape material extension: {err}");
+            bind_group.dirty = false;
+        }
+    }
+}
+
+/// Index of the extension bind group, following the texture bind group if the shape is textured.
+fn extension_group(material: &ShapePipelineMaterial) -> usize {
+    if material.texture().is_some() {
+        3
+    } else {
+        2
+    }
+}
+
+pub struct SetShape2dExtensionBindGroup<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for SetShape2dExtensionBindGroup<T> {
+    type ViewQuery = ();
+    type ItemQuery = ();
+    type Param = (
+        Option<SRes<ShapeExtensionBindGroup<T>>>,
+        SRes<Shape2dInstances<T>>,
+    );
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        (bind_group, instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Success;
+        };
+        let Some(material) = instances.get(&item.entity()).map(|i| &i.material) else {
+            return RenderCommandResult::Success;
+        };
+        let Some(value) = &bind_group.into_inner().value else {
+            return RenderCommandResult::Skip;
+        };
+        pass.set_bind_group(extension_group(material), value, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetShape3dExtensionBindGroup<T: ShapeData>(PhantomData<T>);
+
+impl<T: ShapeData, P: PhaseItem> RenderCommand<P> for SetShape3dExtensionBindGroup<T> {
+    type ViewQuery = ();
+    type ItemQuery = ();
+    type Param = (
+        Option<SRes<ShapeExtensionBindGroup<T>>>,
+        SRes<Shape3dInstances<T>>,
+    );
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        (bind_group, instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Success;
+        };
+        let Some(material) = instances.get(&item.entity()).map(|i| &i.material) else {
+            return RenderCommandResult::Success;
+        };
+        let Some(value) = &bind_group.into_inner().value else {
+            return RenderCommandResult::Skip;
+        };
+        pass.set_bind_group(extension_group(material), value, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+/// Plugin that extends the pipelines of the shape type `T` with the [`ShapeMaterialExtension`] `E`.
+///
+/// Must be added after [`ShapeTypePlugin`] and, for 3D shapes, [`ShapeType3dPlugin`] of the same type.
+/// Each shape type can be extended by at most one extension, shapes are skipped until the resource `E` has been inserted.
+pub struct ShapeMaterialExtensionPlugin<T: ShapeComponent, E: ShapeMaterialExtension>(
+    PhantomData<(T, E)>,
+);
+
+impl<T: ShapeComponent, E: ShapeMaterialExtension> Default for ShapeMaterialExtensionPlugin<T, E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ShapeComponent, E: ShapeMaterialExtension> Plugin for ShapeMaterialExtensionPlugin<T, E> {
+    fn build(&self, _app: &mut App) {}
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let layout = E::bind_group_layout(render_app.world().resource::<RenderDevice>());
+        let shader = match E::shader() {
+            ShaderRef::Default => None,
+            ShaderRef::Handle(handle) => Some(handle),
+            ShaderRef::Path(path) => Some(render_app.world().resource::<AssetServer>().load(path)),
+        };
+        let extension = ShapePipelineExtension {
+            layout: layout.clone(),
+            shader,
+        };
+
+        let world = render_app.world_mut();
+        if let Some(mut pipeline) = world.get_resource_mut::<Shape2dPipeline<T::Data>>() {
+            pipeline.extension = Some(extension.clone());
+        }
+        if let Some(mut pipeline) = world.get_resource_mut::<Shape3dPipeline<T::Data>>() {
+            pipeline.extension = Some(extension);
+        }
+
+        render_app
+            .insert_resource(ShapeExtensionBindGroup::<T::Data> {
+                layout,
+                value: None,
+                dirty: true,
+                _marker: PhantomData,
+            })
+            .add_systems(ExtractSchedule, extract_shape_extension::<E>)
+            .add_systems(
+                Render,
+                prepare_shape_extension_bind_group::<T::Data, E>
+                    .in_set(RenderSet::PrepareBindGroups),
+            );
+    }
+}
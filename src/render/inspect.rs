@@ -0,0 +1,128 @@
+use std::{
+    any::type_name,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    prelude::*,
+    render::{sync_world::TemporaryRenderEntity, Render, RenderApp, RenderSet},
+};
+
+use crate::render::*;
+
+/// A single shape prepared for rendering, requires the `inspect` feature.
+#[derive(Debug, Clone)]
+pub struct ShapeInstanceInfo {
+    /// Type name of the shape's [`ShapeData`].
+    pub shape_type: &'static str,
+    /// Entity of a retained shape, `None` for shapes drawn with a [`ShapePainter`].
+    pub entity: Option<Entity>,
+    /// Global transform the shape is drawn with.
+    pub transform: Mat4,
+    /// Color the shape is drawn with.
+    pub color: LinearRgba,
+    /// Material the shape is batched and specialized by.
+    ///
+    /// Canvases are identified by their render world entity.
+    pub material: ShapePipelineMaterial,
+}
+
+/// Resource listing every shape prepared for rendering in a frame, requires the `inspect` feature.
+///
+/// Instances are gathered in the render world and so describe the previous frame when pipelined rendering is enabled.
+/// Intended for editors and inspectors that want to list or highlight what is being drawn.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ShapeFrameInstances {
+    /// Each instance across both pipelines, grouped by shape type.
+    pub instances: Vec<ShapeInstanceInfo>,
+}
+
+impl ShapeFrameInstances {
+    /// Iterate over the instances of the given shape type.
+    pub fn of_type<T: ShapeData>(&self) -> impl Iterator<Item = &ShapeInstanceInfo> {
+        let shape_type = type_name::<T>();
+        self.instances
+            .iter()
+            .filter(move |instance| instance.shape_type == shape_type)
+    }
+
+    /// Iterate over the instances of the given retained shape entity.
+    pub fn of_entity(&self, entity: Entity) -> impl Iterator<Item = &ShapeInstanceInfo> {
+        self.instances
+            .iter()
+            .filter(move |instance| instance.entity == Some(entity))
+    }
+}
+
+/// Instances gathered by each shape type in the render world.
+#[derive(Resource, Default)]
+struct PendingShapeInstances(Vec<ShapeInstanceInfo>);
+
+/// Instances of the last completed frame, shared between the main and render worlds.
+#[derive(Resource, Clone, Default)]
+struct ShapeInstancesChannel(Arc<Mutex<Option<Vec<ShapeInstanceInfo>>>>);
+
+fn collect_instances<'a, T: ShapeData>(
+    pending: &mut PendingShapeInstances,
+    instances: impl Iterator<Item = (&'a Entity, &'a ShapeInstance<T>)>,
+    temporary: &Query<(), With<TemporaryRenderEntity>>,
+) {
+    for (entity, instance) in instances {
+        let data = &instance.data;
+        pending.0.push(ShapeInstanceInfo {
+            shape_type: type_name::<T>(),
+            // Immediate mode shapes are extracted to temporary entities, retained shapes keep their own
+            entity: (!temporary.contains(*entity)).then_some(*entity),
+            transform: data.transform(),
+            color: LinearRgba::from_f32_array(data.color()),
+            material: instance.material.clone(),
+        });
+    }
+}
+
+fn collect_shape_instances<T: ShapeData>(
+    mut pending: ResMut<PendingShapeInstances>,
+    instances_2d: Res<Shape2dInstances<T>>,
+    instances_3d: Option<Res<Shape3dInstances<T>>>,
+    temporary: Query<(), With<TemporaryRenderEntity>>,
+) {
+    collect_instances(&mut pending, instances_2d.iter(), &temporary);
+    if let Some(instances_3d) = &instances_3d {
+        collect_instances(&mut pending, instances_3d.iter(), &temporary);
+    }
+}
+
+fn finish_shape_instances(
+    mut pending: ResMut<PendingShapeInstances>,
+    channel: Res<ShapeInstancesChannel>,
+) {
+    *channel.0.lock().unwrap() = Some(std::mem::take(&mut pending.0));
+}
+
+fn receive_shape_instances(
+    channel: Res<ShapeInstancesChannel>,
+    mut instances: ResMut<ShapeFrameInstances>,
+) {
+    if let Some(received) = channel.0.lock().unwrap().take() {
+        instances.instances = received;
+    }
+}
+
+pub(crate) fn setup_inspect(app: &mut App) {
+    let channel = ShapeInstancesChannel::default();
+    app.insert_resource(channel.clone())
+        .init_resource::<ShapeFrameInstances>()
+        .add_systems(First, receive_shape_instances);
+
+    app.sub_app_mut(RenderApp)
+        .insert_resource(channel)
+        .init_resource::<PendingShapeInstances>()
+        .add_systems(Render, finish_shape_instances.in_set(RenderSet::Cleanup));
+}
+
+pub(crate) fn setup_type_inspect<T: ShapeData>(app: &mut App) {
+    app.sub_app_mut(RenderApp).add_systems(
+        Render,
+        collect_shape_instances::<T>.in_set(RenderSet::PrepareBindGroups),
+    );
+}
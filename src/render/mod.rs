@@ -1,12 +1,18 @@
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
+use std::ops::Range;
 
+use bevy::ecs::entity::EntityHashMap;
 use bevy::ecs::system::StaticSystemParam;
+use bevy::math::bounding::{Aabb3d, BoundingVolume};
 use bevy::math::FloatOrd;
+use bevy::math::Mat3A;
 use bevy::render::batching::no_gpu_preprocessing::BatchedInstanceBuffer;
 use bevy::render::batching::GetBatchData;
-use bevy::render::render_phase::{PhaseItemExtraIndex, SortedPhaseItem, ViewSortedRenderPhases};
+use bevy::render::render_phase::{
+    PhaseItemExtraIndex, SortedPhaseItem, ViewRangefinder3d, ViewSortedRenderPhases,
+};
 use bevy::render::sync_world::MainEntity;
 use bevy::render::sync_world::RenderEntity;
 use bevy::{
@@ -23,7 +29,7 @@ use bevy::{
             Buffer, CachedRenderPipelineId, GpuArrayBuffer, GpuArrayBufferable, ShaderDefVal,
             ShaderRef,
         },
-        renderer::{RenderDevice, RenderQueue},
+        renderer::{render_system, RenderDevice, RenderQueue},
         view::RenderLayers,
         Extract, Render, RenderApp, RenderSet,
     },
@@ -35,7 +41,6 @@ use wgpu::{util::BufferInitDescriptor, BufferUsages, VertexAttribute};
 use crate::prelude::*;
 
 pub(crate) mod pipeline;
-use pipeline::*;
 
 pub(crate) mod commands;
 use commands::*;
@@ -46,6 +51,49 @@ use render_2d::*;
 pub(crate) mod render_3d;
 use render_3d::*;
 
+pub(crate) mod canvas;
+use canvas::*;
+
+mod composite;
+pub use composite::*;
+
+#[cfg(feature = "ui")]
+mod ui_mask;
+#[cfg(feature = "ui")]
+pub use ui_mask::*;
+
+pub(crate) mod dash_pattern;
+use dash_pattern::*;
+
+pub(crate) mod polyline_points;
+use polyline_points::*;
+
+pub(crate) mod custom_phase;
+pub use custom_phase::*;
+
+pub(crate) mod extension;
+pub use extension::*;
+
+#[cfg(feature = "stats")]
+pub(crate) mod stats;
+#[cfg(feature = "stats")]
+pub use stats::{ShapeBatchBreak, ShapeBatchReportPlugin, ShapeFrameStats, ShapeTypeStats};
+
+#[cfg(feature = "inspect")]
+pub(crate) mod inspect;
+#[cfg(feature = "inspect")]
+pub use inspect::{ShapeFrameInstances, ShapeInstanceInfo};
+
+// Exposed so that downstream render graph nodes can re-draw the same shape data in custom passes
+pub use commands::{
+    DrawShape, DrawShape2dCommand, DrawShape3dCommand, SetShape2dBindGroup,
+    SetShape2dTextureBindGroup, SetShape3dBindGroup, SetShape3dTextureBindGroup,
+    SetShapeViewBindGroup, ShapeTextureBindGroups, ShapeTextureViewFormats, ShapeViewBindGroup,
+};
+pub use pipeline::{Shape2dPipeline, Shape3dPipeline, ShapePipelineKey, ShapePipelines};
+pub use render_2d::{Shape2dBindGroup, Shape2dInstances, Shape2dMaterials};
+pub use render_3d::{Shape3dBindGroup, Shape3dInstances, Shape3dMaterials};
+
 /// Handler to shader containing shared functionality.
 pub const CORE_HANDLE: Handle<Shader> = Handle::weak_from_u128(13215291696265391738);
 
@@ -58,12 +106,46 @@ pub const DISC_HANDLE: Handle<Shader> = Handle::weak_from_u128(12563478638216678
 /// Handler to shader for drawing lines.
 pub const LINE_HANDLE: Handle<Shader> = Handle::weak_from_u128(13656934768948239208);
 
+/// Handler to shader for drawing polylines.
+pub const POLYLINE_HANDLE: Handle<Shader> = Handle::weak_from_u128(11478230955284733156);
+
+/// Handler to shader for drawing metaballs.
+pub const METABALL_HANDLE: Handle<Shader> = Handle::weak_from_u128(16027419934561830472);
+
+/// Handler to shader for drawing boolean shapes.
+pub const BOOLEAN_HANDLE: Handle<Shader> = Handle::weak_from_u128(16614028857301934267);
+
+/// Handler to shader for drawing bezier curves.
+pub const BEZIER_HANDLE: Handle<Shader> = Handle::weak_from_u128(10853617209458316227);
+
+/// Handler to shader for drawing capsules.
+pub const CAPSULE_HANDLE: Handle<Shader> = Handle::weak_from_u128(14861292307413905761);
+
+/// Handler to shader for drawing compact circles and rectangles.
+pub const COMPACT_HANDLE: Handle<Shader> = Handle::weak_from_u128(12650381927463019457);
+
+/// Handler to shader for drawing glyphs of text.
+#[cfg(feature = "text")]
+pub const GLYPH_HANDLE: Handle<Shader> = Handle::weak_from_u128(15357912640835227129);
+
+/// Handler to shader for drawing grids.
+pub const GRID_HANDLE: Handle<Shader> = Handle::weak_from_u128(13578240934762519081);
+
 /// Handler to shader for drawing regular polygons.
 pub const NGON_HANDLE: Handle<Shader> = Handle::weak_from_u128(17394960287230910395);
 
+/// Handler to shader for drawing outlines.
+pub const OUTLINE_HANDLE: Handle<Shader> = Handle::weak_from_u128(12907350617482961523);
+
+/// Handler to shader for drawing quads.
+pub const QUAD_HANDLE: Handle<Shader> = Handle::weak_from_u128(11739026514873269983);
+
 /// Handler to shader for drawing rectangles.
 pub const RECT_HANDLE: Handle<Shader> = Handle::weak_from_u128(15069348348279052351);
 
+/// Handler to shader for drawing rings.
+pub const RING_HANDLE: Handle<Shader> = Handle::weak_from_u128(10385163734129650827);
+
 /// Handler to shader for drawing triangles.
 pub const TRIANGLE_HANDLE: Handle<Shader> = Handle::weak_from_u128(12344032791831516511);
 
@@ -92,6 +174,65 @@ pub fn load_shaders(app: &mut App) {
         Shader::from_wgsl_with_defs,
         defs
     );
+    let defs = PolylineData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        POLYLINE_HANDLE,
+        "shaders/shapes/polyline.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = MetaballData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        METABALL_HANDLE,
+        "shaders/shapes/metaball.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = BezierData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        BEZIER_HANDLE,
+        "shaders/shapes/bezier.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = BooleanData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        BOOLEAN_HANDLE,
+        "shaders/shapes/boolean.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = CapsuleData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        CAPSULE_HANDLE,
+        "shaders/shapes/capsule.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    #[cfg(feature = "text")]
+    {
+        let defs = GlyphData::shader_defs(app);
+        load_internal_asset!(
+            app,
+            GLYPH_HANDLE,
+            "shaders/shapes/glyph.wgsl",
+            Shader::from_wgsl_with_defs,
+            defs
+        );
+    }
+    let defs = GridData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        GRID_HANDLE,
+        "shaders/shapes/grid.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
     let defs = NgonData::shader_defs(app);
     load_internal_asset!(
         app,
@@ -100,6 +241,30 @@ pub fn load_shaders(app: &mut App) {
         Shader::from_wgsl_with_defs,
         defs
     );
+    let defs = OutlineData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        OUTLINE_HANDLE,
+        "shaders/shapes/outline.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = CompactData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        COMPACT_HANDLE,
+        "shaders/shapes/compact.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
+    let defs = QuadData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        QUAD_HANDLE,
+        "shaders/shapes/quad.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
     let defs = RectData::shader_defs(app);
     load_internal_asset!(
         app,
@@ -108,6 +273,14 @@ pub fn load_shaders(app: &mut App) {
         Shader::from_wgsl_with_defs,
         defs
     );
+    let defs = RingData::shader_defs(app);
+    load_internal_asset!(
+        app,
+        RING_HANDLE,
+        "shaders/shapes/ring.wgsl",
+        Shader::from_wgsl_with_defs,
+        defs
+    );
     let defs = TriangleData::shader_defs(app);
     load_internal_asset!(
         app,
@@ -128,16 +301,126 @@ pub struct ShapeInstance<T> {
     /// Ignored by the 3D pipeline.
     pub origin: Vec3,
 
+    /// Distances from the camera over which this shape fades.
+    /// Ignored by the 2D pipeline.
+    pub fade: ShapeFade,
+
+    /// Overrides the distance used for ordering in 2D, see [`ShapeSortKey`](crate::painter::ShapeSortKey).
+    /// Ignored by the 3D pipeline.
+    pub sort_key: Option<f32>,
+
+    /// Offset added to the distance used for ordering in both pipelines, see [`ShapeConfig::sort_bias`].
+    pub sort_bias: f32,
+
+    /// Number of slices the shape is split into by depth, see [`ShapeConfig::depth_slices`].
+    /// Ignored by the 2D pipeline.
+    pub depth_slices: u8,
+
+    /// Camera viewport this shape was drawn relative to, converted to world space as it is extracted.
+    pub screen_space: Option<ScreenSpace>,
+
     /// The [`ShapeData`] of this shape.
     pub data: T,
 }
 
+impl<T: ShapeData> ShapeInstance<T> {
+    /// Distance used to order this shape in the 2D pipeline.
+    pub fn distance_2d(&self) -> f32 {
+        self.sort_key.unwrap_or_else(|| self.data.distance()) + self.sort_bias
+    }
+
+    /// Distance used to order this shape in the 3D pipeline when seen from `view_position`.
+    pub fn distance_3d(&self, rangefinder: &ViewRangefinder3d, view_position: Vec3) -> f32 {
+        rangefinder.distance_translation(&self.origin_3d(view_position)) + self.sort_bias
+    }
+
+    /// Split the shape into [`ShapeInstance::depth_slices`] strips along it's local axis spanning the most depth
+    /// from a view looking along `forward`, each with the distance it's sorted at, empty if the shape isn't sliced.
+    ///
+    /// The strip of the shape's quad each slice covers is sent in the otherwise unused bottom row of it's transform.
+    pub fn slice_by_depth(&self, rangefinder: &ViewRangefinder3d, forward: Vec3) -> Vec<(f32, Self)>
+    where
+        T: Clone,
+    {
+        if !T::DEPTH_SLICES || self.depth_slices < 2 {
+            return Vec::new();
+        }
+        let Some(bounds) = self.data.local_bounds() else {
+            return Vec::new();
+        };
+
+        let transform = self.data.transform();
+        let center = Vec3::from(bounds.center());
+        let half_size = Vec3::from(bounds.half_size());
+        let span = |axis: Vec4, half_size: f32| (axis.truncate() * half_size).dot(forward).abs();
+        let (axis, code) =
+            if span(transform.x_axis, half_size.x) >= span(transform.y_axis, half_size.y) {
+                (Vec3::X, 1.0)
+            } else {
+                (Vec3::Y, 2.0)
+            };
+
+        let count = self.depth_slices as f32;
+        (0..self.depth_slices)
+            .map(|i| {
+                let start = -1.0 + 2.0 * i as f32 / count;
+                let end = -1.0 + 2.0 * (i + 1) as f32 / count;
+                let origin =
+                    transform.transform_point3(center + axis * half_size * (start + end) / 2.0);
+
+                let mut sliced = transform;
+                sliced.x_axis.w = start;
+                sliced.y_axis.w = end;
+                sliced.z_axis.w = code;
+                let mut slice = self.clone();
+                slice.data.set_transform(sliced);
+                (
+                    rangefinder.distance_translation(&origin) + self.sort_bias,
+                    slice,
+                )
+            })
+            .collect()
+    }
+
+    /// Move a shape drawn in [`ScreenSpace`] to the world space of it's camera, `false` if the shape can't be placed.
+    pub fn place_in_world(&mut self, cameras: &Query<(&Camera, &GlobalTransform)>) -> bool {
+        let Some(screen_space) = self.screen_space.take() else {
+            return true;
+        };
+        let Ok((camera, camera_transform)) = cameras.get(screen_space.camera) else {
+            return false;
+        };
+        let to_world = |transform| screen_space.to_world(camera, camera_transform, transform);
+        let (Some(transform), Some(origin)) = (
+            to_world(self.data.transform()),
+            to_world(Mat4::from_translation(self.origin)),
+        ) else {
+            return false;
+        };
+
+        self.data.set_transform(transform);
+        self.origin = origin.w_axis.truncate();
+        true
+    }
+
+    /// Point used to order this shape in the 3D pipeline when seen from `view_position`, see [`ShapeData::sort_origin`].
+    pub fn origin_3d(&self, view_position: Vec3) -> Vec3 {
+        self.data.sort_origin(self.origin, view_position)
+    }
+}
+
 /// Trait implemented by each shapes shader data, defines common methods used in the rendering pipeline.
+///
+/// Shaders can import `bevy_vector_shapes::core` for the view bindings and helpers shared by the built in shapes,
+/// and should define their instance buffer based on `PER_OBJECT_BUFFER_BATCH_SIZE` as the built in shapes do.
 pub trait ShapeData: Send + Sync + GpuArrayBufferable + 'static {
     /// Corresponding component representing the given shape.
     type Component: ShapeComponent<Data = Self>;
 
     const VERTICES: u32 = 6;
+    /// Whether the shape's shader draws it on a quad through `get_vertex_data` so that it can be split into
+    /// depth slices, see [`ShapeConfig::depth_slices`]. Slices are spaced across [`ShapeData::local_bounds`].
+    const DEPTH_SLICES: bool = false;
     /// Vertex layout to be sent to the shader.
     fn vertex_layout() -> Vec<VertexAttribute>;
     /// Reference to the shader to be used when rendering the shape.
@@ -148,6 +431,49 @@ pub trait ShapeData: Send + Sync + GpuArrayBufferable + 'static {
     }
     /// Transform of the shape to be used for z-ordering in 3D.
     fn transform(&self) -> Mat4;
+    /// Replace the transform of the shape, used to place shapes recorded in the local space of a [`CompoundShape`](crate::painter::CompoundShape).
+    fn set_transform(&mut self, transform: Mat4);
+    /// Point in world space used to order the shape in 3D when seen from `view_position`.
+    ///
+    /// `origin` is the shape's translation, or it's [`ShapeOrigin`](crate::shapes::ShapeOrigin) if set, and is used by default.
+    /// Large shapes can instead return their point nearest the view so they don't pop in front of or behind smaller shapes they overlap.
+    fn sort_origin(&self, origin: Vec3, view_position: Vec3) -> Vec3 {
+        let _ = view_position;
+        origin
+    }
+    /// Radius of a sphere around the shape's transform that encloses the shape in local space.
+    ///
+    /// Used to cull immediate mode shapes, see [`ShapeConfig::cull`], `None` if the shape should never be culled.
+    fn bounding_radius(&self) -> Option<f32> {
+        None
+    }
+    /// Box around the shape's transform that encloses the shape in local space, `None` if it's size isn't known in world units.
+    ///
+    /// Defaults to the box enclosing [`ShapeData::bounding_radius`].
+    fn local_bounds(&self) -> Option<Aabb3d> {
+        let radius = self.bounding_radius()?;
+        Some(Aabb3d::new(Vec3::ZERO, Vec3::splat(radius)))
+    }
+    /// Box in world space that encloses the shape, for sizing layouts or hit-testing without repeating the shape's math,
+    /// e.g. `RectData::new(&config, size).world_bounds()` for the rect a painter would draw with `config`.
+    ///
+    /// Billboarded shapes are bounded as if they were flat, shapes with [`Alignment::BillboardFixedSize`] as if each pixel was a world unit.
+    fn world_bounds(&self) -> Option<Aabb3d> {
+        let local = self.local_bounds()?;
+        let transform = self.transform();
+        let axes = Mat3A::from_mat4(transform);
+        let half_size = local.half_size();
+        Some(Aabb3d::new(
+            transform.transform_point3a(local.center()),
+            axes.x_axis.abs() * half_size.x
+                + axes.y_axis.abs() * half_size.y
+                + axes.z_axis.abs() * half_size.z,
+        ))
+    }
+    /// Linear color of the shape.
+    fn color(&self) -> [f32; 4];
+    /// Replace the linear color of the shape, used to apply per view effects such as fading.
+    fn set_color(&mut self, color: [f32; 4]);
 
     fn shader_defs(app: &App) -> Vec<ShaderDefVal> {
         let mut shader_defs = Vec::with_capacity(1);
@@ -180,14 +506,78 @@ pub enum ShapePipelineType {
     Shape2d,
 }
 
+/// Resource that toggles the rendering of every shape, checked before shapes are extracted each frame.
+///
+/// Disabling a pipeline skips both immediate and retained shapes without removing any systems,
+/// useful for measuring how much of the frame time is spent on shapes or hiding debug drawing with a hotkey.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct ShapesEnabled {
+    /// Draw shapes at all, when false no shapes are drawn regardless of the per pipeline toggles.
+    pub all: bool,
+    /// Draw shapes with the 2D pipeline.
+    pub shapes_2d: bool,
+    /// Draw shapes with the 3D pipeline.
+    pub shapes_3d: bool,
+}
+
+impl ShapesEnabled {
+    /// Whether shapes drawn with the given pipeline are rendered.
+    pub fn enabled(&self, pipeline: ShapePipelineType) -> bool {
+        self.all
+            && match pipeline {
+                ShapePipelineType::Shape2d => self.shapes_2d,
+                ShapePipelineType::Shape3d => self.shapes_3d,
+            }
+    }
+
+    /// Flip whether any shapes are drawn, leaving the per pipeline toggles unchanged.
+    pub fn toggle(&mut self) {
+        self.all = !self.all;
+    }
+}
+
+impl Default for ShapesEnabled {
+    fn default() -> Self {
+        Self {
+            all: true,
+            shapes_2d: true,
+            shapes_3d: true,
+        }
+    }
+}
+
 bitfield! {
     /// Flags consumed in shape shaders
     pub struct Flags(u32);
-    pub u32, from into ThicknessType, _, set_thickness_type: 1, 0;
-    pub u32, from into Alignment, _, set_alignment: 2, 2;
-    pub u32, _, set_hollow: 3, 3;
-    pub u32, from into Cap, _, set_cap: 5, 4;
-    pub u32, _, set_arc: 6, 6;
+    pub u32, from into ThicknessType, _, set_thickness_type_bits: 1, 0;
+    pub u32, from into Alignment, _, set_alignment: 3, 2;
+    pub u32, hollow, set_hollow: 4, 4;
+    pub u32, from into Cap, _, set_cap: 6, 5;
+    pub u32, _, set_arc: 7, 7;
+    pub u32, from into Join, _, set_join: 9, 8;
+    pub u32, from into Cap, _, set_end_cap: 11, 10;
+    pub u32, _, set_interior_edges: 14, 12;
+    pub u32, _, set_smooth_corners: 15, 15;
+    pub u32, _, set_thickness_min_px: 23, 16;
+    pub u32, _, set_thickness_max_px: 31, 24;
+}
+
+impl Flags {
+    /// Padding needed around the shape for it's stroke in local space, `None` if it is not measured in world units.
+    pub fn stroke_bounds(&self, thickness: f32) -> Option<f32> {
+        let world = u32::from(ThicknessType::World);
+        (self.0 & 0b11 == world).then_some(thickness.abs())
+    }
+
+    /// Set the thickness type along with the pixel range of [`ThicknessType::WorldClampedPx`].
+    pub fn set_thickness_type(&mut self, thickness_type: ThicknessType) {
+        self.set_thickness_type_bits(thickness_type);
+        if let ThicknessType::WorldClampedPx(min, max) = thickness_type {
+            self.set_thickness_min_px(min as u32);
+            self.set_thickness_max_px(max as u32);
+        }
+    }
 }
 
 /// Properties attached to a batch of shapes that are needed for pipeline specialization
@@ -197,8 +587,16 @@ pub struct ShapePipelineMaterial {
     alpha_mode: ShapeAlphaMode,
     disable_laa: bool,
     texture: Option<Handle<Image>>,
+    texture_color_space: ShapeTextureColorSpace,
     canvas: Option<Entity>,
     pipeline: ShapePipelineType,
+    render_phase: ShapeRenderPhase,
+    sdf_view: ShapeSdfView,
+    edge_softness: u8,
+    edge_falloff: EdgeFalloff,
+    cull_mode: ShapeCullMode,
+    depth_test: bool,
+    clip_rect: Option<ClipRectHash>,
 }
 
 impl ShapePipelineMaterial {
@@ -212,8 +610,117 @@ impl ShapePipelineMaterial {
             canvas: material.canvas,
             pipeline: material.pipeline,
             texture: material.texture,
+            texture_color_space: material.texture_color_space,
+            render_phase: material.render_phase,
+            sdf_view: material.sdf_view,
+            edge_softness: material.edge_softness,
+            edge_falloff: material.edge_falloff,
+            cull_mode: material.cull_mode,
+            depth_test: material.depth_test,
+            clip_rect: material.clip_rect.map(ClipRectHash),
         }
     }
+
+    /// Render layers the shapes are drawn to.
+    pub fn render_layers(&self) -> &RenderLayers {
+        &self.render_layers.0
+    }
+
+    /// Whether the shapes are drawn to a view with the given render layers.
+    pub fn is_visible_in(&self, view_layers: Option<&RenderLayers>) -> bool {
+        match view_layers {
+            Some(layers) => layers.intersects(&self.render_layers.0),
+            None => self.render_layers.0.intersects(&RenderLayers::default()),
+        }
+    }
+
+    /// Alpha mode the shapes are drawn with.
+    pub fn alpha_mode(&self) -> ShapeAlphaMode {
+        self.alpha_mode
+    }
+
+    /// Whether local anti-aliasing is disabled for the shapes.
+    pub fn disable_laa(&self) -> bool {
+        self.disable_laa
+    }
+
+    /// Texture applied to the shapes, if any.
+    pub fn texture(&self) -> Option<&Handle<Image>> {
+        self.texture.as_ref()
+    }
+
+    /// Color space the texture of the shapes is sampled in.
+    pub fn texture_color_space(&self) -> ShapeTextureColorSpace {
+        self.texture_color_space
+    }
+
+    /// Render world entity of the [`Canvas`] the shapes are drawn to, if any.
+    pub fn canvas(&self) -> Option<Entity> {
+        self.canvas
+    }
+
+    /// Replaces the canvas the shapes are drawn to.
+    pub(crate) fn with_canvas(mut self, canvas: Option<Entity>) -> Self {
+        self.canvas = canvas;
+        self
+    }
+
+    /// Maps the canvas the shapes are drawn to from it's main world entity to it's render world entity,
+    /// caching the entities mapped during an extraction in `canvases`.
+    pub(crate) fn map_canvas(
+        &mut self,
+        render_entities: &Query<&RenderEntity>,
+        canvases: &mut EntityHashMap<Entity>,
+    ) {
+        if let Some(canvas) = &mut self.canvas {
+            *canvas = *canvases.entry(*canvas).or_insert_with(|| {
+                render_entities
+                    .get(*canvas)
+                    .map(|e| e.id())
+                    .unwrap_or(Entity::PLACEHOLDER)
+            });
+        }
+    }
+
+    /// Pipeline the shapes are drawn by.
+    pub fn pipeline(&self) -> ShapePipelineType {
+        self.pipeline
+    }
+
+    /// Render phase the shapes are queued into.
+    pub fn render_phase(&self) -> ShapeRenderPhase {
+        self.render_phase
+    }
+
+    /// Distance visualization the shapes are drawn with.
+    pub fn sdf_view(&self) -> ShapeSdfView {
+        self.sdf_view
+    }
+
+    /// Width in pixels over which the edges of the shapes fade out.
+    pub fn edge_softness(&self) -> u8 {
+        self.edge_softness
+    }
+
+    /// Curve the edges of the shapes fade out with.
+    pub fn edge_falloff(&self) -> EdgeFalloff {
+        self.edge_falloff
+    }
+
+    /// Side of the shapes that is hidden.
+    pub fn cull_mode(&self) -> ShapeCullMode {
+        self.cull_mode
+    }
+
+    /// Whether the shapes are hidden behind geometry nearer to the camera.
+    pub fn depth_test(&self) -> bool {
+        self.depth_test
+    }
+
+    /// Rect in world space on the xy plane the shapes are clipped to, if any.
+    pub fn clip_rect(&self) -> Option<Rect> {
+        self.clip_rect.map(|clip| clip.0)
+    }
 }
 
 impl From<&ShapeConfig> for ShapePipelineMaterial {
@@ -224,8 +731,16 @@ impl From<&ShapeConfig> for ShapePipelineMaterial {
             disable_laa: config.disable_laa,
             // || material.alpha_mode == AlphaMode::Opaque
             texture: config.texture.clone(),
+            texture_color_space: config.texture_color_space,
             pipeline: config.pipeline,
             canvas: config.canvas,
+            render_phase: config.render_phase,
+            sdf_view: config.sdf_view,
+            edge_softness: config.edge_softness,
+            edge_falloff: config.edge_falloff,
+            cull_mode: config.cull_mode,
+            depth_test: config.depth_test,
+            clip_rect: config.clip_rect.map(ClipRectHash),
         }
     }
 }
@@ -239,6 +754,41 @@ impl Hash for RenderLayersHash {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+struct ClipRectHash(Rect);
+
+impl ClipRectHash {
+    fn bits(&self) -> [u32; 4] {
+        [self.0.min.x, self.0.min.y, self.0.max.x, self.0.max.y].map(f32::to_bits)
+    }
+}
+
+impl PartialEq for ClipRectHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for ClipRectHash {}
+
+impl PartialOrd for ClipRectHash {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ClipRectHash {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.bits().cmp(&other.bits())
+    }
+}
+
+impl Hash for ClipRectHash {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state)
+    }
+}
+
 #[derive(Resource)]
 pub struct QuadVertices {
     buffer: Buffer,
@@ -284,23 +834,69 @@ pub fn extract_render_layers(
     }
 }
 
+/// System that copies the [`Shape2dSortBias`] into the render world whenever it changes.
+pub fn extract_sort_bias(mut commands: Commands, sort_bias: Extract<Res<Shape2dSortBias>>) {
+    if sort_bias.is_changed() {
+        commands.insert_resource(**sort_bias);
+    }
+}
+
 fn setup_pipeline(app: &mut App) {
     app.sub_app_mut(RenderApp)
         .init_resource::<ShapePipelines>()
         .init_resource::<ShapeTextureBindGroups>()
+        .init_resource::<ShapeTextureViewFormats>()
         .init_resource::<QuadVertices>()
-        .add_systems(ExtractSchedule, extract_render_layers)
+        .init_resource::<ExtractedCanvasBlits>()
+        .init_resource::<CanvasMirrorSampler>()
+        .init_resource::<Shape2dSortBias>()
+        .init_resource::<DashPatternBuffer>()
+        .init_resource::<PolylinePointBuffer>()
+        .add_systems(
+            ExtractSchedule,
+            (
+                extract_render_layers,
+                extract_texture_view_formats,
+                extract_sort_bias,
+                extract_canvas_blits,
+                extract_dash_patterns,
+                extract_polyline_points,
+            ),
+        )
         .add_systems(
             Render,
-            prepare_shape_view_bind_groups.in_set(RenderSet::PrepareBindGroups),
+            (
+                prepare_canvas_blits.in_set(RenderSet::PrepareResources),
+                prepare_dash_patterns.in_set(RenderSet::PrepareResources),
+                prepare_polyline_points.in_set(RenderSet::PrepareResources),
+                prepare_shape_view_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                update_canvas_mirrors
+                    .in_set(RenderSet::Render)
+                    .after(render_system),
+            ),
         );
+
+    #[cfg(feature = "stats")]
+    stats::setup_stats(app);
+    #[cfg(feature = "inspect")]
+    inspect::setup_inspect(app);
 }
 
 fn setup_type_pipeline<T: ShapeData + 'static>(app: &mut App) {
-    app.sub_app_mut(RenderApp).add_systems(
-        Render,
-        write_batched_instance_buffer::<T>.in_set(RenderSet::PrepareResourcesFlush),
-    );
+    app.sub_app_mut(RenderApp)
+        .init_resource::<ShapeBatches<T>>()
+        .add_systems(
+            Render,
+            (
+                write_batched_instance_buffer::<T>.in_set(RenderSet::PrepareResourcesFlush),
+                clear_shape_batches::<T>.in_set(RenderSet::Cleanup),
+            ),
+        );
+
+    #[cfg(feature = "stats")]
+    stats::setup_type_stats::<T>(app);
+    #[cfg(feature = "inspect")]
+    inspect::setup_type_inspect::<T>(app);
 }
 
 fn setup_type_pipeline_3d<T: ShapeData + 'static>(app: &mut App) {
@@ -358,10 +954,46 @@ pub fn write_batched_instance_buffer<T: ShapeData + 'static>(
     gpu_array_buffer.clear();
 }
 
+/// A contiguous range of instances in the [`BatchedInstanceBuffer`] that are drawn with a single draw call.
+#[derive(Clone, Debug)]
+pub struct ShapeBatch {
+    /// Material shared by every shape in the batch.
+    pub material: ShapePipelineMaterial,
+    /// Range of instances within the buffer.
+    pub range: Range<u32>,
+    /// Dynamic offset into the buffer when it is bound as a uniform buffer.
+    pub dynamic_offset: Option<NonMaxU32>,
+}
+
+/// Batches of shapes of a given type prepared for each view this frame, keyed by view entity.
+///
+/// Available from [`RenderSet::PrepareResources`] until [`RenderSet::Cleanup`] so that
+/// custom render graph nodes can re-draw shape data without re-extracting it.
+#[derive(Resource, Deref)]
+pub struct ShapeBatches<T: ShapeData>(#[deref] EntityHashMap<Vec<ShapeBatch>>, PhantomData<T>);
+
+impl<T: ShapeData> Default for ShapeBatches<T> {
+    fn default() -> Self {
+        Self(Default::default(), Default::default())
+    }
+}
+
+pub fn clear_shape_batches<T: ShapeData>(mut batches: ResMut<ShapeBatches<T>>) {
+    batches.0.clear();
+}
+
 /// Plugin that sets up the 2d render pipeline for the given [`ShapeComponent`].
-#[derive(Default)]
+///
+/// Add this after [`Shape2dPlugin`] or [`ShapePlugin`] to render shape types defined outside this crate,
+/// their data can then be submitted with [`ShapePainter::send`] or spawned as a [`ShapeBundle`].
 pub struct ShapeTypePlugin<T: ShapeComponent>(PhantomData<T>);
 
+impl<T: ShapeComponent> Default for ShapeTypePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 impl<T: ShapeComponent> Plugin for ShapeTypePlugin<T> {
     fn build(&self, app: &mut App) {
         app.register_type::<T>();
@@ -376,9 +1008,14 @@ impl<T: ShapeComponent> Plugin for ShapeTypePlugin<T> {
 /// Plugin that sets up the 3d render pipeline for the given [`ShapeComponent`].
 ///
 /// Requires [`ShapeTypePlugin`] of the same type to have already been built.
-#[derive(Default)]
 pub struct ShapeType3dPlugin<T: ShapeComponent>(PhantomData<T>);
 
+impl<T: ShapeComponent> Default for ShapeType3dPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 impl<T: ShapeComponent> Plugin for ShapeType3dPlugin<T> {
     fn build(&self, _app: &mut App) {}
 
@@ -391,11 +1028,22 @@ impl<T: ShapeComponent> Plugin for ShapeType3dPlugin<T> {
 pub struct ShapeRenderPlugin;
 
 impl Plugin for ShapeRenderPlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Shape2dSortBias>()
+            .register_type::<Shape2dSortBias>()
+            .init_resource::<ShapesEnabled>()
+            .register_type::<ShapesEnabled>();
+        build_composite(app);
+        #[cfg(feature = "ui")]
+        build_ui_mask(app);
+    }
 
     fn finish(&self, app: &mut App) {
         load_shaders(app);
         setup_pipeline(app);
+        setup_composite(app);
+        #[cfg(feature = "ui")]
+        setup_ui_mask(app);
     }
 }
 
@@ -426,12 +1074,15 @@ impl<T: PartialEq> BatchMeta<T> {
 
 pub fn batch_and_prepare_render_phase<
     I: CachedRenderPipelinePhaseItem + SortedPhaseItem,
-    GBD: GetBatchData,
+    GBD: GetBatchData<CompareData = ShapePipelineMaterial>,
 >(
     mut gpu_array_buffer: ResMut<BatchedInstanceBuffer<GBD::BufferData>>,
     mut phases: ResMut<ViewSortedRenderPhases<I>>,
+    mut batches: ResMut<ShapeBatches<GBD::BufferData>>,
     param: StaticSystemParam<GBD::Param>,
-) {
+) where
+    GBD::BufferData: ShapeData,
+{
     let system_param_item = param.into_inner();
 
     let mut process_item = |item: &mut I| {
@@ -449,20 +1100,37 @@ pub fn batch_and_prepare_render_phase<
         compare
     };
 
-    for phase in phases.values_mut() {
+    for (view, phase) in phases.iter_mut() {
+        let view_batches = batches.0.entry(*view).or_default();
+        let mut push_batch =
+            |range: &Range<u32>, meta: Option<BatchMeta<ShapePipelineMaterial>>| {
+                if let Some(meta) = meta {
+                    view_batches.push(ShapeBatch {
+                        material: meta.user_data,
+                        range: range.clone(),
+                        dynamic_offset: meta.dynamic_offset,
+                    });
+                }
+            };
+
         let items = phase.items.iter_mut().map(|item| {
             let batch_data = process_item(item).map(|c| BatchMeta::new(item, c));
 
             (item.batch_range_mut(), batch_data)
         });
 
-        items.reduce(|(start_range, prev_batch_meta), (range, batch_meta)| {
+        let last = items.reduce(|(start_range, prev_batch_meta), (range, batch_meta)| {
             if batch_meta.is_some() && prev_batch_meta == batch_meta {
                 start_range.end = range.end;
                 (start_range, prev_batch_meta)
             } else {
+                push_batch(start_range, prev_batch_meta);
                 (range, batch_meta)
             }
         });
+
+        if let Some((range, meta)) = last {
+            push_batch(range, meta);
+        }
     }
 }
@@ -12,10 +12,10 @@ use bevy::{
     },
     utils::HashMap,
 };
-use binding_types::uniform_buffer;
+use binding_types::{storage_buffer_read_only_sized, uniform_buffer, uniform_buffer_sized};
 use wgpu::vertex_attr_array;
 
-use super::*;
+use super::{dash_pattern::DashPatternsUniform, polyline_points::*, *};
 
 bitflags::bitflags! {
     #[derive(Eq, PartialEq, Hash, Clone, Copy)]
@@ -23,14 +23,28 @@ bitflags::bitflags! {
     pub struct ShapePipelineKey: u32 {
         const NONE                              = 0;
         const HDR                               = (1 << 0);
+        const NO_DEPTH_TEST                     = (1 << 1);
         const PIPELINE_2D                       = (1 << 2);
         const LOCAL_AA                          = (1 << 3);
         const TEXTURED                          = (1 << 4);
+        const SDF_CONTOURS                      = (1 << 5);
+        const SDF_RAMP                          = (1 << 6);
+        const SOFTNESS_RESERVED_BITS            = Self::SOFTNESS_MASK_BITS << Self::SOFTNESS_SHIFT_BITS;
+        const FALLOFF_RESERVED_BITS             = Self::FALLOFF_MASK_BITS << Self::FALLOFF_SHIFT_BITS;
+        const FALLOFF_LINEAR                    = (0 << Self::FALLOFF_SHIFT_BITS);
+        const FALLOFF_SMOOTHSTEP                = (1 << Self::FALLOFF_SHIFT_BITS);
+        const FALLOFF_EXPONENTIAL               = (2 << Self::FALLOFF_SHIFT_BITS);
+        const CULL_RESERVED_BITS                = Self::CULL_MASK_BITS << Self::CULL_SHIFT_BITS;
+        const CULL_NONE                         = (0 << Self::CULL_SHIFT_BITS);
+        const CULL_BACK                         = (1 << Self::CULL_SHIFT_BITS);
+        const CULL_FRONT                        = (2 << Self::CULL_SHIFT_BITS);
         const BLEND_RESERVED_BITS               = Self::BLEND_MASK_BITS << Self::BLEND_SHIFT_BITS;
         const BLEND_OPAQUE                      = (0 << Self::BLEND_SHIFT_BITS);
         const BLEND_ADD                         = (1 << Self::BLEND_SHIFT_BITS);
         const BLEND_MULTIPLY                    = (2 << Self::BLEND_SHIFT_BITS);
         const BLEND_ALPHA                       = (3 << Self::BLEND_SHIFT_BITS);
+        const BLEND_ERASE                       = (4 << Self::BLEND_SHIFT_BITS);
+        const BLEND_ALPHA_TO_COVERAGE           = (5 << Self::BLEND_SHIFT_BITS);
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
     }
 }
@@ -38,8 +52,15 @@ bitflags::bitflags! {
 impl ShapePipelineKey {
     const MSAA_MASK_BITS: u32 = 0b111;
     const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
-    const BLEND_MASK_BITS: u32 = 0b11;
+    const BLEND_MASK_BITS: u32 = 0b111;
     const BLEND_SHIFT_BITS: u32 = Self::MSAA_SHIFT_BITS - Self::BLEND_MASK_BITS.count_ones();
+    const SOFTNESS_MASK_BITS: u32 = 0xFF;
+    const SOFTNESS_SHIFT_BITS: u32 = 7;
+    const FALLOFF_MASK_BITS: u32 = 0b11;
+    const FALLOFF_SHIFT_BITS: u32 =
+        Self::SOFTNESS_SHIFT_BITS + Self::SOFTNESS_MASK_BITS.count_ones();
+    const CULL_MASK_BITS: u32 = 0b11;
+    const CULL_SHIFT_BITS: u32 = Self::FALLOFF_SHIFT_BITS + Self::FALLOFF_MASK_BITS.count_ones();
 
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
@@ -55,6 +76,11 @@ impl ShapePipelineKey {
         }
     }
 
+    /// Width in pixels over which the edges of shapes fade out, see [`ShapeConfig::edge_softness`].
+    pub fn edge_softness(&self) -> u8 {
+        ((self.bits() >> Self::SOFTNESS_SHIFT_BITS) & Self::SOFTNESS_MASK_BITS) as u8
+    }
+
     pub fn msaa_samples(&self) -> u32 {
         1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
@@ -63,11 +89,32 @@ impl ShapePipelineKey {
         let mut key = match material.alpha_mode {
             ShapeAlphaMode::Add => Self::BLEND_ADD,
             ShapeAlphaMode::Multiply => Self::BLEND_MULTIPLY,
+            ShapeAlphaMode::Erase => Self::BLEND_ERASE,
+            ShapeAlphaMode::AlphaToCoverage => Self::BLEND_ALPHA_TO_COVERAGE,
             _ => Self::BLEND_ALPHA,
         };
         if material.texture.is_some() {
             key |= Self::TEXTURED;
         }
+        match material.sdf_view {
+            ShapeSdfView::None => {}
+            ShapeSdfView::Contours => key |= Self::SDF_CONTOURS,
+            ShapeSdfView::Ramp => key |= Self::SDF_RAMP,
+        }
+        key |= Self::from_bits_retain((material.edge_softness as u32) << Self::SOFTNESS_SHIFT_BITS);
+        key |= match material.edge_falloff {
+            EdgeFalloff::Linear => Self::FALLOFF_LINEAR,
+            EdgeFalloff::Smoothstep => Self::FALLOFF_SMOOTHSTEP,
+            EdgeFalloff::Exponential => Self::FALLOFF_EXPONENTIAL,
+        };
+        key |= match material.cull_mode {
+            ShapeCullMode::None => Self::CULL_NONE,
+            ShapeCullMode::Back => Self::CULL_BACK,
+            ShapeCullMode::Front => Self::CULL_FRONT,
+        };
+        if !material.depth_test {
+            key |= Self::NO_DEPTH_TEST;
+        }
 
         key
     }
@@ -84,6 +131,12 @@ impl FromWorld for ShapePipelines {
     fn from_world(world: &mut World) -> Self {
         let render_device = world.resource::<RenderDevice>();
         let tonemapping_lut_entries = get_lut_bind_group_layout_entries();
+        let polyline_points = if PolylinePointBuffer::is_uniform(render_device) {
+            let size = MAX_UNIFORM_POLYLINE_POINTS as u64 * std::mem::size_of::<Vec4>() as u64;
+            uniform_buffer_sized(false, BufferSize::new(size))
+        } else {
+            storage_buffer_read_only_sized(false, None)
+        };
         let view_layout = render_device.create_bind_group_layout(
             "shape_view_layout",
             &BindGroupLayoutEntries::with_indices(
@@ -99,6 +152,8 @@ impl FromWorld for ShapePipelines {
                         3,
                         tonemapping_lut_entries[1].visibility(ShaderStages::FRAGMENT),
                     ),
+                    (4, uniform_buffer::<DashPatternsUniform>(false)),
+                    (5, polyline_points),
                 ),
             ),
         );
@@ -159,6 +214,10 @@ impl ShapePipelines {
 pub struct Shape2dPipeline<T: ShapeData> {
     pub shader: Handle<Shader>,
     pub layout: BindGroupLayout,
+    /// Extra bind group and shader added by a [`ShapeMaterialExtensionPlugin`].
+    pub extension: Option<ShapePipelineExtension>,
+    /// Number of shapes per uniform batch on platforms without storage buffers.
+    batch_size: Option<u32>,
     _marker: PhantomData<T>,
 }
 
@@ -168,14 +227,19 @@ impl<T: ShapeData> FromWorld for Shape2dPipeline<T> {
         let layout = render_device.create_bind_group_layout(
             Some("shape_layout"),
             &BindGroupLayoutEntries::with_indices(
-                ShaderStages::VERTEX,
+                // Visible to the fragment stage for shapes that read their instance data per fragment
+                ShaderStages::VERTEX_FRAGMENT,
                 ((0, GpuArrayBuffer::<T>::binding_layout(render_device)),),
             ),
         );
 
+        let batch_size = GpuArrayBuffer::<T>::batch_size(render_device);
+
         let asset_server = world.resource_mut::<AssetServer>();
         Self {
             layout,
+            extension: None,
+            batch_size,
             shader: match T::shader() {
                 ShaderRef::Default => RECT_HANDLE,
                 ShaderRef::Handle(handle) => handle,
@@ -196,8 +260,26 @@ impl<T: ShapeData> Shape2dPipeline<T> {
     ) -> RenderPipelineDescriptor {
         let mut shader_defs = Vec::new();
         let (label, blend, depth_stencil, depth_write_enabled);
+        let depth_compare = |compare| match key.contains(ShapePipelineKey::NO_DEPTH_TEST) {
+            true => CompareFunction::Always,
+            false => compare,
+        };
 
-        let pass = key.intersection(ShapePipelineKey::BLEND_RESERVED_BITS);
+        // Shaders loaded from a path don't have their defs baked in by load_shaders
+        if let Some(batch_size) = self.batch_size {
+            shader_defs.push(ShaderDefVal::UInt(
+                "PER_OBJECT_BUFFER_BATCH_SIZE".into(),
+                batch_size,
+            ));
+        }
+
+        let mut pass = key.intersection(ShapePipelineKey::BLEND_RESERVED_BITS);
+        // Alpha to coverage requires multisampling, so without it shapes are alpha blended instead
+        let alpha_to_coverage =
+            pass == ShapePipelineKey::BLEND_ALPHA_TO_COVERAGE && key.msaa_samples() > 1;
+        if pass == ShapePipelineKey::BLEND_ALPHA_TO_COVERAGE && !alpha_to_coverage {
+            pass = ShapePipelineKey::BLEND_ALPHA;
+        }
 
         if pass == ShapePipelineKey::BLEND_ALPHA {
             label = "alpha_blend_shape_pipeline".into();
@@ -232,6 +314,25 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             });
             shader_defs.push("BLEND_MULTIPLY".into());
             depth_write_enabled = false;
+        } else if pass == ShapePipelineKey::BLEND_ERASE {
+            label = "erase_shape_pipeline".into();
+            // Scale down everything beneath the shape by it's coverage, including the destination alpha
+            let erase = BlendComponent {
+                src_factor: BlendFactor::Zero,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            };
+            blend = Some(BlendState {
+                color: erase,
+                alpha: erase,
+            });
+            shader_defs.push("BLEND_ALPHA".into());
+            depth_write_enabled = false;
+        } else if alpha_to_coverage {
+            label = "alpha_to_coverage_shape_pipeline".into();
+            blend = Some(BlendState::REPLACE);
+            shader_defs.push("BLEND_ALPHA".into());
+            depth_write_enabled = true;
         } else {
             label = "opaque_shape_pipeline".into();
             blend = Some(BlendState::REPLACE);
@@ -243,7 +344,7 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             depth_stencil = Some(DepthStencilState {
                 format: CORE_2D_DEPTH_FORMAT,
                 depth_write_enabled,
-                depth_compare: CompareFunction::GreaterEqual,
+                depth_compare: depth_compare(CompareFunction::GreaterEqual),
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -261,7 +362,7 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             depth_stencil = Some(DepthStencilState {
                 format: TextureFormat::Depth32Float,
                 depth_write_enabled,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: depth_compare(CompareFunction::Greater),
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -277,7 +378,33 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             shader_defs.push("PIPELINE_3D".into());
         }
 
-        if key.contains(ShapePipelineKey::LOCAL_AA) {
+        // Distances are recovered from the partial derivatives used for local anti-aliasing
+        let sdf_view = key.intersects(ShapePipelineKey::SDF_CONTOURS | ShapePipelineKey::SDF_RAMP);
+        if sdf_view {
+            shader_defs.push("SDF_VIEW".into());
+            if key.contains(ShapePipelineKey::SDF_CONTOURS) {
+                shader_defs.push("SDF_CONTOURS".into());
+            } else {
+                shader_defs.push("SDF_RAMP".into());
+            }
+        }
+
+        // Soft edges widen the fade applied by local anti-aliasing
+        let edge_softness = key.edge_softness();
+        if edge_softness > 0 {
+            shader_defs.push(ShaderDefVal::UInt(
+                "EDGE_SOFTNESS".into(),
+                edge_softness as u32,
+            ));
+            let falloff = key.intersection(ShapePipelineKey::FALLOFF_RESERVED_BITS);
+            if falloff == ShapePipelineKey::FALLOFF_SMOOTHSTEP {
+                shader_defs.push("FALLOFF_SMOOTHSTEP".into());
+            } else if falloff == ShapePipelineKey::FALLOFF_EXPONENTIAL {
+                shader_defs.push("FALLOFF_EXPONENTIAL".into());
+            }
+        }
+
+        if key.contains(ShapePipelineKey::LOCAL_AA) || sdf_view || edge_softness > 0 {
             shader_defs.push("LOCAL_AA".into());
         } else {
             shader_defs.push("DISABLE_LOCAL_AA".into())
@@ -294,12 +421,36 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             shader_defs.push("TEXTURED".into());
         }
 
+        // Extensions are bound after the texture so their group depends on whether the shape is textured
+        let mut shader = self.shader.clone();
+        if let Some(extension) = &self.extension {
+            shader_defs.push("SHAPE_EXTENSION".into());
+            shader_defs.push(ShaderDefVal::UInt(
+                "SHAPE_EXTENSION_GROUP".into(),
+                layout.len() as u32,
+            ));
+            layout.push(extension.layout.clone());
+            if let Some(handle) = &extension.shader {
+                shader = handle.clone();
+            }
+        }
+
+        // Shapes wind counter-clockwise when seen from the front
+        let cull = key.intersection(ShapePipelineKey::CULL_RESERVED_BITS);
+        let cull_mode = if cull == ShapePipelineKey::CULL_BACK {
+            Some(Face::Back)
+        } else if cull == ShapePipelineKey::CULL_FRONT {
+            Some(Face::Front)
+        } else {
+            None
+        };
+
         let mut fragment_defs = shader_defs.clone();
         fragment_defs.push("FRAGMENT".into());
 
         RenderPipelineDescriptor {
             vertex: VertexState {
-                shader: self.shader.clone(),
+                shader: shader.clone(),
                 entry_point: "vertex".into(),
                 shader_defs: shader_defs.clone(),
                 buffers: vec![VertexBufferLayout {
@@ -309,7 +460,7 @@ impl<T: ShapeData> Shape2dPipeline<T> {
                 }],
             },
             fragment: Some(FragmentState {
-                shader: self.shader.clone(),
+                shader,
                 shader_defs: fragment_defs,
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
@@ -321,7 +472,7 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             layout,
             primitive: PrimitiveState {
                 front_face: FrontFace::Ccw,
-                cull_mode: None,
+                cull_mode,
                 unclipped_depth: false,
                 polygon_mode: PolygonMode::Fill,
                 conservative: false,
@@ -332,7 +483,7 @@ impl<T: ShapeData> Shape2dPipeline<T> {
             multisample: MultisampleState {
                 count: key.msaa_samples(),
                 mask: !0,
-                alpha_to_coverage_enabled: false,
+                alpha_to_coverage_enabled: alpha_to_coverage,
             },
             label: Some(label),
             push_constant_ranges: vec![],
@@ -0,0 +1,131 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::Buffer,
+        renderer::{RenderDevice, RenderQueue},
+        Extract,
+    },
+};
+use wgpu::{util::BufferInitDescriptor, BufferUsages};
+
+use crate::painter::ShapePolylinePoints;
+
+/// Number of points available to polylines on platforms without storage buffers, points beyond it are not drawn.
+///
+/// Must match the size of `polyline_points` in `shaders/shapes/polyline.wgsl`.
+pub const MAX_UNIFORM_POLYLINE_POINTS: u32 = 1024;
+
+/// Initial number of points allocated when storage buffers are available, the buffer grows as needed.
+const INITIAL_POLYLINE_POINTS: u32 = 1024;
+
+/// Buffer containing the contents of [`ShapePolylinePoints`], bound alongside the view.
+///
+/// Bound as a storage buffer where supported, otherwise as a fixed size uniform buffer.
+#[derive(Resource)]
+pub struct PolylinePointBuffer {
+    pub buffer: Buffer,
+    capacity: u32,
+    uniform: bool,
+    /// Points to write this frame along with the index of the first point.
+    writes: Vec<(u32, Vec<Vec4>)>,
+    /// Full contents of the buffer if it needs to be recreated at a larger size.
+    resize: Option<Vec<Vec4>>,
+}
+
+impl PolylinePointBuffer {
+    /// Whether the buffer is bound as a uniform buffer rather than a storage buffer.
+    pub fn is_uniform(render_device: &RenderDevice) -> bool {
+        render_device.limits().max_storage_buffers_per_shader_stage == 0
+    }
+
+    fn create_buffer(render_device: &RenderDevice, uniform: bool, points: &[Vec4]) -> Buffer {
+        let usage = if uniform {
+            BufferUsages::UNIFORM
+        } else {
+            BufferUsages::STORAGE
+        };
+        render_device.create_buffer_with_data(&BufferInitDescriptor {
+            usage: usage | BufferUsages::COPY_DST,
+            label: Some("polyline_point_buffer"),
+            contents: as_bytes(points),
+        })
+    }
+}
+
+impl FromWorld for PolylinePointBuffer {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let uniform = Self::is_uniform(render_device);
+        let capacity = if uniform {
+            MAX_UNIFORM_POLYLINE_POINTS
+        } else {
+            INITIAL_POLYLINE_POINTS
+        };
+        let buffer =
+            Self::create_buffer(render_device, uniform, &vec![Vec4::ZERO; capacity as usize]);
+
+        Self {
+            buffer,
+            capacity,
+            uniform,
+            writes: default(),
+            resize: None,
+        }
+    }
+}
+
+fn as_bytes(points: &[Vec4]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(points.as_ptr().cast(), std::mem::size_of_val(points)) }
+}
+
+pub fn extract_polyline_points(
+    points: Extract<Res<ShapePolylinePoints>>,
+    mut buffer: ResMut<PolylinePointBuffer>,
+) {
+    if points.changed().is_empty() {
+        return;
+    }
+
+    let values = points.values();
+    if values.len() as u32 > buffer.capacity && !buffer.uniform {
+        buffer.resize = Some(values.to_vec());
+        buffer.writes.clear();
+        return;
+    }
+
+    let capacity = buffer.capacity;
+    for range in points.changed() {
+        // Points beyond the capacity of the uniform buffer are not drawn
+        let original_end = range.end;
+        let range = range.start.min(capacity)..range.end.min(capacity);
+        if range.end < original_end {
+            warn_once!(
+                "Polylines use more than {} points which is the limit without storage buffers, the remaining points are not drawn",
+                MAX_UNIFORM_POLYLINE_POINTS
+            );
+        }
+        if !range.is_empty() {
+            let values = values[range.start as usize..range.end as usize].to_vec();
+            buffer.writes.push((range.start, values));
+        }
+    }
+}
+
+pub fn prepare_polyline_points(
+    buffer: ResMut<PolylinePointBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let buffer = buffer.into_inner();
+    if let Some(mut values) = buffer.resize.take() {
+        let capacity = (values.len() as u32).next_power_of_two();
+        values.resize(capacity as usize, Vec4::ZERO);
+        buffer.buffer = PolylinePointBuffer::create_buffer(&render_device, false, &values);
+        buffer.capacity = capacity;
+    }
+
+    for (start, values) in buffer.writes.drain(..) {
+        let offset = start as u64 * std::mem::size_of::<Vec4>() as u64;
+        render_queue.write_buffer(&buffer.buffer, offset, as_bytes(&values));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{painter::ShapeStorage, render::*, shapes::Shape3d};
+use crate::{
+    painter::{RetainedShapeData, ShapeSortKey, ShapeStorage},
+    render::*,
+    shapes::Shape3d,
+};
 use bevy::{
     ecs::entity::EntityHashMap,
     render::{
@@ -11,6 +15,7 @@ use bevy::{
     utils::HashMap,
 };
 
+/// Extracted [`ShapeInstance`]s of a given type drawn by the 2D pipeline this frame, keyed by render entity.
 #[derive(Resource, Deref, DerefMut)]
 pub struct Shape2dInstances<T: ShapeData>(EntityHashMap<ShapeInstance<T>>);
 
@@ -20,6 +25,7 @@ impl<T: ShapeData> Default for Shape2dInstances<T> {
     }
 }
 
+/// Render entities of a given shape type drawn by the 2D pipeline this frame, grouped by material.
 #[derive(Resource, Deref, DerefMut)]
 pub struct Shape2dMaterials<T: ShapeData>(
     #[deref] HashMap<ShapePipelineMaterial, Vec<Entity>>,
@@ -32,6 +38,7 @@ impl<T: ShapeData> Default for Shape2dMaterials<T> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_shapes_2d<T: ShapeData>(
     mut commands: Commands,
     shapes: Extract<
@@ -44,11 +51,30 @@ pub fn extract_shapes_2d<T: ShapeData>(
                 &InheritedVisibility,
                 Option<&ShapeMaterial>,
                 Option<&RenderLayers>,
+                Option<&ShapeSortKey>,
+                Option<&ShapeTransformModifier>,
+            ),
+            Without<Shape3d>,
+        >,
+    >,
+    retained: Extract<
+        Query<
+            (
+                Entity,
+                &RetainedShapeData<T>,
+                &GlobalTransform,
+                &InheritedVisibility,
+                &ShapeMaterial,
+                Option<&RenderLayers>,
+                Option<&ShapeSortKey>,
+                Option<&ShapeTransformModifier>,
             ),
             Without<Shape3d>,
         >,
     >,
+    cameras: Extract<Query<(&Camera, &GlobalTransform)>>,
     storage: Extract<Res<ShapeStorage>>,
+    enabled: Extract<Res<ShapesEnabled>>,
     mut instance_data: ResMut<Shape2dInstances<T>>,
     mut materials: ResMut<Shape2dMaterials<T>>,
     render_entities: Extract<Query<&RenderEntity>>,
@@ -58,49 +84,81 @@ pub fn extract_shapes_2d<T: ShapeData>(
     materials.clear();
     canvases.clear();
 
+    if !enabled.enabled(ShapePipelineType::Shape2d) {
+        return;
+    }
+
     shapes
         .iter()
-        .filter_map(|(e, cp, fill, tf, vis, flags, rl)| {
-            if vis.get() {
-                Some((
-                    e,
-                    ShapePipelineMaterial::new(flags, rl),
-                    cp.get_data(tf, fill),
-                ))
-            } else {
-                None
+        .filter(|(_, _, _, _, vis, ..)| vis.get())
+        .for_each(|(entity, cp, fill, tf, _, flags, rl, key, modifier)| {
+            let tf = modifier.map_or(*tf, |modifier| modifier.apply(tf));
+            let mut material = ShapePipelineMaterial::new(flags, rl);
+            material.map_canvas(&render_entities, &mut canvases);
+
+            // Outlines of filled shapes are drawn by a second instance queued after the fill, so they sort over it
+            let stroke = fill
+                .stroke_fill()
+                .map(|stroke| (commands.spawn(TemporaryRenderEntity).id(), stroke));
+            for (entity, fill) in std::iter::once((entity, *fill)).chain(stroke) {
+                materials.entry(material.clone()).or_default().push(entity);
+                instance_data.insert(
+                    entity,
+                    ShapeInstance {
+                        material: material.clone(),
+                        origin: Vec3::ZERO,
+                        fade: default(),
+                        sort_key: key.map(|k| k.0),
+                        sort_bias: flags.map_or(0.0, |m| m.sort_bias),
+                        depth_slices: 0,
+                        screen_space: None,
+                        data: cp.get_data(&tf, &fill),
+                    },
+                );
             }
-        })
-        .for_each(|(entity, material, data)| {
+        });
+
+    retained
+        .iter()
+        .filter(|(_, _, _, vis, ..)| vis.get())
+        .for_each(|(entity, data, tf, _, flags, rl, key, modifier)| {
+            let tf = modifier.map_or(*tf, |modifier| modifier.apply(tf));
+            let mut data = data.0.clone();
+            data.set_transform(tf.compute_matrix() * data.transform());
+
+            let mut material = ShapePipelineMaterial::new(Some(flags), rl);
+            material.map_canvas(&render_entities, &mut canvases);
             materials.entry(material.clone()).or_default().push(entity);
             instance_data.insert(
                 entity,
                 ShapeInstance {
                     material,
                     origin: Vec3::ZERO,
+                    fade: default(),
+                    sort_key: key.map(|k| k.0),
+                    sort_bias: flags.sort_bias,
+                    depth_slices: 0,
+                    screen_space: None,
                     data,
                 },
             );
         });
 
-    if let Some(iter) = storage.get::<T>(ShapePipelineType::Shape2d) {
-        iter.cloned().for_each(|mut instance| {
+    storage
+        .get_with_groups::<T>(ShapePipelineType::Shape2d)
+        .cloned()
+        .filter_map(|mut instance| instance.place_in_world(&cameras).then_some(instance))
+        .for_each(|mut instance| {
             let entity = commands.spawn(TemporaryRenderEntity).id();
-            if let Some(canvas) = &mut instance.material.canvas {
-                *canvas = *canvases.entry(*canvas).or_insert_with(|| {
-                    render_entities
-                        .get(*canvas)
-                        .map(|e| e.id())
-                        .unwrap_or(Entity::PLACEHOLDER)
-                });
-            }
+            instance
+                .material
+                .map_canvas(&render_entities, &mut canvases);
             materials
                 .entry(instance.material.clone())
                 .or_default()
                 .push(entity);
             instance_data.insert(entity, instance);
         });
-    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -111,6 +169,7 @@ pub fn queue_shapes_2d<T: ShapeData>(
     materials: Res<Shape2dMaterials<T>>,
     instance_data: Res<Shape2dInstances<T>>,
     mut shape_pipelines: ResMut<ShapePipelines>,
+    sort_bias: Res<Shape2dSortBias>,
     mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(Entity, &ExtractedView, &Msaa, Option<&RenderLayers>)>,
 ) {
@@ -120,6 +179,11 @@ pub fn queue_shapes_2d<T: ShapeData>(
     let view_count = views.iter().count();
 
     for (material, entities) in materials.iter() {
+        // Shapes in custom phases are queued by ShapeCustomPhasePlugin
+        if material.render_phase != ShapeRenderPhase::Transparent {
+            continue;
+        }
+
         let mut key = ShapePipelineKey::from_material(material);
         if !material.disable_laa {
             key |= ShapePipelineKey::LOCAL_AA;
@@ -133,10 +197,7 @@ pub fn queue_shapes_2d<T: ShapeData>(
         } else {
             views
                 .iter_mut()
-                .filter(|(_, _, _, layers)| {
-                    let render_layers = layers.cloned().unwrap_or_default();
-                    render_layers.intersects(&material.render_layers.0)
-                })
+                .filter(|(_, _, _, layers)| material.is_visible_in(*layers))
                 .for_each(|view| visible_views.push(view))
         };
 
@@ -158,7 +219,7 @@ pub fn queue_shapes_2d<T: ShapeData>(
                     entity: (entity, MainEntity::from(Entity::PLACEHOLDER)),
                     pipeline,
                     draw_function,
-                    sort_key: FloatOrd(instance.data.distance()),
+                    sort_key: FloatOrd(sort_bias.apply(instance.distance_2d())),
                     batch_range: 0..1,
                     extra_index: PhaseItemExtraIndex::NONE,
                 });
@@ -167,6 +228,7 @@ pub fn queue_shapes_2d<T: ShapeData>(
     }
 }
 
+/// Bind group containing the instance buffer of a given shape type for the 2D pipeline.
 #[derive(Resource)]
 pub struct Shape2dBindGroup<T: ShapeData> {
     pub value: BindGroup,
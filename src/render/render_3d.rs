@@ -12,8 +12,13 @@ use bevy::{
     utils::HashMap,
 };
 
-use crate::{painter::ShapeStorage, render::*, shapes::Shape3d};
+use crate::{
+    painter::{RetainedShapeData, ShapeStorage},
+    render::*,
+    shapes::{FillType, Shape3d, ShapeLod},
+};
 
+/// Extracted [`ShapeInstance`]s of a given type drawn by the 3D pipeline this frame, keyed by render entity.
 #[derive(Resource, Deref, DerefMut)]
 pub struct Shape3dInstances<T: ShapeData>(EntityHashMap<ShapeInstance<T>>);
 
@@ -23,6 +28,7 @@ impl<T: ShapeData> Default for Shape3dInstances<T> {
     }
 }
 
+/// Render entities of a given shape type drawn by the 3D pipeline this frame, grouped by material.
 #[derive(Resource, Deref, DerefMut)]
 pub struct Shape3dMaterials<T: ShapeData>(
     #[deref] HashMap<ShapePipelineMaterial, Vec<Entity>>,
@@ -35,6 +41,7 @@ impl<T: ShapeData> Default for Shape3dMaterials<T> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_shapes_3d<T: ShapeData>(
     mut commands: Commands,
     entities: Extract<
@@ -48,11 +55,30 @@ pub fn extract_shapes_3d<T: ShapeData>(
                 Option<&ShapeMaterial>,
                 Option<&RenderLayers>,
                 Option<&ShapeOrigin>,
+                Option<&ShapeLod>,
+                Option<&ShapeTransformModifier>,
             ),
             With<Shape3d>,
         >,
     >,
+    retained: Extract<
+        Query<
+            (
+                Entity,
+                &RetainedShapeData<T>,
+                &GlobalTransform,
+                &InheritedVisibility,
+                &ShapeMaterial,
+                Option<&RenderLayers>,
+                Option<&ShapeOrigin>,
+                Option<&ShapeTransformModifier>,
+            ),
+            With<Shape3d>,
+        >,
+    >,
+    cameras: Extract<Query<(&Camera, &GlobalTransform)>>,
     storage: Extract<Res<ShapeStorage>>,
+    enabled: Extract<Res<ShapesEnabled>>,
     mut instance_data: ResMut<Shape3dInstances<T>>,
     mut materials: ResMut<Shape3dMaterials<T>>,
     render_entities: Extract<Query<&RenderEntity>>,
@@ -62,63 +88,137 @@ pub fn extract_shapes_3d<T: ShapeData>(
     materials.clear();
     canvases.clear();
 
+    if !enabled.enabled(ShapePipelineType::Shape3d) {
+        return;
+    }
+
+    let camera_positions: Vec<Vec3> = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .map(|(_, tf)| tf.translation())
+        .collect();
+
     entities
         .iter()
-        .filter_map(|(e, cp, fill, tf, vis, flags, rl, or)| {
+        .filter_map(|(e, cp, fill, tf, vis, flags, rl, or, lod, modifier)| {
             if vis.get() {
+                let mut tf = modifier.map_or(*tf, |modifier| modifier.apply(tf));
+
                 // find global origin of shape
                 let local_origin = or.map(|or| or.0).unwrap_or(Vec3::ZERO);
                 let origin = tf.transform_point(local_origin);
 
-                Some((
-                    e,
-                    ShapeInstance {
-                        material: ShapePipelineMaterial::new(flags, rl),
-                        origin,
-                        data: cp.get_data(tf, fill),
-                    },
-                ))
+                let mut material = ShapePipelineMaterial::new(flags, rl);
+                material.map_canvas(&render_entities, &mut canvases);
+                let mut fill = *fill;
+
+                // Apply the level of detail for the closest camera
+                let level = lod.and_then(|lod| {
+                    let distance = camera_positions
+                        .iter()
+                        .map(|position| position.distance(origin))
+                        .reduce(f32::min)?;
+                    lod.level(distance)
+                });
+                if let Some(level) = level {
+                    if level.hide {
+                        return None;
+                    }
+                    if level.fill {
+                        fill.ty = FillType::Fill;
+                    }
+                    if level.drop_texture {
+                        material.texture = None;
+                    }
+                    if level.scale != 1.0 {
+                        tf = tf.mul_transform(Transform::from_scale(Vec3::splat(level.scale)));
+                    }
+                }
+
+                let instance = ShapeInstance {
+                    material,
+                    origin,
+                    fade: flags.map(|m| m.fade.clone()).unwrap_or_default(),
+                    sort_key: None,
+                    sort_bias: flags.map_or(0.0, |m| m.sort_bias),
+                    depth_slices: flags.map_or(0, |m| m.depth_slices),
+                    screen_space: None,
+                    data: cp.get_data(&tf, &fill),
+                };
+                let stroke = fill.stroke_fill().map(|stroke| ShapeInstance {
+                    data: cp.get_data(&tf, &stroke),
+                    ..instance.clone()
+                });
+                Some((e, instance, stroke))
             } else {
                 None
             }
         })
-        .for_each(|(entity, instance)| {
-            materials
-                .entry(instance.material.clone())
-                .or_default()
-                .push(entity);
-            instance_data.insert(entity, instance);
+        .for_each(|(entity, instance, stroke)| {
+            // Outlines of filled shapes are drawn by a second instance queued after the fill, so they sort over it
+            let stroke = stroke.map(|stroke| (commands.spawn(TemporaryRenderEntity).id(), stroke));
+            for (entity, instance) in std::iter::once((entity, instance)).chain(stroke) {
+                materials
+                    .entry(instance.material.clone())
+                    .or_default()
+                    .push(entity);
+                instance_data.insert(entity, instance);
+            }
+        });
+
+    retained
+        .iter()
+        .filter(|(_, _, _, vis, ..)| vis.get())
+        .for_each(|(entity, data, tf, _, flags, rl, or, modifier)| {
+            let tf = modifier.map_or(*tf, |modifier| modifier.apply(tf));
+            let mut data = data.0.clone();
+            data.set_transform(tf.compute_matrix() * data.transform());
+
+            let mut material = ShapePipelineMaterial::new(Some(flags), rl);
+            material.map_canvas(&render_entities, &mut canvases);
+            materials.entry(material.clone()).or_default().push(entity);
+            instance_data.insert(
+                entity,
+                ShapeInstance {
+                    material,
+                    origin: tf.transform_point(or.map_or(Vec3::ZERO, |or| or.0)),
+                    fade: flags.fade.clone(),
+                    sort_key: None,
+                    sort_bias: flags.sort_bias,
+                    depth_slices: flags.depth_slices,
+                    screen_space: None,
+                    data,
+                },
+            );
         });
 
-    if let Some(iter) = storage.get::<T>(ShapePipelineType::Shape3d) {
-        iter.cloned().for_each(|mut instance| {
+    storage
+        .get_with_groups::<T>(ShapePipelineType::Shape3d)
+        .cloned()
+        .filter_map(|mut instance| instance.place_in_world(&cameras).then_some(instance))
+        .for_each(|mut instance| {
             let entity = commands.spawn(TemporaryRenderEntity).id();
-            if let Some(canvas) = &mut instance.material.canvas {
-                *canvas = *canvases.entry(*canvas).or_insert_with(|| {
-                    render_entities
-                        .get(*canvas)
-                        .map(|e| e.id())
-                        .unwrap_or(Entity::PLACEHOLDER)
-                });
-            }
+            instance
+                .material
+                .map_canvas(&render_entities, &mut canvases);
             materials
                 .entry(instance.material.clone())
                 .or_default()
                 .push(entity);
             instance_data.insert(entity, instance);
         });
-    }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn queue_shapes_3d<T: ShapeData>(
+    mut commands: Commands,
     // opaque_draw_functions: Res<DrawFunctions<Opaque3d>>,
     // alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
     transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
     pipeline: Res<Shape3dPipeline<T>>,
     pipeline_cache: Res<PipelineCache>,
     materials: Res<Shape3dMaterials<T>>,
-    instance_data: Res<Shape3dInstances<T>>,
+    mut instance_data: ResMut<Shape3dInstances<T>>,
     mut shape_pipelines: ResMut<ShapePipelines>,
     // mut opaque_phases: ResMut<ViewBinnedRenderPhases<Opaque3d>>,
     // mut alpha_phases: ResMut<ViewBinnedRenderPhases<AlphaMask3d>>,
@@ -135,6 +235,11 @@ pub fn queue_shapes_3d<T: ShapeData>(
     let view_count = views.iter().count();
 
     for (material, entities) in materials.iter() {
+        // Shapes in custom phases are queued by ShapeCustomPhasePlugin
+        if material.render_phase != ShapeRenderPhase::Transparent {
+            continue;
+        }
+
         let mut key = ShapePipelineKey::from_material(material);
         if !material.disable_laa {
             key |= ShapePipelineKey::LOCAL_AA;
@@ -148,10 +253,7 @@ pub fn queue_shapes_3d<T: ShapeData>(
         } else {
             views
                 .iter_mut()
-                .filter(|(_, _, _, layers)| {
-                    let render_layers = layers.cloned().unwrap_or_default();
-                    render_layers.intersects(&material.render_layers.0)
-                })
+                .filter(|(_, _, _, layers)| material.is_visible_in(*layers))
                 .for_each(|view| visible_views.push(view))
         };
 
@@ -175,23 +277,62 @@ pub fn queue_shapes_3d<T: ShapeData>(
             //     uuid: AssetId::<Mesh>::DEFAULT_UUID,
             // };
             let rangefinder = view.rangefinder3d();
+            let view_position = view.world_from_view.translation();
+            let view_forward = *view.world_from_view.forward();
             for &entity in entities {
                 // SAFETY: we insert this alongside inserting into the vector we are currently iterating
                 let instance = unsafe { instance_data.get(&entity).unwrap_unchecked() };
-                let distance = rangefinder.distance_translation(&instance.origin);
-                transparent_phase.add(Transparent3d {
-                    entity: (entity, MainEntity::from(Entity::PLACEHOLDER)),
-                    draw_function: draw_transparent,
-                    pipeline,
-                    distance,
-                    batch_range: 0..1,
-                    extra_index: PhaseItemExtraIndex::NONE,
+                let distance = instance.distance_3d(&rangefinder, view_position);
+
+                // Fading depends on the view so partially faded shapes get their own instance per view
+                let alpha = instance.fade.alpha(view_position.distance(instance.origin));
+                if alpha <= 0.0 {
+                    continue;
+                }
+                let faded = (alpha < 1.0).then(|| {
+                    let mut faded = instance.clone();
+                    let mut color = faded.data.color();
+                    color[3] *= alpha;
+                    faded.data.set_color(color);
+                    faded
                 });
+
+                // As does slicing, each slice is queued as it's own instance sorted at it's own depth
+                let slices = faded
+                    .as_ref()
+                    .unwrap_or(instance)
+                    .slice_by_depth(&rangefinder, view_forward);
+                let mut items = Vec::with_capacity(slices.len().max(1));
+                if !slices.is_empty() {
+                    for (distance, slice) in slices {
+                        let entity = commands.spawn(TemporaryRenderEntity).id();
+                        instance_data.insert(entity, slice);
+                        items.push((entity, distance));
+                    }
+                } else if let Some(faded) = faded {
+                    let entity = commands.spawn(TemporaryRenderEntity).id();
+                    instance_data.insert(entity, faded);
+                    items.push((entity, distance));
+                } else {
+                    items.push((entity, distance));
+                }
+
+                for (entity, distance) in items {
+                    transparent_phase.add(Transparent3d {
+                        entity: (entity, MainEntity::from(Entity::PLACEHOLDER)),
+                        draw_function: draw_transparent,
+                        pipeline,
+                        distance,
+                        batch_range: 0..1,
+                        extra_index: PhaseItemExtraIndex::NONE,
+                    });
+                }
             }
         }
     }
 }
 
+/// Bind group containing the instance buffer of a given shape type for the 3D pipeline.
 #[derive(Resource)]
 pub struct Shape3dBindGroup<T: ShapeData> {
     pub value: BindGroup,
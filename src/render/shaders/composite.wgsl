@@ -0,0 +1,118 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+// Contents of the view beneath the layer, read through a copy as the view can't be read while it is being drawn to
+@group(0) @binding(0) var backdrop: texture_2d<f32>;
+@group(0) @binding(1) var layer: texture_2d<f32>;
+@group(0) @binding(2) var layer_sampler: sampler;
+
+// Matches the order of ShapeBlendMode in render/composite.rs
+const NORMAL: u32 = 0u;
+const MULTIPLY: u32 = 1u;
+const SCREEN: u32 = 2u;
+const OVERLAY: u32 = 3u;
+const DARKEN: u32 = 4u;
+const LIGHTEN: u32 = 5u;
+const COLOR_DODGE: u32 = 6u;
+const COLOR_BURN: u32 = 7u;
+const HARD_LIGHT: u32 = 8u;
+const SOFT_LIGHT: u32 = 9u;
+const DIFFERENCE: u32 = 10u;
+const EXCLUSION: u32 = 11u;
+
+const BLEND_MODE: u32 = #{BLEND_MODE}u;
+
+// Blend modes are defined on gamma encoded colors, as in image editors, while textures are sampled as linear colors
+fn to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let c = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+fn to_linear(color: vec3<f32>) -> vec3<f32> {
+    return select(pow((color + 0.055) / 1.055, vec3<f32>(2.4)), color / 12.92, color <= vec3<f32>(0.04045));
+}
+
+fn multiply(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    return b * s;
+}
+
+fn screen(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    return b + s - b * s;
+}
+
+fn hard_light(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    return select(screen(b, 2.0 * s - 1.0), multiply(b, 2.0 * s), s <= vec3<f32>(0.5));
+}
+
+fn color_dodge(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    let dodged = min(vec3<f32>(1.0), b / max(1.0 - s, vec3<f32>(1e-5)));
+    return select(select(dodged, vec3<f32>(1.0), s >= vec3<f32>(1.0)), vec3<f32>(0.0), b <= vec3<f32>(0.0));
+}
+
+fn color_burn(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    let burned = 1.0 - min(vec3<f32>(1.0), (1.0 - b) / max(s, vec3<f32>(1e-5)));
+    return select(select(burned, vec3<f32>(0.0), s <= vec3<f32>(0.0)), vec3<f32>(1.0), b >= vec3<f32>(1.0));
+}
+
+fn soft_light(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    let d = select(sqrt(b), ((16.0 * b - 12.0) * b + 4.0) * b, b <= vec3<f32>(0.25));
+    return select(b + (2.0 * s - 1.0) * (d - b), b - (1.0 - 2.0 * s) * b * (1.0 - b), s <= vec3<f32>(0.5));
+}
+
+// Separable blend modes as defined by https://www.w3.org/TR/compositing-1/#blending
+fn blend(b: vec3<f32>, s: vec3<f32>) -> vec3<f32> {
+    switch BLEND_MODE {
+        case MULTIPLY: {
+            return multiply(b, s);
+        }
+        case SCREEN: {
+            return screen(b, s);
+        }
+        case OVERLAY: {
+            return hard_light(s, b);
+        }
+        case DARKEN: {
+            return min(b, s);
+        }
+        case LIGHTEN: {
+            return max(b, s);
+        }
+        case COLOR_DODGE: {
+            return color_dodge(b, s);
+        }
+        case COLOR_BURN: {
+            return color_burn(b, s);
+        }
+        case HARD_LIGHT: {
+            return hard_light(b, s);
+        }
+        case SOFT_LIGHT: {
+            return soft_light(b, s);
+        }
+        case DIFFERENCE: {
+            return abs(b - s);
+        }
+        case EXCLUSION: {
+            return b + s - 2.0 * b * s;
+        }
+        default: {
+            return s;
+        }
+    }
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let backdrop_color = textureLoad(backdrop, vec2<i32>(in.position.xy), 0);
+
+    // Shapes are blended onto the layer's transparent clear color, leaving it premultiplied by alpha
+    let layer_color = textureSample(layer, layer_sampler, in.uv);
+    let alpha = layer_color.a;
+    if alpha <= 0.0 {
+        return backdrop_color;
+    }
+
+    let b = to_srgb(backdrop_color.rgb);
+    let s = to_srgb(layer_color.rgb / alpha);
+    let color = mix(b, blend(b, s), alpha);
+    return vec4<f32>(to_linear(color), alpha + backdrop_color.a * (1.0 - alpha));
+}
@@ -25,6 +25,49 @@ struct View {
 @group(0) @binding(0)
 var<uniform> view: View;
 
+// Values of all dash patterns registered with ShapeDashPatterns
+struct DashPatterns {
+    values: array<vec4<f32>, 64>,
+};
+
+@group(0) @binding(4)
+var<uniform> dash_patterns: DashPatterns;
+
+fn dash_pattern_value(index: u32) -> f32 {
+    return dash_patterns.values[index / 4u][index % 4u];
+}
+
+// Signed distance from the nearest dash of a pattern of alternating dash and gap lengths, negative within a dash
+fn dash_pattern_distance(pos: f32, pattern: u32) -> f32 {
+    var offset = pattern >> 16u;
+    var len = pattern & 0xFFFFu;
+
+    var period = 0.0;
+    for (var i = 0u; i < len; i++) {
+        period += dash_pattern_value(offset + i);
+    }
+    var p = pos - floor(pos / period) * period;
+
+    // Find the segment containing our position, even segments are dashes and odd are gaps
+    var start = 0.0;
+    for (var i = 0u; i < len; i++) {
+        var end = start + dash_pattern_value(offset + i);
+        if p <= end || i == len - 1u {
+            var dist = min(p - start, end - p);
+            return select(dist, -dist, i % 2u == 0u);
+        }
+        start = end;
+    }
+    return 0.0;
+}
+
+#ifdef FRAGMENT
+// Mask of the dashes of a pattern at the given distance along a stroke, dashes are cut square without caps
+fn dash_pattern_mask(along: f32, pattern: u32) -> f32 {
+    return step_aa(dash_pattern_distance(along, pattern), 0.);
+}
+#endif
+
 #ifdef TEXTURED
 #ifdef FRAGMENT
 
@@ -50,7 +93,7 @@ fn pixels_per_unit(pos: vec3<f32>, dir: vec3<f32>) -> f32 {
 
 // Convert thickness from given type to pixels
 fn get_thickness_pixels(thickness: f32, thickness_type: u32, pixels_per_u: f32) -> f32 {
-    switch thickness_type {
+    switch thickness_type & 3u {
         default: { // WORLD
             return thickness * pixels_per_u;
         }
@@ -60,6 +103,11 @@ fn get_thickness_pixels(thickness: f32, thickness_type: u32, pixels_per_u: f32)
         case 2u: { // SCREEN
             return min(view.viewport.z, view.viewport.w) * (thickness / 100.);
         }
+        case 3u: { // WORLD_CLAMPED_PX
+            let min_px = f32((thickness_type >> 16u) & 255u);
+            let max_px = f32((thickness_type >> 24u) & 255u);
+            return clamp(thickness * pixels_per_u, min_px, max_px);
+        }
     }
 }
 
@@ -111,35 +159,68 @@ fn rotate_vec_a(v: vec2<f32>, a: f32) -> vec2<f32> {
 // Functions to extract info from flags, format should match the following field taken from render/mod.rs
 // bitfield! {
 //     pub struct Flags(u32);
-//     pub u32, from into ThicknessType, _, set_thickness_type: 1, 0;
-//     pub u32, from into Alignment, _, set_alignment: 2, 2;
-//     pub u32, _, set_hollow: 3, 3;
-//     pub u32, from into Cap, _, set_cap: 5, 4;
-//     pub u32, _, set_arc: 6, 6;
+//     pub u32, from into ThicknessType, _, set_thickness_type_bits: 1, 0;
+//     pub u32, from into Alignment, _, set_alignment: 3, 2;
+//     pub u32, _, set_hollow: 4, 4;
+//     pub u32, from into Cap, _, set_cap: 6, 5;
+//     pub u32, _, set_arc: 7, 7;
+//     pub u32, from into Join, _, set_join: 9, 8;
+//     pub u32, from into Cap, _, set_end_cap: 11, 10;
+//     pub u32, _, set_interior_edges: 14, 12;
+//     pub u32, _, set_smooth_corners: 15, 15;
+//     pub u32, _, set_thickness_min_px: 23, 16;
+//     pub u32, _, set_thickness_max_px: 31, 24;
 // }
 
+// Thickness type is in the lowest 2 bits, the pixel range of WorldClampedPx is kept in the upper 16 bits
 fn f_thickness_type(flags: u32) -> u32 {
-    return flags & 3u;
+    // Fixed size shapes are measured in pixels, so world thicknesses are too
+    if f_alignment(flags) == 2u && (flags & 3u) != 2u {
+        return 1u;
+    }
+    return (flags & 3u) | (flags & 0xFFFF0000u);
 }
 
 fn f_alignment(flags: u32) -> u32 {
-    return (flags >> 2u) & 1u;
+    return (flags >> 2u) & 3u;
 }
 
 fn f_hollow(flags: u32) -> u32 {
-    return (flags >> 3u) & 1u;
+    return (flags >> 4u) & 1u;
 }
 
 fn f_cap(flags: u32) -> u32 {
-    return (flags >> 4u) & 3u;
+    return (flags >> 5u) & 3u;
 }
 
 fn f_arc(flags: u32) -> u32 {
-    return (flags >> 6u) & 1u;
+    return (flags >> 7u) & 1u;
+}
+
+fn f_join(flags: u32) -> u32 {
+    return (flags >> 8u) & 3u;
+}
+
+fn f_end_cap(flags: u32) -> u32 {
+    return (flags >> 10u) & 3u;
+}
+
+fn f_interior_edges(flags: u32) -> u32 {
+    return (flags >> 12u) & 7u;
+}
+
+fn f_smooth_corners(flags: u32) -> u32 {
+    return (flags >> 15u) & 1u;
 }
 
 #ifdef LOCAL_AA
+#ifdef EDGE_SOFTNESS
+// Width in pixels over which edges fade out
+const EDGE_SOFTNESS_PX: f32 = f32(#{EDGE_SOFTNESS}u);
+const AA_PADDING: f32 = 2.0 + EDGE_SOFTNESS_PX;
+#else
 const AA_PADDING: f32 = 2.0;
+#endif
 
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
 #ifdef FRAGMENT
@@ -153,16 +234,52 @@ fn partial_derivative(v: f32) -> f32 {
 fn step_aa(edge: f32, x: f32) -> f32 {
     var value = x - edge;
     var pd = partial_derivative(value);
-    return 1.0 - saturate(-value / pd);
+#ifdef SDF_VIEW
+    track_sdf(value / pd);
+#endif
+    return edge_coverage(-value / pd);
 }
 
 fn step_aa_pd(edge: f32, x: f32, in: f32) -> f32 {
     var value = x - edge;
     var pd = partial_derivative(in);
-    return 1.0 - saturate(-value / pd);
+#ifdef SDF_VIEW
+    track_sdf(value / pd);
+#endif
+    return edge_coverage(-value / pd);
+}
+
+// Coverage of a fragment the given number of pixels outside of an edge
+fn edge_coverage(outside: f32) -> f32 {
+#ifdef EDGE_SOFTNESS
+    var coverage = 1.0 - saturate(outside / EDGE_SOFTNESS_PX);
+#ifdef FALLOFF_SMOOTHSTEP
+    coverage = coverage * coverage * (3.0 - 2.0 * coverage);
+#endif
+#ifdef FALLOFF_EXPONENTIAL
+    // Decay exponentially with distance, offset so that we still reach zero at the end of the fade
+    coverage = (exp(-4.0 * (1.0 - coverage)) - exp(-4.0)) / (1.0 - exp(-4.0));
+#endif
+    return coverage;
+#else
+    return 1.0 - saturate(outside);
+#endif
+}
+
+#ifdef SDF_VIEW
+// Spacing in pixels between contour lines and length in pixels of the ramp when visualizing distances
+const SDF_CONTOUR_SPACING: f32 = 8.0;
+const SDF_RAMP_LENGTH: f32 = 32.0;
+
+// Approximate signed distance in pixels to the nearest edge tested by step_aa, positive within the shape
+var<private> sdf_distance: f32 = 1e9;
+
+fn track_sdf(distance: f32) {
+    sdf_distance = min(sdf_distance, distance);
 }
 #endif
 #endif
+#endif
 
 #ifdef DISABLE_LOCAL_AA
 const AA_PADDING: f32 = 0.0;
@@ -181,6 +298,24 @@ fn get_scale(matrix: mat4x4<f32>) -> vec2<f32> {
     return vec2<f32>(length(matrix[0].xyz), length(matrix[1].xyz));
 }
 
+// Scale the transform of shapes with Alignment::BillboardFixedSize such that one unit covers one pixel at their origin
+fn get_fixed_size_matrix(matrix: mat4x4<f32>, flags: u32) -> mat4x4<f32> {
+    if f_alignment(flags) != 2u {
+        return matrix;
+    }
+
+    var up = normalize((view.view * vec4<f32>(0.0, 1.0, 0.0, 0.0)).xyz);
+    var scale = 1.0 / abs(pixels_per_unit(matrix[3].xyz, up));
+
+    // The bottom row of the matrix holds the shape's depth slice so is left as is
+    return mat4x4<f32>(
+        vec4<f32>(matrix[0].xyz * scale, matrix[0].w),
+        vec4<f32>(matrix[1].xyz * scale, matrix[1].w),
+        vec4<f32>(matrix[2].xyz * scale, matrix[2].w),
+        matrix[3]
+    );
+}
+
 // Take the y basis directly from the matrix and pass along to get_basis_vectors_from_up
 fn get_basis_vectors(matrix: mat4x4<f32>, origin: vec3<f32>, flags: u32) -> mat3x3<f32> {
     return get_basis_vectors_from_up(matrix, origin, normalize(matrix[1].xyz), f_alignment(flags));
@@ -197,13 +332,19 @@ fn get_basis_vectors_from_up(matrix: mat4x4<f32>, origin: vec3<f32>, up: vec3<f3
         default: {
             z_basis = normalize(matrix[2].xyz);
         }
-        // Alignment::Billboard
-        case 1u: {
+        // Alignment::Billboard and Alignment::BillboardFixedSize
+        case 1u, 2u: {
             y_basis = normalize((view.view * vec4<f32>(0.0, 1.0, 0.0, 0.0)).xyz);
             z_basis = p_to_camera_dir(origin);
         }
+        // Alignment::BillboardY, facing the camera as closely as possible while rotating around the y basis
+        case 3u: {
+            var to_camera = p_to_camera_dir(origin);
+            var flat = to_camera - y_basis * dot(to_camera, y_basis);
+            z_basis = select(normalize(matrix[2].xyz), normalize(flat), length(flat) > 0.0001);
+        }
         // Alignment::Billboard for lines
-        case 2u: {
+        case 4u: {
             z_basis = p_to_camera_dir(origin);
         }
     }
@@ -222,12 +363,28 @@ fn get_basis_vectors_from_up(matrix: mat4x4<f32>, origin: vec3<f32>, up: vec3<f3
     );
 }
 
+// Narrow a vertex of a quad from -1 to 1 to the depth slice of the shape, see ShapeConfig::depth_slices
+// The slice is kept in the bottom row of the shape's matrix, spanning from the first to the second column's w
+// along the axis in the third column's w, 1 for x and 2 for y, the whole quad is drawn when the axis is 0
+fn get_slice_vertex(matrix: mat4x4<f32>, vertex: vec2<f32>) -> vec2<f32> {
+    var axis = u32(matrix[2].w);
+    var out = vertex;
+    if axis == 1u {
+        out.x = mix(matrix[0].w, matrix[1].w, (vertex.x + 1.0) / 2.0);
+    } else if axis == 2u {
+        out.y = mix(matrix[0].w, matrix[1].w, (vertex.y + 1.0) / 2.0);
+    }
+    return out;
+}
+
 struct VertexData {
     thickness_data: ThicknessData,
     clip_pos: vec4<f32>,
     local_pos: vec2<f32>,
     uv_ratio: vec2<f32>,
-    scale: vec2<f32>
+    scale: vec2<f32>,
+    // Position of the vertex on the quad from -1 to 1 without padding, narrowed to the shape's depth slice
+    vertex: vec2<f32>,
 };
 
 // Calculate the full set of vertex data shared between each shape type
@@ -246,12 +403,18 @@ fn get_vertex_data(matrix: mat4x4<f32>, vertex: vec2<f32>, thickness: f32, flags
     out.scale = get_scale(matrix);
     out.local_pos = vertex.xy * out.scale;
 
+    // Shapes split into depth slices only cover their slice of the quad
+    var corner = sign(vertex.xy);
+    out.vertex = get_slice_vertex(matrix, corner);
+    var sliced_pos = out.vertex * abs(out.local_pos);
+
     // Convert our padding into world space and match direction of our vertex
+    // Edges between slices aren't padded so that neighbouring slices don't overlap
     var aa_padding_u = AA_PADDING / out.thickness_data.pixels_per_u;
-    var aa_padding = sign(vertex.xy) * aa_padding_u;
+    var aa_padding = select(vec2<f32>(0.0), corner, abs(out.vertex) > vec2<f32>(0.9999)) * aa_padding_u;
 
     // Pad our position and determine the ratio by which to scale uv such that uvs ignore padding
-    var padded_pos = out.local_pos + aa_padding;
+    var padded_pos = sliced_pos + aa_padding;
     out.uv_ratio = padded_pos / out.local_pos;
 
     // Rotate the position based on our basis vectors and add the world position offset
@@ -262,6 +425,72 @@ fn get_vertex_data(matrix: mat4x4<f32>, vertex: vec2<f32>, thickness: f32, flags
     return out;
 }
 
+// Interpolated uvs lose precision far from the center of shapes many times larger than the view, making their edges wobble
+// Shapes with more than this many pixels per unit of uv space near the camera are evaluated relative to a local frame instead
+const LOCAL_FRAME_PIXELS: f32 = 65536.0;
+
+// A point in the plane of a shape near the camera and the view space vectors mapping offsets from it into the shape's uv space
+struct LocalFrame {
+    // Position of the anchor in view space, w is 1 when the frame is in use
+    anchor_view: vec4<f32>,
+    // View space vectors giving the uv offset from the anchor along each axis, with the uv of the anchor in w
+    uv_x: vec4<f32>,
+    uv_y: vec4<f32>,
+};
+
+// Calculate the local frame of a shape whose uv space is `uv_unit` world units long along each axis
+fn get_local_frame(matrix: mat4x4<f32>, uv_unit: vec2<f32>, flags: u32) -> LocalFrame {
+    var out: LocalFrame;
+
+    var origin = (matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz;
+    var basis = get_basis_vectors(matrix, origin, flags);
+
+    // Anchor the frame at the point of the shape's plane nearest the camera
+    var to_camera = view.world_position - origin;
+    var anchor_uv = vec2<f32>(dot(to_camera, basis[0]), dot(to_camera, basis[1])) / uv_unit;
+    var anchor = origin + basis[0] * anchor_uv.x * uv_unit.x + basis[1] * anchor_uv.y * uv_unit.y;
+    var anchor_view = (view.inverse_view * vec4<f32>(anchor, 1.0)).xyz;
+
+    // Skip shapes that are small enough on screen, or that the camera is within the plane of
+    var pixels = max(uv_unit.x, uv_unit.y) * abs(pixels_per_unit(anchor, basis[1]));
+    if pixels < LOCAL_FRAME_PIXELS || (view.projection * vec4<f32>(anchor_view, 1.0)).w <= 0.0 {
+        return out;
+    }
+
+    out.anchor_view = vec4<f32>(anchor_view, 1.0);
+    out.uv_x = vec4<f32>((view.inverse_view * vec4<f32>(basis[0], 0.0)).xyz / uv_unit.x, anchor_uv.x);
+    out.uv_y = vec4<f32>((view.inverse_view * vec4<f32>(basis[1], 0.0)).xyz / uv_unit.y, anchor_uv.y);
+    return out;
+}
+
+// Rotate the uv space of a local frame in the same way as rotate_vec_a
+fn rotate_local_frame(frame: LocalFrame, a: f32) -> LocalFrame {
+    var out = frame;
+    var point = vec2<f32>(cos(a), sin(a));
+    out.uv_x = point.x * frame.uv_x - point.y * frame.uv_y;
+    out.uv_y = point.y * frame.uv_x + point.x * frame.uv_y;
+    return out;
+}
+
+// Offset in uv space from the anchor of a local frame to the point of the shape's plane under the given fragment
+fn local_frame_offset(frame: LocalFrame, frag_coord: vec2<f32>) -> vec2<f32> {
+    var anchor = frame.anchor_view.xyz;
+    var normal = cross(frame.uv_x.xyz, frame.uv_y.xyz);
+    var ndc = (frag_coord - view.viewport.xy) / view.viewport.zw * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0);
+
+    // Cast a ray through the fragment starting at the depth of the anchor, so the intersection stays small and precise
+    var anchor_clip = view.projection * vec4<f32>(anchor, 1.0);
+    var depth = anchor_clip.z / anchor_clip.w;
+    var start = view.inverse_projection * vec4<f32>(ndc, depth, 1.0);
+    var end = view.inverse_projection * vec4<f32>(ndc, select(depth * 0.5, depth + 0.5, depth < 0.5), 1.0);
+    var ray_start = start.xyz / start.w;
+    var ray_dir = end.xyz / end.w - ray_start;
+
+    var t = dot(anchor - ray_start, normal) / dot(ray_dir, normal);
+    var offset = (ray_start - anchor) + ray_dir * t;
+    return vec2<f32>(dot(offset, frame.uv_x.xyz), dot(offset, frame.uv_y.xyz));
+}
+
 fn get_texture_uv(vertex: vec2<f32>) -> vec2<f32> {
     return (vertex + 1.0) / 2.0;
 }
@@ -269,14 +498,25 @@ fn get_texture_uv(vertex: vec2<f32>) -> vec2<f32> {
 #ifdef FRAGMENT
 // Transform our color output to respect the alpha mode set for our shape and combine with our texture if any
 fn color_output(in: vec4<f32>) -> vec4<f32> {
+    var alpha = in.a;
+#ifdef SDF_CONTOURS
+    // Draw a one pixel line every SDF_CONTOUR_SPACING pixels in from the edge
+    var contour = abs(fract(sdf_distance / SDF_CONTOUR_SPACING + 0.5) - 0.5) * SDF_CONTOUR_SPACING;
+    alpha *= 1.0 - saturate(contour - 0.5);
+#endif
+#ifdef SDF_RAMP
+    // Fade from the edge towards the interior of the shape
+    alpha *= 1.0 - saturate(sdf_distance / SDF_RAMP_LENGTH);
+#endif
+
 #ifdef BLEND_MULTIPLY
-    var color = vec4<f32>(in.rgb * in.a, in.a);
+    var color = vec4<f32>(in.rgb * alpha, alpha);
 #endif
 #ifdef BLEND_ADD
-    var color = vec4<f32>(in.rgb * in.a, 0.0);
+    var color = vec4<f32>(in.rgb * alpha, 0.0);
 #endif
 #ifdef BLEND_ALPHA
-    var color = in;
+    var color = vec4<f32>(in.rgb, alpha);
 #endif
 
     return color;
@@ -0,0 +1,244 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+// Number of line segments the curve is flattened into when calculating distance
+const SEGMENTS: u32 = 32u;
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) p_0: vec2<f32>,
+    @location(8) p_1: vec2<f32>,
+    @location(9) p_2: vec2<f32>,
+    @location(10) p_3: vec2<f32>,
+    @location(11) dash_pattern: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) radius: f32,
+    @location(3) cap: u32,
+    @location(4) p_01: vec4<f32>,
+    @location(5) p_23: vec4<f32>,
+#ifdef TEXTURED
+    @location(6) texture_uv: vec2<f32>,
+#endif
+    @location(7) @interpolate(flat) dash_pattern: u32,
+    // Scale from local space to our world space positions
+    @location(8) scale: vec2<f32>,
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Transform the origin into world space
+    var origin = (matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz;
+    var basis_vectors = core::get_basis_vectors(matrix, origin, shape.flags);
+
+    // Calculate thickness data
+    var thickness_type = core::f_thickness_type(shape.flags);
+    var thickness_data = core::get_thickness_data(shape.thickness, thickness_type, origin, basis_vectors[1]);
+
+    let scale = core::get_scale(matrix);
+
+    // If our thickness in pixels is less than 1, clamp to 1 and reduce the alpha instead
+    var out_color = shape.color;
+    if thickness_data.thickness_p * max(scale.x, scale.y) < 1.0 {
+        out_color.a = out_color.a * thickness_data.thickness_p * max(scale.x, scale.y);
+        thickness_data.thickness_p = 1.;
+    }
+
+    // Calculate the radius of our stroke in world units
+    var radius = thickness_data.thickness_p / thickness_data.pixels_per_u / 2.0;
+
+    // Scale our control points into world units
+    let p_0 = shape.p_0 * scale;
+    let p_1 = shape.p_1 * scale;
+    let p_2 = shape.p_2 * scale;
+    let p_3 = shape.p_3 * scale;
+
+    // A bezier curve always lies within the convex hull of it's control points so use their bounds
+    let min_pos = min(min(p_0, p_1), min(p_2, p_3));
+    let max_pos = max(max(p_0, p_1), max(p_2, p_3));
+
+    // Pad our bounds by the stroke, square caps extend diagonally, and our anti-aliasing padding
+    var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
+    let center = (min_pos + max_pos) / 2.0;
+    let half_size = (max_pos - min_pos) / 2.0 + radius * 1.5 + aa_padding_u;
+    let local_pos = center + vertex.xy * half_size;
+
+    // Rotate the position based on our basis vectors and add the world position offset
+    var world_pos = origin + (local_pos.x * basis_vectors[0]) - (local_pos.y * basis_vectors[1]);
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+    out.pos = local_pos;
+    out.radius = radius;
+    out.cap = core::f_cap(shape.flags);
+    out.p_01 = vec4<f32>(p_0, p_1);
+    out.p_23 = vec4<f32>(p_2, p_3);
+    out.dash_pattern = shape.dash_pattern;
+    out.scale = scale;
+
+    out.color = out_color;
+#ifdef TEXTURED
+    // Our y axis is flipped relative to the quad so flip our texture to match
+    out.texture_uv = core::get_texture_uv(vertex.xy * vec2<f32>(1.0, -1.0));
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) radius: f32,
+    @location(3) cap: u32,
+    @location(4) p_01: vec4<f32>,
+    @location(5) p_23: vec4<f32>,
+#ifdef TEXTURED
+    @location(6) texture_uv: vec2<f32>,
+#endif
+    @location(7) @interpolate(flat) dash_pattern: u32,
+    // Scale from local space to our world space positions
+    @location(8) scale: vec2<f32>,
+};
+
+// Evaluate a cubic bezier curve at t
+fn bezier(p_0: vec2<f32>, p_1: vec2<f32>, p_2: vec2<f32>, p_3: vec2<f32>, t: f32) -> vec2<f32> {
+    let u = 1.0 - t;
+    return u * u * u * p_0 + 3.0 * u * u * t * p_1 + 3.0 * u * t * t * p_2 + t * t * t * p_3;
+}
+
+// Calculate the distance from an end of the curve given the outwards tangent at that end
+// Only valid when the end point is the closest point on the curve
+fn capSDF(p: vec2<f32>, end: vec2<f32>, tangent: vec2<f32>, radius: f32, cap: u32) -> f32 {
+    let to_p = p - end;
+    let along = dot(to_p, tangent);
+    let across = abs(tangent.x * to_p.y - tangent.y * to_p.x);
+
+    // Square caps extend the curve by our radius, no caps cut it off at the end point
+    var extension = 0.0;
+    if cap == 1u {
+        extension = radius;
+    }
+
+    let to_corner = vec2<f32>(along - extension, across - radius);
+    return length(max(to_corner, vec2<f32>(0.))) + min(0., max(to_corner.x, to_corner.y));
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    let p_0 = f.p_01.xy;
+    let p_1 = f.p_01.zw;
+    let p_2 = f.p_23.xy;
+    let p_3 = f.p_23.zw;
+
+    // Flatten our curve into line segments and find the closest point on any of them
+    var closest = 1e10;
+    var closest_t = 0.0;
+    var prev = p_0;
+
+    // Distance along the curve in local space to the closest point and to the start of the current segment, for placing dashes
+    var closest_along = 0.0;
+    var closest_scale = 1.0;
+    var segment_start = 0.0;
+    for (var i = 1u; i <= SEGMENTS; i++) {
+        let next = bezier(p_0, p_1, p_2, p_3, f32(i) / f32(SEGMENTS));
+        let segment = next - prev;
+        let to_p = f.pos - prev;
+        let h = clamp(dot(to_p, segment) / max(dot(segment, segment), 0.000001), 0.0, 1.0);
+        let dist = length(to_p - segment * h);
+        let local_length = length(segment / f.scale);
+        if dist < closest {
+            closest = dist;
+            closest_t = (f32(i - 1u) + h) / f32(SEGMENTS);
+            closest_along = segment_start + h * local_length;
+            closest_scale = length(segment) / max(local_length, 0.000001);
+        }
+        segment_start += local_length;
+        prev = next;
+    }
+
+    // Distance to our stroke, this is equivalent to round caps
+    var dist = closest - f.radius;
+
+    // Replace the rounded ends with the requested cap type
+    if f.cap != 2u {
+        if closest_t <= 0.0 {
+            let tangent = normalize(p_0 - bezier(p_0, p_1, p_2, p_3, 1.0 / f32(SEGMENTS)));
+            dist = capSDF(f.pos, p_0, tangent, f.radius, f.cap);
+        } else if closest_t >= 1.0 {
+            let tangent = normalize(p_3 - bezier(p_0, p_1, p_2, p_3, 1.0 - 1.0 / f32(SEGMENTS)));
+            dist = capSDF(f.pos, p_3, tangent, f.radius, f.cap);
+        }
+    }
+
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a * core::step_aa(dist, 0.);
+
+    // Mask out the gaps between dashes along the curve
+    if f.dash_pattern > 0u {
+        let along = core::dash_pattern_distance(closest_along, f.dash_pattern) * closest_scale;
+        in_shape = min(in_shape, dash_mask(along, closest, f.radius, f.cap));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+
+// Each dash is treated as a short line with the same caps as the ends of the curve
+fn dash_mask(along: f32, across: f32, radius: f32, cap: u32) -> f32 {
+    if cap == 2u {
+        return core::step_aa(length(vec2<f32>(across, max(0., along))), radius);
+    }
+
+    // Square caps extend each dash by our radius
+    var cap_length = select(0.0, radius, cap == 1u);
+    return core::step_aa(along, cap_length);
+}
+#endif
\ No newline at end of file
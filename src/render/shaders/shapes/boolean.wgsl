@@ -0,0 +1,206 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) offset_a: vec2<f32>,
+    @location(8) params_a: vec4<f32>,
+    @location(9) params_b: vec4<f32>,
+    @location(10) offset_b: vec2<f32>,
+    @location(11) rotations: vec2<f32>,
+    @location(12) ops: u32,
+    @location(13) smoothness: f32,
+    @location(14) extent: f32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) thickness: f32,
+
+    @location(3) params_a: vec4<f32>,
+    @location(4) params_b: vec4<f32>,
+    @location(5) offset_a: vec2<f32>,
+    @location(6) offset_b: vec2<f32>,
+    @location(7) rotations: vec2<f32>,
+    @location(8) @interpolate(flat) ops: u32,
+    @location(9) smoothness: f32,
+#ifdef TEXTURED
+    @location(10) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Our quad covers both operands, sized on the CPU to the bounds of the combined shape
+    var vertex_data = core::get_vertex_data(matrix, vertex.xy * shape.extent, shape.thickness, shape.flags);
+    out.clip_position = vertex_data.clip_pos;
+
+    // Operands are placed in local space, our y axis is flipped relative to the quad so flip it back
+    out.pos = vertex.xy * vec2<f32>(1.0, -1.0) * shape.extent * vertex_data.uv_ratio;
+
+    // Filled shapes have no inner edge
+    out.thickness = 1e10;
+    if core::f_hollow(shape.flags) > 0u {
+        out.thickness = core::calculate_thickness(vertex_data.thickness_data, 1.0, shape.flags);
+    }
+
+    out.params_a = shape.params_a;
+    out.params_b = shape.params_b;
+    out.offset_a = shape.offset_a;
+    out.offset_b = shape.offset_b;
+    out.rotations = shape.rotations;
+    out.ops = shape.ops;
+    out.smoothness = shape.smoothness;
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) thickness: f32,
+
+    @location(3) params_a: vec4<f32>,
+    @location(4) params_b: vec4<f32>,
+    @location(5) offset_a: vec2<f32>,
+    @location(6) offset_b: vec2<f32>,
+    @location(7) rotations: vec2<f32>,
+    @location(8) @interpolate(flat) ops: u32,
+    @location(9) smoothness: f32,
+#ifdef TEXTURED
+    @location(10) texture_uv: vec2<f32>,
+#endif
+};
+
+// Distance to a circle with the given radius
+fn circleSDF(position: vec2<f32>, radius: f32) -> f32 {
+    return length(position) - radius;
+}
+
+// Distance to a rectangle with the given side lengths whose corners are rounded by the given radius
+fn rectSDF(position: vec2<f32>, size: vec2<f32>, roundness: f32) -> f32 {
+    var to_corner = abs(position) - size / 2.0 + roundness;
+    return length(max(to_corner, vec2<f32>(0.))) + min(0., max(to_corner.x, to_corner.y)) - roundness;
+}
+
+// Distance to a regular polygon with a vertex pointing up whose corners are rounded by the given radius
+fn ngonSDF(position: vec2<f32>, sides: f32, radius: f32, roundness: f32) -> f32 {
+    var half_angle = PI / sides;
+    var corner = vec2<f32>(cos(half_angle), sin(half_angle)) * (radius - roundness);
+
+    // Fold our point into the half of a single side that starts at the nearest vertex
+    var angle = atan2(position.x, position.y);
+    angle = angle - 2.0 * half_angle * floor(angle / (2.0 * half_angle)) - half_angle;
+    var pos = length(position) * vec2<f32>(cos(angle), abs(sin(angle))) - corner;
+    pos.y += clamp(-pos.y, 0.0, corner.y);
+
+    return length(pos) * sign(pos.x) - roundness;
+}
+
+// Distance to the primitive of the given kind, see BooleanPrimitive in shapes/boolean.rs
+fn primitiveSDF(position: vec2<f32>, kind: u32, params: vec4<f32>) -> f32 {
+    switch kind {
+        case 1u: {
+            return rectSDF(position, params.xy, params.z);
+        }
+        case 2u: {
+            return ngonSDF(position, params.x, params.y, params.z);
+        }
+        default: {
+            return circleSDF(position, params.x);
+        }
+    }
+}
+
+// Polynomial smooth minimum, blending the two distances within the given smoothness of each other
+fn smoothMin(a: f32, b: f32, smoothness: f32) -> f32 {
+    if smoothness <= 0.0 {
+        return min(a, b);
+    }
+    var h = clamp(0.5 + 0.5 * (b - a) / smoothness, 0.0, 1.0);
+    return mix(b, a, h) - smoothness * h * (1.0 - h);
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Move our position into the space of each operand
+    var pos_a = core::rotate_vec_a(f.pos - f.offset_a, -f.rotations.x);
+    var pos_b = core::rotate_vec_a(f.pos - f.offset_b, -f.rotations.y);
+    var dist_a = primitiveSDF(pos_a, f.ops & 3u, f.params_a);
+    var dist_b = primitiveSDF(pos_b, (f.ops >> 2u) & 3u, f.params_b);
+
+    // Intersection and subtraction are unions of the inverted distances
+    var dist: f32;
+    switch (f.ops >> 4u) & 3u {
+        case 1u: {
+            dist = -smoothMin(-dist_a, -dist_b, f.smoothness);
+        }
+        case 2u: {
+            dist = -smoothMin(-dist_a, dist_b, f.smoothness);
+        }
+        default: {
+            dist = smoothMin(dist_a, dist_b, f.smoothness);
+        }
+    }
+
+    // Cut off points outside the shape or within the hollow area
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
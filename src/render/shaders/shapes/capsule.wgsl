@@ -0,0 +1,182 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) start: vec3<f32>,
+    @location(8) end: vec3<f32>,
+    @location(9) radius: f32,
+    @location(10) dash_pattern: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) half_length: f32,
+    @location(3) radius: f32,
+    @location(4) thickness: f32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+    @location(6) @interpolate(flat) dash_pattern: u32,
+    // Scale from local space to our world space positions
+    @location(7) scale: f32,
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Get our start and end in world space
+    var world_start = (matrix * vec4<f32>(shape.start, 1.0)).xyz;
+    var world_end = (matrix * vec4<f32>(shape.end, 1.0)).xyz;
+    var world_length = length(world_end - world_start);
+
+    // The y basis is the normalized vector along the capsule, fall back to the transform's y axis for circles
+    var y_basis = select(normalize(matrix[1].xyz), (world_start - world_end) / world_length, world_length > 0.0);
+
+    // Choose which point we will work in reference to based on our y position
+    var origin = select(world_end, world_start, vertex.y < 0.0);
+
+    // Calculate the remainder of our basis vectors, billboarded capsules rotate around their axis like lines
+    var alignment = select(0u, 4u, core::f_alignment(shape.flags) > 0u);
+    var basis_vectors = core::get_basis_vectors_from_up(matrix, origin, y_basis, alignment);
+
+    // Calculate thickness data
+    var thickness_type = core::f_thickness_type(shape.flags);
+    var thickness_data = core::get_thickness_data(shape.thickness, thickness_type, origin, basis_vectors[1]);
+
+    // Our radius is in the local space of the transform so scale it to world units
+    let scale = core::get_scale(matrix);
+    var radius = shape.radius * scale.x;
+
+    // Extend our quad by our radius at each end and by our padding in each direction
+    var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
+    var local_offset = vertex.xy * (radius + aa_padding_u);
+
+    // Determine final world position by offsetting by the origin we chose and rotating by our basis vectors
+    var world_pos = origin + local_offset.x * basis_vectors[0] + local_offset.y * basis_vectors[1];
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+
+    // Position relative to the center of the capsule in world units
+    out.pos = vec2<f32>(local_offset.x, local_offset.y + sign(vertex.y) * world_length / 2.0);
+    out.half_length = world_length / 2.0;
+    out.radius = radius;
+    out.thickness = core::calculate_thickness(thickness_data, 1.0, shape.flags);
+
+    // Our uv space is in world units so filled capsules need a thickness that covers the entire shape
+    if core::f_hollow(shape.flags) == 0u {
+        out.thickness = radius;
+    }
+
+    out.dash_pattern = select(0u, shape.dash_pattern, core::f_hollow(shape.flags) == 1u);
+    out.scale = scale.x;
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex.xy);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) half_length: f32,
+    @location(3) radius: f32,
+    @location(4) thickness: f32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+    @location(6) @interpolate(flat) dash_pattern: u32,
+    // Scale from local space to our world space positions
+    @location(7) scale: f32,
+};
+
+// Distance around the outline of a capsule with the given half length and radius to the point on the outline
+//  nearest to the given position, starting from the bottom of it's right side
+fn perimeterDistance(pos: vec2<f32>, half_length: f32, radius: f32) -> f32 {
+    var side = 2.0 * half_length;
+    var end = PI * radius;
+    if pos.y > half_length {
+        return side + radius * atan2(pos.y - half_length, pos.x);
+    }
+    if pos.y < -half_length {
+        return 2.0 * side + end + radius * atan2(-pos.y - half_length, -pos.x);
+    }
+    if pos.x >= 0.0 {
+        return pos.y + half_length;
+    }
+    return side + end + half_length - pos.y;
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Capsules are symmetrical across both axis so mirror our point onto the positive x and y axis,
+    // then find the shortest vector to the line segment at the core of the capsule
+    var pos = abs(f.pos);
+    pos.y = max(pos.y - f.half_length, 0.0);
+    var dist = length(pos) - f.radius;
+
+    // Cut off points outside the shape or within the hollow area
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+
+    // Mask out the gaps between dashes, measured in local space around the middle of the outline
+    if f.dash_pattern > 0u {
+        var along = perimeterDistance(f.pos, f.half_length, f.radius - f.thickness / 2.0) / f.scale;
+        in_shape = min(in_shape, core::dash_pattern_mask(along, f.dash_pattern));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
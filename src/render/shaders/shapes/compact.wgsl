@@ -0,0 +1,88 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::view
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) position: vec2<f32>,
+    @location(1) half_size: vec2<f32>,
+    @location(2) rotation: f32,
+    @location(3) z: f32,
+    // sRGB color with 8 bits per channel
+    @location(4) color: u32,
+    // 0 for circles, 1 for rectangles
+    @location(5) kind: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) half_size: vec2<f32>,
+    @location(3) @interpolate(flat) kind: u32,
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Pad our quad so that anti-aliasing at the edges isn't clipped
+    var center = vec3<f32>(shape.position, shape.z);
+    var padding = core::AA_PADDING / core::pixels_per_unit(center, vec3<f32>(1.0, 0.0, 0.0));
+    out.pos = vertex.xy * (shape.half_size + padding);
+
+    // Compact shapes lie in the xy plane of their transform, there is no alignment to the camera
+    var world_pos = center + vec3<f32>(core::rotate_vec_a(out.pos, shape.rotation), 0.0);
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+
+    var color = unpack4x8unorm(shape.color);
+    out.color = vec4<f32>(pow(color.rgb, vec3<f32>(2.2)), color.a);
+    out.half_size = shape.half_size;
+    out.kind = shape.kind;
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) half_size: vec2<f32>,
+    @location(3) @interpolate(flat) kind: u32,
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    var dist: f32;
+    if f.kind == 1u {
+        var to_edge = abs(f.pos) - f.half_size;
+        dist = length(max(to_edge, vec2<f32>(0.))) + min(0., max(to_edge.x, to_edge.y));
+    } else {
+        dist = length(f.pos) - f.half_size.x;
+    }
+
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a * core::step_aa(dist, 0.);
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
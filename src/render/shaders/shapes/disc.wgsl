@@ -20,6 +20,12 @@ struct Shape {
     @location(7) radius: f32,
     @location(8) start_angle: f32, 
     @location(9) end_angle: f32,
+
+    @location(10) dash_pattern: u32,
+
+    @location(11) gradient_color: vec4<f32>,
+    @location(12) gradient: u32,
+    @location(13) hue_turns: f32,
 };
 
 #ifdef PER_OBJECT_BUFFER_BATCH_SIZE
@@ -37,8 +43,17 @@ struct VertexOutput {
     @location(4) delta: f32,
     @location(5) cap: u32,
 #ifdef TEXTURED
-    @location(6) texture_uv: vec2<f32>,
+    @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) gradient_color: vec4<f32>,
+    @location(9) @interpolate(flat) gradient: u32,
+    // Additional turns of hue and the offset of the start of the gradient in turns
+    @location(10) gradient_params: vec2<f32>,
+    @location(11) @interpolate(flat) dash_pattern: u32,
+    // Radius in local space, the length of one unit of our uv space
+    @location(12) radius: f32,
+    // Index of the shape plus one for discs large enough to be evaluated in a local frame, see core::LocalFrame
+    @location(13) @interpolate(flat) frame_index: u32,
 };
 
 @vertex
@@ -49,12 +64,12 @@ fn vertex(v: Vertex) -> VertexOutput {
     let vertex = v.pos;
     let shape = shapes[v.index];
 
-    let matrix = mat4x4<f32>(
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
         shape.matrix_0,
         shape.matrix_1,
         shape.matrix_2,
         shape.matrix_3
-    );
+    ), shape.flags);
 
     var vertex_data = core::get_vertex_data(matrix, vertex.xy * shape.radius, shape.thickness, shape.flags);
 
@@ -76,19 +91,31 @@ fn vertex(v: Vertex) -> VertexOutput {
 
         // Rotate our uv space such that y up is towards the center of our arc
         out.uv = core::rotate_vec_a(out.uv, -out.angle);
+        out.gradient_params = vec2<f32>(shape.hue_turns, 0.0);
     } else {
         out.angle = 0.0;
         out.delta = PI;
+
+        // Full circles sweep their gradient from the top, a quarter turn from where their angles start
+        out.gradient_params = vec2<f32>(shape.hue_turns, 0.25);
     }
 
+    out.gradient_color = shape.gradient_color;
+    out.gradient = shape.gradient;
+
+    out.dash_pattern = select(0u, shape.dash_pattern, core::f_hollow(shape.flags) == 1u);
+    out.radius = shape.radius;
+    var frame = core::get_local_frame(matrix, vertex_data.scale * shape.radius, shape.flags);
+    out.frame_index = select(0u, v.index + 1u, frame.anchor_view.w > 0.0);
     out.color = shape.color;
 #ifdef TEXTURED
-    out.texture_uv = core::get_texture_uv(vertex.xy);
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex);
 #endif
     return out;
 }
 
 struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) thickness: f32,
@@ -96,27 +123,53 @@ struct FragmentInput {
     @location(4) delta: f32,
     @location(5) cap: u32,
 #ifdef TEXTURED
-    @location(6) texture_uv: vec2<f32>,
+    @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) gradient_color: vec4<f32>,
+    @location(9) @interpolate(flat) gradient: u32,
+    // Additional turns of hue and the offset of the start of the gradient in turns
+    @location(10) gradient_params: vec2<f32>,
+    @location(11) @interpolate(flat) dash_pattern: u32,
+    // Radius in local space, the length of one unit of our uv space
+    @location(12) radius: f32,
+    // Index of the shape plus one for discs large enough to be evaluated in a local frame, see core::LocalFrame
+    @location(13) @interpolate(flat) frame_index: u32,
 };
 
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
 #ifdef FRAGMENT
 @fragment
-fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    var f = in;
+    var dist = length(f.uv) - 1.;
+
+    // Very large discs find our position relative to a point near the camera, as our interpolated uv lacks precision
+    if f.frame_index > 0u {
+        var frame = local_frame(f);
+        if frame.anchor_view.w > 0.0 {
+            var anchor = vec2<f32>(frame.uv_x.w, frame.uv_y.w);
+            var offset = core::local_frame_offset(frame, f.position.xy);
+            f.uv = anchor + offset;
+
+            // Expand |anchor + offset|^2 - 1 so that the small offset isn't lost when added to the anchor
+            dist = (dot(anchor, anchor) - 1.0 + dot(offset, 2.0 * anchor + offset)) / (length(f.uv) + 1.0);
+        }
+    }
+
+    var angle = atan2(f.uv.y, f.uv.x);
+    var shape_color = gradient_color(f, angle);
+
     // Mask representing whether this fragment falls within the shape
-    var in_shape = f.color.a;
+    var in_shape = shape_color.a;
 
     // Cut off points outside the shape or within the hollow area
-    var dist = length(f.uv) - 1.;
     in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
 
     // Cut off points outside the allowed range of angles
-    var angle = atan2(f.uv.y, f.uv.x);
     in_shape *= core::step_aa_pd(-f.delta, angle, abs(angle)) * core::step_aa_pd(angle, f.delta, abs(angle));
 
-    // Handle rounded caps
-    if f.cap == 2u {
+    // Handle rounded caps, circles have no ends to cap
+    if f.cap == 2u && f.delta < PI {
         // Take the delta in the direction towards our point
         var nearest_angle = sign(angle) * f.delta;
 
@@ -128,10 +181,15 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
         var dist = length(end_point - f.uv);
 
         var mask = core::step_aa(dist, f.thickness / 2.0);
-        in_shape = min(max(in_shape, mask), f.color.a);
+        in_shape = min(max(in_shape, mask), shape_color.a);
+    }
+
+    // Mask out the gaps between dashes
+    if f.dash_pattern > 0u {
+        in_shape = min(in_shape, dash_mask(f, angle));
     }
 
-    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+    var color = core::color_output(vec4<f32>(shape_color.rgb, in_shape));
 #ifdef TEXTURED
     color = color * textureSample(image, image_sampler, f.texture_uv);
 #endif
@@ -143,4 +201,97 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
 
     return color;
 }
+
+// Local frame of the disc, with it's uv space rotated in the same way as in the vertex shader
+fn local_frame(f: FragmentInput) -> core::LocalFrame {
+    let shape = shapes[f.frame_index - 1u];
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    var frame = core::get_local_frame(matrix, core::get_scale(matrix) * shape.radius, shape.flags);
+    if core::f_arc(shape.flags) > 0u {
+        frame = core::rotate_local_frame(frame, -f.angle);
+    }
+    return frame;
+}
+
+// Color of the disc at the given angle, swept from the shape's color to the gradient color
+fn gradient_color(f: FragmentInput, angle: f32) -> vec4<f32> {
+    if f.gradient == 0u {
+        return f.color;
+    }
+
+    // Progress around the disc, arcs are already rotated such that their start is at -delta
+    var t = (angle + f.delta) / (2.0 * f.delta);
+    if f.gradient_params.y > 0.0 {
+        t = fract(t - f.gradient_params.y);
+    }
+
+    if f.gradient == 2u {
+        var start = rgb_to_hsv(linear_to_srgb(f.color.rgb));
+        var end = rgb_to_hsv(linear_to_srgb(f.gradient_color.rgb));
+        // Hue always increases towards the end color
+        var hue = start.x + (fract(end.x - start.x) + f.gradient_params.x) * t;
+        var hsv = vec3<f32>(fract(hue), mix(start.yz, end.yz, t));
+        return vec4<f32>(srgb_to_linear(hsv_to_rgb(hsv)), mix(f.color.a, f.gradient_color.a, t));
+    }
+
+    return mix(f.color, f.gradient_color, t);
+}
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    return pow(max(color, vec3<f32>(0.0)), vec3<f32>(1.0 / 2.2));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    return pow(color, vec3<f32>(2.2));
+}
+
+// Hue, saturation and value with hue in turns
+fn rgb_to_hsv(color: vec3<f32>) -> vec3<f32> {
+    var value = max(color.r, max(color.g, color.b));
+    var range = value - min(color.r, min(color.g, color.b));
+
+    var hue = 0.0;
+    if range > 0.0 {
+        if value == color.r {
+            hue = (color.g - color.b) / range;
+        } else if value == color.g {
+            hue = 2.0 + (color.b - color.r) / range;
+        } else {
+            hue = 4.0 + (color.r - color.g) / range;
+        }
+        hue = fract(hue / 6.0);
+    }
+
+    var saturation = select(0.0, range / value, value > 0.0);
+    return vec3<f32>(hue, saturation, value);
+}
+
+fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
+    var channels = abs(fract(hsv.xxx + vec3<f32>(1.0, 2.0 / 3.0, 1.0 / 3.0)) * 6.0 - 3.0);
+    return hsv.z * mix(vec3<f32>(1.0), saturate(channels - 1.0), hsv.y);
+}
+
+// Each dash is treated as a short arc with the same caps as the disc
+fn dash_mask(f: FragmentInput, angle: f32) -> f32 {
+    var mid_radius = 1.0 - f.thickness / 2.0;
+
+    // Signed distance along the outline from the body of the nearest dash, negative when within it
+    // Patterns are measured in local space along the middle of the outline from the start of the arc
+    var pos = (angle + f.delta) * mid_radius * f.radius;
+    var along = core::dash_pattern_distance(pos, f.dash_pattern) / f.radius;
+
+    if f.cap == 2u {
+        var dist = length(vec2<f32>(length(f.uv) - mid_radius, max(0., along)));
+        return core::step_aa(dist, f.thickness / 2.0);
+    }
+
+    var cap_length = select(0.0, f.thickness / 2.0, f.cap == 1u);
+    return core::step_aa(along, cap_length);
+}
 #endif
\ No newline at end of file
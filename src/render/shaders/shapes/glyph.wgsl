@@ -0,0 +1,99 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    // Bounds of the glyph relative to the origin of it's text, min in xy and max in zw
+    @location(7) rect: vec4<f32>,
+    // Region of the font atlas containing the glyph, min in xy and max in zw
+    @location(8) atlas_rect: vec4<f32>,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) atlas_uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Glyphs are laid out around the origin of their text so that billboarded text stays together
+    var origin = (matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz;
+    var basis_vectors = core::get_basis_vectors(matrix, origin, shape.flags);
+
+    // The atlas is padded around each glyph so no anti-aliasing padding is needed
+    var t = (vertex.xy + 1.0) / 2.0;
+    var local_pos = mix(shape.rect.xy, shape.rect.zw, t) * core::get_scale(matrix);
+
+    // Our y basis is flipped relative to world space so flip our position back
+    var world_pos = origin + local_pos.x * basis_vectors[0] - local_pos.y * basis_vectors[1];
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+
+    // Texture space has y down
+    out.atlas_uv = mix(shape.atlas_rect.xw, shape.atlas_rect.zy, t);
+    out.color = shape.color;
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) atlas_uv: vec2<f32>,
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+#ifdef TEXTURED
+    // The atlas stores the distance to the edge of each glyph, mapped such that the edge lies at 0.5 and the inside is above it
+    var dist = textureSample(image, image_sampler, f.atlas_uv).r;
+    in_shape *= core::step_aa(0.5, dist);
+#endif
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
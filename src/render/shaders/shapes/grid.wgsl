@@ -0,0 +1,157 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) cells: vec2<f32>,
+    @location(8) cell_size: vec2<f32>,
+    @location(9) major_interval: f32,
+    @location(10) major_thickness: f32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) cells: vec2<f32>,
+    @location(3) minor: vec2<f32>,
+    @location(4) major: vec2<f32>,
+    @location(5) alpha: vec2<f32>,
+    @location(6) major_interval: f32,
+#ifdef TEXTURED
+    @location(7) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    var half_size = shape.cells * shape.cell_size / 2.0;
+
+    // Determine our line thickness in pixels at the origin
+    var thickness_data = core::get_vertex_data(matrix, vertex.xy * half_size, shape.thickness, shape.flags).thickness_data;
+    var minor_p = thickness_data.thickness_p;
+    var major_p = minor_p * shape.major_thickness;
+
+    // If a thickness in pixels is less than 1, clamp to 1 and reduce the alpha instead
+    out.alpha = vec2<f32>(1.0);
+    if minor_p < 1.0 {
+        out.alpha.x = minor_p;
+        minor_p = 1.0;
+    }
+    if major_p < 1.0 {
+        out.alpha.y = major_p;
+        major_p = 1.0;
+    }
+
+    // Lines on the edges of the grid extend past our cells by half of their thickness
+    var minor = minor_p / thickness_data.pixels_per_u;
+    var major = major_p / thickness_data.pixels_per_u;
+    var extent = half_size + max(minor, major) / 2.0;
+
+    var vertex_data = core::get_vertex_data(matrix, vertex.xy * extent, shape.thickness, shape.flags);
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = vertex_data.clip_pos;
+
+    // Express our position and thicknesses in cells, with the first line on each axis at 0
+    out.pos = (vertex.xy * vertex_data.uv_ratio * extent + half_size) / shape.cell_size;
+    out.cells = shape.cells;
+    out.minor = minor / shape.cell_size;
+    out.major = major / shape.cell_size;
+    out.major_interval = shape.major_interval;
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) cells: vec2<f32>,
+    @location(3) minor: vec2<f32>,
+    @location(4) major: vec2<f32>,
+    @location(5) alpha: vec2<f32>,
+    @location(6) major_interval: f32,
+#ifdef TEXTURED
+    @location(7) texture_uv: vec2<f32>,
+#endif
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a * max(axis_mask(f, 0u), axis_mask(f, 1u));
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+
+// Thickness and alpha of the line at the given index along an axis
+fn line_data(f: FragmentInput, index: f32, axis: u32) -> vec2<f32> {
+    if f.major_interval > 0.0 && index % f.major_interval == 0.0 {
+        return vec2<f32>(f.major[axis], f.alpha.y);
+    }
+    return vec2<f32>(f.minor[axis], f.alpha.x);
+}
+
+// Mask for the nearest line crossing the given axis
+fn axis_mask(f: FragmentInput, axis: u32) -> f32 {
+    var other = 1u - axis;
+    var index = clamp(round(f.pos[axis]), 0.0, f.cells[axis]);
+    var line = line_data(f, index, axis);
+
+    // Clip the line to the outer edges of the first and last lines crossing the other axis
+    var start = -line_data(f, 0.0, other).x / 2.0;
+    var end = f.cells[other] + line_data(f, f.cells[other], other).x / 2.0;
+
+    var mask = core::step_aa(abs(f.pos[axis] - index), line.x / 2.0);
+    mask *= core::step_aa(start, f.pos[other]) * core::step_aa(f.pos[other], end);
+    return mask * line.y;
+}
+#endif
@@ -19,6 +19,7 @@ struct Shape {
 
     @location(7) start: vec3<f32>,
     @location(8) end: vec3<f32>,
+    @location(9) dash_pattern: u32,
 };
 
 #ifdef PER_OBJECT_BUFFER_BATCH_SIZE
@@ -31,9 +32,12 @@ struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
-    @location(2) cap_ratio: f32,
+    @location(2) caps: vec2<u32>,
+    @location(3) dash: vec2<f32>,
+    @location(4) dash_pattern: u32,
+    @location(5) line: vec2<f32>,
 #ifdef TEXTURED
-    @location(3) texture_uv: vec2<f32>,
+    @location(6) texture_uv: vec2<f32>,
 #endif
 };
 
@@ -46,12 +50,12 @@ fn vertex(v: Vertex) -> VertexOutput {
     let shape = shapes[v.index];
 
     // Reconstruct our transformation matrix
-    let matrix = mat4x4<f32>(
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
         shape.matrix_0,
         shape.matrix_1,
         shape.matrix_2,
         shape.matrix_3
-    );
+    ), shape.flags);
 
     // Vector from start -> end
     var line_vec = shape.end - shape.start;
@@ -73,7 +77,7 @@ fn vertex(v: Vertex) -> VertexOutput {
     var origin = select(world_end, world_start, vertex.y < 0.0);
 
     // Calculate the remainder of our basis vectors
-    var alignment = core::f_alignment(shape.flags) << 1u;
+    var alignment = select(0u, 4u, core::f_alignment(shape.flags) > 0u);
     var basis_vectors = core::get_basis_vectors_from_up(matrix, origin, y_basis, alignment);
 
     // Calculate thickness data
@@ -93,21 +97,19 @@ fn vertex(v: Vertex) -> VertexOutput {
     var thickness = thickness_data.thickness_p / thickness_data.pixels_per_u;
     var radius = thickness / 2.0;
 
-    var cap_type = core::f_cap(shape.flags);
-    var cap_length = 0.0;
+    // Each end of the line may have a different cap type
+    out.caps = vec2<u32>(core::f_cap(shape.flags), core::f_end_cap(shape.flags));
 
-    // If we have caps increase the cap length to our radius
-    if cap_type > 0u {
-        cap_length = radius;
-    }
+    // If we have square or round caps increase the cap length at that end to our radius, arrows end at the end point
+    var start_cap_length = select(0.0, radius, out.caps.x == 1u || out.caps.x == 2u);
+    var end_cap_length = select(0.0, radius, out.caps.y == 1u || out.caps.y == 2u);
+    var cap_length = select(end_cap_length, start_cap_length, vertex.y < 0.0);
 
-    // If our caps are round store the ratio of the length of our caps to the entire length of the line
-    if cap_type == 2u {
-        out.cap_ratio = thickness / (line_length + thickness);
-    }
+    // Widen the quad to fit arrowheads
+    var half_width = radius * select(1.0, ARROW_WIDTH, out.caps.x == 3u || out.caps.y == 3u);
 
     // Calculate the vertex position with scaling
-    var local_pos = vertex.xy * vec2<f32>(radius, cap_length + line_length / 2.0) * scale.xy;
+    var local_pos = vertex.xy * vec2<f32>(half_width, cap_length + line_length / 2.0) * scale.xy;
 
     // Scale our padding to world space and match direction of our vertex
     var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
@@ -118,7 +120,7 @@ fn vertex(v: Vertex) -> VertexOutput {
     var uv_ratio = padded_pos / local_pos;
 
     // Calculate the offset from our origin point
-    var local_offset = vertex.xy * (vec2<f32>(radius, cap_length) * scale.xy + aa_padding_u);
+    var local_offset = vertex.xy * (vec2<f32>(half_width, cap_length) * scale.xy + aa_padding_u);
 
     // Determine final world position by offsetting by the origin we chose and rotating by our basis vectors
     var world_pos = origin + local_offset.x * basis_vectors[0] + local_offset.y * basis_vectors[1];
@@ -127,6 +129,17 @@ fn vertex(v: Vertex) -> VertexOutput {
     out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
     out.uv = vertex.xy * uv_ratio;
 
+    // Keep our x uv in terms of our radius
+    out.uv.x *= half_width / radius;
+
+    // Store our distance along the line from the start and cap length for dashing
+    // The quad is offset from the nearest end so the distance is measured from that end, including padding
+    var beyond_end = cap_length + aa_padding_u / scale.y;
+    var dash_pos = select(line_length + beyond_end, -beyond_end, vertex.y < 0.0);
+    out.dash = vec2<f32>(dash_pos, start_cap_length);
+    out.dash_pattern = shape.dash_pattern;
+    out.line = vec2<f32>(line_length, radius);
+
     out.color = out_color;
 #ifdef TEXTURED
     out.texture_uv = core::get_texture_uv(vertex.xy);
@@ -137,44 +150,52 @@ fn vertex(v: Vertex) -> VertexOutput {
 struct FragmentInput {
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
-    @location(2) cap_ratio: f32,
+    @location(2) caps: vec2<u32>,
+    @location(3) dash: vec2<f32>,
+    @location(4) dash_pattern: u32,
+    @location(5) line: vec2<f32>,
 #ifdef TEXTURED
-    @location(3) texture_uv: vec2<f32>,
+    @location(6) texture_uv: vec2<f32>,
 #endif
 };
 
+// Length and half width of arrowheads in radii
+const ARROW_LENGTH: f32 = 6.0;
+const ARROW_WIDTH: f32 = 3.0;
+
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
 #ifdef FRAGMENT
 @fragment
 fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
     var in_shape = f.color.a;
 
-    // If we have rounded caps mask them
-    if f.cap_ratio > 0.0 {
-        // Lines are symmetrical across both axis so we can mirror our point 
-        //  onto the positive x and y axis by taking the absolute value
-        var pos = abs(f.uv);
+    // Distance beyond the nearest end of the line in radii, negative within the body of the line
+    var along = f.dash.x;
+    var beyond = max(-along, along - f.line.x) / f.line.y;
+    var cap = select(f.caps.y, f.caps.x, along < f.line.x / 2.0);
 
-        // Our x value already represents the x distance to our line so we now must transform our y value
-        // We want y = 0 to represent being within the body of the line, and 1 to be at the tip of our cap
-
-        // Calculate the -y distance to the end of the quad in caps
-        // The end of the quad is y = 1 so subtract to get the distance and then scale by cap length
-        var to_end_cap = (pos.y - 1.) / f.cap_ratio;
-
-        // We want y = 0 when the amount of caps until the end of the quad is 1 
-        //  and y = 1 when the number of quads is 0 so take 1 + to_end_cap
-        // If the total is negative we are within the line so clamp to > 0
-        pos.y = max(0., 1. + to_end_cap);
+    // Our x value already represents the distance to our line in radii
+    var x = abs(f.uv.x);
 
+    // Mask our caps, arrowheads are masked along with the body of the line up to their base
+    if cap == 3u {
+        in_shape = min(in_shape, arrow_mask(x, beyond));
+    } else if cap == 2u {
         // We now have the shortest vector from our point to the line so take the distance
-        var dist = length(pos);
+        var dist = length(vec2<f32>(x, max(0., beyond)));
 
         // Mask out corners
         in_shape = min(in_shape, core::step_aa(dist, 1.));
     } else {
-        // Simple rectangle sdf for no caps or square caps
-        in_shape = min(in_shape, core::step_aa(abs(f.uv.x), 1.) * core::step_aa(abs(f.uv.y), 1.0));
+        // Simple rectangle sdf for no caps or square caps, square caps extend the line by it's radius
+        var cap_length = select(0.0, 1.0, cap == 1u);
+        in_shape = min(in_shape, core::step_aa(x, 1.) * core::step_aa(beyond, cap_length));
+    }
+
+    // Mask out the gaps between dashes, leaving arrowheads whole
+    var in_arrow = cap == 3u && beyond > -ARROW_LENGTH;
+    if f.dash_pattern > 0u && !in_arrow {
+        in_shape = min(in_shape, dash_mask(f));
     }
 
     var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
@@ -189,4 +210,31 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
 
     return color;
 }
+
+// Mask of an arrowhead with it's tip at the end of the line, joined to the body of the line at it's base
+fn arrow_mask(x: f32, beyond: f32) -> f32 {
+    var body = core::step_aa(x, 1.) * core::step_aa(beyond, -ARROW_LENGTH);
+
+    // Signed distance from the sloped side of the arrowhead, negative within it
+    var side = (x * ARROW_LENGTH + beyond * ARROW_WIDTH) / length(vec2<f32>(ARROW_LENGTH, ARROW_WIDTH));
+    var head = core::step_aa(-ARROW_LENGTH, beyond) * core::step_aa(side, 0.);
+
+    return max(body, head);
+}
+
+// Each dash is treated as a short line with the same caps as the start of the line
+fn dash_mask(f: FragmentInput) -> f32 {
+    var cap_length = f.dash.y;
+
+    // Signed distance along the line from the body of the nearest dash, negative when within it
+    var along = core::dash_pattern_distance(f.dash.x, f.dash_pattern);
+
+    // Round caps have a length equal to our radius
+    if f.caps.x == 2u {
+        var dist = length(vec2<f32>(f.uv.x * cap_length, max(0., along))) / cap_length;
+        return core::step_aa(dist, 1.);
+    }
+
+    return core::step_aa(along, cap_length);
+}
 #endif
\ No newline at end of file
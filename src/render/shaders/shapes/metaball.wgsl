@@ -0,0 +1,160 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) center_a: vec2<f32>,
+    @location(8) center_b: vec2<f32>,
+    @location(9) radius_a: f32,
+    @location(10) radius_b: f32,
+    @location(11) blend: f32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) thickness: f32,
+    @location(3) center_a: vec2<f32>,
+    @location(4) center_b: vec2<f32>,
+    @location(5) radius_a: f32,
+    @location(6) radius_b: f32,
+    @location(7) blend: f32,
+#ifdef TEXTURED
+    @location(8) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Transform the origin into world space
+    var origin = (matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz;
+    var basis_vectors = core::get_basis_vectors(matrix, origin, shape.flags);
+
+    // Get thickness data at our origin given our up vector
+    var thickness_type = core::f_thickness_type(shape.flags);
+    let thickness_data = core::get_thickness_data(shape.thickness, thickness_type, origin, basis_vectors[1]);
+
+    // Work in world units so that both circles share the same uv space
+    let scale = core::get_scale(matrix);
+    let radius_scale = max(scale.x, scale.y);
+    out.center_a = shape.center_a * scale;
+    out.center_b = shape.center_b * scale;
+    out.radius_a = shape.radius_a * radius_scale;
+    out.radius_b = shape.radius_b * radius_scale;
+    out.blend = max(shape.blend * radius_scale, 0.0);
+
+    // Find the bounds of both circles, smooth blending can bulge outwards by up to a quarter of the blend distance
+    let min_pos = min(out.center_a - out.radius_a, out.center_b - out.radius_b);
+    let max_pos = max(out.center_a + out.radius_a, out.center_b + out.radius_b);
+    var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
+    let center = (min_pos + max_pos) / 2.0;
+    let half_size = (max_pos - min_pos) / 2.0 + out.blend / 4.0 + aa_padding_u;
+    let local_pos = center + vertex.xy * half_size;
+
+    // Rotate the position based on our basis vectors and add the world position offset
+    var world_pos = origin + (local_pos.x * basis_vectors[0]) - (local_pos.y * basis_vectors[1]);
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+    out.pos = local_pos;
+    out.thickness = core::calculate_thickness(thickness_data, 1.0, shape.flags);
+
+    // Our uv space is in world units so filled metaballs need a thickness that covers the entire shape
+    if core::f_hollow(shape.flags) == 0u {
+        out.thickness = max(out.radius_a, out.radius_b) + out.blend;
+    }
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    // Our y axis is flipped relative to the quad so flip our texture to match
+    out.texture_uv = core::get_texture_uv(vertex.xy * vec2<f32>(1.0, -1.0));
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) thickness: f32,
+    @location(3) center_a: vec2<f32>,
+    @location(4) center_b: vec2<f32>,
+    @location(5) radius_a: f32,
+    @location(6) radius_b: f32,
+    @location(7) blend: f32,
+#ifdef TEXTURED
+    @location(8) texture_uv: vec2<f32>,
+#endif
+};
+
+// Polynomial smooth minimum, see https://iquilezles.org/articles/smin/
+fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return min(a, b);
+    }
+    let h = max(k - abs(a - b), 0.0) / k;
+    return min(a, b) - h * h * k * 0.25;
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Calculate our distance to each circle and blend them together
+    let dist_a = length(f.pos - f.center_a) - f.radius_a;
+    let dist_b = length(f.pos - f.center_b) - f.radius_b;
+    var dist = smooth_min(dist_a, dist_b, f.blend);
+
+    // Cut off points outside the shape or within the hollow area
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
@@ -19,7 +19,10 @@ struct Shape {
   
     @location(7) sides: f32,
     @location(8) radius: f32,
-    @location(9) roundness: f32
+    @location(9) roundness: f32,
+    @location(10) rotation: f32,
+    @location(11) miter_limit: f32,
+    @location(12) dash_pattern: u32,
 };
 
 #ifdef PER_OBJECT_BUFFER_BATCH_SIZE
@@ -36,9 +39,15 @@ struct VertexOutput {
     @location(3) central_angle: f32,
     @location(4) half_side_length: f32,
     @location(5) roundness: f32,
+    @location(6) bevel: f32,
 #ifdef TEXTURED
-    @location(6) texture_uv: vec2<f32>,
+    @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(9) apothem: f32,
+    // Index of the shape plus one for polygons large enough to be evaluated in a local frame, see core::LocalFrame
+    @location(10) @interpolate(flat) frame_index: u32,
 };
 
 @vertex
@@ -50,12 +59,12 @@ fn vertex(v: Vertex) -> VertexOutput {
     let shape = shapes[v.index];
 
     // Reconstruct our transformation matrix
-    let matrix = mat4x4<f32>(
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
         shape.matrix_0,
         shape.matrix_1,
         shape.matrix_2,
         shape.matrix_3
-    );
+    ), shape.flags);
 
     // Calculate vertex data shared between most shapes
     var vertex_data = core::get_vertex_data(matrix, vertex.xy * shape.radius, shape.thickness, shape.flags);
@@ -81,36 +90,74 @@ fn vertex(v: Vertex) -> VertexOutput {
     // We want 1 unit in uv space to be the length of the apothem of our polygon 
     // so scale world to uv space using the world space apothem
     out.uv = vertex_data.local_pos / (apothem * vertex_data.scale) * vertex_data.uv_ratio;
+
+    // Rotate our uv space to rotate the polygon within our quad, leaving our texture untouched
+    // Sampling at points rotated by the inverse rotation turns the polygon counter-clockwise by our rotation
+    out.uv = core::rotate_vec_a(out.uv, -shape.rotation);
     out.thickness = core::calculate_thickness(vertex_data.thickness_data, apothem, shape.flags);
     out.roundness = min(shape.roundness / apothem, 1.0);
 
+    // Round joins on a hollow polygon round it's outer corners by half our thickness, leaving the inner corners sharp
+    let hollow = core::f_hollow(shape.flags) == 1u;
+    let join = core::f_join(shape.flags);
+    if hollow && join == 0u {
+        out.roundness = max(out.roundness, min(out.thickness / 2.0, 1.0));
+    }
+
+    // Bevel joins, and miter joins longer than the miter limit, cut the outer corners of a hollow polygon
+    // at this distance from it's center, negative values leave the corners uncut
+    out.bevel = -1.0;
+    let miter_ratio = 1.0 / unit_apothem;
+    if hollow && (join == 2u || (join == 1u && miter_ratio > shape.miter_limit)) {
+        let half_thickness = out.thickness / 2.0;
+        out.bevel = (1.0 - half_thickness) / unit_apothem + half_thickness * unit_apothem;
+    }
+
     // Scale our half side length to match our uv space of 1 unit per apothem
     // Precalculate our scaling by the inverse of roundness for our sdf
     out.half_side_length = half_side_length / unit_apothem * (1.0 - out.roundness);
 
+    out.dash_pattern = select(0u, shape.dash_pattern, hollow);
+    out.apothem = apothem;
+
+    var frame = core::get_local_frame(matrix, vertex_data.scale * apothem, shape.flags);
+    out.frame_index = select(0u, v.index + 1u, frame.anchor_view.w > 0.0);
+
     out.color = shape.color;
 #ifdef TEXTURED
-    out.texture_uv = core::get_texture_uv(vertex.xy);
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex);
 #endif
     return out;
 }
 
 struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) thickness: f32,
     @location(3) central_angle: f32,
     @location(4) half_side_length: f32,
     @location(5) roundness: f32,
+    @location(6) bevel: f32,
 #ifdef TEXTURED
-    @location(6) texture_uv: vec2<f32>,
+    @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(9) apothem: f32,
+    // Index of the shape plus one for polygons large enough to be evaluated in a local frame, see core::LocalFrame
+    @location(10) @interpolate(flat) frame_index: u32,
 };
 
-// Given a position, a central angle and a half side length determine the distance
-//  between the point and a polygon with the given properties
-fn ngonSDF(position: vec2<f32>, central_angle: f32, half_side_length: f32, apothem: f32) -> f32 {
+// Transform a position such that the x axis is along the apothem to the nearest side of a polygon
+//  and the y axis is along that side
+fn sideSpace(position: vec2<f32>, central_angle: f32) -> vec2<f32> {
     // Rotate our position because pentagons look better when they point up :)
+    return sideRotation(position, central_angle) * position.yx;
+}
+
+// The rotation applied by sideSpace to the flipped position of a point
+fn sideRotation(position: vec2<f32>, central_angle: f32) -> mat2x2<f32> {
     var pos = position.yx;
 
     // Calculate the angle between our point and positive y
@@ -124,15 +171,66 @@ fn ngonSDF(position: vec2<f32>, central_angle: f32, half_side_length: f32, apoth
 
     // Transform our point such that the x axis is along the apothem and the y axis is 
     //  along the side connected to the nearest vertex clockwise
-    pos = mat2x2<f32>(nearest_vertex.x, -nearest_vertex.y, nearest_vertex.y, nearest_vertex.x) * pos;
+    return mat2x2<f32>(nearest_vertex.x, -nearest_vertex.y, nearest_vertex.y, nearest_vertex.x);
+}
+
+// Given a position, a central angle and a half side length determine the distance
+//  between the point and a polygon with the given properties
+// The position is given as a small offset from an anchor, both are transformed separately and the anchor's terms
+//  are combined before the offset is added so that the offset isn't lost when the anchor is far from the center
+fn ngonSDF(anchor: vec2<f32>, offset: vec2<f32>, central_angle: f32, half_side_length: f32, apothem: f32) -> f32 {
+    var rotation = sideRotation(anchor + offset, central_angle);
+    var anchor_pos = rotation * anchor.yx;
+    var offset_pos = rotation * offset.yx;
 
     // The nearest point along the side to our point
     // Ensure that the y position falls along the length of the side
-    var nearest_point = vec2<f32>(apothem, clamp(pos.y, -half_side_length, half_side_length));
+    var nearest_y = clamp(anchor_pos.y + offset_pos.y, -half_side_length, half_side_length);
 
     // Get the distance between our point and the nearest point on the side
     // If our x value is less than the apothem we fall inside the shape so multiply by -1
-    return length(pos - nearest_point) * sign(pos.x - apothem);
+    var delta = vec2<f32>((anchor_pos.x - apothem) + offset_pos.x, (anchor_pos.y - nearest_y) + offset_pos.y);
+    return length(delta) * sign(delta.x);
+}
+
+// Given a position, a central angle and an apothem determine the distance around the outline of a polygon
+//  with those properties to the point on the outline nearest to the position
+fn perimeterDistance(position: vec2<f32>, central_angle: f32, apothem: f32) -> f32 {
+    // Find the index of our nearest side in the same manner as sideSpace, wrapped to be positive
+    var pos = position.yx;
+    var angle = atan2(pos.y, pos.x);
+    var side = floor((angle + 0.5 * central_angle) / central_angle);
+    var sides = round(TAU / central_angle);
+    side = side - floor(side / sides) * sides;
+
+    var half_side_length = apothem * tan(central_angle / 2.);
+    var along_side = clamp(sideSpace(position, central_angle).y, -half_side_length, half_side_length);
+    return (2.0 * side + 1.0) * half_side_length + along_side;
+}
+
+// Given a position, a central angle and a distance from the center determine the distance between the point
+//  and a line cutting off the nearest corner of a polygon at that distance
+fn bevelSDF(position: vec2<f32>, central_angle: f32, bevel: f32) -> f32 {
+    let pos = sideSpace(position, central_angle);
+
+    // Direction from the center to the corner at the end of the nearest side closest to our point
+    let corner_dir = vec2<f32>(cos(central_angle / 2.), sign(pos.y) * sin(central_angle / 2.));
+    return dot(pos, corner_dir) - bevel;
+}
+
+// Local frame of the polygon, with it's uv space rotated in the same way as in the vertex shader
+fn local_frame(f: FragmentInput) -> core::LocalFrame {
+    let shape = shapes[f.frame_index - 1u];
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    let apothem = cos(PI / shape.sides) * shape.radius;
+    let frame = core::get_local_frame(matrix, core::get_scale(matrix) * apothem, shape.flags);
+    return core::rotate_local_frame(frame, shape.rotation);
 }
 
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
@@ -142,12 +240,33 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
     // Mask representing whether this fragment falls within the shape
     var in_shape = f.color.a;
 
+    // Very large polygons find our position relative to a point near the camera, as our interpolated uv lacks precision
+    var anchor = vec2<f32>(0.0);
+    var offset = f.uv;
+    if f.frame_index > 0u {
+        var frame = local_frame(f);
+        if frame.anchor_view.w > 0.0 {
+            anchor = vec2<f32>(frame.uv_x.w, frame.uv_y.w);
+            offset = core::local_frame_offset(frame, f.position.xy);
+        }
+    }
+    var uv = anchor + offset;
+
     // Calculate our positions distance from the polygon
-    var dist = ngonSDF(f.uv, f.central_angle, f.half_side_length, 1.0 - f.roundness) - f.roundness;
+    var dist = ngonSDF(anchor, offset, f.central_angle, f.half_side_length, 1.0 - f.roundness) - f.roundness;
+    if f.bevel > 0.0 {
+        dist = max(dist, bevelSDF(uv, f.central_angle, f.bevel));
+    }
     
     // Cut off points outside the shape or within the hollow area
     in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
 
+    // Mask out the gaps between dashes, measured in local space around the middle of the outline
+    if f.dash_pattern > 0u {
+        var along = perimeterDistance(uv, f.central_angle, 1.0 - f.thickness / 2.0) * f.apothem;
+        in_shape = min(in_shape, core::dash_pattern_mask(along, f.dash_pattern));
+    }
+
     var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
 #ifdef TEXTURED
     color = color * textureSample(image, image_sampler, f.texture_uv);
@@ -0,0 +1,110 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+// Number of directions searched for covered pixels of the mask
+const DIRECTIONS: u32 = 16u;
+
+// Outlines are limited to this many pixels to bound the number of mask samples
+const MAX_THICKNESS: f32 = 32.0;
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) thickness: f32,
+#ifdef TEXTURED
+    @location(2) texture_uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Outlines cover the whole view in front of everything else, the transform is ignored
+    out.clip_position = vec4<f32>(vertex.xy, 1.0, 1.0);
+    out.thickness = min(shape.thickness, MAX_THICKNESS);
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    // Our y axis is flipped relative to the texture so flip our texture to match
+    out.texture_uv = core::get_texture_uv(vertex.xy * vec2<f32>(1.0, -1.0));
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) thickness: f32,
+#ifdef TEXTURED
+    @location(2) texture_uv: vec2<f32>,
+#endif
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = 0.0;
+
+#ifdef TEXTURED
+    let texel = 1.0 / vec2<f32>(textureDimensions(image));
+    let covered = textureSampleLevel(image, image_sampler, f.texture_uv, 0.0).a;
+
+    // Search outwards in each direction for the nearest covered pixel of the mask
+    var dist = MAX_THICKNESS + 1.0;
+    let steps = u32(ceil(f.thickness)) + 1u;
+    for (var d = 0u; d < DIRECTIONS; d++) {
+        let angle = f32(d) * TAU / f32(DIRECTIONS);
+        let dir = vec2<f32>(cos(angle), sin(angle));
+        for (var s = 1u; s <= steps; s++) {
+            let coverage = textureSampleLevel(image, image_sampler, f.texture_uv + dir * f32(s) * texel, 0.0).a;
+            if coverage > 0.0 {
+                // Partially covered pixels of the mask are treated as being closer
+                dist = min(dist, f32(s) - coverage);
+                break;
+            }
+        }
+    }
+
+    // Our outline surrounds the mask without covering it
+    in_shape = f.color.a * clamp(f.thickness + 0.5 - dist, 0.0, 1.0) * (1.0 - covered);
+#endif
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
@@ -0,0 +1,343 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) offset: u32,
+    @location(8) count: u32,
+    @location(9) miter_limit: f32,
+    @location(10) dash_pattern: u32,
+    // Bounds of our points, min in xy and max in zw
+    @location(11) bounds: vec4<f32>,
+};
+
+// Points of every polyline in xy, with the scale of the polyline's thickness at each point in z
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+// Must hold MAX_UNIFORM_POLYLINE_POINTS in render/polyline_points.rs
+@group(0) @binding(5) var<uniform> polyline_points: array<vec4<f32>, 1024u>;
+#else
+@group(0) @binding(5) var<storage> polyline_points: array<vec4<f32>>;
+#endif
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) scale: vec2<f32>,
+    @location(3) radius: f32,
+    @location(4) @interpolate(flat) index: u32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+};
+
+// Fetch a point from the point array, with it's thickness scale in z
+fn get_point(shape: Shape, i: u32) -> vec3<f32> {
+    return polyline_points[shape.offset + i].xyz;
+}
+
+// Furthest any join or cap can extend from it's point in multiples of our radius
+fn get_extent(shape: Shape) -> f32 {
+    // Miter joins extend up to the miter limit, square caps extend diagonally
+    if core::f_join(shape.flags) == 1u {
+        return max(shape.miter_limit, 1.5);
+    }
+    return 1.5;
+}
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad, mirrored so that it's triangles wind counter-clockwise when seen from the front
+    let vertex = v.pos * vec3<f32>(1.0, -1.0, 1.0);
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Transform the origin into world space
+    var origin = (matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz;
+    var basis_vectors = core::get_basis_vectors(matrix, origin, shape.flags);
+
+    // Calculate thickness data
+    var thickness_type = core::f_thickness_type(shape.flags);
+    var thickness_data = core::get_thickness_data(shape.thickness, thickness_type, origin, basis_vectors[1]);
+
+    let scale = core::get_scale(matrix);
+
+    // If our thickness in pixels is less than 1, clamp to 1 and reduce the alpha instead
+    var out_color = shape.color;
+    if thickness_data.thickness_p * max(scale.x, scale.y) < 1.0 {
+        out_color.a = out_color.a * thickness_data.thickness_p * max(scale.x, scale.y);
+        thickness_data.thickness_p = 1.;
+    }
+
+    // Calculate the radius of our stroke in world units
+    var radius = thickness_data.thickness_p / thickness_data.pixels_per_u / 2.0;
+
+    // Find the bounds of our points, scaled into world units
+    var min_pos = min(shape.bounds.xy * scale, shape.bounds.zw * scale);
+    var max_pos = max(shape.bounds.xy * scale, shape.bounds.zw * scale);
+
+    // Joins and caps can extend past the radius of our stroke
+    let extent = radius * get_extent(shape);
+
+    // Pad our bounds by the stroke and our anti-aliasing padding
+    var aa_padding_u = core::AA_PADDING / thickness_data.pixels_per_u;
+    let center = (min_pos + max_pos) / 2.0;
+    let half_size = (max_pos - min_pos) / 2.0 + extent + aa_padding_u;
+    let local_pos = center + vertex.xy * half_size;
+
+    // Rotate the position based on our basis vectors and add the world position offset
+    var world_pos = origin + (local_pos.x * basis_vectors[0]) - (local_pos.y * basis_vectors[1]);
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = view.view_proj * vec4<f32>(world_pos, 1.0);
+    out.pos = local_pos;
+    out.scale = scale;
+    out.radius = radius;
+    out.index = v.index;
+
+    out.color = out_color;
+#ifdef TEXTURED
+    // Our y axis is flipped relative to the quad so flip our texture to match
+    out.texture_uv = core::get_texture_uv(vertex.xy * vec2<f32>(1.0, -1.0));
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) pos: vec2<f32>,
+    @location(2) scale: vec2<f32>,
+    @location(3) radius: f32,
+    @location(4) @interpolate(flat) index: u32,
+#ifdef TEXTURED
+    @location(5) texture_uv: vec2<f32>,
+#endif
+};
+
+fn cross2d(a: vec2<f32>, b: vec2<f32>) -> f32 {
+    return (a.x * b.y) - (a.y * b.x);
+}
+
+// Distance from p to a stroke along the segment a -> b, tapering from a half width of r_a at a to r_b at b
+// The segment can be extended beyond a and b by start_ext and end_ext respectively
+fn segmentSDF(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, r_a: f32, r_b: f32, start_ext: f32, end_ext: f32) -> f32 {
+    let ab = b - a;
+    let len = length(ab);
+    let dir = ab / len;
+
+    // Transform our point such that x is along the segment and y is perpendicular to it
+    let ap = p - a;
+    var seg_pos = vec2<f32>(dot(ap, dir), cross2d(dir, ap));
+
+    // Tapered segments are approximated by a rectangle of the half width at our position along the segment
+    let r = mix(r_a, r_b, clamp(seg_pos.x / len, 0.0, 1.0));
+
+    // Recenter on the middle of the extended segment
+    let half_len = (len + start_ext + end_ext) / 2.0;
+    seg_pos.x = seg_pos.x - (len + end_ext - start_ext) / 2.0;
+
+    // Simple rectangle sdf
+    let to_corner = abs(seg_pos) - vec2<f32>(half_len, r);
+    return length(max(to_corner, vec2<f32>(0.))) + min(0., max(to_corner.x, to_corner.y));
+}
+
+// Distance from p to the triangle a, b, c, heavily inspired by https://iquilezles.org/articles/distfunctions2d/
+fn triangleSDF(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, c: vec2<f32>) -> f32 {
+    var ab = b - a; var bc = c - b; var ca = a - c;
+    var ap = p - a; var bp = p - b; var cp = p - c;
+
+    var pq_ab = ap - ab * clamp(dot(ap, ab) / dot(ab, ab), 0.0, 1.0);
+    var pq_bc = bp - bc * clamp(dot(bp, bc) / dot(bc, bc), 0.0, 1.0);
+    var pq_ca = cp - ca * clamp(dot(cp, ca) / dot(ca, ca), 0.0, 1.0);
+
+    var s = sign(cross2d(ab, ca));
+
+    var d_ab = vec2<f32>(dot(pq_ab, pq_ab), s * cross2d(ap, ab));
+    var d_bc = vec2<f32>(dot(pq_bc, pq_bc), s * cross2d(bp, bc));
+    var d_ca = vec2<f32>(dot(pq_ca, pq_ca), s * cross2d(cp, ca));
+
+    var d = min(min(d_ab, d_bc), d_ca);
+
+    return -sqrt(d.x) * sign(d.y);
+}
+
+// Distance from p to the region filling the outside of the corner at point b between segments a -> b and b -> c
+fn joinSDF(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, c: vec2<f32>, r: f32, join: u32, miter_limit: f32) -> f32 {
+    // Round joins are simply a circle centered on the shared point
+    if join == 0u {
+        return length(p - b) - r;
+    }
+
+    let d_in = normalize(b - a);
+    let d_out = normalize(c - b);
+
+    // Straight segments need no join
+    let turn = cross2d(d_in, d_out);
+    if abs(turn) < 0.0001 {
+        return 1e10;
+    }
+
+    // The outside of the corner is opposite the direction we are turning
+    let n_in = -sign(turn) * vec2<f32>(-d_in.y, d_in.x);
+    let n_out = -sign(turn) * vec2<f32>(-d_out.y, d_out.x);
+    let edge_in = b + n_in * r;
+    let edge_out = b + n_out * r;
+
+    // Bevel joins fill the triangle between the shared point and the outer edges
+    var dist = triangleSDF(p, b, edge_in, edge_out);
+
+    // Miter joins extend the outer edges until they meet if within the miter limit
+    if join == 1u {
+        let miter_dir = normalize(n_in + n_out);
+        let miter_length = r / dot(miter_dir, n_in);
+        if miter_length <= r * miter_limit {
+            let miter = b + miter_dir * miter_length;
+            dist = min(triangleSDF(p, b, edge_in, miter), triangleSDF(p, b, miter, edge_out));
+        }
+    }
+
+    return dist;
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    let shape = shapes[f.index];
+    let count = shape.count;
+    let cap = core::f_cap(shape.flags);
+    let join = core::f_join(shape.flags);
+
+    // Segments further away than any join or cap could extend cannot affect this fragment
+    let cull_dist = f.radius * get_extent(shape) + length(fwidth(f.pos)) * core::AA_PADDING;
+
+    // Distance along the polyline to the start of the current segment in local space, for placing dashes
+    var length_before = 0.0;
+    // Distance from the nearest segment, along the polyline and across it, along with that segment's scale from local space
+    // and it's radius at the nearest point
+    var dash_nearest = 1e10;
+    var dash_along = 0.0;
+    var dash_across = 0.0;
+    var dash_scale = 1.0;
+    var dash_radius = f.radius;
+
+    // Take the union of each segment and each join between segments
+    var dist = 1e10;
+    for (var i = 0u; i + 1u < count; i++) {
+        let point_a = get_point(shape, i);
+        let point_b = get_point(shape, i + 1u);
+        let a = point_a.xy * f.scale;
+        let b = point_b.xy * f.scale;
+        let r_a = f.radius * point_a.z;
+        let r_b = f.radius * point_b.z;
+        let local_length = length(point_b.xy - point_a.xy);
+        let segment_start = length_before;
+        length_before += local_length;
+
+        let to_bounds = max(min(a, b) - f.pos, f.pos - max(a, b));
+        if max(to_bounds.x, to_bounds.y) > cull_dist {
+            continue;
+        }
+
+        // Skip degenerate segments, they would otherwise have no direction
+        if length(b - a) < 0.00001 {
+            continue;
+        }
+
+        // Square caps extend the end segments by their radius
+        var start_ext = 0.0;
+        var end_ext = 0.0;
+        if i == 0u {
+            start_ext = select(0.0, r_a, cap == 1u);
+            if cap == 2u {
+                dist = min(dist, length(f.pos - a) - r_a);
+            }
+        }
+        if i + 2u == count {
+            end_ext = select(0.0, r_b, cap == 1u);
+            if cap == 2u {
+                dist = min(dist, length(f.pos - b) - r_b);
+            }
+        } else {
+            let c = get_point(shape, i + 2u).xy * f.scale;
+            if length(c - b) >= 0.00001 {
+                dist = min(dist, joinSDF(f.pos, a, b, c, r_b, join, shape.miter_limit));
+            }
+        }
+
+        let segment_dist = segmentSDF(f.pos, a, b, r_a, r_b, start_ext, end_ext);
+        dist = min(dist, segment_dist);
+
+        if shape.dash_pattern > 0u && segment_dist < dash_nearest {
+            let ab = b - a;
+            let t = clamp(dot(f.pos - a, ab) / dot(ab, ab), 0.0, 1.0);
+            dash_nearest = segment_dist;
+            dash_along = segment_start + t * local_length;
+            dash_across = abs(cross2d(normalize(ab), f.pos - a));
+            dash_scale = length(ab) / local_length;
+            dash_radius = mix(r_a, r_b, t);
+        }
+    }
+
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a * core::step_aa(dist, 0.);
+
+    // Mask out the gaps between dashes of the nearest segment
+    if shape.dash_pattern > 0u {
+        let along = core::dash_pattern_distance(dash_along, shape.dash_pattern) * dash_scale;
+        in_shape = min(in_shape, dash_mask(along, dash_across, dash_radius, cap));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+
+// Each dash is treated as a short line with the same caps as the ends of the polyline
+fn dash_mask(along: f32, across: f32, radius: f32, cap: u32) -> f32 {
+    if cap == 2u {
+        return core::step_aa(length(vec2<f32>(across, max(0., along))), radius);
+    }
+
+    // Square caps extend each dash by our radius
+    var cap_length = select(0.0, radius, cap == 1u);
+    return core::step_aa(along, cap_length);
+}
+#endif
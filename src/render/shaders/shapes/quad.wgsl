@@ -0,0 +1,219 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) v_0: vec2<f32>,
+    @location(8) v_1: vec2<f32>,
+    @location(9) v_2: vec2<f32>,
+    @location(10) v_3: vec2<f32>,
+    @location(11) roundness: f32,
+    @location(12) dash_pattern: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) thickness: f32,
+
+    @location(3) v_0: vec2<f32>,
+    @location(4) v_1: vec2<f32>,
+    @location(5) v_2: vec2<f32>,
+    @location(6) v_3: vec2<f32>,
+    @location(7) roundness: f32,
+#ifdef TEXTURED
+    @location(8) texture_uv: vec2<f32>,
+#endif
+    @location(9) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(10) uv_scale: f32,
+};
+
+// Move a vertex inwards such that the edges either side of it are offset inwards by the given distance
+fn inset_vertex(prev: vec2<f32>, v: vec2<f32>, next: vec2<f32>, winding: f32, dist: f32) -> vec2<f32> {
+    let e_0 = normalize(prev - v);
+    let e_1 = normalize(next - v);
+
+    // The offset vertex lies along the bisector of the corner, inside for convex corners and outside for concave ones
+    let convex = sign(cross2d(v - prev, next - v) * winding);
+    let bisector = normalize(e_0 + e_1) * convex;
+    let sin_half_angle = max(sqrt((1.0 - dot(e_0, e_1)) / 2.0), 0.05);
+
+    return v + bisector * dist / sin_half_angle;
+}
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    // Reconstruct our transformation matrix
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    // Our quad is drawn over a rect centered on the origin that covers all of it's vertices
+    let min_v = min(min(shape.v_0, shape.v_1), min(shape.v_2, shape.v_3));
+    let max_v = max(max(shape.v_0, shape.v_1), max(shape.v_2, shape.v_3));
+    let half_size = max(max(abs(min_v), abs(max_v)), vec2<f32>(0.0001));
+
+    // Our vertex outputs should all be in uv space so scale our uv space such that the shortest side is of length 1
+    let uv_scale = min(half_size.x, half_size.y);
+
+    var vertex_data = core::get_vertex_data(matrix, vertex.xy * half_size, shape.thickness, shape.flags);
+    out.clip_position = vertex_data.clip_pos;
+
+    // Our y axis is flipped relative to the quad so flip our positions to match our vertices
+    let local_pos = vertex.xy * vec2<f32>(1.0, -1.0) * half_size * vertex_data.uv_ratio;
+    out.uv = local_pos / uv_scale;
+    out.thickness = core::calculate_thickness(vertex_data.thickness_data, uv_scale, shape.flags);
+    out.roundness = shape.roundness / uv_scale;
+    out.dash_pattern = select(0u, shape.dash_pattern, core::f_hollow(shape.flags) == 1u);
+    out.uv_scale = uv_scale;
+
+    // Inset our vertices by the roundness so that rounding doesn't grow the quad
+    let v_0 = shape.v_0 / uv_scale;
+    let v_1 = shape.v_1 / uv_scale;
+    let v_2 = shape.v_2 / uv_scale;
+    let v_3 = shape.v_3 / uv_scale;
+    let winding = sign(cross2d(v_2 - v_0, v_3 - v_1));
+    out.v_0 = inset_vertex(v_3, v_0, v_1, winding, out.roundness);
+    out.v_1 = inset_vertex(v_0, v_1, v_2, winding, out.roundness);
+    out.v_2 = inset_vertex(v_1, v_2, v_3, winding, out.roundness);
+    out.v_3 = inset_vertex(v_2, v_3, v_0, winding, out.roundness);
+
+    out.color = shape.color;
+#ifdef TEXTURED
+    // Textures span the bounds of the quad's vertices, with v increasing downwards
+    let tex_pos = vec2<f32>(local_pos.x - min_v.x, max_v.y - local_pos.y);
+    out.texture_uv = tex_pos / max(max_v - min_v, vec2<f32>(0.0001));
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) thickness: f32,
+
+    @location(3) v_0: vec2<f32>,
+    @location(4) v_1: vec2<f32>,
+    @location(5) v_2: vec2<f32>,
+    @location(6) v_3: vec2<f32>,
+    @location(7) roundness: f32,
+#ifdef TEXTURED
+    @location(8) texture_uv: vec2<f32>,
+#endif
+    @location(9) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(10) uv_scale: f32,
+};
+
+fn cross2d(a: vec2<f32>, b: vec2<f32>) -> f32 {
+    return (a.x * b.y) - (a.y * b.x);
+}
+
+fn quadSDF(p: vec2<f32>, v_0: vec2<f32>, v_1: vec2<f32>, v_2: vec2<f32>, v_3: vec2<f32>) -> f32 {
+    // Heavily inspired by https://iquilezles.org/articles/distfunctions2d/
+    var v = array<vec2<f32>, 4>(v_0, v_1, v_2, v_3);
+
+    var d = dot(p - v[0], p - v[0]);
+    var s = 1.0;
+    for (var i = 0u; i < 4u; i++) {
+        let j = (i + 3u) % 4u;
+        let e = v[j] - v[i];
+        let w = p - v[i];
+
+        // Distance to the edge, clamped to stay between it's vertices
+        let b = w - e * clamp(dot(w, e) / dot(e, e), 0.0, 1.0);
+        d = min(d, dot(b, b));
+
+        // Count the edges crossed by a ray from our point to determine whether we're inside the quad
+        let c = vec3<bool>(p.y >= v[i].y, p.y < v[j].y, e.x * w.y > e.y * w.x);
+        if all(c) || all(!c) {
+            s = -s;
+        }
+    }
+
+    return s * sqrt(d);
+}
+
+// Distance around the outline through the given vertices to the point on the outline nearest to p
+fn perimeterDistance(p: vec2<f32>, vertices: array<vec2<f32>, 4>) -> f32 {
+    var v = vertices;
+    var nearest = 1e10;
+    var along = 0.0;
+    var before = 0.0;
+    for (var i = 0u; i < 4u; i++) {
+        let e = v[(i + 1u) % 4u] - v[i];
+        let w = p - v[i];
+        let t = clamp(dot(w, e) / dot(e, e), 0.0, 1.0);
+        let dist = length(w - e * t);
+        if dist < nearest {
+            nearest = dist;
+            along = before + t * length(e);
+        }
+        before += length(e);
+    }
+    return along;
+}
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Calculate our positions distance from the quad
+    var dist = quadSDF(f.uv, f.v_0, f.v_1, f.v_2, f.v_3) - f.roundness;
+
+    // Cut off points outside the shape or within the hollow area
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+
+    // Mask out the gaps between dashes, measured in local space around the outline
+    if f.dash_pattern > 0u {
+        var along = perimeterDistance(f.uv, array<vec2<f32>, 4>(f.v_0, f.v_1, f.v_2, f.v_3)) * f.uv_scale;
+        in_shape = min(in_shape, core::dash_pattern_mask(along, f.dash_pattern));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
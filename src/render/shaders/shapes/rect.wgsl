@@ -18,7 +18,18 @@ struct Shape {
     @location(6) flags: u32,
 
     @location(7) size: vec2<f32>,
+    // Chamfered corners have negative radii
     @location(8) corner_radii: vec4<f32>,
+    // Region of the texture to draw, min in xy and max in zw
+    @location(9) texture_rect: vec4<f32>,
+    // Colors multiplied with the shape's color at the top right, top left, bottom left and bottom right corners
+    @location(10) corner_color_0: vec4<f32>,
+    @location(11) corner_color_1: vec4<f32>,
+    @location(12) corner_color_2: vec4<f32>,
+    @location(13) corner_color_3: vec4<f32>,
+    @location(14) dash_pattern: u32,
+    // Size of each repetition of the texture in local space, axes of zero stretch the texture instead
+    @location(15) texture_tile: vec2<f32>,
 }
 
 #ifdef PER_OBJECT_BUFFER_BATCH_SIZE
@@ -34,8 +45,22 @@ struct VertexOutput {
     @location(2) size: vec2<f32>,
     @location(3) corner_radii: vec4<f32>,
     @location(4) thickness: f32,
+    @location(5) @interpolate(flat) chamfers: u32,
+    @location(6) @interpolate(flat) smooth_corners: u32,
 #ifdef TEXTURED
-    @location(5) texture_uv: vec2<f32>,
+    // Position in repetitions of the texture from the top left
+    @location(7) texture_uv: vec2<f32>,
+#endif
+    @location(8) @interpolate(flat) corner_color_0: vec4<f32>,
+    @location(9) @interpolate(flat) corner_color_1: vec4<f32>,
+    @location(10) @interpolate(flat) corner_color_2: vec4<f32>,
+    @location(11) @interpolate(flat) corner_color_3: vec4<f32>,
+    @location(12) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(13) uv_scale: f32,
+#ifdef TEXTURED
+    @location(14) @interpolate(flat) texture_rect: vec4<f32>,
+    @location(15) @interpolate(flat) texture_tiles: vec2<f32>,
 #endif
 };
 
@@ -48,12 +73,12 @@ fn vertex(v: Vertex) -> VertexOutput {
     let shape = shapes[v.index];
 
     // Reconstruct our transformation matrix
-    let matrix = mat4x4<f32>(
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
         shape.matrix_0,
         shape.matrix_1,
         shape.matrix_2,
         shape.matrix_3
-    );
+    ), shape.flags);
     // Shortest of the two side lengths for the rectangle
     var shortest_side = min(shape.size.x, shape.size.y);
 
@@ -66,11 +91,22 @@ fn vertex(v: Vertex) -> VertexOutput {
     out.thickness = core::calculate_thickness(vertex_data.thickness_data, shortest_side / 2.0, shape.flags);
 
     // Our corner radii cannot be more than half the shortest side so cap them
-    out.corner_radii = 2.0 * min(shape.corner_radii / shortest_side, vec4<f32>(0.5));
+    out.corner_radii = 2.0 * min(abs(shape.corner_radii) / shortest_side, vec4<f32>(0.5));
+    let chamfered = shape.corner_radii < vec4<f32>(0.0);
+    out.chamfers = select(0u, 1u, chamfered.x) | select(0u, 2u, chamfered.y) | select(0u, 4u, chamfered.z) | select(0u, 8u, chamfered.w);
+    out.smooth_corners = core::f_smooth_corners(shape.flags);
+    out.dash_pattern = select(0u, shape.dash_pattern, core::f_hollow(shape.flags) == 1u);
+    out.uv_scale = shortest_side / 2.0;
 
     out.color = shape.color;
+    out.corner_color_0 = shape.corner_color_0;
+    out.corner_color_1 = shape.corner_color_1;
+    out.corner_color_2 = shape.corner_color_2;
+    out.corner_color_3 = shape.corner_color_3;
 #ifdef TEXTURED
-    out.texture_uv = core::get_texture_uv(vertex.xy);
+    out.texture_tiles = select(vec2<f32>(1.0), shape.size / shape.texture_tile, shape.texture_tile > vec2<f32>(0.0));
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex) * out.texture_tiles;
+    out.texture_rect = shape.texture_rect;
 #endif
     return out;
 }
@@ -81,8 +117,22 @@ struct FragmentInput {
     @location(2) size: vec2<f32>,
     @location(3) corner_radii: vec4<f32>,
     @location(4) thickness: f32,
+    @location(5) @interpolate(flat) chamfers: u32,
+    @location(6) @interpolate(flat) smooth_corners: u32,
+#ifdef TEXTURED
+    // Position in repetitions of the texture from the top left
+    @location(7) texture_uv: vec2<f32>,
+#endif
+    @location(8) @interpolate(flat) corner_color_0: vec4<f32>,
+    @location(9) @interpolate(flat) corner_color_1: vec4<f32>,
+    @location(10) @interpolate(flat) corner_color_2: vec4<f32>,
+    @location(11) @interpolate(flat) corner_color_3: vec4<f32>,
+    @location(12) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(13) uv_scale: f32,
 #ifdef TEXTURED
-    @location(5) texture_uv: vec2<f32>,
+    @location(14) @interpolate(flat) texture_rect: vec4<f32>,
+    @location(15) @interpolate(flat) texture_tiles: vec2<f32>,
 #endif
 };
 
@@ -107,6 +157,40 @@ fn rectSDF(position: vec2<f32>, size: vec2<f32>) -> f32 {
     return length(outside_to_edge) + inside_length;
 }
 
+// Distance to a rectangle whose corners of the given radius follow a superellipse instead of a circle
+// The superellipse meets the straight edges with zero curvature, avoiding the visible kink of circular corners
+fn smoothRectSDF(position: vec2<f32>, size: vec2<f32>, radius: f32) -> f32 {
+    var to_corner = abs(position) - (size - radius);
+    var outside_to_edge = max(vec2<f32>(0.), to_corner);
+
+    // Length with an exponent of 4, our corners are the quarter of the superellipse |x|^4 + |y|^4 = r^4
+    var squared = outside_to_edge * outside_to_edge;
+    var outside_length = sqrt(sqrt(dot(squared, squared)));
+
+    var inside_length = min(0., max(to_corner.x, to_corner.y));
+    return outside_length + inside_length - radius;
+}
+
+// Distance clockwise around the outline of a rectangle with the given half size from it's top left corner
+//  to the point on the outline nearest to the given position
+fn perimeterDistance(position: vec2<f32>, size: vec2<f32>) -> f32 {
+    // Points belong to whichever side they are closest to
+    var to_edge = size - abs(position);
+    var pos = clamp(position, -size, size);
+
+    // Our uvs increase downwards so the top side is at negative y
+    if to_edge.y < to_edge.x {
+        if pos.y < 0.0 {
+            return pos.x + size.x;
+        }
+        return 3.0 * size.x + 2.0 * size.y - pos.x;
+    }
+    if pos.x > 0.0 {
+        return 2.0 * size.x + size.y + pos.y;
+    }
+    return 4.0 * size.x + 3.0 * size.y - pos.y;
+}
+
 // Given a uv position get which quadrant that position is in
 // Return an integer from 0 to 3
 fn quadrant(in: vec2<f32>) -> i32 {
@@ -114,12 +198,23 @@ fn quadrant(in: vec2<f32>) -> i32 {
     return -uv.y + (-uv.x * uv.y + 3) / 2;
 }
 
+// Bilinearly interpolate the corner colors at the given uv position
+fn corner_color(f: FragmentInput) -> vec4<f32> {
+    // Position within the rectangle from 0 at the top left to 1 at the bottom right, uvs increase downwards like textures
+    var t = saturate(f.uv / f.size * 0.5 + 0.5);
+    var top = mix(f.corner_color_1, f.corner_color_0, t.x);
+    var bottom = mix(f.corner_color_2, f.corner_color_3, t.x);
+    return mix(top, bottom, t.y);
+}
+
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
 #ifdef FRAGMENT
 @fragment
 fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    var shape_color = f.color * corner_color(f);
+
     // Mask representing whether this fragment falls within the shape
-    var in_shape = f.color.a;
+    var in_shape = shape_color.a;
 
     // Use quadrant to determine which corner radii to use
     var quadrant = quadrant(f.uv);
@@ -127,15 +222,28 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
 
     // Calculate our positions distance from the rectangle
     var dist = rectSDF(f.uv, f.size - radii) - radii;
+    if f.smooth_corners == 1u {
+        dist = smoothRectSDF(f.uv, f.size, radii);
+    }
+    if ((f.chamfers >> u32(quadrant)) & 1u) == 1u {
+        // Chamfered corners are cut along the line between the points radii away from the corner on each side
+        var pos = abs(f.uv);
+        var cut = (pos.x + pos.y - (f.size.x + f.size.y - radii)) / sqrt(2.0);
+        dist = max(rectSDF(f.uv, f.size), cut);
+    }
     
     // Cut off points outside the shape or within the hollow area
     in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
 
+    // Mask out the gaps between dashes, measured in local space around the middle of the outline
+    if f.dash_pattern > 0u {
+        var mid_size = max(f.size - f.thickness / 2.0, vec2<f32>(0.0));
+        in_shape = min(in_shape, core::dash_pattern_mask(perimeterDistance(f.uv, mid_size) * f.uv_scale, f.dash_pattern));
+    }
 
-
-    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+    var color = core::color_output(vec4<f32>(shape_color.rgb, in_shape));
 #ifdef TEXTURED
-    color = color * textureSample(image, image_sampler, f.texture_uv);
+    color = color * sample_texture(f);
 #endif
 
     // Discard fragments no longer in the shape
@@ -145,4 +253,15 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
 
     return color;
 }
+#ifdef TEXTURED
+// Sample the texture rect within the repetition containing the fragment, the last repetition along each axis may be partial
+fn sample_texture(f: FragmentInput) -> vec4<f32> {
+    var tile = clamp(floor(f.texture_uv), vec2<f32>(0.0), ceil(f.texture_tiles) - 1.0);
+    var extent = f.texture_rect.zw - f.texture_rect.xy;
+    var uv = f.texture_rect.xy + (f.texture_uv - tile) * extent;
+
+    // Take gradients from the continuous position so that mip levels aren't disturbed at the seams between repetitions
+    return textureSampleGrad(image, image_sampler, uv, dpdx(f.texture_uv) * extent, dpdy(f.texture_uv) * extent);
+}
+#endif
 #endif
\ No newline at end of file
@@ -0,0 +1,137 @@
+#import bevy_vector_shapes::core
+#import bevy_vector_shapes::core::{view, image, image_sampler}
+#import bevy_vector_shapes::constants::{PI, TAU}
+
+struct Vertex {
+    @builtin(instance_index) index: u32,
+    @location(0) pos: vec3<f32>
+};
+
+struct Shape {
+    @location(0) matrix_0: vec4<f32>,
+    @location(1) matrix_1: vec4<f32>,
+    @location(2) matrix_2: vec4<f32>,
+    @location(3) matrix_3: vec4<f32>,
+
+    @location(4) color: vec4<f32>,
+    @location(5) thickness: f32,
+    @location(6) flags: u32,
+
+    @location(7) inner_radius: f32,
+    @location(8) outer_radius: f32,
+    @location(9) start_angle: f32,
+    @location(10) end_angle: f32,
+    @location(11) dash_pattern: u32,
+};
+
+#ifdef PER_OBJECT_BUFFER_BATCH_SIZE
+@group(1) @binding(0) var<uniform> shapes: array<Shape, #{PER_OBJECT_BUFFER_BATCH_SIZE}u>;
+#else
+@group(1) @binding(0) var<storage> shapes: array<Shape>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) inner_ratio: f32,
+    @location(3) delta: f32,
+#ifdef TEXTURED
+    @location(4) texture_uv: vec2<f32>,
+#endif
+    @location(5) @interpolate(flat) dash_pattern: u32,
+    // Outer radius in local space, the length of one unit of our uv space
+    @location(6) outer_radius: f32,
+};
+
+@vertex
+fn vertex(v: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Vertex positions for a basic quad
+    let vertex = v.pos;
+    let shape = shapes[v.index];
+
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
+        shape.matrix_0,
+        shape.matrix_1,
+        shape.matrix_2,
+        shape.matrix_3
+    ), shape.flags);
+
+    var vertex_data = core::get_vertex_data(matrix, vertex.xy * shape.outer_radius, shape.thickness, shape.flags);
+
+    // Multiply the world space position by the view projection matrix to convert to our clip position
+    out.clip_position = vertex_data.clip_pos;
+    out.uv = vertex.xy * vertex_data.uv_ratio;
+
+    // Our uv space is relative to the outer radius so express the inner radius in the same terms
+    out.inner_ratio = clamp(shape.inner_radius / shape.outer_radius, 0.0, 1.0);
+
+    // Setup angles for the fragment shader if we are an arc
+    var arc = core::f_arc(shape.flags);
+    if arc > 0u {
+        // Rotate our uv space such that y up is towards the center of our arc
+        var delta = (shape.end_angle - shape.start_angle) / 2.0;
+        out.uv = core::rotate_vec_a(out.uv, -(shape.start_angle - PI / 2.0 + delta));
+        out.delta = delta;
+    } else {
+        out.delta = PI;
+    }
+
+    out.dash_pattern = shape.dash_pattern;
+    out.outer_radius = shape.outer_radius;
+    out.color = shape.color;
+#ifdef TEXTURED
+    out.texture_uv = core::get_texture_uv(vertex_data.vertex);
+#endif
+    return out;
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) inner_ratio: f32,
+    @location(3) delta: f32,
+#ifdef TEXTURED
+    @location(4) texture_uv: vec2<f32>,
+#endif
+    @location(5) @interpolate(flat) dash_pattern: u32,
+    // Outer radius in local space, the length of one unit of our uv space
+    @location(6) outer_radius: f32,
+};
+
+// Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
+#ifdef FRAGMENT
+@fragment
+fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
+    // Mask representing whether this fragment falls within the shape
+    var in_shape = f.color.a;
+
+    // Cut off points outside the outer radius or within the inner radius
+    var dist = length(f.uv);
+    in_shape *= core::step_aa(f.inner_ratio, dist) * core::step_aa(dist, 1.);
+
+    // Cut off points outside the allowed range of angles
+    var angle = atan2(f.uv.y, f.uv.x);
+    in_shape *= core::step_aa_pd(-f.delta, angle, abs(angle)) * core::step_aa_pd(angle, f.delta, abs(angle));
+
+    // Mask out the gaps between dashes, measured in local space around the middle of the ring from the start of the arc
+    if f.dash_pattern > 0u {
+        var pos = (angle + f.delta) * (f.inner_ratio + 1.0) / 2.0 * f.outer_radius;
+        in_shape = min(in_shape, core::dash_pattern_mask(pos, f.dash_pattern));
+    }
+
+    var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
+#ifdef TEXTURED
+    color = color * textureSample(image, image_sampler, f.texture_uv);
+#endif
+
+    // Discard fragments no longer in the shape
+    if in_shape < 0.0001 {
+        discard;
+    }
+
+    return color;
+}
+#endif
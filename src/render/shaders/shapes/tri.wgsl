@@ -22,6 +22,12 @@ struct Shape {
     @location(8) v_1: vec2<f32>,
     @location(9) v_2: vec2<f32>,
     @location(10) roundness: f32,
+    @location(11) dash_pattern: u32,
+
+    // Colors multiplied with the shape's color at each vertex
+    @location(12) color_0: vec4<f32>,
+    @location(13) color_1: vec4<f32>,
+    @location(14) color_2: vec4<f32>,
 };
 
 #ifdef PER_OBJECT_BUFFER_BATCH_SIZE
@@ -44,6 +50,11 @@ struct VertexOutput {
 #ifdef TEXTURED
     @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(9) uv_scale: f32,
+    // Edges shared with another triangle, bit i being the edge from v_i to the next vertex
+    @location(10) @interpolate(flat) interior_edges: u32,
 };
 
 @vertex
@@ -53,25 +64,29 @@ fn vertex(v: Vertex) -> VertexOutput {
     // Vertex positions for a basic quad
     let shape = shapes[v.index];
     var vertex: vec2<f32>;
+    var vertex_color: vec4<f32>;
     switch v.vertex_index {
         default: {
             vertex = shape.v_0;
+            vertex_color = shape.color_0;
         }
         case 1u: {
             vertex = shape.v_1;
+            vertex_color = shape.color_1;
         }
         case 2u: {
             vertex = shape.v_2;
+            vertex_color = shape.color_2;
         }
     }
 
     // Reconstruct our transformation matrix
-    let matrix = mat4x4<f32>(
+    let matrix = core::get_fixed_size_matrix(mat4x4<f32>(
         shape.matrix_0,
         shape.matrix_1,
         shape.matrix_2,
         shape.matrix_3
-    );
+    ), shape.flags);
 
     let l_s_0 = length(shape.v_1 - shape.v_2);
     let l_s_1 = length(shape.v_2 - shape.v_0);
@@ -127,12 +142,18 @@ fn vertex(v: Vertex) -> VertexOutput {
     out.uv = vertex.xy * uv_ratio / min_dist;
     out.thickness = core::calculate_thickness(thickness_data, min_dist, shape.flags);
     out.roundness = min(shape.roundness / min_dist, 1.0);
+    out.dash_pattern = select(0u, shape.dash_pattern, core::f_hollow(shape.flags) == 1u);
+    out.uv_scale = min_dist;
+    out.interior_edges = core::f_interior_edges(shape.flags);
 
     out.v_0 = (v_0 / min_dist) * ((min_dist - 2.0 * shape.roundness) / min_dist);
     out.v_1 = (v_1 / min_dist) * ((min_dist - 2.0 * shape.roundness) / min_dist) ;
     out.v_2 = (v_2 / min_dist) * ((min_dist - 2.0 * shape.roundness) / min_dist) ;
 
-    out.color = shape.color;
+    // Our vertices are pushed away from the center by the padding so extrapolate their colors to match,
+    // the center's color is weighted by side length the same way as it's position
+    let center_color = (l_s_0 * shape.color_0 + l_s_1 * shape.color_1 + l_s_2 * shape.color_2) / p;
+    out.color = shape.color * mix(center_color, vertex_color, uv_ratio);
 #ifdef TEXTURED
     out.texture_uv = core::get_texture_uv(vertex.xy);
 #endif
@@ -151,6 +172,10 @@ struct FragmentInput {
 #ifdef TEXTURED
     @location(7) texture_uv: vec2<f32>,
 #endif
+    @location(8) @interpolate(flat) dash_pattern: u32,
+    // Length of one unit of our uv space in local space
+    @location(9) uv_scale: f32,
+    @location(10) @interpolate(flat) interior_edges: u32,
 };
 
 fn cross2d(a: vec2<f32>, b: vec2<f32>) -> f32 {
@@ -190,6 +215,33 @@ fn triangleSDF(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, c: vec2<f32>) -> f32 {
     return -sqrt(d.x) * sign(d.y);
 }
 
+// Distance around the outline through the given vertices to the point on the outline nearest to p
+fn perimeterDistance(p: vec2<f32>, vertices: array<vec2<f32>, 3>) -> f32 {
+    var v = vertices;
+    var nearest = 1e10;
+    var along = 0.0;
+    var before = 0.0;
+    for (var i = 0u; i < 3u; i++) {
+        let e = v[(i + 1u) % 3u] - v[i];
+        let w = p - v[i];
+        let t = clamp(dot(w, e) / dot(e, e), 0.0, 1.0);
+        let dist = length(w - e * t);
+        if dist < nearest {
+            nearest = dist;
+            along = before + t * length(e);
+        }
+        before += length(e);
+    }
+    return along;
+}
+
+// Signed distance from p to the line through the edge a -> b, positive on the opposite side to c
+fn edgeDistance(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, c: vec2<f32>) -> f32 {
+    let ab = b - a;
+    let s = sign(cross2d(ab, c - a));
+    return -s * cross2d(ab, p - a) / length(ab);
+}
+
 // Due to https://github.com/gfx-rs/naga/issues/1743 this cannot be compiled into the vertex shader on web
 #ifdef FRAGMENT
 @fragment
@@ -200,8 +252,30 @@ fn fragment(f: FragmentInput) -> @location(0) vec4<f32> {
     // Calculate our positions distance from the polygon
     var dist = triangleSDF(f.uv, f.v_0, f.v_1, f.v_2) - f.roundness;
 
+    // Edges shared with another triangle of the same polygon are cut without anti-aliasing so no seam shows between them,
+    // only the remaining edges are anti-aliased
+    var edge_dist = dist;
+    if f.interior_edges != 0u {
+        let v = array<vec2<f32>, 3>(f.v_0, f.v_1, f.v_2);
+        edge_dist = -1e10;
+        for (var i = 0u; i < 3u; i++) {
+            let d = edgeDistance(f.uv, v[i], v[(i + 1u) % 3u], v[(i + 2u) % 3u]);
+            if (f.interior_edges & (1u << i)) != 0u {
+                in_shape *= select(1.0, 0.0, d > 0.0);
+            } else {
+                edge_dist = max(edge_dist, d);
+            }
+        }
+    }
+
     // Cut off points outside the shape or within the hollow area
-    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(dist, 0.);
+    in_shape *= core::step_aa(-f.thickness, dist) * core::step_aa(edge_dist, 0.);
+
+    // Mask out the gaps between dashes, measured in local space around the outline
+    if f.dash_pattern > 0u {
+        var along = perimeterDistance(f.uv, array<vec2<f32>, 3>(f.v_0, f.v_1, f.v_2)) * f.uv_scale;
+        in_shape = min(in_shape, core::dash_pattern_mask(along, f.dash_pattern));
+    }
 
     var color = core::color_output(vec4<f32>(f.color.rgb, in_shape));
 #ifdef TEXTURED
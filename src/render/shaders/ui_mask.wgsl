@@ -0,0 +1,16 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0) var<uniform> color: vec4<f32>;
+@group(1) @binding(1) var image: texture_2d<f32>;
+@group(1) @binding(2) var image_sampler: sampler;
+@group(1) @binding(3) var mask: texture_2d<f32>;
+@group(1) @binding(4) var mask_sampler: sampler;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let image_color = textureSample(image, image_sampler, in.uv) * color;
+
+    // Canvases are cleared to transparent and shapes blended over them, so the mask's alpha is the coverage of it's shapes
+    let coverage = textureSample(mask, mask_sampler, in.uv).a;
+    return vec4<f32>(image_color.rgb, image_color.a * coverage);
+}
@@ -0,0 +1,303 @@
+use std::{
+    any::type_name,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy::{
+    prelude::*,
+    render::{Render, RenderApp, RenderSet},
+    utils::{HashMap, HashSet},
+};
+
+use crate::render::*;
+
+/// Instances and batches drawn for a single shape type in a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShapeTypeStats {
+    /// Number of extracted instances across both pipelines.
+    pub instances: u32,
+    /// Number of batches across all views.
+    pub batches: u32,
+}
+
+/// Event sent every frame with statistics about the shapes rendered, requires the `stats` feature.
+///
+/// Statistics are gathered in the render world and so describe the previous frame when pipelined rendering is enabled.
+#[derive(Event, Debug, Clone, Default, PartialEq)]
+pub struct ShapeFrameStats {
+    /// Statistics for each shape type drawn, keyed by the type name of it's [`ShapeData`].
+    pub types: HashMap<&'static str, ShapeTypeStats>,
+    /// Total number of batches across all shape types and views.
+    pub batches: u32,
+    /// Number of distinct textures bound.
+    pub textures: u32,
+    /// Number of distinct canvases drawn to.
+    pub canvases: u32,
+    /// Number of times consecutive batches of the same shape type in a view were split for each reason,
+    /// a split with several differences between it's batches is counted once for each.
+    pub breaks: HashMap<ShapeBatchBreak, u32>,
+}
+
+/// Reason that consecutive batches of the same shape type in a view couldn't be drawn as one, see [`ShapeFrameStats::breaks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ShapeBatchBreak {
+    /// Other items were sorted between the batches, such as shapes of another type or other transparent meshes.
+    Interleaved,
+    /// The batches were split at the number of instances a uniform buffer can hold, only on platforms without storage buffers.
+    BufferLimit,
+    Texture,
+    AlphaMode,
+    Canvas,
+    RenderLayers,
+    RenderPhase,
+    ClipRect,
+    /// Local anti-aliasing was disabled for one of the batches.
+    LocalAa,
+    SdfView,
+    /// Edge softness or edge falloff.
+    EdgeSoftness,
+    CullMode,
+    DepthTest,
+}
+
+impl ShapeBatchBreak {
+    /// Reasons the two consecutive batches were split.
+    pub fn between(a: &ShapeBatch, b: &ShapeBatch) -> Vec<Self> {
+        let (ma, mb) = (&a.material, &b.material);
+        let differences = [
+            (
+                ma.texture != mb.texture || ma.texture_color_space != mb.texture_color_space,
+                Self::Texture,
+            ),
+            (ma.alpha_mode != mb.alpha_mode, Self::AlphaMode),
+            (ma.canvas != mb.canvas, Self::Canvas),
+            (ma.render_layers != mb.render_layers, Self::RenderLayers),
+            (ma.render_phase != mb.render_phase, Self::RenderPhase),
+            (ma.clip_rect != mb.clip_rect, Self::ClipRect),
+            (ma.disable_laa != mb.disable_laa, Self::LocalAa),
+            (ma.sdf_view != mb.sdf_view, Self::SdfView),
+            (
+                ma.edge_softness != mb.edge_softness || ma.edge_falloff != mb.edge_falloff,
+                Self::EdgeSoftness,
+            ),
+            (ma.cull_mode != mb.cull_mode, Self::CullMode),
+            (ma.depth_test != mb.depth_test, Self::DepthTest),
+        ];
+        let reasons: Vec<Self> = differences
+            .into_iter()
+            .filter_map(|(differs, reason)| differs.then_some(reason))
+            .collect();
+        if !reasons.is_empty() {
+            reasons
+        } else if a.dynamic_offset != b.dynamic_offset {
+            vec![Self::BufferLimit]
+        } else {
+            vec![Self::Interleaved]
+        }
+    }
+
+    /// A suggestion for avoiding splits for this reason.
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::Interleaved => "draw shapes of the same type at similar depths or with a shared sort_bias so they sort next to each other",
+            Self::BufferLimit => "unavoidable without storage buffers, e.g. on WebGL",
+            Self::Texture => "pack textures into a ShapeTextureAtlas so shapes share a single image",
+            Self::AlphaMode => "draw shapes with the same alpha mode next to each other",
+            Self::Canvas => "draw everything for each canvas together",
+            Self::RenderLayers => "avoid alternating render layers between neighbouring shapes",
+            Self::RenderPhase => "avoid alternating render phases between neighbouring shapes",
+            Self::ClipRect => "share clip rects between neighbouring shapes, e.g. clip a whole panel at once",
+            Self::LocalAa => "avoid toggling disable_laa between neighbouring shapes",
+            Self::SdfView => "avoid toggling sdf_view between neighbouring shapes",
+            Self::EdgeSoftness => "share edge softness and falloff values between neighbouring shapes",
+            Self::CullMode => "avoid alternating cull modes between neighbouring shapes",
+            Self::DepthTest => "draw shapes without the depth test next to each other",
+        }
+    }
+}
+
+/// Statistics gathered by each shape type in the render world.
+#[derive(Resource, Default)]
+struct PendingShapeStats {
+    types: HashMap<&'static str, ShapeTypeStats>,
+    textures: HashSet<AssetId<Image>>,
+    canvases: HashSet<Entity>,
+    breaks: HashMap<ShapeBatchBreak, u32>,
+}
+
+/// Statistics of the last completed frame, shared between the main and render worlds.
+#[derive(Resource, Clone, Default)]
+struct ShapeStatsChannel(Arc<Mutex<Option<ShapeFrameStats>>>);
+
+fn collect_materials<'a>(
+    pending: &mut PendingShapeStats,
+    materials: impl Iterator<Item = &'a ShapePipelineMaterial>,
+) {
+    for material in materials {
+        if let Some(texture) = material.texture() {
+            pending.textures.insert(texture.id());
+        }
+        if let Some(canvas) = material.canvas() {
+            pending.canvases.insert(canvas);
+        }
+    }
+}
+
+fn collect_shape_stats<T: ShapeData>(
+    mut pending: ResMut<PendingShapeStats>,
+    batches: Res<ShapeBatches<T>>,
+    instances_2d: Res<Shape2dInstances<T>>,
+    materials_2d: Res<Shape2dMaterials<T>>,
+    instances_3d: Option<Res<Shape3dInstances<T>>>,
+    materials_3d: Option<Res<Shape3dMaterials<T>>>,
+) {
+    let instances = instances_2d.len() + instances_3d.as_ref().map_or(0, |i| i.len());
+    let batch_count: usize = batches.values().map(Vec::len).sum();
+    if instances == 0 && batch_count == 0 {
+        return;
+    }
+
+    pending.types.insert(
+        type_name::<T>(),
+        ShapeTypeStats {
+            instances: instances as u32,
+            batches: batch_count as u32,
+        },
+    );
+
+    for view_batches in batches.values() {
+        for pair in view_batches.windows(2) {
+            for reason in ShapeBatchBreak::between(&pair[0], &pair[1]) {
+                *pending.breaks.entry(reason).or_default() += 1;
+            }
+        }
+    }
+
+    collect_materials(&mut pending, materials_2d.keys());
+    if let Some(materials_3d) = &materials_3d {
+        collect_materials(&mut pending, materials_3d.keys());
+    }
+}
+
+fn finish_shape_stats(mut pending: ResMut<PendingShapeStats>, channel: Res<ShapeStatsChannel>) {
+    let PendingShapeStats {
+        types,
+        textures,
+        canvases,
+        breaks,
+    } = std::mem::take(&mut *pending);
+
+    let stats = ShapeFrameStats {
+        batches: types.values().map(|t| t.batches).sum(),
+        textures: textures.len() as u32,
+        canvases: canvases.len() as u32,
+        types,
+        breaks,
+    };
+    *channel.0.lock().unwrap() = Some(stats);
+}
+
+fn send_shape_stats(channel: Res<ShapeStatsChannel>, mut events: EventWriter<ShapeFrameStats>) {
+    if let Some(stats) = channel.0.lock().unwrap().take() {
+        events.send(stats);
+    }
+}
+
+/// Plugin that periodically logs why shapes weren't drawn in fewer batches with a suggested fix for each reason,
+/// aggregated from [`ShapeFrameStats::breaks`], requires the `stats` feature.
+///
+/// Intended for investigating unexpectedly high batch counts, reports are only logged while batches are being split.
+pub struct ShapeBatchReportPlugin {
+    /// Time between reports.
+    pub interval: Duration,
+}
+
+impl Default for ShapeBatchReportPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Plugin for ShapeBatchReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ShapeBatchReport {
+            timer: Timer::new(self.interval, TimerMode::Repeating),
+            ..default()
+        })
+        .add_systems(Update, report_batch_breaks);
+    }
+}
+
+/// Statistics accumulated since the last report of [`ShapeBatchReportPlugin`].
+#[derive(Resource, Default)]
+struct ShapeBatchReport {
+    timer: Timer,
+    frames: u32,
+    batches: u32,
+    breaks: HashMap<ShapeBatchBreak, u32>,
+}
+
+fn report_batch_breaks(
+    time: Res<Time>,
+    mut events: EventReader<ShapeFrameStats>,
+    mut report: ResMut<ShapeBatchReport>,
+) {
+    for stats in events.read() {
+        report.frames += 1;
+        report.batches += stats.batches;
+        for (reason, count) in &stats.breaks {
+            *report.breaks.entry(*reason).or_default() += count;
+        }
+    }
+
+    if !report.timer.tick(time.delta()).just_finished() || report.frames == 0 {
+        return;
+    }
+    let frames = std::mem::take(&mut report.frames);
+    let batches = std::mem::take(&mut report.batches);
+    let breaks = std::mem::take(&mut report.breaks);
+    if breaks.is_empty() {
+        return;
+    }
+
+    let mut breaks: Vec<_> = breaks.into_iter().collect();
+    breaks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut message = format!(
+        "Shapes were drawn in {:.1} batches per frame over the last {} frames, batches were split by:",
+        batches as f32 / frames as f32,
+        frames
+    );
+    for (reason, count) in breaks {
+        let _ = write!(
+            message,
+            "\n  {:?}: {:.1} per frame, {}",
+            reason,
+            count as f32 / frames as f32,
+            reason.suggestion()
+        );
+    }
+    info!("{message}");
+}
+
+pub(crate) fn setup_stats(app: &mut App) {
+    let channel = ShapeStatsChannel::default();
+    app.insert_resource(channel.clone())
+        .add_event::<ShapeFrameStats>()
+        .add_systems(First, send_shape_stats);
+
+    app.sub_app_mut(RenderApp)
+        .insert_resource(channel)
+        .init_resource::<PendingShapeStats>()
+        .add_systems(Render, finish_shape_stats.in_set(RenderSet::Cleanup));
+}
+
+pub(crate) fn setup_type_stats<T: ShapeData>(app: &mut App) {
+    app.sub_app_mut(RenderApp).add_systems(
+        Render,
+        collect_shape_stats::<T>.in_set(RenderSet::PrepareBindGroups),
+    );
+}
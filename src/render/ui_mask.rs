@@ -0,0 +1,66 @@
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+pub const UI_MASK_HANDLE: Handle<Shader> = Handle::weak_from_u128(11906270335926451783);
+
+/// [`UiMaterial`] drawing an image through the alpha of a mask image stretched over the node, requires the `ui` feature.
+///
+/// The mask is usually the image of a [`Canvas`](crate::painter::Canvas) cleared to transparent, so the node is clipped
+/// to the shapes drawn to the canvas, e.g. rounded or irregular frames around UI images.
+/// Use as a [`MaterialNode`]. To mask text or other node contents render them to an image with a camera targeting
+/// the image and [`TargetCamera`] on the nodes, then draw that image through the mask.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct ShapeUiMask {
+    /// Tint applied to the image, or the color of the node if it has no image.
+    #[uniform(0)]
+    pub color: LinearRgba,
+    #[texture(1)]
+    #[sampler(2)]
+    pub image: Option<Handle<Image>>,
+    #[texture(3)]
+    #[sampler(4)]
+    pub mask: Handle<Image>,
+}
+
+impl ShapeUiMask {
+    /// Fills the node with white through the given mask.
+    pub fn new(mask: Handle<Image>) -> Self {
+        Self {
+            color: LinearRgba::WHITE,
+            image: None,
+            mask,
+        }
+    }
+
+    pub fn with_image(mut self, image: Handle<Image>) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub fn with_color(mut self, color: impl Into<LinearRgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+}
+
+impl UiMaterial for ShapeUiMask {
+    fn fragment_shader() -> ShaderRef {
+        UI_MASK_HANDLE.into()
+    }
+}
+
+pub(crate) fn build_ui_mask(app: &mut App) {
+    app.add_plugins(UiMaterialPlugin::<ShapeUiMask>::default());
+}
+
+pub(crate) fn setup_ui_mask(app: &mut App) {
+    load_internal_asset!(
+        app,
+        UI_MASK_HANDLE,
+        "shaders/ui_mask.wgsl",
+        Shader::from_wgsl
+    );
+}
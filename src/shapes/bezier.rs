@@ -0,0 +1,238 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, BEZIER_HANDLE},
+};
+
+/// Component containing the data for drawing a bezier curve.
+///
+/// Curves are stored as cubic bezier curves in the local xy plane of their transform, quadratic curves are elevated to cubic curves.
+/// Dash patterns are measured in local space along the curve and each dash is capped according to `cap`.
+#[derive(Component, Reflect)]
+pub struct BezierComponent {
+    pub alignment: Alignment,
+    pub cap: Cap,
+
+    /// Control points of the curve in world space relative to it's transform.
+    ///
+    /// The curve starts at the first point and ends at the last point.
+    pub points: [Vec2; 4],
+}
+
+impl BezierComponent {
+    pub fn new(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        Self {
+            alignment: config.alignment,
+            cap: config.cap,
+
+            points: [p0, p1, p2, p3],
+        }
+    }
+
+    pub fn quadratic(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2) -> Self {
+        let [p0, p1, p2, p3] = quadratic_to_cubic(p0, p1, p2);
+        Self::new(config, p0, p1, p2, p3)
+    }
+}
+
+impl Default for BezierComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+            cap: default(),
+
+            points: [
+                Vec2::new(-1.0, 0.0),
+                Vec2::new(-0.5, 1.0),
+                Vec2::new(0.5, -1.0),
+                Vec2::new(1.0, 0.0),
+            ],
+        }
+    }
+}
+
+/// Convert the control points of a quadratic bezier curve to those of the equivalent cubic bezier curve.
+fn quadratic_to_cubic(p0: Vec2, p1: Vec2, p2: Vec2) -> [Vec2; 4] {
+    [
+        p0,
+        p0 + (p1 - p0) * 2.0 / 3.0,
+        p2 + (p1 - p2) * 2.0 / 3.0,
+        p2,
+    ]
+}
+
+impl ShapeComponent for BezierComponent {
+    type Data = BezierData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> BezierData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+        flags.set_cap(self.cap);
+
+        BezierData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            points: self.points.map(Into::into),
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+        }
+    }
+}
+
+/// Raw data sent to the bezier shader to draw a bezier curve
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct BezierData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    points: [[f32; 2]; 4],
+    dash_pattern: u32,
+
+    padding: f32,
+}
+
+impl BezierData {
+    pub fn new(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_cap(config.cap);
+
+        BezierData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            points: [p0.into(), p1.into(), p2.into(), p3.into()],
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+        }
+    }
+
+    pub fn quadratic(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2) -> Self {
+        let [p0, p1, p2, p3] = quadratic_to_cubic(p0, p1, p2);
+        Self::new(config, p0, p1, p2, p3)
+    }
+}
+
+impl ShapeData for BezierData {
+    type Component = BezierComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x2,
+            8 => Float32x2,
+            9 => Float32x2,
+            10 => Float32x2,
+            11 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        BEZIER_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw bezier curves.
+pub trait BezierPainter {
+    fn bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> &mut Self;
+    fn quadratic_bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2) -> &mut Self;
+}
+
+impl<'w, 's> BezierPainter for ShapePainter<'w, 's> {
+    fn bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> &mut Self {
+        self.send(BezierData::new(self.config(), p0, p1, p2, p3))
+    }
+
+    fn quadratic_bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2) -> &mut Self {
+        self.send(BezierData::quadratic(self.config(), p0, p1, p2))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of bezier curve bundles.
+pub trait BezierBundle {
+    fn bezier(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self;
+    fn quadratic_bezier(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2) -> Self;
+}
+
+impl BezierBundle for ShapeBundle<BezierComponent> {
+    fn bezier(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        let mut bundle = Self::new(config, BezierComponent::new(config, p0, p1, p2, p3));
+        bundle.fill.ty = FillType::Stroke(config.thickness, config.thickness_type);
+        bundle
+    }
+
+    fn quadratic_bezier(config: &ShapeConfig, p0: Vec2, p1: Vec2, p2: Vec2) -> Self {
+        let mut bundle = Self::new(config, BezierComponent::quadratic(config, p0, p1, p2));
+        bundle.fill.ty = FillType::Stroke(config.thickness, config.thickness_type);
+        bundle
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of bezier curve entities.
+pub trait BezierSpawner<'w> {
+    fn bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> ShapeEntityCommands;
+    fn quadratic_bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> BezierSpawner<'w> for T {
+    fn bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::bezier(self.config(), p0, p1, p2, p3))
+    }
+
+    fn quadratic_bezier(&mut self, p0: Vec2, p1: Vec2, p2: Vec2) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::quadratic_bezier(self.config(), p0, p1, p2))
+    }
+}
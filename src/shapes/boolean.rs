@@ -0,0 +1,486 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, BOOLEAN_HANDLE},
+};
+
+/// Operation used to combine the two operands of a boolean shape.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum BooleanOp {
+    /// Area covered by either operand.
+    #[default]
+    Union,
+    /// Area covered by both operands.
+    Intersection,
+    /// Area covered by the first operand but not the second, useful for cutouts.
+    Subtraction,
+}
+
+/// Primitive shape that can be combined by a boolean shape.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum BooleanPrimitive {
+    Circle {
+        radius: f32,
+    },
+    Rect {
+        size: Vec2,
+        /// Radius of the rounded corners.
+        roundness: f32,
+    },
+    /// Regular polygon with a vertex pointing up.
+    Ngon {
+        sides: f32,
+        /// Distance from the center to each vertex.
+        radius: f32,
+        /// Radius of the rounded corners.
+        roundness: f32,
+    },
+}
+
+impl BooleanPrimitive {
+    pub fn circle(radius: f32) -> Self {
+        Self::Circle { radius }
+    }
+
+    pub fn rect(size: Vec2) -> Self {
+        Self::Rect {
+            size,
+            roundness: 0.0,
+        }
+    }
+
+    pub fn ngon(sides: f32, radius: f32) -> Self {
+        Self::Ngon {
+            sides,
+            radius,
+            roundness: 0.0,
+        }
+    }
+
+    /// Place this primitive at the given offset from the center of the boolean shape.
+    pub fn at(self, offset: Vec2) -> BooleanOperand {
+        BooleanOperand {
+            primitive: self,
+            offset,
+            rotation: 0.0,
+        }
+    }
+
+    /// Distance from the center of the primitive to it's furthest point.
+    pub fn bounding_radius(&self) -> f32 {
+        match *self {
+            Self::Circle { radius } => radius.abs(),
+            Self::Rect { size, .. } => size.length() / 2.0,
+            Self::Ngon { radius, .. } => radius.abs(),
+        }
+    }
+
+    fn kind(&self) -> u32 {
+        match self {
+            Self::Circle { .. } => 0,
+            Self::Rect { .. } => 1,
+            Self::Ngon { .. } => 2,
+        }
+    }
+
+    fn params(&self) -> [f32; 4] {
+        match *self {
+            Self::Circle { radius } => [radius, 0.0, 0.0, 0.0],
+            Self::Rect { size, roundness } => [size.x, size.y, roundness, 0.0],
+            Self::Ngon {
+                sides,
+                radius,
+                roundness,
+            } => [sides, radius, roundness, 0.0],
+        }
+    }
+}
+
+/// A [`BooleanPrimitive`] placed relative to the center of a boolean shape.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct BooleanOperand {
+    pub primitive: BooleanPrimitive,
+    pub offset: Vec2,
+    /// Counter-clockwise rotation of the primitive around it's own center, in radians.
+    pub rotation: f32,
+}
+
+impl BooleanOperand {
+    pub fn rotated(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    fn bounding_radius(&self) -> f32 {
+        self.offset.length() + self.primitive.bounding_radius()
+    }
+}
+
+impl From<BooleanPrimitive> for BooleanOperand {
+    fn from(primitive: BooleanPrimitive) -> Self {
+        primitive.at(Vec2::ZERO)
+    }
+}
+
+impl Default for BooleanOperand {
+    fn default() -> Self {
+        BooleanPrimitive::circle(1.0).into()
+    }
+}
+
+/// Component containing the data for drawing a boolean shape.
+///
+/// Boolean shapes combine the signed distance fields of two primitives in a single instance,
+/// e.g. a rect minus a circle for a cutout frame.
+#[derive(Component, Reflect)]
+pub struct BooleanComponent {
+    pub alignment: Alignment,
+
+    pub op: BooleanOp,
+    pub a: BooleanOperand,
+    pub b: BooleanOperand,
+    /// Distance over which the operands are blended together, zero for a sharp seam.
+    pub smoothness: f32,
+}
+
+impl BooleanComponent {
+    pub fn new(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: BooleanOperand,
+        b: BooleanOperand,
+        smoothness: f32,
+    ) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            op,
+            a,
+            b,
+            smoothness,
+        }
+    }
+}
+
+impl ShapeComponent for BooleanComponent {
+    type Data = BooleanData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> BooleanData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        BooleanData::from_parts(
+            tf.compute_matrix(),
+            fill.color,
+            thickness,
+            flags,
+            self.op,
+            self.a,
+            self.b,
+            self.smoothness,
+        )
+    }
+}
+
+impl Default for BooleanComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            op: default(),
+            a: default(),
+            b: default(),
+            smoothness: 0.0,
+        }
+    }
+}
+
+/// Raw data sent to the boolean shader to draw a boolean shape
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct BooleanData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    offset_a: [f32; 2],
+    params_a: [f32; 4],
+    params_b: [f32; 4],
+    offset_b: [f32; 2],
+    rotations: [f32; 2],
+    ops: u32,
+    smoothness: f32,
+    extent: f32,
+
+    padding: [f32; 1],
+}
+
+impl BooleanData {
+    pub fn new(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: BooleanOperand,
+        b: BooleanOperand,
+        smoothness: f32,
+    ) -> BooleanData {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_hollow(config.hollow as u32);
+
+        Self::from_parts(
+            config.transform.compute_matrix(),
+            config.color,
+            config.thickness,
+            flags,
+            op,
+            a,
+            b,
+            smoothness,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn from_parts(
+        transform: Mat4,
+        color: Color,
+        thickness: f32,
+        flags: Flags,
+        op: BooleanOp,
+        a: BooleanOperand,
+        b: BooleanOperand,
+        smoothness: f32,
+    ) -> BooleanData {
+        // Smoothing a union extends it's surface by at most a quarter of the smoothness,
+        // intersections and subtractions only ever shrink
+        let extent = match op {
+            BooleanOp::Union => {
+                a.bounding_radius().max(b.bounding_radius()) + smoothness.max(0.0) / 4.0
+            }
+            BooleanOp::Intersection => a.bounding_radius().min(b.bounding_radius()),
+            BooleanOp::Subtraction => a.bounding_radius(),
+        };
+
+        BooleanData {
+            transform: transform.to_cols_array_2d(),
+
+            color: color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            offset_a: a.offset.into(),
+            params_a: a.primitive.params(),
+            params_b: b.primitive.params(),
+            offset_b: b.offset.into(),
+            rotations: [a.rotation, b.rotation],
+            ops: a.primitive.kind() | (b.primitive.kind() << 2) | ((op as u32) << 4),
+            smoothness,
+            extent,
+
+            padding: default(),
+        }
+    }
+}
+
+impl ShapeData for BooleanData {
+    type Component = BooleanComponent;
+    const DEPTH_SLICES: bool = true;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x2,
+            8 => Float32x4,
+            9 => Float32x4,
+            10 => Float32x2,
+            11 => Float32x2,
+            12 => Uint32,
+            13 => Float32,
+            14 => Float32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        BOOLEAN_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        // Outlines are drawn inside the combined shape so never extend past it
+        Some(self.extent)
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw boolean shapes.
+pub trait BooleanPainter {
+    /// Draws the combination of two primitives, e.g. `painter.boolean(BooleanOp::Subtraction, rect, circle)`.
+    fn boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> &mut Self;
+
+    /// Draws the combination of two primitives, blending them together over the given distance.
+    fn smooth_boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> &mut Self;
+}
+
+impl<'w, 's> BooleanPainter for ShapePainter<'w, 's> {
+    fn boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> &mut Self {
+        self.smooth_boolean(op, a, b, 0.0)
+    }
+
+    fn smooth_boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> &mut Self {
+        self.send(BooleanData::new(
+            self.config(),
+            op,
+            a.into(),
+            b.into(),
+            smoothness,
+        ))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of boolean shape bundles.
+pub trait BooleanBundle {
+    fn boolean(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> Self;
+
+    fn smooth_boolean(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> Self;
+}
+
+impl BooleanBundle for ShapeBundle<BooleanComponent> {
+    fn boolean(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> Self {
+        Self::smooth_boolean(config, op, a, b, 0.0)
+    }
+
+    fn smooth_boolean(
+        config: &ShapeConfig,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> Self {
+        Self::new(
+            config,
+            BooleanComponent::new(config, op, a.into(), b.into(), smoothness),
+        )
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of boolean shape entities.
+pub trait BooleanSpawner<'w> {
+    fn boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> ShapeEntityCommands;
+
+    fn smooth_boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> BooleanSpawner<'w> for T {
+    fn boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+    ) -> ShapeEntityCommands {
+        self.smooth_boolean(op, a, b, 0.0)
+    }
+
+    fn smooth_boolean(
+        &mut self,
+        op: BooleanOp,
+        a: impl Into<BooleanOperand>,
+        b: impl Into<BooleanOperand>,
+        smoothness: f32,
+    ) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::smooth_boolean(
+            self.config(),
+            op,
+            a,
+            b,
+            smoothness,
+        ))
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, CAPSULE_HANDLE},
+};
+
+/// Component containing the data for drawing a capsule.
+///
+/// Unlike a line with round caps the size of a capsule is independent of it's thickness, allowing it to be hollow.
+#[derive(Component, Clone, PartialEq, Reflect)]
+pub struct CapsuleComponent {
+    pub alignment: Alignment,
+
+    /// Position of the center of the first cap in world space relative to it's transform.
+    pub start: Vec3,
+    /// Position of the center of the second cap in world space relative to it's transform.
+    pub end: Vec3,
+    /// Radius of the capsule.
+    pub radius: f32,
+}
+
+impl CapsuleComponent {
+    pub fn new(config: &ShapeConfig, start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            start,
+            end,
+            radius,
+        }
+    }
+}
+
+impl Default for CapsuleComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            start: Vec3::new(0.0, -0.5, 0.0),
+            end: Vec3::new(0.0, 0.5, 0.0),
+            radius: 0.5,
+        }
+    }
+}
+
+impl ShapeComponent for CapsuleComponent {
+    type Data = CapsuleData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> CapsuleData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        CapsuleData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            start: self.start,
+            end: self.end,
+            radius: self.radius,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+        }
+    }
+}
+
+/// Raw data sent to the capsule shader to draw a capsule
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct CapsuleData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    start: Vec3,
+    end: Vec3,
+    radius: f32,
+    dash_pattern: u32,
+}
+
+impl CapsuleData {
+    pub fn new(config: &ShapeConfig, start: Vec3, end: Vec3, radius: f32) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_hollow(config.hollow as u32);
+
+        CapsuleData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            start,
+            end,
+            radius,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+        }
+    }
+}
+
+impl ShapeData for CapsuleData {
+    type Component = CapsuleComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+            7 => Float32x3,
+            8 => Float32x3,
+            9 => Float32,
+            10 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        CAPSULE_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw capsules.
+pub trait CapsulePainter {
+    fn capsule(&mut self, start: Vec3, end: Vec3, radius: f32) -> &mut Self;
+}
+
+impl<'w, 's> CapsulePainter for ShapePainter<'w, 's> {
+    fn capsule(&mut self, start: Vec3, end: Vec3, radius: f32) -> &mut Self {
+        self.send_filled(|config| CapsuleData::new(config, start, end, radius))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of capsule bundles.
+pub trait CapsuleBundle {
+    fn capsule(config: &ShapeConfig, start: Vec3, end: Vec3, radius: f32) -> Self;
+}
+
+impl CapsuleBundle for ShapeBundle<CapsuleComponent> {
+    fn capsule(config: &ShapeConfig, start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self::new(config, CapsuleComponent::new(config, start, end, radius))
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of capsule entities.
+pub trait CapsuleSpawner<'w> {
+    fn capsule(&mut self, start: Vec3, end: Vec3, radius: f32) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> CapsuleSpawner<'w> for T {
+    fn capsule(&mut self, start: Vec3, end: Vec3, radius: f32) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::capsule(self.config(), start, end, radius))
+    }
+}
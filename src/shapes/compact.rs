@@ -0,0 +1,225 @@
+use bevy::{
+    color::ColorToPacked,
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{ShapeComponent, ShapeData, COMPACT_HANDLE},
+};
+
+/// Primitive drawn by a [`CompactComponent`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CompactKind {
+    #[default]
+    Circle,
+    Rectangle,
+}
+
+/// Component containing the data for drawing a compact circle or rectangle.
+///
+/// Compact shapes trade every feature beyond a filled circle or rectangle in the plane of their transform for
+/// a fraction of the instance data of a [`DiscComponent`] or [`RectangleComponent`], for very large numbers of particle like shapes.
+/// Alignment, outlines, rounding, textures and shear are ignored, colors are stored with 8 bits per channel
+/// and only the rotation of the transform about the z axis is kept.
+#[derive(Component, Clone, PartialEq, Reflect)]
+pub struct CompactComponent {
+    pub kind: CompactKind,
+    /// Radius of a circle or the size of a rectangle.
+    pub size: Vec2,
+}
+
+impl CompactComponent {
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            kind: CompactKind::Circle,
+            size: Vec2::splat(radius),
+        }
+    }
+
+    pub fn rect(size: Vec2) -> Self {
+        Self {
+            kind: CompactKind::Rectangle,
+            size,
+        }
+    }
+
+    /// Extents of the shape from it's center, the radius for circles.
+    fn half_size(&self) -> Vec2 {
+        match self.kind {
+            CompactKind::Circle => self.size,
+            CompactKind::Rectangle => self.size / 2.0,
+        }
+    }
+}
+
+impl Default for CompactComponent {
+    fn default() -> Self {
+        Self::circle(1.0)
+    }
+}
+
+impl ShapeComponent for CompactComponent {
+    type Data = CompactData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> CompactData {
+        CompactData::new(
+            &tf.compute_transform(),
+            fill.color,
+            self.kind,
+            self.half_size(),
+        )
+    }
+}
+
+/// Raw data sent to the compact shader to draw a circle or rectangle, under a quarter of the size of [`DiscData`].
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct CompactData {
+    position: [f32; 2],
+    half_size: [f32; 2],
+    rotation: f32,
+    z: f32,
+    /// sRGB color with 8 bits per channel, red in the lowest byte.
+    color: u32,
+    kind: u32,
+}
+
+impl CompactData {
+    pub fn new(transform: &Transform, color: Color, kind: CompactKind, half_size: Vec2) -> Self {
+        let mut data = CompactData {
+            color: u32::from_le_bytes(color.to_srgba().to_u8_array()),
+            kind: kind as u32,
+            half_size: half_size.to_array(),
+            ..default()
+        };
+        data.set_transform(transform.compute_matrix());
+        data
+    }
+
+    pub fn circle(config: &ShapeConfig, radius: f32) -> Self {
+        Self::new(
+            &config.transform,
+            config.color,
+            CompactKind::Circle,
+            Vec2::splat(radius),
+        )
+    }
+
+    pub fn rect(config: &ShapeConfig, size: Vec2) -> Self {
+        Self::new(
+            &config.transform,
+            config.color,
+            CompactKind::Rectangle,
+            size / 2.0,
+        )
+    }
+}
+
+impl ShapeData for CompactData {
+    type Component = CompactComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x2,
+            1 => Float32x2,
+            2 => Float32,
+            3 => Float32,
+            4 => Uint32,
+            5 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        COMPACT_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        // Scale is baked into the size of the shape
+        Mat4::from_rotation_translation(
+            Quat::from_rotation_z(self.rotation),
+            Vec3::new(self.position[0], self.position[1], self.z),
+        )
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+        self.position = translation.truncate().to_array();
+        self.z = translation.z;
+        self.rotation = rotation.to_euler(EulerRot::ZYX).0;
+        self.half_size = (Vec2::from_array(self.half_size) * scale.truncate()).to_array();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        let half_size = Vec2::from_array(self.half_size);
+        Some(match self.kind {
+            0 => half_size.x.abs(),
+            _ => half_size.length(),
+        })
+    }
+
+    fn color(&self) -> [f32; 4] {
+        Color::from(Srgba::from_u8_array(self.color.to_le_bytes()))
+            .to_linear()
+            .to_f32_array()
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        let color = Color::from(LinearRgba::from_f32_array(color));
+        self.color = u32::from_le_bytes(color.to_srgba().to_u8_array());
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw compact shapes.
+pub trait CompactPainter {
+    /// Draws a filled circle with minimal instance data, see [`CompactComponent`].
+    fn compact_circle(&mut self, radius: f32) -> &mut Self;
+    /// Draws a filled rectangle with minimal instance data, see [`CompactComponent`].
+    fn compact_rect(&mut self, size: Vec2) -> &mut Self;
+}
+
+impl<'w, 's> CompactPainter for ShapePainter<'w, 's> {
+    fn compact_circle(&mut self, radius: f32) -> &mut Self {
+        self.send(CompactData::circle(self.config(), radius))
+    }
+
+    fn compact_rect(&mut self, size: Vec2) -> &mut Self {
+        self.send(CompactData::rect(self.config(), size))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of compact shape bundles.
+pub trait CompactBundle {
+    fn compact_circle(config: &ShapeConfig, radius: f32) -> Self;
+    fn compact_rect(config: &ShapeConfig, size: Vec2) -> Self;
+}
+
+impl CompactBundle for ShapeBundle<CompactComponent> {
+    fn compact_circle(config: &ShapeConfig, radius: f32) -> Self {
+        Self::new(config, CompactComponent::circle(radius))
+    }
+
+    fn compact_rect(config: &ShapeConfig, size: Vec2) -> Self {
+        Self::new(config, CompactComponent::rect(size))
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of compact shape entities.
+pub trait CompactSpawner<'w> {
+    fn compact_circle(&mut self, radius: f32) -> ShapeEntityCommands;
+    fn compact_rect(&mut self, size: Vec2) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> CompactSpawner<'w> for T {
+    fn compact_circle(&mut self, radius: f32) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::compact_circle(self.config(), radius))
+    }
+
+    fn compact_rect(&mut self, size: Vec2) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::compact_rect(self.config(), size))
+    }
+}
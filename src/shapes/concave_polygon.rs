@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Triangulate a simple polygon by ear clipping, returning indices into `points` for each triangle.
+///
+/// Points may be given in either winding order, triangles are always returned counter-clockwise.
+/// Self intersecting polygons are not supported and will produce incomplete results.
+pub fn triangulate_polygon(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    if points.len() < 3 {
+        return triangles;
+    }
+
+    // Ensure counter-clockwise winding so that convex corners have a positive cross product
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    let area: f32 = (0..points.len())
+        .map(|i| points[i].perp_dot(points[(i + 1) % points.len()]))
+        .sum();
+    if area < 0.0 {
+        indices.reverse();
+    }
+
+    let mut i = 0;
+    let mut attempts = 0;
+    while indices.len() > 3 {
+        let len = indices.len();
+        let (a, b, c) = (
+            indices[(i + len - 1) % len],
+            indices[i],
+            indices[(i + 1) % len],
+        );
+        let convex = (points[b] - points[a]).perp_dot(points[c] - points[b]) > 0.0;
+
+        // If we have gone all the way around without finding an ear the polygon is degenerate,
+        // clip the vertex anyway to guarantee termination but only keep the triangle if it's valid
+        let forced = attempts >= len;
+        if forced || (convex && is_ear(points, &indices, a, b, c)) {
+            if convex {
+                triangles.push([a, b, c]);
+            }
+            indices.remove(i);
+            i %= indices.len();
+            attempts = 0;
+        } else {
+            i = (i + 1) % len;
+            attempts += 1;
+        }
+    }
+
+    let (a, b, c) = (indices[0], indices[1], indices[2]);
+    if (points[b] - points[a]).perp_dot(points[c] - points[b]) > 0.0 {
+        triangles.push([a, b, c]);
+    }
+
+    triangles
+}
+
+/// Check that no remaining vertex of the polygon falls within the triangle formed by `a`, `b` and `c`.
+fn is_ear(points: &[Vec2], indices: &[usize], a: usize, b: usize, c: usize) -> bool {
+    let (v_a, v_b, v_c) = (points[a], points[b], points[c]);
+    !indices
+        .iter()
+        .filter(|&&p| p != a && p != b && p != c)
+        .map(|&p| points[p])
+        .any(|p| {
+            (v_b - v_a).perp_dot(p - v_a) >= 0.0
+                && (v_c - v_b).perp_dot(p - v_b) >= 0.0
+                && (v_a - v_c).perp_dot(p - v_c) >= 0.0
+        })
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw concave polygons.
+pub trait ConcavePolygonPainter {
+    /// Fill an arbitrary simple polygon by triangulating it and drawing each of the resulting triangles.
+    ///
+    /// The polygon is always filled and ignores roundness and vertex colors, use a [`PolylinePainter::polyline`] to draw it's outline.
+    fn polygon_concave(&mut self, points: &[Vec2]) -> &mut Self;
+}
+
+impl<'w, 's> ConcavePolygonPainter for ShapePainter<'w, 's> {
+    fn polygon_concave(&mut self, points: &[Vec2]) -> &mut Self {
+        let mut config = self.config().clone();
+        config.hollow = false;
+        config.roundness = 0.0;
+        config.vertex_colors = None;
+
+        // Edges between consecutive points are on the outline, any others are shared with another triangle
+        let len = points.len();
+        let interior = |a: usize, b: usize| (b != (a + 1) % len && a != (b + 1) % len) as u32;
+        for [a, b, c] in triangulate_polygon(points) {
+            let edges = interior(a, b) | interior(b, c) << 1 | interior(c, a) << 2;
+            self.send_with_config(
+                &config,
+                TriangleData::new(&config, points[a], points[b], points[c])
+                    .with_interior_edges(edges),
+            );
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(points: &[Vec2], [a, b, c]: [usize; 3]) -> f32 {
+        (points[b] - points[a]).perp_dot(points[c] - points[a]) / 2.0
+    }
+
+    fn total_area(points: &[Vec2], triangles: &[[usize; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|&triangle| area(points, triangle))
+            .sum()
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert!(triangulate_polygon(&[]).is_empty());
+        assert!(triangulate_polygon(&[Vec2::ZERO, Vec2::X]).is_empty());
+    }
+
+    #[test]
+    fn square() {
+        let points = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+        let triangles = triangulate_polygon(&points);
+        assert_eq!(triangles.len(), 2);
+        assert!((total_area(&points, &triangles) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clockwise_triangles_are_counter_clockwise() {
+        let points = [Vec2::ZERO, Vec2::Y, Vec2::ONE, Vec2::X];
+        let triangles = triangulate_polygon(&points);
+        assert_eq!(triangles.len(), 2);
+        assert!(triangles
+            .iter()
+            .all(|&triangle| area(&points, triangle) > 0.0));
+    }
+
+    #[test]
+    fn concave() {
+        // An L shape with a reflex corner at (1, 1)
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+        let triangles = triangulate_polygon(&points);
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles
+            .iter()
+            .all(|&triangle| area(&points, triangle) > 0.0));
+        assert!((total_area(&points, &triangles) - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn collinear_vertex_is_skipped() {
+        // The midpoint of the bottom edge forms no triangle of it's own
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let triangles = triangulate_polygon(&points);
+        assert!(triangles
+            .iter()
+            .all(|&triangle| area(&points, triangle) > 0.0));
+        assert!((total_area(&points, &triangles) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn degenerate_polygon_is_forced_to_terminate() {
+        // No vertex of a line is convex, so each is clipped once we have gone all the way around without finding an ear
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(3.0, 0.0),
+        ];
+        assert!(triangulate_polygon(&points).is_empty());
+    }
+
+    #[test]
+    fn forced_clip_keeps_valid_triangles() {
+        // Repeating the first point blocks every ear as it lies on the triangles' edges,
+        // the forced clips still keep the triangles that are valid
+        let points = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y, Vec2::ZERO];
+        let triangles = triangulate_polygon(&points);
+        assert!(triangles
+            .iter()
+            .all(|&triangle| area(&points, triangle) > 0.0));
+        assert!((total_area(&points, &triangles) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,284 @@
+use std::borrow::Cow;
+
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, ShapeType3dPlugin, ShapeTypePlugin},
+};
+
+/// Handles of the shaders drawing each custom sdf shape are offset from this by their id.
+const CUSTOM_SDF_HANDLE_BASE: u128 = 13460871025372984619;
+
+/// Source of the WGSL distance function of a custom sdf shape, see [`CustomSdfPlugin`].
+#[derive(Clone, Debug)]
+pub enum CustomSdfSource {
+    /// WGSL source defining the distance function, must not define it's own import path.
+    Wgsl(Cow<'static, str>),
+    /// Path of a WGSL asset defining the distance function, loaded with the [`AssetServer`].
+    Asset(String),
+}
+
+/// Plugin that draws [`CustomSdfComponent`]s with the given id using a distance function written in WGSL.
+///
+/// The source must define `fn sdf(p: vec2<f32>, size: vec2<f32>, params: vec4<f32>) -> f32`, returning the signed distance
+/// from `p` to the edge of the shape, negative inside. `p` is the position in the shape's local space with y up, relative
+/// to the center of a quad of the given `size` that the shape must fit within, `params` are passed through from the shape.
+///
+/// Each id is drawn with it's own specialized pipeline, ids must be unique within the app.
+/// Must be added after [`Shape2dPlugin`] or [`ShapePlugin`], the 3D pipeline is only set up alongside [`ShapePlugin`].
+pub struct CustomSdfPlugin<const ID: u32> {
+    pub source: CustomSdfSource,
+}
+
+impl<const ID: u32> CustomSdfPlugin<ID> {
+    /// Create the plugin from WGSL source, e.g. from `include_str!`.
+    pub fn from_wgsl(source: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            source: CustomSdfSource::Wgsl(source.into()),
+        }
+    }
+
+    /// Create the plugin from the path of a WGSL asset.
+    pub fn from_asset(path: impl Into<String>) -> Self {
+        Self {
+            source: CustomSdfSource::Asset(path.into()),
+        }
+    }
+}
+
+/// Keeps the distance function of each custom sdf shape loaded.
+#[derive(Resource, Default)]
+struct CustomSdfModules(Vec<Handle<Shader>>);
+
+impl<const ID: u32> Plugin for CustomSdfPlugin<ID> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CustomSdfModules>()
+            .add_plugins(ShapeTypePlugin::<CustomSdfComponent<ID>>::default());
+        if app.is_plugin_added::<ShapePlugin>() {
+            app.add_plugins(ShapeType3dPlugin::<CustomSdfComponent<ID>>::default());
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Import the distance function from wherever it was defined
+        let (module, import) = match &self.source {
+            CustomSdfSource::Wgsl(source) => {
+                let import_path = format!("bevy_vector_shapes::custom_sdf_{ID}");
+                let mut shader =
+                    Shader::from_wgsl(source.clone(), format!("custom_sdf_{ID}_module.wgsl"));
+                shader.set_import_path(&import_path);
+                let handle = app.world_mut().resource_mut::<Assets<Shader>>().add(shader);
+                (handle, import_path)
+            }
+            CustomSdfSource::Asset(path) => {
+                let handle = app.world().resource::<AssetServer>().load(path.clone());
+                (handle, format!("\"{path}\""))
+            }
+        };
+        app.world_mut()
+            .resource_mut::<CustomSdfModules>()
+            .0
+            .push(module);
+
+        let source =
+            include_str!("../render/shaders/shapes/custom_sdf.wgsl").replace("SDF_MODULE", &import);
+        let shader = Shader::from_wgsl_with_defs(
+            source,
+            format!("custom_sdf_{ID}.wgsl"),
+            CustomSdfData::<ID>::shader_defs(app),
+        );
+        app.world_mut()
+            .resource_mut::<Assets<Shader>>()
+            .insert(&CustomSdfData::<ID>::shader_handle(), shader);
+    }
+}
+
+/// Component containing the data for drawing a shape defined by the distance function of the [`CustomSdfPlugin`] with the same id.
+#[derive(Component, Reflect)]
+pub struct CustomSdfComponent<const ID: u32> {
+    pub alignment: Alignment,
+
+    /// Size of the quad the shape is drawn within.
+    pub size: Vec2,
+    /// Parameters passed through to the distance function.
+    pub params: Vec4,
+}
+
+impl<const ID: u32> CustomSdfComponent<ID> {
+    pub fn new(config: &ShapeConfig, size: Vec2, params: Vec4) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            size,
+            params,
+        }
+    }
+}
+
+impl<const ID: u32> ShapeComponent for CustomSdfComponent<ID> {
+    type Data = CustomSdfData<ID>;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> CustomSdfData<ID> {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        CustomSdfData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            size: self.size.into(),
+            params: self.params.into(),
+        }
+    }
+}
+
+impl<const ID: u32> Default for CustomSdfComponent<ID> {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            size: Vec2::ONE,
+            params: Vec4::ZERO,
+        }
+    }
+}
+
+/// Raw data sent to the shader of the [`CustomSdfPlugin`] with the same id.
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct CustomSdfData<const ID: u32> {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    size: [f32; 2],
+    params: [f32; 4],
+}
+
+impl<const ID: u32> CustomSdfData<ID> {
+    pub fn new(config: &ShapeConfig, size: Vec2, params: Vec4) -> Self {
+        let mut flags = Flags(0);
+        flags.set_alignment(config.alignment);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_hollow(config.hollow as u32);
+
+        Self {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            size: size.into(),
+            params: params.into(),
+        }
+    }
+
+    /// Handle of the shader generated by the [`CustomSdfPlugin`] with the same id.
+    pub fn shader_handle() -> Handle<Shader> {
+        Handle::weak_from_u128(CUSTOM_SDF_HANDLE_BASE + ID as u128)
+    }
+}
+
+impl<const ID: u32> ShapeData for CustomSdfData<ID> {
+    type Component = CustomSdfComponent<ID>;
+    const DEPTH_SLICES: bool = true;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x2,
+            8 => Float32x4,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        Self::shader_handle().into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        Some(Vec2::from(self.size).length() / 2.0)
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw custom sdf shapes.
+pub trait CustomSdfPainter {
+    /// Draw the shape of the [`CustomSdfPlugin`] with the given id within a quad of the given size.
+    fn custom_sdf<const ID: u32>(&mut self, size: Vec2, params: Vec4) -> &mut Self;
+}
+
+impl<'w, 's> CustomSdfPainter for ShapePainter<'w, 's> {
+    fn custom_sdf<const ID: u32>(&mut self, size: Vec2, params: Vec4) -> &mut Self {
+        self.send(CustomSdfData::<ID>::new(self.config(), size, params))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of custom sdf bundles.
+pub trait CustomSdfBundle {
+    fn custom_sdf(config: &ShapeConfig, size: Vec2, params: Vec4) -> Self;
+}
+
+impl<const ID: u32> CustomSdfBundle for ShapeBundle<CustomSdfComponent<ID>> {
+    fn custom_sdf(config: &ShapeConfig, size: Vec2, params: Vec4) -> Self {
+        Self::new(config, CustomSdfComponent::new(config, size, params))
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of custom sdf entities.
+pub trait CustomSdfSpawner<'w> {
+    fn custom_sdf<const ID: u32>(&mut self, size: Vec2, params: Vec4) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> CustomSdfSpawner<'w> for T {
+    fn custom_sdf<const ID: u32>(&mut self, size: Vec2, params: Vec4) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::<CustomSdfComponent<ID>>::custom_sdf(
+            self.config(),
+            size,
+            params,
+        ))
+    }
+}
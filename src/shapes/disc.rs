@@ -1,4 +1,5 @@
 use bevy::{
+    math::bounding::Aabb3d,
     prelude::*,
     reflect::Reflect,
     render::render_resource::{ShaderRef, ShaderType},
@@ -10,13 +11,59 @@ use crate::{
     render::{Flags, ShapeComponent, ShapeData, DISC_HANDLE},
 };
 
+/// Color swept around the center of a disc, from the shape's color to another.
+///
+/// Arcs and sectors are swept from their start angle to their end angle,
+/// full circles are swept once around starting from the top.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum ConicGradient {
+    /// Interpolate linearly towards the given color.
+    Linear(Color),
+    /// Interpolate hue, saturation and value towards the given color, so the colors in between stay saturated.
+    ///
+    /// Hue always increases towards the end color, passing through `turns` additional full turns of hue on the way,
+    /// e.g. a red to red gradient with one turn draws a color wheel.
+    Hue { end_color: Color, turns: f32 },
+}
+
+impl ConicGradient {
+    /// Every hue starting and ending at the shape's color, as in a color wheel.
+    pub fn wheel(color: impl Into<Color>) -> Self {
+        ConicGradient::Hue {
+            end_color: color.into(),
+            turns: 1.0,
+        }
+    }
+
+    fn end_color(&self) -> Color {
+        match self {
+            ConicGradient::Linear(color) => *color,
+            ConicGradient::Hue { end_color, .. } => *end_color,
+        }
+    }
+}
+
+/// Gradient fields of [`DiscData`], the gradient kind is zero when there is no gradient.
+fn gradient_data(gradient: Option<ConicGradient>) -> ([f32; 4], u32, f32) {
+    match gradient {
+        None => ([0.0; 4], 0, 0.0),
+        Some(gradient) => {
+            let color = gradient.end_color().to_linear().to_f32_array();
+            match gradient {
+                ConicGradient::Linear(_) => (color, 1, 0.0),
+                ConicGradient::Hue { turns, .. } => (color, 2, turns),
+            }
+        }
+    }
+}
+
 /// Component containing the data for drawing a disc.
 ///
 /// Discs include both arcs and circles
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, PartialEq, Reflect)]
 pub struct DiscComponent {
     pub alignment: Alignment,
-    /// Cap type for an arc, only supports None or Round
+    /// Cap type for the ends of an arc and for each dash, arcs only support None or Round
     pub cap: Cap,
     /// Whether to treat this disc like an arc
     pub arc: bool,
@@ -27,6 +74,9 @@ pub struct DiscComponent {
     pub start_angle: f32,
     /// Ending angle for an arc
     pub end_angle: f32,
+
+    /// Color swept around the disc from the shape's color, see [`ConicGradient`]
+    pub gradient: Option<ConicGradient>,
 }
 
 impl DiscComponent {
@@ -46,16 +96,22 @@ impl DiscComponent {
             radius,
             start_angle,
             end_angle,
+
+            gradient: config.conic_gradient,
         }
     }
 
     pub fn circle(config: &ShapeConfig, radius: f32) -> Self {
-        Self::new(config, radius, false, 0.0, 0.0, Cap::None)
+        Self::new(config, radius, false, 0.0, 0.0, config.cap)
     }
 
     pub fn arc(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> Self {
         Self::new(config, radius, true, start_angle, end_angle, config.cap)
     }
+
+    pub fn sector(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        Self::new(config, radius, true, start_angle, end_angle, Cap::None)
+    }
 }
 
 impl ShapeComponent for DiscComponent {
@@ -74,6 +130,7 @@ impl ShapeComponent for DiscComponent {
         flags.set_alignment(self.alignment);
         flags.set_cap(self.cap);
         flags.set_arc(self.arc as u32);
+        let (gradient_color, gradient, hue_turns) = gradient_data(self.gradient);
 
         DiscData {
             transform: tf.compute_matrix().to_cols_array_2d(),
@@ -86,7 +143,13 @@ impl ShapeComponent for DiscComponent {
             start_angle: self.start_angle,
             end_angle: self.end_angle,
 
-            padding: default(),
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+
+            gradient_color,
+            gradient,
+            hue_turns,
+
+            padding_2: default(),
         }
     }
 }
@@ -101,6 +164,8 @@ impl Default for DiscComponent {
             radius: 1.0,
             start_angle: 0.0,
             end_angle: 0.0,
+
+            gradient: None,
         }
     }
 }
@@ -119,7 +184,13 @@ pub struct DiscData {
     start_angle: f32,
     end_angle: f32,
 
-    padding: [f32; 3],
+    dash_pattern: u32,
+
+    gradient_color: [f32; 4],
+    gradient: u32,
+    hue_turns: f32,
+
+    padding_2: [f32; 2],
 }
 
 impl DiscData {
@@ -128,7 +199,9 @@ impl DiscData {
         flags.set_thickness_type(config.thickness_type);
         flags.set_alignment(config.alignment);
         flags.set_hollow(config.hollow as u32);
+        flags.set_cap(config.cap);
         flags.set_arc(false as u32);
+        let (gradient_color, gradient, hue_turns) = gradient_data(config.conic_gradient);
 
         DiscData {
             transform: config.transform.compute_matrix().to_cols_array_2d(),
@@ -142,7 +215,13 @@ impl DiscData {
             start_angle: 0.0,
             end_angle: 0.0,
 
-            padding: default(),
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            gradient_color,
+            gradient,
+            hue_turns,
+
+            padding_2: default(),
         }
     }
 
@@ -153,6 +232,7 @@ impl DiscData {
         flags.set_hollow(config.hollow as u32);
         flags.set_cap(config.cap);
         flags.set_arc(true as u32);
+        let (gradient_color, gradient, hue_turns) = gradient_data(config.conic_gradient);
 
         DiscData {
             transform: config.transform.compute_matrix().to_cols_array_2d(),
@@ -166,13 +246,50 @@ impl DiscData {
             start_angle,
             end_angle,
 
-            padding: default(),
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            gradient_color,
+            gradient,
+            hue_turns,
+
+            padding_2: default(),
+        }
+    }
+
+    pub fn sector(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> DiscData {
+        // A filled arc extends all the way to the center, caps and dashes only apply to outlines
+        let mut flags = Flags(0);
+        flags.set_alignment(config.alignment);
+        flags.set_cap(Cap::None);
+        flags.set_arc(true as u32);
+        let (gradient_color, gradient, hue_turns) = gradient_data(config.conic_gradient);
+
+        DiscData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: 1.0,
+            flags: flags.0,
+
+            radius,
+
+            start_angle,
+            end_angle,
+
+            dash_pattern: 0,
+
+            gradient_color,
+            gradient,
+            hue_turns,
+
+            padding_2: default(),
         }
     }
 }
 
 impl ShapeData for DiscData {
     type Component = DiscComponent;
+    const DEPTH_SLICES: bool = true;
 
     fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
         vertex_attr_array![
@@ -187,6 +304,11 @@ impl ShapeData for DiscData {
             7 => Float32,
             8 => Float32,
             9 => Float32,
+            10 => Uint32,
+
+            11 => Float32x4,
+            12 => Uint32,
+            13 => Float32,
         ]
         .to_vec()
     }
@@ -198,29 +320,80 @@ impl ShapeData for DiscData {
     fn transform(&self) -> Mat4 {
         Mat4::from_cols_array_2d(&self.transform)
     }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn sort_origin(&self, origin: Vec3, view_position: Vec3) -> Vec3 {
+        // Respect origins overridden with ShapeOrigin
+        let transform = self.transform();
+        if origin != transform.w_axis.truncate() || transform.determinant() == 0.0 {
+            return origin;
+        }
+
+        // Large discs and arcs are sorted by the point of the full disc nearest the view
+        let local = transform.inverse().transform_point3(view_position);
+        let nearest = local.truncate().clamp_length_max(self.radius.abs());
+        transform.transform_point3(nearest.extend(0.0))
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        let flags = Flags(self.flags);
+        let stroke = match flags.hollow() {
+            0 => 0.0,
+            _ => flags.stroke_bounds(self.thickness)?,
+        };
+        Some(self.radius.abs() + stroke)
+    }
+
+    fn local_bounds(&self) -> Option<Aabb3d> {
+        let radius = self.bounding_radius()?;
+        Some(Aabb3d::new(Vec3::ZERO, Vec2::splat(radius).extend(0.0)))
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
 }
 
 /// Extension trait for [`ShapePainter`] to enable it to draw disc type shapes.
 pub trait DiscPainter {
     fn circle(&mut self, radius: f32) -> &mut Self;
     fn arc(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self;
+    /// Draws a filled sector of a circle, ignoring `hollow`, useful for pie charts and cooldown indicators.
+    fn sector(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self;
 }
 
 impl<'w, 's> DiscPainter for ShapePainter<'w, 's> {
     fn circle(&mut self, radius: f32) -> &mut Self {
-        self.send(DiscData::circle(self.config(), radius))
+        self.send_filled(|config| DiscData::circle(config, radius))
     }
 
     fn arc(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self {
         self.send(DiscData::arc(self.config(), radius, start_angle, end_angle));
         self
     }
+
+    fn sector(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self {
+        self.send(DiscData::sector(
+            self.config(),
+            radius,
+            start_angle,
+            end_angle,
+        ))
+    }
 }
 
 /// Extension trait for [`ShapeBundle`] to enable creation of bundles for disc type shapes.
 pub trait DiscBundle {
     fn circle(config: &ShapeConfig, radius: f32) -> Self;
     fn arc(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> Self;
+    fn sector(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> Self;
 }
 
 impl DiscBundle for ShapeBundle<DiscComponent> {
@@ -234,12 +407,22 @@ impl DiscBundle for ShapeBundle<DiscComponent> {
             DiscComponent::arc(config, radius, start_angle, end_angle),
         )
     }
+
+    fn sector(config: &ShapeConfig, radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        let mut bundle = Self::new(
+            config,
+            DiscComponent::sector(config, radius, start_angle, end_angle),
+        );
+        bundle.fill.ty = FillType::Fill;
+        bundle
+    }
 }
 
 /// Extension trait for [`ShapeSpawner`] to enable spawning of entities for disc type shapes.
 pub trait DiscSpawner<'w> {
     fn circle(&mut self, radius: f32) -> ShapeEntityCommands;
     fn arc(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> ShapeEntityCommands;
+    fn sector(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> ShapeEntityCommands;
 }
 
 impl<'w, T: ShapeSpawner<'w>> DiscSpawner<'w> for T {
@@ -255,4 +438,13 @@ impl<'w, T: ShapeSpawner<'w>> DiscSpawner<'w> for T {
             end_angle,
         ))
     }
+
+    fn sector(&mut self, radius: f32, start_angle: f32, end_angle: f32) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::sector(
+            self.config(),
+            radius,
+            start_angle,
+            end_angle,
+        ))
+    }
 }
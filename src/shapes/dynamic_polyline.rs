@@ -0,0 +1,278 @@
+use std::ops::Range;
+
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, POLYLINE_HANDLE},
+};
+
+/// Component containing the data for drawing a polyline whose points can be modified every frame.
+///
+/// Unlike [`PolylineComponent`], which writes all of it's points whenever it changes, each dynamic polyline
+/// is given it's own block of [`ShapePolylinePoints`] and only the points that changed are re-uploaded.
+/// Appending a point every frame, as for telemetry traces, only uploads the new point.
+///
+/// Every fragment of the polyline is tested against each segment so prefer multiple entities for very long traces.
+#[derive(Component, Reflect)]
+pub struct DynamicPolylineComponent {
+    pub alignment: Alignment,
+    /// Cap type for the ends of the polyline.
+    pub cap: Cap,
+    /// Join type for the corners between segments.
+    pub join: Join,
+    /// Largest ratio of a miter join's length to the thickness before it is replaced by a bevel.
+    pub miter_limit: f32,
+
+    #[reflect(ignore)]
+    points: Vec<Vec2>,
+    /// Range of points modified since they were last written to [`ShapePolylinePoints`].
+    #[reflect(ignore)]
+    changed: Option<Range<usize>>,
+    /// Bounds of the points, min in xy and max in zw.
+    #[reflect(ignore)]
+    bounds: Vec4,
+    /// Offset of our points in [`ShapePolylinePoints`], assigned during [`PostUpdate`].
+    #[reflect(ignore)]
+    offset: u32,
+}
+
+impl DynamicPolylineComponent {
+    pub fn new(config: &ShapeConfig, points: &[Vec2]) -> Self {
+        let mut polyline = Self {
+            alignment: config.alignment,
+            cap: config.cap,
+            join: config.join,
+            miter_limit: config.miter_limit,
+
+            ..default()
+        };
+        polyline.extend(points.iter().copied());
+        polyline
+    }
+
+    /// Points the polyline is drawn through in the local xy plane of it's transform.
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Append a point to the end of the polyline.
+    pub fn push(&mut self, point: Vec2) {
+        self.extend([point]);
+    }
+
+    /// Append points to the end of the polyline.
+    pub fn extend(&mut self, points: impl IntoIterator<Item = Vec2>) {
+        let start = self.points.len();
+        for point in points {
+            self.grow_bounds(point);
+            self.points.push(point);
+        }
+        self.mark_changed(start..self.points.len());
+    }
+
+    /// Move the point at `index`.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, point: Vec2) {
+        self.points[index] = point;
+        self.recompute_bounds();
+        self.mark_changed(index..index + 1);
+    }
+
+    /// Shorten the polyline to `len` points, has no effect if it is already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        self.points.truncate(len);
+        self.recompute_bounds();
+    }
+
+    /// Remove all points from the polyline.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Take the range of points modified since the last call.
+    pub(crate) fn take_changed(&mut self) -> Option<Range<usize>> {
+        self.changed.take()
+    }
+
+    pub(crate) fn set_offset(&mut self, offset: u32) {
+        self.offset = offset;
+    }
+
+    fn mark_changed(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        self.changed = Some(match self.changed.take() {
+            Some(changed) => changed.start.min(range.start)..changed.end.max(range.end),
+            None => range,
+        });
+    }
+
+    fn grow_bounds(&mut self, point: Vec2) {
+        if self.points.is_empty() {
+            self.bounds = point.extend(point.x).extend(point.y);
+        } else {
+            let min = self.bounds.xy().min(point);
+            let max = self.bounds.zw().max(point);
+            self.bounds = min.extend(max.x).extend(max.y);
+        }
+    }
+
+    fn recompute_bounds(&mut self) {
+        let points = self.points.iter().copied();
+        let min = points.clone().reduce(Vec2::min).unwrap_or_default();
+        let max = points.reduce(Vec2::max).unwrap_or_default();
+        self.bounds = min.extend(max.x).extend(max.y);
+    }
+}
+
+impl Default for DynamicPolylineComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+            cap: default(),
+            join: default(),
+            miter_limit: 4.0,
+
+            points: default(),
+            changed: default(),
+            bounds: default(),
+            offset: default(),
+        }
+    }
+}
+
+impl ShapeComponent for DynamicPolylineComponent {
+    type Data = DynamicPolylineData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> DynamicPolylineData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+        flags.set_cap(self.cap);
+        flags.set_join(self.join);
+
+        DynamicPolylineData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            offset: self.offset,
+            count: self.points.len() as u32,
+            miter_limit: self.miter_limit,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+            bounds: self.bounds.to_array(),
+        }
+    }
+}
+
+/// Raw data sent to the polyline shader to draw a dynamic polyline.
+///
+/// The points themselves are read from the buffer backing [`ShapePolylinePoints`].
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct DynamicPolylineData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    offset: u32,
+    count: u32,
+    miter_limit: f32,
+    dash_pattern: u32,
+    bounds: [f32; 4],
+}
+
+impl ShapeData for DynamicPolylineData {
+    type Component = DynamicPolylineComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Uint32,
+            8 => Uint32,
+            9 => Float32,
+            10 => Uint32,
+            11 => Float32x4,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        POLYLINE_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of dynamic polyline bundles.
+pub trait DynamicPolylineBundle {
+    fn dynamic_polyline(config: &ShapeConfig, points: &[Vec2]) -> Self;
+}
+
+impl DynamicPolylineBundle for ShapeBundle<DynamicPolylineComponent> {
+    fn dynamic_polyline(config: &ShapeConfig, points: &[Vec2]) -> Self {
+        let mut bundle = Self::new(config, DynamicPolylineComponent::new(config, points));
+        bundle.fill.ty = FillType::Stroke(config.thickness, config.thickness_type);
+        bundle
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of dynamic polyline entities.
+pub trait DynamicPolylineSpawner<'w> {
+    fn dynamic_polyline(&mut self, points: &[Vec2]) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> DynamicPolylineSpawner<'w> for T {
+    fn dynamic_polyline(&mut self, points: &[Vec2]) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::dynamic_polyline(self.config(), points))
+    }
+}
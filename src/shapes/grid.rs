@@ -0,0 +1,200 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, GRID_HANDLE},
+};
+
+/// Component containing the data for drawing a grid.
+///
+/// The grid is centered on it's transform and all of it's lines are drawn by a single instance.
+///
+/// Note: grids should use [`FillType::Stroke`], otherwise they will default to thickness `1.0`.
+#[derive(Component, Reflect)]
+pub struct GridComponent {
+    pub alignment: Alignment,
+
+    /// Number of cells along each axis
+    pub cells: UVec2,
+    /// Size of each cell
+    pub cell_size: Vec2,
+    /// Every `major_interval`th line is a major line, zero to only draw minor lines
+    pub major_interval: u32,
+    /// Multiplier applied to the thickness of major lines
+    pub major_thickness: f32,
+}
+
+impl GridComponent {
+    pub fn new(config: &ShapeConfig, cells: UVec2, cell_size: Vec2) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            cells,
+            cell_size,
+            major_interval: config.grid_major_interval,
+            major_thickness: config.grid_major_thickness,
+        }
+    }
+}
+
+impl Default for GridComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            cells: UVec2::ONE,
+            cell_size: Vec2::ONE,
+            major_interval: 0,
+            major_thickness: 2.0,
+        }
+    }
+}
+
+impl ShapeComponent for GridComponent {
+    type Data = GridData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> GridData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        GridData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            cells: self.cells.as_vec2(),
+            cell_size: self.cell_size,
+            major_interval: self.major_interval as f32,
+            major_thickness: self.major_thickness,
+        }
+    }
+}
+
+/// Raw data sent to the grid shader to draw a grid
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct GridData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    cells: Vec2,
+    cell_size: Vec2,
+    major_interval: f32,
+    major_thickness: f32,
+}
+
+impl GridData {
+    pub fn new(config: &ShapeConfig, cells: UVec2, cell_size: Vec2) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+
+        GridData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            cells: cells.as_vec2(),
+            cell_size,
+            major_interval: config.grid_major_interval as f32,
+            major_thickness: config.grid_major_thickness,
+        }
+    }
+}
+
+impl ShapeData for GridData {
+    type Component = GridComponent;
+    const DEPTH_SLICES: bool = true;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+            7 => Float32x2,
+            8 => Float32x2,
+            9 => Float32,
+            10 => Float32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        GRID_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw grids.
+pub trait GridPainter {
+    fn grid(&mut self, cells: UVec2, cell_size: Vec2) -> &mut Self;
+}
+
+impl<'w, 's> GridPainter for ShapePainter<'w, 's> {
+    fn grid(&mut self, cells: UVec2, cell_size: Vec2) -> &mut Self {
+        self.send(GridData::new(self.config(), cells, cell_size))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of grid bundles.
+pub trait GridBundle {
+    fn grid(config: &ShapeConfig, cells: UVec2, cell_size: Vec2) -> Self;
+}
+
+impl GridBundle for ShapeBundle<GridComponent> {
+    fn grid(config: &ShapeConfig, cells: UVec2, cell_size: Vec2) -> Self {
+        let mut bundle = Self::new(config, GridComponent::new(config, cells, cell_size));
+        bundle.fill.ty = FillType::Stroke(config.thickness, config.thickness_type);
+        bundle
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of grid entities.
+pub trait GridSpawner<'w> {
+    fn grid(&mut self, cells: UVec2, cell_size: Vec2) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> GridSpawner<'w> for T {
+    fn grid(&mut self, cells: UVec2, cell_size: Vec2) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::grid(self.config(), cells, cell_size))
+    }
+}
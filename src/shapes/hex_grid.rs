@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+use crate::prelude::*;
+
+/// Orientation of the hexagons in a hex grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum HexOrientation {
+    /// Hexagons have a corner at the top, odd rows are shifted right by half a cell.
+    #[default]
+    Pointy,
+    /// Hexagons have a side at the top, odd columns are shifted up by half a cell.
+    Flat,
+}
+
+impl HexOrientation {
+    /// Offset from the center of a grid of the given dimensions to the center of a cell, in offset coordinates.
+    pub fn cell_center(&self, radius: f32, dims: UVec2, cell: UVec2) -> Vec2 {
+        let width = f32::sqrt(3.0) * radius;
+        let (spacing, shift, shifted, parity) = match self {
+            HexOrientation::Pointy => (
+                Vec2::new(width, 1.5 * radius),
+                Vec2::X * width / 2.0,
+                dims.y > 1,
+                cell.y % 2,
+            ),
+            HexOrientation::Flat => (
+                Vec2::new(1.5 * radius, width),
+                Vec2::Y * width / 2.0,
+                dims.x > 1,
+                cell.x % 2,
+            ),
+        };
+
+        // Shifted rows or columns only widen the grid if there are any
+        let mut extent = (dims.max(UVec2::ONE) - 1).as_vec2() * spacing;
+        if shifted {
+            extent += shift;
+        }
+        cell.as_vec2() * spacing + shift * parity as f32 - extent / 2.0
+    }
+
+    /// Angle to rotate a regular hexagon by to match this orientation.
+    pub fn rotation(&self) -> f32 {
+        match self {
+            HexOrientation::Pointy => 0.0,
+            HexOrientation::Flat => PI / 6.0,
+        }
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw hex grids.
+pub trait HexGridPainter {
+    /// Draws a grid of `dims` hexagons of the given radius centered on the painter's transform.
+    ///
+    /// `color` is called with the offset coordinates of each cell and returns it's color, or `None` to skip the cell.
+    /// Each cell is a regular polygon sharing the painter's material, so the whole grid is drawn as a single batch.
+    fn hex_grid(
+        &mut self,
+        radius: f32,
+        dims: UVec2,
+        orientation: HexOrientation,
+        color: impl FnMut(UVec2) -> Option<Color>,
+    ) -> &mut Self;
+}
+
+impl<'w, 's> HexGridPainter for ShapePainter<'w, 's> {
+    fn hex_grid(
+        &mut self,
+        radius: f32,
+        dims: UVec2,
+        orientation: HexOrientation,
+        mut color: impl FnMut(UVec2) -> Option<Color>,
+    ) -> &mut Self {
+        let mut config = self.config().clone();
+        config.ngon_rotation = orientation.rotation();
+        let origin = config.transform;
+
+        for y in 0..dims.y {
+            for x in 0..dims.x {
+                let cell = UVec2::new(x, y);
+                let Some(cell_color) = color(cell) else {
+                    continue;
+                };
+                let center = orientation.cell_center(radius, dims, cell);
+                config.transform = origin * Transform::from_translation(center.extend(0.0));
+                config.color = cell_color;
+                self.send_with_config(&config, NgonData::new(&config, 6.0, radius));
+            }
+        }
+        self
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    math::bounding::Aabb3d,
     prelude::*,
     reflect::Reflect,
     render::render_resource::{ShaderRef, ShaderType},
@@ -11,10 +12,13 @@ use crate::{
 };
 
 /// Component containing the data for drawing a line.
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, PartialEq, Reflect)]
 pub struct LineComponent {
     pub alignment: Alignment,
+    /// Cap type for the start of the line and for each dash.
     pub cap: Cap,
+    /// Cap type for the end of the line, uses `cap` when `None`.
+    pub end_cap: Option<Cap>,
 
     /// Position to draw the start of the line in world space relative to it's transform.
     pub start: Vec3,
@@ -27,6 +31,7 @@ impl LineComponent {
         Self {
             alignment: config.alignment,
             cap: config.cap,
+            end_cap: config.end_cap,
 
             start,
             end,
@@ -39,6 +44,7 @@ impl Default for LineComponent {
         Self {
             alignment: default(),
             cap: default(),
+            end_cap: None,
 
             start: default(),
             end: default(),
@@ -61,6 +67,7 @@ impl ShapeComponent for LineComponent {
         };
         flags.set_alignment(self.alignment);
         flags.set_cap(self.cap);
+        flags.set_end_cap(self.end_cap.unwrap_or(self.cap));
 
         LineData {
             transform: tf.compute_matrix().to_cols_array_2d(),
@@ -71,6 +78,7 @@ impl ShapeComponent for LineComponent {
 
             start: self.start,
             end: self.end,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
         }
     }
 }
@@ -87,6 +95,7 @@ pub struct LineData {
 
     start: Vec3,
     end: Vec3,
+    dash_pattern: u32,
 }
 
 impl LineData {
@@ -95,6 +104,7 @@ impl LineData {
         flags.set_thickness_type(config.thickness_type);
         flags.set_alignment(config.alignment);
         flags.set_cap(config.cap);
+        flags.set_end_cap(config.end_cap.unwrap_or(config.cap));
 
         LineData {
             transform: config.transform.compute_matrix().to_cols_array_2d(),
@@ -105,6 +115,7 @@ impl LineData {
 
             start,
             end,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
         }
     }
 }
@@ -124,6 +135,7 @@ impl ShapeData for LineData {
             6 => Uint32,
             7 => Float32x3,
             8 => Float32x3,
+            9 => Uint32,
         ]
         .to_vec()
     }
@@ -135,6 +147,48 @@ impl ShapeData for LineData {
     fn transform(&self) -> Mat4 {
         Mat4::from_cols_array_2d(&self.transform)
     }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn sort_origin(&self, origin: Vec3, view_position: Vec3) -> Vec3 {
+        // Respect origins overridden with ShapeOrigin
+        let transform = self.transform();
+        if origin != transform.w_axis.truncate() {
+            return origin;
+        }
+
+        // Long lines are sorted by their point nearest the view rather than their transform, which may be far from the line
+        let start = transform.transform_point3(self.start);
+        let end = transform.transform_point3(self.end);
+        let direction = end - start;
+        let t =
+            (view_position - start).dot(direction) / direction.length_squared().max(f32::EPSILON);
+        start + direction * t.clamp(0.0, 1.0)
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        // Caps and arrowheads extend at most a thickness beyond each end
+        let stroke = Flags(self.flags).stroke_bounds(self.thickness)?;
+        Some(self.start.length().max(self.end.length()) + stroke * 2.0)
+    }
+
+    fn local_bounds(&self) -> Option<Aabb3d> {
+        let stroke = Flags(self.flags).stroke_bounds(self.thickness)?;
+        Some(Aabb3d {
+            min: (self.start.min(self.end) - stroke * 2.0).into(),
+            max: (self.start.max(self.end) + stroke * 2.0).into(),
+        })
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
 }
 
 /// Extension trait for [`ShapePainter`] to enable it to draw lines.
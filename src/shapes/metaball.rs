@@ -0,0 +1,285 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, METABALL_HANDLE},
+};
+
+/// Component containing the data for drawing a metaball.
+///
+/// Metaballs are a pair of circles smoothly blended together into a single shape.
+#[derive(Component, Reflect)]
+pub struct MetaballComponent {
+    pub alignment: Alignment,
+
+    /// Center of the first circle in world space relative to it's transform.
+    pub center_a: Vec2,
+    /// Radius of the first circle.
+    pub radius_a: f32,
+    /// Center of the second circle in world space relative to it's transform.
+    pub center_b: Vec2,
+    /// Radius of the second circle.
+    pub radius_b: f32,
+    /// Distance in world units over which the two circles blend into each other, 0.0 is a hard union.
+    pub blend: f32,
+}
+
+impl MetaballComponent {
+    pub fn new(
+        config: &ShapeConfig,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            center_a,
+            radius_a,
+            center_b,
+            radius_b,
+            blend,
+        }
+    }
+}
+
+impl Default for MetaballComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            center_a: Vec2::new(-1.0, 0.0),
+            radius_a: 1.0,
+            center_b: Vec2::new(1.0, 0.0),
+            radius_b: 1.0,
+            blend: 1.0,
+        }
+    }
+}
+
+impl ShapeComponent for MetaballComponent {
+    type Data = MetaballData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> MetaballData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        MetaballData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            center_a: self.center_a.into(),
+            center_b: self.center_b.into(),
+            radius_a: self.radius_a,
+            radius_b: self.radius_b,
+            blend: self.blend,
+
+            padding: default(),
+        }
+    }
+}
+
+/// Raw data sent to the metaball shader to draw a metaball
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct MetaballData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    center_a: [f32; 2],
+    center_b: [f32; 2],
+    radius_a: f32,
+    radius_b: f32,
+    blend: f32,
+
+    padding: [f32; 3],
+}
+
+impl MetaballData {
+    pub fn new(
+        config: &ShapeConfig,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_hollow(config.hollow as u32);
+
+        MetaballData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            center_a: center_a.into(),
+            center_b: center_b.into(),
+            radius_a,
+            radius_b,
+            blend,
+
+            padding: default(),
+        }
+    }
+}
+
+impl ShapeData for MetaballData {
+    type Component = MetaballComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x2,
+            8 => Float32x2,
+            9 => Float32,
+            10 => Float32,
+            11 => Float32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        METABALL_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw metaballs.
+pub trait MetaballPainter {
+    fn metaball(
+        &mut self,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> &mut Self;
+}
+
+impl<'w, 's> MetaballPainter for ShapePainter<'w, 's> {
+    fn metaball(
+        &mut self,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> &mut Self {
+        self.send(MetaballData::new(
+            self.config(),
+            center_a,
+            radius_a,
+            center_b,
+            radius_b,
+            blend,
+        ))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of metaball bundles.
+pub trait MetaballBundle {
+    fn metaball(
+        config: &ShapeConfig,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> Self;
+}
+
+impl MetaballBundle for ShapeBundle<MetaballComponent> {
+    fn metaball(
+        config: &ShapeConfig,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> Self {
+        Self::new(
+            config,
+            MetaballComponent::new(config, center_a, radius_a, center_b, radius_b, blend),
+        )
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of metaball entities.
+pub trait MetaballSpawner<'w> {
+    fn metaball(
+        &mut self,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> MetaballSpawner<'w> for T {
+    fn metaball(
+        &mut self,
+        center_a: Vec2,
+        radius_a: f32,
+        center_b: Vec2,
+        radius_b: f32,
+        blend: f32,
+    ) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::metaball(
+            self.config(),
+            center_a,
+            radius_a,
+            center_b,
+            radius_b,
+            blend,
+        ))
+    }
+}
@@ -1,19 +1,77 @@
-use bevy::prelude::*;
+use std::{ops::Range, sync::Arc};
+
+use bevy::{prelude::*, render::render_resource::TextureFormat};
 
 use crate::{prelude::*, render::ShapePipelineType};
 
+mod bezier;
+pub use bezier::*;
+
+mod boolean;
+pub use boolean::*;
+
+mod capsule;
+pub use capsule::*;
+
+mod compact;
+pub use compact::*;
+
+mod concave_polygon;
+pub use concave_polygon::*;
+
+mod custom_sdf;
+pub use custom_sdf::*;
+
 mod disc;
 pub use disc::*;
 
+mod dynamic_polyline;
+pub use dynamic_polyline::*;
+
+mod grid;
+pub use grid::*;
+
+mod hex_grid;
+pub use hex_grid::*;
+
 mod line;
 pub use line::*;
 
+mod metaball;
+pub use metaball::*;
+
+mod outline;
+pub use outline::*;
+
+mod path;
+pub use path::*;
+
+mod polyline;
+pub use polyline::*;
+
+mod quad;
+pub use quad::*;
+
 mod rectangle;
 pub use rectangle::*;
 
 mod regular_polygon;
 pub use regular_polygon::*;
 
+mod reticle;
+pub use reticle::*;
+
+mod ring;
+pub use ring::*;
+
+mod spline;
+pub use spline::*;
+
+#[cfg(feature = "text")]
+mod text;
+#[cfg(feature = "text")]
+pub use text::*;
+
 mod triangle;
 pub use triangle::*;
 
@@ -26,10 +84,58 @@ pub struct ShapeMaterial {
     pub disable_laa: bool,
     /// Target pipeline draw the shape.
     pub pipeline: ShapePipelineType,
-    /// [`Canvas`] to draw the shape to.
+    /// [`Canvas`] to draw the shape to, set from `canvas_label` each frame if it is set.
     pub canvas: Option<Entity>,
+    /// [`CanvasLabel`] of the [`Canvas`] to draw the shape to, see [`ShapeConfig::canvas_label`].
+    pub canvas_label: Option<CanvasLabel>,
     /// Texture to apply to the shape.
     pub texture: Option<Handle<Image>>,
+    /// Color space `texture` is sampled in, see [`ShapeTextureColorSpace`].
+    pub texture_color_space: ShapeTextureColorSpace,
+    /// Distances from the camera over which the shape fades, only supported by the 3D pipeline.
+    pub fade: ShapeFade,
+    /// Render phase the shape is queued into, see [`ShapeRenderPhase`].
+    pub render_phase: ShapeRenderPhase,
+    /// Visualizes the distance to the edge of the shape instead of filling it, see [`ShapeSdfView`].
+    pub sdf_view: ShapeSdfView,
+    /// Width in pixels over which the edges of the shape fade out, zero for standard anti-aliasing.
+    pub edge_softness: u8,
+    /// Curve the edges of the shape fade out with when `edge_softness` is set.
+    pub edge_falloff: EdgeFalloff,
+    /// Side of the shape that is hidden, see [`ShapeCullMode`].
+    pub cull_mode: ShapeCullMode,
+    /// Whether the shape is hidden behind geometry nearer to the camera, see [`ShapeConfig::depth_test`].
+    pub depth_test: bool,
+    /// Rect in world space on the xy plane that the shape is clipped to, see [`ShapeConfig::clip_rect`].
+    pub clip_rect: Option<Rect>,
+    /// Offset added to the value the shape is sorted by, see [`ShapeConfig::sort_bias`].
+    pub sort_bias: f32,
+    /// Number of slices the shape is split into by depth, see [`ShapeConfig::depth_slices`].
+    pub depth_slices: u8,
+}
+
+impl ShapeMaterial {
+    pub fn new(config: &ShapeConfig) -> Self {
+        Self {
+            alpha_mode: config.alpha_mode,
+            disable_laa: config.disable_laa,
+            pipeline: config.pipeline,
+            canvas: config.canvas,
+            canvas_label: config.canvas_label.clone(),
+            texture: config.texture.clone(),
+            texture_color_space: config.texture_color_space,
+            fade: config.fade.clone(),
+            render_phase: config.render_phase,
+            sdf_view: config.sdf_view,
+            edge_softness: config.edge_softness,
+            edge_falloff: config.edge_falloff,
+            cull_mode: config.cull_mode,
+            depth_test: config.depth_test,
+            clip_rect: config.clip_rect,
+            sort_bias: config.sort_bias,
+            depth_slices: config.depth_slices,
+        }
+    }
 }
 
 impl Default for ShapeMaterial {
@@ -39,11 +145,53 @@ impl Default for ShapeMaterial {
             disable_laa: false,
             pipeline: ShapePipelineType::Shape2d,
             texture: None,
+            texture_color_space: default(),
             canvas: None,
+            canvas_label: None,
+            fade: default(),
+            render_phase: default(),
+            sdf_view: default(),
+            edge_softness: 0,
+            edge_falloff: default(),
+            cull_mode: default(),
+            depth_test: true,
+            clip_rect: None,
+            sort_bias: 0.0,
+            depth_slices: 0,
         }
     }
 }
 
+/// Ranges of distances from the camera over which a 3D shape fades in or out, given as `start..end`.
+///
+/// Useful for hiding markers and billboards that would otherwise fill the screen when the camera gets too close.
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+pub struct ShapeFade {
+    /// Shapes are invisible closer than `start` and fully visible beyond `end`.
+    pub near: Option<Range<f32>>,
+    /// Shapes are fully visible closer than `start` and invisible beyond `end`.
+    pub far: Option<Range<f32>>,
+}
+
+impl ShapeFade {
+    /// Calculate the alpha multiplier for a shape at the given distance from the camera.
+    pub fn alpha(&self, distance: f32) -> f32 {
+        let progress = |range: &Range<f32>| {
+            if range.end > range.start {
+                ((distance - range.start) / (range.end - range.start)).clamp(0.0, 1.0)
+            } else if distance < range.start {
+                0.0
+            } else {
+                1.0
+            }
+        };
+
+        let near = self.near.as_ref().map_or(1.0, progress);
+        let far = self.far.as_ref().map_or(1.0, |range| 1.0 - progress(range));
+        near * far
+    }
+}
+
 /// Alpha mode to use when rendering, a subset of [`AlphaMode`].
 #[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Reflect)]
 pub enum ShapeAlphaMode {
@@ -51,6 +199,17 @@ pub enum ShapeAlphaMode {
     Blend,
     Add,
     Multiply,
+    /// Remove the existing contents beneath the shape, scaled by the shape's alpha, ignoring it's color.
+    ///
+    /// Intended for canvases with [`CanvasMode::Persistent`](crate::painter::CanvasMode::Persistent) so that parts of a drawing
+    /// can be removed without clearing the whole canvas, when drawn to a camera the erased area shows as transparent black.
+    Erase,
+    /// Converts the shape's alpha into the fraction of MSAA samples it covers, drawing hard edged shapes that write depth
+    /// so they are hidden correctly regardless of the order they are sorted in, while keeping multisampled edges.
+    ///
+    /// Shapes are still queued with transparent items, but overlapping shapes no longer depend on their sort order.
+    /// Partially transparent colors dither between samples rather than blending. Drawn as [`ShapeAlphaMode::Blend`] without MSAA.
+    AlphaToCoverage,
 }
 
 impl From<AlphaMode> for ShapeAlphaMode {
@@ -58,13 +217,219 @@ impl From<AlphaMode> for ShapeAlphaMode {
         match value {
             AlphaMode::Add => ShapeAlphaMode::Add,
             AlphaMode::Multiply => ShapeAlphaMode::Multiply,
+            AlphaMode::AlphaToCoverage => ShapeAlphaMode::AlphaToCoverage,
             _ => ShapeAlphaMode::Blend,
         }
     }
 }
 
+/// Debug visualization of the signed distance to the edge of a shape.
+///
+/// Distances are approximated in pixels from the edges the shader anti-aliases, so they are
+/// exact for simple edges but only approximate where edges are combined such as at round caps.
+/// Local anti-aliasing is always enabled for shapes with a visualization.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum ShapeSdfView {
+    /// Shapes are filled as usual.
+    #[default]
+    None,
+    /// Draws a line every 8 pixels inwards from the edge of the shape.
+    Contours,
+    /// Fades the shape out over the 32 pixels inwards from it's edge.
+    Ramp,
+}
+
+/// Curve with which the edges of a shape fade out over [`ShapeConfig::edge_softness`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum EdgeFalloff {
+    /// Fades out at a constant rate.
+    #[default]
+    Linear,
+    /// Eases in and out of the fade for a softer edge.
+    Smoothstep,
+    /// Fades out quickly near the edge with a long tail, suited to glows and auras.
+    Exponential,
+}
+
+/// Side of a shape that is hidden, for one sided panels in 3D that disappear when seen from behind.
+///
+/// The front of a shape faces along the positive z axis of it's transform, billboarded shapes always show their front to the camera.
+/// Triangles are front facing when their vertices are counter-clockwise in the xy plane of their transform,
+/// custom shapes should emit their triangles with the same winding to support culling.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum ShapeCullMode {
+    /// Both sides of the shape are drawn.
+    #[default]
+    None,
+    /// The shape is hidden when seen from behind.
+    Back,
+    /// The shape is hidden when seen from the front.
+    Front,
+}
+
+/// Color space a shape's texture is sampled in, independent of the format of it's image.
+///
+/// Images loaded as sRGB are converted to linear when sampled, which is correct for colors but not for data
+/// such as heatmaps or lookup tables. Overriding the color space samples the image through a separate view
+/// with the sRGB suffix of it's format added or removed, which requires the image to list that format in it's
+/// `texture_descriptor.view_formats`, see [`ShapeTextureColorSpace::allow_views`].
+/// Images that don't allow the view are sampled in their own color space with a warning.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum ShapeTextureColorSpace {
+    /// Sampled in the color space of the image's format.
+    #[default]
+    Image,
+    /// Sampled as sRGB and converted to linear, e.g. a color texture stored in a linear format.
+    Srgb,
+    /// Sampled as is, e.g. a data texture loaded as sRGB.
+    Linear,
+}
+
+impl ShapeTextureColorSpace {
+    /// Format of the view the texture is sampled through for an image of the given format.
+    pub fn view_format(self, format: TextureFormat) -> TextureFormat {
+        match self {
+            Self::Image => format,
+            Self::Srgb => format.add_srgb_suffix(),
+            Self::Linear => format.remove_srgb_suffix(),
+        }
+    }
+
+    /// Allows the image to be sampled in either color space by listing both formats in it's view formats.
+    ///
+    /// Only 8 bit RGBA and BGRA images are supported, other images are left unchanged.
+    pub fn allow_views(image: &mut Image) {
+        use TextureFormat::*;
+        let view_formats: &'static [TextureFormat] = match image.texture_descriptor.format {
+            Rgba8Unorm | Rgba8UnormSrgb => &[Rgba8Unorm, Rgba8UnormSrgb],
+            Bgra8Unorm | Bgra8UnormSrgb => &[Bgra8Unorm, Bgra8UnormSrgb],
+            _ => return,
+        };
+        image.texture_descriptor.view_formats = view_formats;
+    }
+}
+
+/// Determines which render phase a shape is queued into.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum ShapeRenderPhase {
+    /// Drawn in the built in transparent phase of the shape's pipeline.
+    #[default]
+    Transparent,
+    /// Drawn only in custom phases whose [`ShapePhaseItem::ID`](crate::render::ShapePhaseItem::ID) matches the given id,
+    /// see [`ShapeCustomPhasePlugin`](crate::render::ShapeCustomPhasePlugin).
+    Custom(u32),
+}
+
+/// Resource that determines where 2D shapes are sorted relative to other transparent 2D items such as `Sprite`s and `Mesh2d`s.
+///
+/// Items in the 2D transparent phase are sorted by their z translation, or [`ShapeSortKey`](crate::painter::ShapeSortKey) for shapes,
+/// with later items drawn on top. Items sharing a z are otherwise drawn in an unspecified order.
+/// The bias is applied to the sort key when shapes are queued, their transforms are unaffected.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum Shape2dSortBias {
+    /// Shapes share sort keys with other items, their order relative to items at the same z is unspecified.
+    #[default]
+    None,
+    /// Shapes are drawn above other items at the same z, but still below items at any greater z.
+    Above,
+    /// Shapes are drawn below other items at the same z, but still above items at any lesser z.
+    Below,
+    /// Added to the sort key of every shape, e.g. to place all shapes above a range of sprite layers.
+    Offset(f32),
+}
+
+impl Shape2dSortBias {
+    /// Apply the bias to the given sort key.
+    pub fn apply(&self, key: f32) -> f32 {
+        match *self {
+            Shape2dSortBias::None => key,
+            Shape2dSortBias::Above => next_float(key, true),
+            Shape2dSortBias::Below => next_float(key, false),
+            Shape2dSortBias::Offset(offset) => key + offset,
+        }
+    }
+}
+
+/// The adjacent representable float above or below `value`, so that no other key can sort between them.
+fn next_float(value: f32, up: bool) -> f32 {
+    if value.is_nan() || value.is_infinite() {
+        return value;
+    }
+    if value == 0.0 {
+        let smallest = f32::from_bits(1);
+        return if up { smallest } else { -smallest };
+    }
+    // Moving away from zero increases the magnitude of the bits regardless of sign
+    let away_from_zero = up == (value > 0.0);
+    let bits = value.to_bits();
+    f32::from_bits(if away_from_zero { bits + 1 } else { bits - 1 })
+}
+
+/// Component that simplifies a retained 3D shape based on it's distance to the closest active camera.
+///
+/// Levels are applied during extraction, the last level whose `distance` has been passed is used.
+/// Useful for keeping large numbers of markers cheap without spawning separate entities for each level of detail.
+#[derive(Component, Default, Debug, Clone, Reflect)]
+pub struct ShapeLod {
+    /// Levels of detail, ordered by increasing distance.
+    pub levels: Vec<ShapeLodLevel>,
+}
+
+impl ShapeLod {
+    pub fn new(levels: impl IntoIterator<Item = ShapeLodLevel>) -> Self {
+        Self {
+            levels: levels.into_iter().collect(),
+        }
+    }
+
+    /// Find the level of detail to use for a shape at the given distance from the camera.
+    pub fn level(&self, distance: f32) -> Option<&ShapeLodLevel> {
+        self.levels
+            .iter()
+            .take_while(|level| level.distance <= distance)
+            .last()
+    }
+}
+
+/// Overrides applied to a shape by a [`ShapeLod`] beyond a given distance.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct ShapeLodLevel {
+    /// Distance from the camera beyond which this level is used.
+    pub distance: f32,
+    /// Skip drawing the shape entirely.
+    pub hide: bool,
+    /// Draw hollow shapes as filled, should not be used with lines as they are always stroked.
+    pub fill: bool,
+    /// Draw the shape without it's texture.
+    pub drop_texture: bool,
+    /// Multiplier applied to the scale of the shape.
+    pub scale: f32,
+}
+
+impl ShapeLodLevel {
+    /// Level beyond the given distance with no overrides.
+    pub fn new(distance: f32) -> Self {
+        Self {
+            distance,
+            ..default()
+        }
+    }
+}
+
+impl Default for ShapeLodLevel {
+    fn default() -> Self {
+        Self {
+            distance: 0.0,
+            hide: false,
+            fill: false,
+            drop_texture: false,
+            scale: 1.0,
+        }
+    }
+}
+
 /// Used in [`ShapeFill`] to determine how a shape is rendered.
-#[derive(Default, Clone, Copy, Reflect)]
+#[derive(Default, Clone, Copy, PartialEq, Reflect)]
 pub enum FillType {
     /// Fully colored shape
     #[default]
@@ -75,11 +440,26 @@ pub enum FillType {
     Stroke(f32, ThicknessType),
 }
 
+/// Outline drawn over a filled shape by it's [`ShapeFill`], see [`ShapeFill::stroke`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct FillStroke {
+    pub color: Color,
+    pub thickness: f32,
+    pub thickness_type: ThicknessType,
+}
+
 /// Component attached to each shape to determine how it is rendered.
-#[derive(Default, Component, Clone, Copy, Reflect)]
+#[derive(Default, Component, Clone, Copy, PartialEq, Reflect)]
 pub struct ShapeFill {
     pub color: Color,
     pub ty: FillType,
+    /// Dash pattern registered with [`ShapeDashPatterns`], see [`ShapeConfig::dash`].
+    pub dash: Option<DashPattern>,
+    /// Outline drawn over the shape while it's [`FillType::Fill`], see [`ShapeConfig::stroke_color`].
+    ///
+    /// The outline is drawn as a second instance directly after the fill, so it's always drawn over the fill
+    /// without offsetting it in z. With an opaque alpha mode in 3D the outline fails the depth test against the fill.
+    pub stroke: Option<FillStroke>,
 }
 
 impl ShapeFill {
@@ -91,8 +471,25 @@ impl ShapeFill {
             } else {
                 FillType::Fill
             },
+            dash: config.dash,
+            stroke: config.stroke_color.map(|color| FillStroke {
+                color,
+                thickness: config.thickness,
+                thickness_type: config.thickness_type,
+            }),
         }
     }
+
+    /// Fill of the outline drawn over this fill, if it has a [`FillStroke`] and isn't already a stroke.
+    pub fn stroke_fill(&self) -> Option<ShapeFill> {
+        let stroke = self.stroke.filter(|_| self.ty == FillType::Fill)?;
+        Some(ShapeFill {
+            color: stroke.color,
+            ty: FillType::Stroke(stroke.thickness, stroke.thickness_type),
+            dash: self.dash,
+            stroke: None,
+        })
+    }
 }
 
 /// Marker component for entities that should be drawn by the 3D pipeline.
@@ -105,6 +502,40 @@ pub struct Shape3d;
 #[derive(Component, Reflect)]
 pub struct ShapeOrigin(pub Vec3);
 
+/// Adjusts the transform a retained shape is drawn with as it is extracted for rendering, without changing it's [`Transform`] or [`GlobalTransform`].
+///
+/// Useful for purely visual effects such as shake or snapping to whole pixels, which would otherwise disturb gameplay transforms.
+/// The function is given the shape's global transform and returns the one to draw it with.
+#[derive(Component, Clone)]
+pub struct ShapeTransformModifier(
+    pub Arc<dyn Fn(&GlobalTransform) -> GlobalTransform + Send + Sync>,
+);
+
+impl ShapeTransformModifier {
+    pub fn new(
+        modifier: impl Fn(&GlobalTransform) -> GlobalTransform + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(modifier))
+    }
+
+    /// Round the shape's translation to the nearest multiple of `step`, e.g. the size of a pixel in world units.
+    pub fn snap_translation(step: f32) -> Self {
+        Self::new(move |tf| {
+            let (scale, rotation, translation) = tf.to_scale_rotation_translation();
+            GlobalTransform::from(Transform {
+                translation: (translation / step).round() * step,
+                rotation,
+                scale,
+            })
+        })
+    }
+
+    /// Apply the modifier to the given transform.
+    pub fn apply(&self, tf: &GlobalTransform) -> GlobalTransform {
+        (self.0)(tf)
+    }
+}
+
 /// Bundle that is required to render a shape.
 ///
 /// Shape specific methods will additionally add the component representing the corresponding shape.
@@ -122,13 +553,7 @@ impl<T: Component> ShapeBundle<T> {
         Self {
             visibility: default(),
             transform: config.transform,
-            shape: ShapeMaterial {
-                alpha_mode: config.alpha_mode,
-                disable_laa: config.disable_laa,
-                pipeline: config.pipeline,
-                canvas: config.canvas,
-                texture: config.texture.clone(),
-            },
+            shape: ShapeMaterial::new(config),
             fill: ShapeFill::new(config),
             shape_type: component,
         }
@@ -150,11 +575,20 @@ pub enum ThicknessType {
     Pixels,
     /// 1.0 thickness corresponds to 1% of the screen size along the shortest axis.
     Screen,
+    /// 1.0 thickness corresponds to 1.0 world unit, clamped to the given minimum and maximum number of pixels.
+    ///
+    /// Useful for zoomable views where strokes should scale with the camera but never vanish or dominate the screen.
+    WorldClampedPx(u8, u8),
 }
 
 impl From<ThicknessType> for u32 {
     fn from(value: ThicknessType) -> Self {
-        value as u32
+        match value {
+            ThicknessType::World => 0,
+            ThicknessType::Pixels => 1,
+            ThicknessType::Screen => 2,
+            ThicknessType::WorldClampedPx(..) => 3,
+        }
     }
 }
 
@@ -168,6 +602,8 @@ pub enum Cap {
     /// Lines or Discs with this cap will have semi-circles attached at each end
     #[default]
     Round,
+    /// Lines with this cap will end in an arrowhead three times their thickness in length and width, with it's tip on the end point
+    Arrow,
 }
 
 impl From<Cap> for u32 {
@@ -176,6 +612,25 @@ impl From<Cap> for u32 {
     }
 }
 
+/// Defines the way in which the corners between connected segments will be rendered on a supported shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum Join {
+    /// Segments will be connected by a circular arc centered on the shared point
+    Round,
+    /// Segments will be extended until their outer edges meet at a point,
+    /// corners sharper than the miter limit will be beveled instead
+    #[default]
+    Miter,
+    /// Segments will be connected by a straight edge across the outside of the corner
+    Bevel,
+}
+
+impl From<Join> for u32 {
+    fn from(value: Join) -> Self {
+        value as u32
+    }
+}
+
 /// Defines how a shape will orient itself in relation to it's transform and the camera
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
 pub enum Alignment {
@@ -185,6 +640,14 @@ pub enum Alignment {
     /// Shapes will always orient themselves towards the camera.
     /// Note that lines rotate around their direction while all other shapes will fully face the camera at all times.
     Billboard,
+    /// Shapes will face the camera like [`Alignment::Billboard`] and keep a constant size on screen regardless of their distance
+    /// from the camera, one unit of their transform covering one pixel at their origin, e.g. for markers and labels in 3D.
+    ///
+    /// Thicknesses in world units are measured in pixels as well. Shapes aren't culled by the painter as their size depends on the view.
+    BillboardFixedSize,
+    /// Shapes will only rotate around the y axis of their transform to face the camera, staying upright when
+    /// the camera looks down on them, e.g. health bars above characters. Lines billboard as with [`Alignment::Billboard`].
+    BillboardY,
 }
 
 impl From<Alignment> for u32 {
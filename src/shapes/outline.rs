@@ -0,0 +1,137 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{ShapeComponent, ShapeData, OUTLINE_HANDLE},
+};
+
+/// Render layer that [`Outlined`] entities are drawn to the mask on by default.
+pub const DEFAULT_OUTLINE_LAYER: usize = 31;
+
+/// Component containing the data for drawing a screen space outline around each [`Outlined`] entity seen by a camera.
+///
+/// The silhouettes of outlined entities are drawn to a mask by a camera spawned as a child of `camera`,
+/// the outline is then stroked around the mask in front of the rest of the scene.
+/// Only supported for 3D cameras, the outline's thickness is always measured in pixels.
+#[derive(Component, Reflect)]
+pub struct OutlineComponent {
+    /// Camera whose view the outline is drawn over.
+    pub camera: Entity,
+    /// Render layer that outlined entities are drawn to the mask on, see [`Outlined::layer`].
+    pub layer: usize,
+    /// Camera drawing the mask, spawned by [`update_outline_masks`].
+    #[reflect(ignore)]
+    pub(crate) mask: Option<Entity>,
+}
+
+impl OutlineComponent {
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            layer: DEFAULT_OUTLINE_LAYER,
+            mask: None,
+        }
+    }
+}
+
+impl ShapeComponent for OutlineComponent {
+    type Data = OutlineData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> OutlineData {
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, _) => thickness,
+            FillType::Fill => 1.0,
+        };
+
+        OutlineData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: 0,
+
+            padding: default(),
+        }
+    }
+}
+
+/// Raw data sent to the outline shader to draw an outline.
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct OutlineData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    padding: [f32; 2],
+}
+
+impl ShapeData for OutlineData {
+    type Component = OutlineComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        OUTLINE_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of outline bundles.
+pub trait OutlineBundle {
+    fn outline(config: &ShapeConfig, camera: Entity) -> Self;
+}
+
+impl OutlineBundle for ShapeBundle<OutlineComponent> {
+    fn outline(config: &ShapeConfig, camera: Entity) -> Self {
+        let mut bundle = Self::new(config, OutlineComponent::new(camera));
+        bundle.fill.ty = FillType::Stroke(config.thickness, ThicknessType::Pixels);
+        bundle
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of outline entities.
+pub trait OutlineSpawner<'w> {
+    /// Spawn an outline around each [`Outlined`] entity seen by `camera`, using the config's color and thickness in pixels.
+    fn outline(&mut self, camera: Entity) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> OutlineSpawner<'w> for T {
+    fn outline(&mut self, camera: Entity) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::outline(self.config(), camera))
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Points further than this fraction of a path's extent from it's plane are not considered coplanar.
+const PLANE_TOLERANCE: f32 = 0.0001;
+
+/// Find a transform whose local xy plane contains every point, `None` if the points are not coplanar.
+///
+/// Collinear points are placed in the plane closest to the local xy plane.
+fn path_plane(points: &[Vec3]) -> Option<Transform> {
+    let origin = points[0];
+    let (axis, extent) = points
+        .iter()
+        .map(|p| *p - origin)
+        .map(|v| (v, v.length()))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if extent <= f32::EPSILON {
+        return None;
+    }
+    let x = axis / extent;
+
+    // Use the point furthest from our axis to determine the plane's normal
+    let normal = points
+        .iter()
+        .map(|p| x.cross(*p - origin))
+        .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+        .filter(|n| n.length() > extent * PLANE_TOLERANCE)
+        .unwrap_or_else(|| Vec3::Z.reject_from_normalized(x))
+        .try_normalize()
+        .unwrap_or_else(|| x.any_orthonormal_vector());
+
+    // Keep the plane facing the same way as the local xy plane where possible
+    let z = if normal.z < 0.0 { -normal } else { normal };
+    if points
+        .iter()
+        .any(|p| z.dot(*p - origin).abs() > extent * PLANE_TOLERANCE)
+    {
+        return None;
+    }
+
+    let rotation = Quat::from_mat3(&Mat3::from_cols(x, z.cross(x), z));
+    Some(Transform::from_translation(origin).with_rotation(rotation))
+}
+
+/// Reorder the points of a closed path to start and end halfway along it's first segment.
+///
+/// The ends of the path then meet on a straight segment, where caps of either type cover one another exactly.
+fn close_path(points: &[Vec2]) -> Vec<Vec2> {
+    let points = &points[..points.len() - 1];
+    let mid = points[0].lerp(points[1], 0.5);
+
+    let mut closed = Vec::with_capacity(points.len() + 2);
+    closed.push(mid);
+    closed.extend_from_slice(&points[1..]);
+    closed.push(points[0]);
+    closed.push(mid);
+    closed
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw connected paths through 3D points.
+pub trait PathPainter {
+    /// Draws a connected strip of segments through the given points, with `cap` at it's ends and `join` between segments.
+    ///
+    /// Paths whose first and last points coincide are closed, joining their last segment to their first without caps.
+    /// Paths within a single plane are drawn as a polyline in that plane so joints have no seams or overlaps,
+    /// other paths are drawn as a line per segment with round caps at each joint.
+    fn path(&mut self, points: &[Vec3]) -> &mut Self;
+}
+
+impl<'w, 's> PathPainter for ShapePainter<'w, 's> {
+    fn path(&mut self, points: &[Vec3]) -> &mut Self {
+        if points.len() < 2 {
+            return self;
+        }
+        let closed =
+            points.len() > 3 && points[0].distance(points[points.len() - 1]) <= f32::EPSILON;
+
+        if let Some(plane) = path_plane(points) {
+            let to_plane = plane.compute_affine().inverse();
+            let flat: Vec<Vec2> = points
+                .iter()
+                .map(|p| to_plane.transform_point3(*p).truncate())
+                .collect();
+            let flat = if closed { close_path(&flat) } else { flat };
+
+            let transform = self.transform;
+            self.transform = transform.mul_transform(plane);
+            self.polyline(&flat);
+            self.transform = transform;
+            return self;
+        }
+
+        // Overlapping round caps stand in for joins between segments that don't share a plane
+        let (cap, end_cap) = (self.cap, self.end_cap);
+        let last = points.len() - 2;
+        for (i, segment) in points.windows(2).enumerate() {
+            self.cap = if i == 0 && !closed { cap } else { Cap::Round };
+            self.end_cap = if i == last && !closed {
+                Some(end_cap.unwrap_or(cap))
+            } else {
+                Some(Cap::None)
+            };
+            self.line(segment[0], segment[1]);
+        }
+        self.cap = cap;
+        self.end_cap = end_cap;
+        self
+    }
+}
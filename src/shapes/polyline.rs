@@ -0,0 +1,306 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, POLYLINE_HANDLE},
+};
+
+/// Component containing the data for drawing a polyline.
+///
+/// Polylines are a connected stroke through a list of points in the local xy plane of their transform.
+///
+/// The points are written to [`ShapePolylinePoints`] whenever the component changes, prefer a
+/// [`DynamicPolylineComponent`] for polylines that are modified every frame.
+///
+/// On platforms without storage buffers, such as WebGL2, all polylines share a total of 1024 points,
+/// polylines beyond that limit are not drawn.
+#[derive(Component, Clone, PartialEq, Reflect)]
+pub struct PolylineComponent {
+    pub alignment: Alignment,
+    /// Cap type for the ends of the polyline.
+    pub cap: Cap,
+    /// Join type for the corners between segments.
+    pub join: Join,
+    /// Largest ratio of a miter join's length to the thickness before it is replaced by a bevel.
+    pub miter_limit: f32,
+
+    /// Points to draw the polyline through in world space relative to it's transform.
+    pub points: Vec<Vec2>,
+    /// Scale of the thickness at each point between zero and one, tapering the segments between them.
+    ///
+    /// Points without a scale are drawn at full thickness, so an empty list draws a uniform stroke.
+    pub thickness_scales: Vec<f32>,
+
+    /// Offset of our points in [`ShapePolylinePoints`], assigned during [`PostUpdate`].
+    #[reflect(ignore)]
+    offset: u32,
+}
+
+impl PolylineComponent {
+    pub fn new(config: &ShapeConfig, points: &[Vec2]) -> Self {
+        Self {
+            alignment: config.alignment,
+            cap: config.cap,
+            join: config.join,
+            miter_limit: config.miter_limit,
+
+            points: points.to_vec(),
+            thickness_scales: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    pub fn tapered(config: &ShapeConfig, points: &[Vec2], thickness_scales: &[f32]) -> Self {
+        Self {
+            thickness_scales: thickness_scales.to_vec(),
+            ..Self::new(config, points)
+        }
+    }
+
+    pub(crate) fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub(crate) fn set_offset(&mut self, offset: u32) {
+        self.offset = offset;
+    }
+}
+
+impl Default for PolylineComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+            cap: default(),
+            join: default(),
+            miter_limit: 4.0,
+
+            points: default(),
+            thickness_scales: default(),
+            offset: default(),
+        }
+    }
+}
+
+impl ShapeComponent for PolylineComponent {
+    type Data = PolylineData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> PolylineData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+        flags.set_cap(self.cap);
+        flags.set_join(self.join);
+
+        PolylineData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            offset: self.offset,
+            count: self.points.len() as u32,
+            miter_limit: self.miter_limit,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+            bounds: polyline_bounds(&self.points),
+        }
+    }
+}
+
+/// Bounds of the given points, min in xy and max in zw.
+pub(crate) fn polyline_bounds(points: &[Vec2]) -> [f32; 4] {
+    let points = points.iter().copied();
+    let min = points.clone().reduce(Vec2::min).unwrap_or_default();
+    let max = points.reduce(Vec2::max).unwrap_or_default();
+    [min.x, min.y, max.x, max.y]
+}
+
+/// Raw data sent to the polyline shader to draw a polyline.
+///
+/// The points themselves are read from the buffer backing [`ShapePolylinePoints`].
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct PolylineData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    offset: u32,
+    count: u32,
+    miter_limit: f32,
+    dash_pattern: u32,
+    bounds: [f32; 4],
+}
+
+impl PolylineData {
+    /// Builds the data for a polyline through the given points, writing them to `polyline_points` if needed.
+    ///
+    /// See [`PolylineComponent::thickness_scales`] for the scale of the thickness at each point.
+    pub fn new(
+        config: &ShapeConfig,
+        polyline_points: &mut ShapePolylinePoints,
+        points: &[Vec2],
+        thickness_scales: &[f32],
+    ) -> Self {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_cap(config.cap);
+        flags.set_join(config.join);
+
+        PolylineData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            offset: polyline_points.intern(points, thickness_scales),
+            count: points.len() as u32,
+            miter_limit: config.miter_limit,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+            bounds: polyline_bounds(points),
+        }
+    }
+
+    /// Offset of our points in [`ShapePolylinePoints`].
+    pub(crate) fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub(crate) fn set_offset(&mut self, offset: u32) {
+        self.offset = offset;
+    }
+}
+
+impl ShapeData for PolylineData {
+    type Component = PolylineComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Uint32,
+            8 => Uint32,
+            9 => Float32,
+            10 => Uint32,
+            11 => Float32x4,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        POLYLINE_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw polylines.
+///
+/// Polylines drawn each frame share [`ShapePolylinePoints`] with every other polyline, see [`PolylineComponent`]
+/// for the limit on the total number of points on platforms without storage buffers.
+pub trait PolylinePainter {
+    fn polyline(&mut self, points: &[Vec2]) -> &mut Self;
+    /// Draw a polyline whose thickness is scaled at each point, see [`PolylineComponent::thickness_scales`].
+    fn tapered_polyline(&mut self, points: &[Vec2], thickness_scales: &[f32]) -> &mut Self;
+}
+
+impl<'w, 's> PolylinePainter for ShapePainter<'w, 's> {
+    fn polyline(&mut self, points: &[Vec2]) -> &mut Self {
+        self.tapered_polyline(points, &[])
+    }
+
+    fn tapered_polyline(&mut self, points: &[Vec2], thickness_scales: &[f32]) -> &mut Self {
+        if points.len() < 2 {
+            return self;
+        }
+        let (config, polyline_points) = self.polyline_points();
+        let data = PolylineData::new(config, polyline_points, points, thickness_scales);
+        self.send(data)
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of polyline bundles.
+pub trait PolylineBundle {
+    fn polyline(config: &ShapeConfig, points: &[Vec2]) -> Self;
+    fn tapered_polyline(config: &ShapeConfig, points: &[Vec2], thickness_scales: &[f32]) -> Self;
+}
+
+impl PolylineBundle for ShapeBundle<PolylineComponent> {
+    fn polyline(config: &ShapeConfig, points: &[Vec2]) -> Self {
+        Self::tapered_polyline(config, points, &[])
+    }
+
+    fn tapered_polyline(config: &ShapeConfig, points: &[Vec2], thickness_scales: &[f32]) -> Self {
+        let mut bundle = Self::new(
+            config,
+            PolylineComponent::tapered(config, points, thickness_scales),
+        );
+        bundle.fill.ty = FillType::Stroke(config.thickness, config.thickness_type);
+        bundle
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of polyline entities.
+pub trait PolylineSpawner<'w> {
+    fn polyline(&mut self, points: &[Vec2]) -> ShapeEntityCommands;
+    fn tapered_polyline(
+        &mut self,
+        points: &[Vec2],
+        thickness_scales: &[f32],
+    ) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> PolylineSpawner<'w> for T {
+    fn polyline(&mut self, points: &[Vec2]) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::polyline(self.config(), points))
+    }
+
+    fn tapered_polyline(
+        &mut self,
+        points: &[Vec2],
+        thickness_scales: &[f32],
+    ) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::tapered_polyline(
+            self.config(),
+            points,
+            thickness_scales,
+        ))
+    }
+}
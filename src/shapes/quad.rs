@@ -0,0 +1,220 @@
+use bevy::{
+    math::{bounding::Aabb3d, vec2},
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, QUAD_HANDLE},
+};
+
+/// Component containing the data for drawing a quad from four arbitrary vertices.
+///
+/// Vertices are connected in order, both convex and concave quads are supported but edges should not cross.
+#[derive(Component, Clone, PartialEq, Reflect)]
+pub struct QuadComponent {
+    pub alignment: Alignment,
+
+    /// Vertices of the quad in winding order.
+    pub vertices: [Vec2; 4],
+    /// Rounding radius of convex corners in world units, the quad is rounded without growing past it's vertices.
+    pub roundness: f32,
+}
+
+impl QuadComponent {
+    pub fn new(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> Self {
+        Self {
+            alignment: config.alignment,
+
+            vertices: [v_a, v_b, v_c, v_d],
+            roundness: config.roundness,
+        }
+    }
+}
+
+impl ShapeComponent for QuadComponent {
+    type Data = QuadData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> QuadData {
+        let mut flags = Flags(0);
+        let thickness = match fill.ty {
+            FillType::Stroke(thickness, thickness_type) => {
+                flags.set_thickness_type(thickness_type);
+                flags.set_hollow(1);
+                thickness
+            }
+            FillType::Fill => 1.0,
+        };
+        flags.set_alignment(self.alignment);
+
+        QuadData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness,
+            flags: flags.0,
+
+            vertices: self.vertices.map(Into::into),
+            roundness: self.roundness,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+        }
+    }
+}
+
+impl Default for QuadComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            vertices: [
+                vec2(-0.5, -0.5),
+                vec2(0.5, -0.5),
+                vec2(0.5, 0.5),
+                vec2(-0.5, 0.5),
+            ],
+            roundness: 0.0,
+        }
+    }
+}
+
+/// Raw data sent to the quad shader to draw a quad
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct QuadData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    vertices: [[f32; 2]; 4],
+    roundness: f32,
+    dash_pattern: u32,
+}
+
+impl QuadData {
+    pub fn new(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> QuadData {
+        let mut flags = Flags(0);
+        flags.set_thickness_type(config.thickness_type);
+        flags.set_alignment(config.alignment);
+        flags.set_hollow(config.hollow as u32);
+
+        QuadData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: config.thickness,
+            flags: flags.0,
+
+            vertices: [v_a.into(), v_b.into(), v_c.into(), v_d.into()],
+            roundness: config.roundness,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+        }
+    }
+}
+
+impl ShapeData for QuadData {
+    type Component = QuadComponent;
+    const DEPTH_SLICES: bool = true;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x2,
+            8 => Float32x2,
+            9 => Float32x2,
+            10 => Float32x2,
+            11 => Float32,
+            12 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        QUAD_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        // Strokes and rounding are both inset so the quad never extends past it's vertices
+        self.vertices
+            .iter()
+            .map(|v| Vec2::from(*v).length())
+            .reduce(f32::max)
+    }
+
+    fn local_bounds(&self) -> Option<Aabb3d> {
+        let (min, max) = self
+            .vertices
+            .iter()
+            .map(|v| Vec2::from(*v))
+            .fold((Vec2::MAX, Vec2::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        Some(Aabb3d {
+            min: min.extend(0.0).into(),
+            max: max.extend(0.0).into(),
+        })
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw quads.
+pub trait QuadPainter {
+    /// Draw a quad with the given vertices in winding order.
+    fn quad(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> &mut Self;
+}
+
+impl<'w, 's> QuadPainter for ShapePainter<'w, 's> {
+    fn quad(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> &mut Self {
+        self.send_filled(|config| QuadData::new(config, v_a, v_b, v_c, v_d))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of quad bundles.
+pub trait QuadBundle {
+    fn quad(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> Self;
+}
+
+impl QuadBundle for ShapeBundle<QuadComponent> {
+    fn quad(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> Self {
+        Self::new(config, QuadComponent::new(config, v_a, v_b, v_c, v_d))
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of quad entities.
+pub trait QuadSpawner<'w> {
+    /// Spawn a quad with the given vertices in winding order.
+    fn quad(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> QuadSpawner<'w> for T {
+    fn quad(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2, v_d: Vec2) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::quad(self.config(), v_a, v_b, v_c, v_d))
+    }
+}
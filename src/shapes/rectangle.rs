@@ -1,7 +1,9 @@
 use bevy::{
+    math::{bounding::Aabb3d, BVec4A},
     prelude::*,
     reflect::Reflect,
     render::render_resource::{ShaderRef, ShaderType},
+    sprite::Anchor,
 };
 use wgpu::vertex_attr_array;
 
@@ -11,14 +13,28 @@ use crate::{
 };
 
 /// Component containing the data for drawing a rectangle.
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, PartialEq, Reflect)]
 pub struct RectangleComponent {
     pub alignment: Alignment,
 
     /// Size of the rectangle on the x and y axis.
     pub size: Vec2,
+    /// Point of the rectangle placed at the entity's transform, see [`ShapeConfig::rect_anchor`].
+    pub anchor: Anchor,
     /// Corner rounding radius for each corner in world units.
     pub corner_radii: Vec4,
+    /// Corners that are chamfered, cut off diagonally `corner_radii` from the corner, instead of rounded.
+    pub corner_chamfers: BVec4,
+    /// If true rounded corners blend into the sides with continuous curvature, like a squircle, instead of following a circle.
+    pub smooth_corners: bool,
+    /// Region of the shape's texture drawn on the rectangle in uv coordinates, the whole texture if `None`.
+    pub texture_rect: Option<Rect>,
+    /// Size in world units of each repetition of the texture, tiled from the top left of the rectangle, stretched across it if `None`.
+    ///
+    /// Axes of zero stretch the texture along that axis only.
+    pub texture_tile: Option<Vec2>,
+    /// Colors at the top right, top left, bottom left and bottom right corners, interpolated across the rectangle and multiplied with the shape's color.
+    pub corner_colors: Option<[Color; 4]>,
 }
 
 impl RectangleComponent {
@@ -27,7 +43,13 @@ impl RectangleComponent {
             alignment: config.alignment,
 
             size,
+            anchor: config.rect_anchor,
             corner_radii: config.corner_radii,
+            corner_chamfers: config.corner_chamfers,
+            smooth_corners: config.smooth_corners,
+            texture_rect: config.texture_rect,
+            texture_tile: config.texture_tile,
+            corner_colors: config.corner_colors,
         }
     }
 }
@@ -46,16 +68,24 @@ impl ShapeComponent for RectangleComponent {
             FillType::Fill => 1.0,
         };
         flags.set_alignment(self.alignment);
+        flags.set_smooth_corners(self.smooth_corners as u32);
 
         RectData {
-            transform: tf.compute_matrix().to_cols_array_2d(),
+            transform: RectData::anchored(tf.compute_matrix(), self.anchor, self.size)
+                .to_cols_array_2d(),
 
             color: fill.color.to_linear().to_f32_array(),
             thickness,
             flags: flags.0,
 
             size: self.size.into(),
-            corner_radii: self.corner_radii.into(),
+            corner_radii: RectData::pack_corner_radii(self.corner_radii, self.corner_chamfers),
+            texture_rect: RectData::pack_texture_rect(self.texture_rect),
+            corner_colors: RectData::pack_corner_colors(self.corner_colors),
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+            texture_tile: self.texture_tile.unwrap_or_default().into(),
         }
     }
 }
@@ -66,7 +96,13 @@ impl Default for RectangleComponent {
             alignment: default(),
 
             size: Vec2::ONE,
+            anchor: Anchor::Center,
             corner_radii: default(),
+            corner_chamfers: BVec4::FALSE,
+            smooth_corners: false,
+            texture_rect: None,
+            texture_tile: None,
+            corner_colors: None,
         }
     }
 }
@@ -83,6 +119,12 @@ pub struct RectData {
 
     size: [f32; 2],
     corner_radii: [f32; 4],
+    texture_rect: [f32; 4],
+    corner_colors: [[f32; 4]; 4],
+    dash_pattern: u32,
+
+    padding: f32,
+    texture_tile: [f32; 2],
 }
 
 impl RectData {
@@ -91,22 +133,60 @@ impl RectData {
         flags.set_alignment(config.alignment);
         flags.set_thickness_type(config.thickness_type);
         flags.set_hollow(config.hollow as u32);
+        flags.set_smooth_corners(config.smooth_corners as u32);
 
         Self {
-            transform: config.transform.compute_matrix().to_cols_array_2d(),
+            transform: Self::anchored(config.transform.compute_matrix(), config.rect_anchor, size)
+                .to_cols_array_2d(),
 
             color: config.color.to_linear().to_f32_array(),
             thickness: config.thickness,
             flags: flags.0,
 
             size: size.into(),
-            corner_radii: config.corner_radii.into(),
+            corner_radii: Self::pack_corner_radii(config.corner_radii, config.corner_chamfers),
+            texture_rect: Self::pack_texture_rect(config.texture_rect),
+            corner_colors: Self::pack_corner_colors(config.corner_colors),
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+            texture_tile: config.texture_tile.unwrap_or_default().into(),
+        }
+    }
+
+    /// Offsets the transform of a rectangle so that the given anchor is placed at it's origin.
+    fn anchored(matrix: Mat4, anchor: Anchor, size: Vec2) -> Mat4 {
+        if anchor == Anchor::Center {
+            return matrix;
         }
+        matrix * Mat4::from_translation((-anchor.as_vec() * size).extend(0.0))
+    }
+
+    /// Chamfered corners are sent as negative radii, as there is no space left in the flags or vertex attributes.
+    fn pack_corner_radii(radii: Vec4, chamfers: BVec4) -> [f32; 4] {
+        Vec4::select(
+            BVec4A::from_array(chamfers.into()),
+            -radii.abs(),
+            radii.abs(),
+        )
+        .into()
+    }
+
+    fn pack_texture_rect(rect: Option<Rect>) -> [f32; 4] {
+        let rect = rect.unwrap_or(Rect::new(0.0, 0.0, 1.0, 1.0));
+        [rect.min.x, rect.min.y, rect.max.x, rect.max.y]
+    }
+
+    fn pack_corner_colors(colors: Option<[Color; 4]>) -> [[f32; 4]; 4] {
+        colors.map_or([[1.0; 4]; 4], |colors| {
+            colors.map(|color| color.to_linear().to_f32_array())
+        })
     }
 }
 
 impl ShapeData for RectData {
     type Component = RectangleComponent;
+    const DEPTH_SLICES: bool = true;
 
     fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
         vertex_attr_array![
@@ -119,7 +199,14 @@ impl ShapeData for RectData {
             5 => Float32,
             6 => Uint32,
             7 => Float32x2,
-            8 => Float32x4
+            8 => Float32x4,
+            9 => Float32x4,
+            10 => Float32x4,
+            11 => Float32x4,
+            12 => Float32x4,
+            13 => Float32x4,
+            14 => Uint32,
+            15 => Float32x2,
         ]
         .to_vec()
     }
@@ -131,18 +218,58 @@ impl ShapeData for RectData {
     fn transform(&self) -> Mat4 {
         Mat4::from_cols_array_2d(&self.transform)
     }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        let flags = Flags(self.flags);
+        let stroke = match flags.hollow() {
+            0 => 0.0,
+            _ => flags.stroke_bounds(self.thickness)?,
+        };
+        Some(Vec2::from(self.size).length() / 2.0 + stroke)
+    }
+
+    fn local_bounds(&self) -> Option<Aabb3d> {
+        let flags = Flags(self.flags);
+        let stroke = match flags.hollow() {
+            0 => 0.0,
+            _ => flags.stroke_bounds(self.thickness)?,
+        };
+        let half_size = Vec2::from(self.size).abs() / 2.0 + stroke;
+        Some(Aabb3d::new(Vec3::ZERO, half_size.extend(0.0)))
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
 }
 
 /// Extension trait for [`ShapePainter`] to enable it to draw rectangles.
 pub trait RectPainter {
     fn rect(&mut self, size: Vec2) -> &mut Self;
 
+    /// Draw a white rect sampling `image`, only the region set by [`ShapeConfig::texture_rect`] if any.
     fn image(&mut self, image: Handle<Image>, size: Vec2) -> &mut Self;
+
+    /// Draw the region `src_rect` of a canvas, in pixels from it's top left, into `dst_rect` in local space.
+    ///
+    /// Typically used with [`ShapeConfig::set_canvas`] to compose canvases, the source canvas should have a
+    /// lower camera order than the destination so that it is drawn first and must not be the destination.
+    /// Canvases are sampled in linear color so sRGB and HDR canvases can be mixed, though HDR values
+    /// are clamped when drawn into a canvas without HDR.
+    fn blit_canvas(&mut self, src_canvas: &Canvas, dst_rect: Rect, src_rect: Rect) -> &mut Self;
 }
 
 impl<'w, 's> RectPainter for ShapePainter<'w, 's> {
     fn rect(&mut self, size: Vec2) -> &mut Self {
-        self.send(RectData::new(self.config(), size))
+        self.send_filled(|config| RectData::new(config, size))
     }
 
     fn image(&mut self, image: Handle<Image>, size: Vec2) -> &mut Self {
@@ -152,6 +279,25 @@ impl<'w, 's> RectPainter for ShapePainter<'w, 's> {
         config.hollow = false;
         self.send_with_config(&config, RectData::new(&config, size))
     }
+
+    fn blit_canvas(&mut self, src_canvas: &Canvas, dst_rect: Rect, src_rect: Rect) -> &mut Self {
+        let canvas_size = Vec2::new(src_canvas.width as f32, src_canvas.height as f32);
+        let mut config = self.config().clone();
+        config.texture = Some(src_canvas.image.clone());
+        config.texture_rect = Some(Rect::from_corners(
+            src_rect.min / canvas_size,
+            src_rect.max / canvas_size,
+        ));
+        config.texture_tile = None;
+        config.color = Color::WHITE;
+        config.hollow = false;
+        config.corner_radii = Vec4::ZERO;
+        config.corner_colors = None;
+        config.sdf_view = ShapeSdfView::None;
+        config.edge_softness = 0;
+        config.translate(dst_rect.center().extend(0.0));
+        self.send_with_config(&config, RectData::new(&config, dst_rect.size()))
+    }
 }
 
 /// Extension trait for [`ShapeBundle`] to enable creation of rectangle bundles.
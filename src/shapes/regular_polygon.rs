@@ -11,7 +11,7 @@ use crate::{
 };
 
 /// Component containing the data for drawing a regular polygon.
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, PartialEq, Reflect)]
 pub struct RegularPolygonComponent {
     pub color: Color,
     pub thickness: f32,
@@ -25,6 +25,12 @@ pub struct RegularPolygonComponent {
     pub radius: f32,
     /// Corner rounding radius for all corner in world units.
     pub roundness: f32,
+    /// Angle in radians to rotate the polygon by within it's transform.
+    pub rotation: f32,
+    /// Join type for the outer corners of a hollow polygon.
+    pub join: Join,
+    /// Largest ratio of a miter join's length to the thickness before it is replaced by a bevel.
+    pub miter_limit: f32,
 }
 
 impl RegularPolygonComponent {
@@ -39,6 +45,9 @@ impl RegularPolygonComponent {
             sides,
             radius,
             roundness: config.roundness,
+            rotation: config.ngon_rotation,
+            join: config.join,
+            miter_limit: config.miter_limit,
         }
     }
 }
@@ -57,6 +66,7 @@ impl ShapeComponent for RegularPolygonComponent {
             FillType::Fill => 1.0,
         };
         flags.set_alignment(self.alignment);
+        flags.set_join(self.join);
 
         NgonData {
             transform: tf.compute_matrix().to_cols_array_2d(),
@@ -68,8 +78,9 @@ impl ShapeComponent for RegularPolygonComponent {
             sides: self.sides,
             radius: self.radius,
             roundness: self.roundness,
-
-            padding: default(),
+            rotation: self.rotation,
+            miter_limit: self.miter_limit,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
         }
     }
 }
@@ -86,6 +97,9 @@ impl Default for RegularPolygonComponent {
             sides: 3.0,
             radius: 1.0,
             roundness: 0.0,
+            rotation: 0.0,
+            join: default(),
+            miter_limit: 4.0,
         }
     }
 }
@@ -103,8 +117,9 @@ pub struct NgonData {
     sides: f32,
     radius: f32,
     roundness: f32,
-
-    padding: [f32; 3],
+    rotation: f32,
+    miter_limit: f32,
+    dash_pattern: u32,
 }
 
 impl NgonData {
@@ -113,6 +128,7 @@ impl NgonData {
         flags.set_thickness_type(config.thickness_type);
         flags.set_alignment(config.alignment);
         flags.set_hollow(config.hollow as u32);
+        flags.set_join(config.join);
 
         NgonData {
             transform: config.transform.compute_matrix().to_cols_array_2d(),
@@ -124,14 +140,16 @@ impl NgonData {
             sides,
             radius,
             roundness: config.roundness,
-
-            padding: default(),
+            rotation: config.ngon_rotation,
+            miter_limit: config.miter_limit,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
         }
     }
 }
 
 impl ShapeData for NgonData {
     type Component = RegularPolygonComponent;
+    const DEPTH_SLICES: bool = true;
 
     fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
         vertex_attr_array![
@@ -145,7 +163,10 @@ impl ShapeData for NgonData {
             6 => Uint32,
             7 => Float32,
             8 => Float32,
-            9 => Float32
+            9 => Float32,
+            10 => Float32,
+            11 => Float32,
+            12 => Uint32
         ]
         .to_vec()
     }
@@ -157,6 +178,18 @@ impl ShapeData for NgonData {
     fn transform(&self) -> Mat4 {
         Mat4::from_cols_array_2d(&self.transform)
     }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
 }
 
 /// Extension trait for [`ShapePainter`] to enable it to draw regular polygons.
@@ -166,7 +199,7 @@ pub trait RegularPolygonPainter {
 
 impl<'w, 's> RegularPolygonPainter for ShapePainter<'w, 's> {
     fn ngon(&mut self, sides: f32, radius: f32) -> &mut Self {
-        self.send(NgonData::new(self.config(), sides, radius))
+        self.send_filled(|config| NgonData::new(config, sides, radius))
     }
 }
 
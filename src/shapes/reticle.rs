@@ -0,0 +1,149 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Parameters of a crosshair drawn by [`ReticlePainter::reticle`], sized in the units of the painter's transform.
+///
+/// Reticles are composed of lines for their arms and discs for their dot and circle, drawn with the painter's color,
+/// thickness and cap, so any number of reticles drawn with the same material are drawn in a batch per shape type.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct Reticle {
+    /// Distance from the center to the inner end of each arm.
+    pub gap: f32,
+    /// Length of each arm, zero for no arms.
+    pub length: f32,
+    /// Number of arms spaced evenly around the center, e.g. 4 for a cross.
+    pub arms: u32,
+    /// Angle of the first arm, counter clockwise from the x axis.
+    pub rotation: f32,
+    /// Radius of the dot at the center, zero for no dot.
+    pub dot: f32,
+    /// Radius of the circle around the center, zero for no circle.
+    pub circle: f32,
+    /// Angle left open in the circle around each arm, splitting it into an arc between each pair of arms.
+    pub circle_gap: f32,
+    /// Distance the arms and circle are pushed away from the center, e.g. the spread of a weapon
+    /// growing while moving or firing and recovering over time.
+    pub spread: f32,
+}
+
+impl Default for Reticle {
+    fn default() -> Self {
+        Self {
+            gap: 0.0,
+            length: 0.0,
+            arms: 4,
+            rotation: 0.0,
+            dot: 0.0,
+            circle: 0.0,
+            circle_gap: 0.0,
+            spread: 0.0,
+        }
+    }
+}
+
+impl Reticle {
+    /// Four arms of the given length starting `gap` from the center.
+    pub fn cross(gap: f32, length: f32) -> Self {
+        Self {
+            gap,
+            length,
+            ..default()
+        }
+    }
+
+    /// A single dot at the center.
+    pub fn dot(radius: f32) -> Self {
+        Self {
+            dot: radius,
+            ..default()
+        }
+    }
+
+    /// A circle around the center.
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            circle: radius,
+            ..default()
+        }
+    }
+
+    pub fn with_arms(mut self, arms: u32) -> Self {
+        self.arms = arms;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_dot(mut self, radius: f32) -> Self {
+        self.dot = radius;
+        self
+    }
+
+    pub fn with_circle(mut self, radius: f32) -> Self {
+        self.circle = radius;
+        self
+    }
+
+    pub fn with_circle_gap(mut self, angle: f32) -> Self {
+        self.circle_gap = angle;
+        self
+    }
+
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Direction of each arm in the painter's local xy plane.
+    pub fn arm_directions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (0..self.arms).map(|i| Vec2::from_angle(self.rotation + i as f32 * TAU / self.arms as f32))
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw crosshairs.
+pub trait ReticlePainter {
+    /// Draws a crosshair centered on the painter's transform, see [`Reticle`].
+    ///
+    /// Arms and the circle are drawn with the painter's thickness, the circle is always hollow and the dot always filled.
+    fn reticle(&mut self, reticle: &Reticle) -> &mut Self;
+}
+
+impl<'w, 's> ReticlePainter for ShapePainter<'w, 's> {
+    fn reticle(&mut self, reticle: &Reticle) -> &mut Self {
+        let inner = reticle.gap + reticle.spread;
+        if reticle.length > 0.0 {
+            for direction in reticle.arm_directions() {
+                let direction = direction.extend(0.0);
+                self.line(direction * inner, direction * (inner + reticle.length));
+            }
+        }
+
+        let hollow = self.hollow;
+        if reticle.circle > 0.0 {
+            self.hollow = true;
+            let radius = reticle.circle + reticle.spread;
+            if reticle.circle_gap > 0.0 && reticle.arms > 0 {
+                let step = TAU / reticle.arms as f32;
+                let half_gap = reticle.circle_gap.min(step) / 2.0;
+                for i in 0..reticle.arms {
+                    let start = reticle.rotation + i as f32 * step + half_gap;
+                    self.arc(radius, start, start + step - half_gap * 2.0);
+                }
+            } else {
+                self.circle(radius);
+            }
+        }
+        if reticle.dot > 0.0 {
+            self.hollow = false;
+            self.circle(reticle.dot);
+        }
+        self.hollow = hollow;
+        self
+    }
+}
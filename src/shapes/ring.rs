@@ -0,0 +1,323 @@
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use wgpu::vertex_attr_array;
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, RING_HANDLE},
+};
+
+/// Component containing the data for drawing a ring.
+///
+/// Unlike a hollow disc the width of a ring is given by it's radii rather than thickness,
+/// so it scales with it's transform and ignores [`ThicknessType`] and [`ShapeFill`] type.
+/// Dash patterns are measured in local space around the middle of the ring and cut square.
+///
+/// When `arc` is set only the annular sector between the start and end angles is drawn,
+/// so wedges of a radial menu line up exactly regardless of thickness.
+#[derive(Component, Clone, PartialEq, Reflect)]
+pub struct RingComponent {
+    pub alignment: Alignment,
+    /// Whether to only draw the sector between the start and end angles
+    pub arc: bool,
+
+    /// Radius of the hole in the center of the ring
+    pub inner_radius: f32,
+    /// External radius of the ring
+    pub outer_radius: f32,
+    /// Starting angle for an arc
+    pub start_angle: f32,
+    /// Ending angle for an arc
+    pub end_angle: f32,
+}
+
+impl RingComponent {
+    pub fn new(config: &ShapeConfig, inner_radius: f32, outer_radius: f32) -> Self {
+        Self {
+            alignment: config.alignment,
+            arc: false,
+
+            inner_radius,
+            outer_radius,
+            start_angle: 0.0,
+            end_angle: 0.0,
+        }
+    }
+
+    pub fn arc(
+        config: &ShapeConfig,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        Self {
+            alignment: config.alignment,
+            arc: true,
+
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+        }
+    }
+}
+
+impl Default for RingComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+            arc: false,
+
+            inner_radius: 0.5,
+            outer_radius: 1.0,
+            start_angle: 0.0,
+            end_angle: 0.0,
+        }
+    }
+}
+
+impl ShapeComponent for RingComponent {
+    type Data = RingData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> RingData {
+        let mut flags = Flags(0);
+        flags.set_alignment(self.alignment);
+        flags.set_arc(self.arc as u32);
+
+        RingData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness: 1.0,
+            flags: flags.0,
+
+            inner_radius: self.inner_radius,
+            outer_radius: self.outer_radius,
+            start_angle: self.start_angle,
+            end_angle: self.end_angle,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+        }
+    }
+}
+
+/// Raw data sent to the ring shader to draw a ring
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct RingData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    dash_pattern: u32,
+
+    padding: f32,
+}
+
+impl RingData {
+    pub fn new(config: &ShapeConfig, inner_radius: f32, outer_radius: f32) -> Self {
+        let mut flags = Flags(0);
+        flags.set_alignment(config.alignment);
+
+        RingData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: 1.0,
+            flags: flags.0,
+
+            inner_radius,
+            outer_radius,
+            start_angle: 0.0,
+            end_angle: 0.0,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+        }
+    }
+
+    pub fn arc(
+        config: &ShapeConfig,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        let mut flags = Flags(0);
+        flags.set_alignment(config.alignment);
+        flags.set_arc(true as u32);
+
+        RingData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: 1.0,
+            flags: flags.0,
+
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
+
+            padding: default(),
+        }
+    }
+}
+
+impl ShapeData for RingData {
+    type Component = RingComponent;
+    const DEPTH_SLICES: bool = true;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+            7 => Float32,
+            8 => Float32,
+            9 => Float32,
+            10 => Float32,
+            11 => Uint32,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        RING_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw rings.
+pub trait RingPainter {
+    fn ring(&mut self, inner_radius: f32, outer_radius: f32) -> &mut Self;
+    fn ring_arc(
+        &mut self,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> &mut Self;
+}
+
+impl<'w, 's> RingPainter for ShapePainter<'w, 's> {
+    fn ring(&mut self, inner_radius: f32, outer_radius: f32) -> &mut Self {
+        self.send(RingData::new(self.config(), inner_radius, outer_radius))
+    }
+
+    fn ring_arc(
+        &mut self,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> &mut Self {
+        self.send(RingData::arc(
+            self.config(),
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+        ))
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of ring bundles.
+pub trait RingBundle {
+    fn ring(config: &ShapeConfig, inner_radius: f32, outer_radius: f32) -> Self;
+    fn ring_arc(
+        config: &ShapeConfig,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self;
+}
+
+impl RingBundle for ShapeBundle<RingComponent> {
+    fn ring(config: &ShapeConfig, inner_radius: f32, outer_radius: f32) -> Self {
+        Self::new(
+            config,
+            RingComponent::new(config, inner_radius, outer_radius),
+        )
+    }
+
+    fn ring_arc(
+        config: &ShapeConfig,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        Self::new(
+            config,
+            RingComponent::arc(config, inner_radius, outer_radius, start_angle, end_angle),
+        )
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of ring entities.
+pub trait RingSpawner<'w> {
+    fn ring(&mut self, inner_radius: f32, outer_radius: f32) -> ShapeEntityCommands;
+    fn ring_arc(
+        &mut self,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> RingSpawner<'w> for T {
+    fn ring(&mut self, inner_radius: f32, outer_radius: f32) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::ring(self.config(), inner_radius, outer_radius))
+    }
+
+    fn ring_arc(
+        &mut self,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::ring_arc(
+            self.config(),
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+        ))
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Sample a uniform Catmull-Rom spline passing through each of the given points.
+///
+/// Each span between two points is split into `segments` straight segments, the end points are repeated
+/// so that the curve starts and ends on the first and last points.
+pub fn catmull_rom(points: &[Vec2], segments: u32) -> Vec<Vec2> {
+    if points.len() < 3 || segments == 0 {
+        return points.to_vec();
+    }
+
+    let last = points.len() - 1;
+    let mut sampled = Vec::with_capacity(last * segments as usize + 1);
+    for i in 0..last {
+        let p0 = points[i.saturating_sub(1)];
+        let p1 = points[i];
+        let p2 = points[i + 1];
+        let p3 = points[(i + 2).min(last)];
+
+        for s in 0..segments {
+            let t = s as f32 / segments as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            sampled.push(
+                0.5 * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3),
+            );
+        }
+    }
+    sampled.push(points[last]);
+    sampled
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw smooth splines.
+pub trait SplinePainter {
+    /// Draws a smooth curve through the given points as a polyline, sampled according to `spline_segments`.
+    fn spline(&mut self, points: &[Vec2]) -> &mut Self;
+}
+
+impl<'w, 's> SplinePainter for ShapePainter<'w, 's> {
+    fn spline(&mut self, points: &[Vec2]) -> &mut Self {
+        let sampled = catmull_rom(points, self.spline_segments);
+        self.polyline(&sampled)
+    }
+}
+
+/// Extension trait for [`ShapeBundle`] to enable creation of spline bundles.
+pub trait SplineBundle {
+    /// Creates a polyline bundle through the given points, sampled according to `spline_segments`.
+    fn spline(config: &ShapeConfig, points: &[Vec2]) -> Self;
+}
+
+impl SplineBundle for ShapeBundle<PolylineComponent> {
+    fn spline(config: &ShapeConfig, points: &[Vec2]) -> Self {
+        let sampled = catmull_rom(points, config.spline_segments);
+        Self::polyline(config, &sampled)
+    }
+}
+
+/// Extension trait for [`ShapeSpawner`] to enable spawning of spline entities.
+pub trait SplineSpawner<'w> {
+    fn spline(&mut self, points: &[Vec2]) -> ShapeEntityCommands;
+}
+
+impl<'w, T: ShapeSpawner<'w>> SplineSpawner<'w> for T {
+    fn spline(&mut self, points: &[Vec2]) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::spline(self.config(), points))
+    }
+}
@@ -0,0 +1,631 @@
+use std::f32::consts::FRAC_PI_2;
+
+use ab_glyph::{Font, FontVec, GlyphId, PxScale, ScaleFont};
+use bevy::{
+    image::ImageSampler,
+    prelude::*,
+    reflect::Reflect,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{ShaderRef, ShaderType},
+    },
+    utils::HashMap,
+};
+use wgpu::{vertex_attr_array, Extent3d, TextureDimension, TextureFormat};
+
+use crate::{
+    prelude::*,
+    render::{Flags, ShapeComponent, ShapeData, GLYPH_HANDLE},
+};
+
+/// Height in pixels of a line of text when rendered into a font atlas.
+const ATLAS_FONT_SIZE: f32 = 48.0;
+/// Distance in pixels around the edge of each glyph in a font atlas that distances are stored for.
+const ATLAS_SPREAD: u32 = 6;
+/// Width in pixels of each font atlas.
+const ATLAS_WIDTH: u32 = 512;
+
+/// Reference to a font registered with [`ShapeFonts`], the default refers to the first font registered.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct ShapeFont(u32);
+
+/// A glyph rendered into a font atlas, all lengths are relative to the height of a line.
+#[derive(Debug, Clone, Copy)]
+struct AtlasGlyph {
+    id: GlyphId,
+    advance: f32,
+    /// Bounds relative to the glyph's origin on the baseline and the region of the atlas it occupies, `None` for whitespace.
+    rects: Option<(Rect, Rect)>,
+}
+
+/// A font along with an atlas storing the signed distance to the edges of each of it's glyphs.
+struct FontAtlas {
+    font: FontVec,
+    image: Handle<Image>,
+    glyphs: HashMap<char, AtlasGlyph>,
+}
+
+impl FontAtlas {
+    fn new(
+        images: &mut Assets<Image>,
+        font: FontVec,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Self {
+        let scale = PxScale::from(ATLAS_FONT_SIZE);
+        let scaled = font.as_scaled(scale);
+        let pad = ATLAS_SPREAD as f32;
+
+        let mut glyphs = HashMap::new();
+        let mut bitmaps = Vec::new();
+        for c in chars {
+            let id = font.glyph_id(c);
+            let mut glyph = AtlasGlyph {
+                id,
+                advance: scaled.h_advance(id) / ATLAS_FONT_SIZE,
+                rects: None,
+            };
+
+            if let Some(outlined) = font.outline_glyph(id.with_scale(scale)) {
+                let bounds = outlined.px_bounds();
+                let width = bounds.width() as u32 + 2 * ATLAS_SPREAD;
+                let height = bounds.height() as u32 + 2 * ATLAS_SPREAD;
+
+                let mut coverage = vec![0.0; (width * height) as usize];
+                outlined.draw(|x, y, c| {
+                    coverage[((y + ATLAS_SPREAD) * width + x + ATLAS_SPREAD) as usize] = c;
+                });
+
+                // Glyphs are rasterized with y down, our y axis is up
+                let rect = Rect::new(
+                    (bounds.min.x - pad) / ATLAS_FONT_SIZE,
+                    (-bounds.max.y - pad) / ATLAS_FONT_SIZE,
+                    (bounds.max.x + pad) / ATLAS_FONT_SIZE,
+                    (-bounds.min.y + pad) / ATLAS_FONT_SIZE,
+                );
+                glyph.rects = Some((rect, Rect::default()));
+                bitmaps.push((c, width, height, signed_distances(&coverage, width, height)));
+            }
+            glyphs.insert(c, glyph);
+        }
+
+        // Pack glyphs into rows from the top of the atlas
+        let (mut x, mut y, mut row_height) = (0, 0, 0);
+        let mut positions = Vec::with_capacity(bitmaps.len());
+        for (_, width, height, _) in &bitmaps {
+            if x + width > ATLAS_WIDTH {
+                x = 0;
+                y += row_height;
+                row_height = 0;
+            }
+            positions.push(UVec2::new(x, y));
+            x += width;
+            row_height = row_height.max(*height);
+        }
+        let atlas_height = (y + row_height).max(1);
+
+        let mut data = vec![0; (ATLAS_WIDTH * atlas_height) as usize];
+        let atlas_size = Vec2::new(ATLAS_WIDTH as f32, atlas_height as f32);
+        for ((c, width, height, distances), position) in bitmaps.into_iter().zip(positions) {
+            for row in 0..height {
+                let start = ((position.y + row) * ATLAS_WIDTH + position.x) as usize;
+                let src = (row * width) as usize;
+                data[start..start + width as usize]
+                    .copy_from_slice(&distances[src..src + width as usize]);
+            }
+
+            if let Some((_, uv)) = glyphs.get_mut(&c).and_then(|glyph| glyph.rects.as_mut()) {
+                let min = position.as_vec2();
+                *uv = Rect::from_corners(
+                    min / atlas_size,
+                    (min + UVec2::new(width, height).as_vec2()) / atlas_size,
+                );
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: ATLAS_WIDTH,
+                height: atlas_height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::R8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.sampler = ImageSampler::linear();
+
+        Self {
+            font,
+            image: images.add(image),
+            glyphs,
+        }
+    }
+}
+
+/// Convert the coverage of a glyph into distances from it's edge, mapped such that the edge lies halfway and the inside is above it.
+fn signed_distances(coverage: &[f32], width: u32, height: u32) -> Vec<u8> {
+    let inside: Vec<bool> = coverage.iter().map(|c| *c >= 0.5).collect();
+    let to_inside = squared_distances(&inside, width, height, true);
+    let to_outside = squared_distances(&inside, width, height, false);
+
+    to_inside
+        .iter()
+        .zip(&to_outside)
+        .map(|(to_inside, to_outside)| {
+            // Each pixel is half a pixel from the edge it borders
+            let dist = to_outside.sqrt() - to_inside.sqrt();
+            let dist = dist - dist.signum() * 0.5;
+            let value = 0.5 + dist / (2.0 * ATLAS_SPREAD as f32);
+            (value.clamp(0.0, 1.0) * 255.0) as u8
+        })
+        .collect()
+}
+
+/// Squared distance from each pixel to the nearest pixel whose value in `mask` matches `target`.
+///
+/// Implements the two pass distance transform from "Distance Transforms of Sampled Functions", Felzenszwalb and Huttenlocher.
+fn squared_distances(mask: &[bool], width: u32, height: u32, target: bool) -> Vec<f32> {
+    const FAR: f32 = 1e20;
+    let (width, height) = (width as usize, height as usize);
+    let mut grid: Vec<f32> = mask
+        .iter()
+        .map(|m| if *m == target { 0.0 } else { FAR })
+        .collect();
+
+    let len = width.max(height);
+    let mut f = vec![0.0; len];
+    let mut d = vec![0.0; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.0; len + 1];
+
+    for x in 0..width {
+        for (y, value) in f[..height].iter_mut().enumerate() {
+            *value = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d, &mut v, &mut z);
+        for (y, value) in d[..height].iter().enumerate() {
+            grid[y * width + x] = *value;
+        }
+    }
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&f[..width], &mut d, &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+    grid
+}
+
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    // Intersection of the parabolas rooted at q and p
+    let intersect = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32
+    };
+
+    // Find the lower envelope of the parabolas rooted at each sample
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in 1..f.len() {
+        let mut s = intersect(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersect(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate().take(f.len()) {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let offset = q as f32 - v[k] as f32;
+        *d = offset * offset + f[v[k]];
+    }
+}
+
+/// Resource containing the fonts that text can be drawn with, requires the `text` feature.
+///
+/// Each font is rendered into an atlas of signed distances when registered,
+/// so text can be drawn crisply at any size through the same instanced pipeline as every other shape.
+/// As every [`ShapePainter`] reads this resource it can't be modified from systems that also use a painter.
+#[derive(Resource, Default)]
+pub struct ShapeFonts {
+    fonts: Vec<FontAtlas>,
+}
+
+impl ShapeFonts {
+    /// Register a font from the contents of a TrueType or OpenType file, including the printable ASCII characters.
+    ///
+    /// Returns `None` if the data is not a valid font.
+    pub fn add(&mut self, images: &mut Assets<Image>, data: Vec<u8>) -> Option<ShapeFont> {
+        self.add_with_chars(images, data, ' '..='~')
+    }
+
+    /// Register a font from the contents of a TrueType or OpenType file, including only the given characters.
+    ///
+    /// Characters that aren't included are skipped when drawing text.
+    pub fn add_with_chars(
+        &mut self,
+        images: &mut Assets<Image>,
+        data: Vec<u8>,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Option<ShapeFont> {
+        let font = FontVec::try_from_vec(data).ok()?;
+        self.fonts.push(FontAtlas::new(images, font, chars));
+        Some(ShapeFont(self.fonts.len() as u32 - 1))
+    }
+
+    /// Atlas texture of the given font.
+    pub fn image(&self, font: ShapeFont) -> Option<&Handle<Image>> {
+        self.fonts.get(font.0 as usize).map(|atlas| &atlas.image)
+    }
+
+    /// Lay out the given text, returning the bounds and atlas region of each glyph relative to the text's anchor.
+    fn layout(
+        &self,
+        font: ShapeFont,
+        text: &str,
+        size: f32,
+        anchor: Vec2,
+    ) -> Option<(Handle<Image>, Vec<(Rect, Rect)>)> {
+        let atlas = self.fonts.get(font.0 as usize)?;
+        let scaled = atlas.font.as_scaled(PxScale::from(ATLAS_FONT_SIZE));
+        let ascent = scaled.ascent() / ATLAS_FONT_SIZE;
+        let line_height =
+            (scaled.ascent() - scaled.descent() + scaled.line_gap()) / ATLAS_FONT_SIZE;
+
+        let lines: Vec<(f32, Vec<(f32, &AtlasGlyph)>)> = text
+            .lines()
+            .map(|line| {
+                let mut pen = 0.0;
+                let mut previous: Option<GlyphId> = None;
+                let mut glyphs = Vec::new();
+                for glyph in line.chars().filter_map(|c| atlas.glyphs.get(&c)) {
+                    if let Some(previous) = previous {
+                        pen += scaled.kern(previous, glyph.id) / ATLAS_FONT_SIZE;
+                    }
+                    glyphs.push((pen, glyph));
+                    pen += glyph.advance;
+                    previous = Some(glyph.id);
+                }
+                (pen, glyphs)
+            })
+            .collect();
+
+        // Text spans from the top of the first line to the bottom of the last,
+        // lines are aligned within the widest line according to the horizontal anchor
+        let width = lines.iter().map(|(width, _)| *width).fold(0.0, f32::max);
+        let height = (scaled.ascent() - scaled.descent()) / ATLAS_FONT_SIZE
+            + line_height * (lines.len().max(1) - 1) as f32;
+        let origin = Vec2::new(width * (anchor.x + 0.5), height * (anchor.y - 0.5));
+
+        let mut rects = Vec::new();
+        for (i, (line_width, glyphs)) in lines.iter().enumerate() {
+            let baseline = Vec2::new(
+                (width - line_width) * (anchor.x + 0.5),
+                -ascent - line_height * i as f32,
+            );
+            for (pen, glyph) in glyphs {
+                let Some((bounds, uv)) = glyph.rects else {
+                    continue;
+                };
+                let offset = baseline + Vec2::X * *pen - origin;
+                let bounds = Rect::from_corners(bounds.min + offset, bounds.max + offset);
+                rects.push((Rect::from_corners(bounds.min * size, bounds.max * size), uv));
+            }
+        }
+        Some((atlas.image.clone(), rects))
+    }
+}
+
+/// Resource formatting the numbers drawn by [`TextPainter::number`], requires the `text` feature.
+///
+/// Replace it to group and localize every number drawn without formatting strings at each call site,
+/// e.g. `ShapeNumberFormat::grouped(".", ",")` draws `1234.5` with one decimal as `1.234,5`.
+/// As every [`ShapePainter`] reads this resource it can't be modified from systems that also use a painter.
+#[derive(Resource)]
+pub struct ShapeNumberFormat {
+    format: Box<dyn Fn(f64, usize) -> String + Send + Sync>,
+}
+
+impl ShapeNumberFormat {
+    /// Format numbers with a function of the value and the number of decimals to draw.
+    pub fn new(format: impl Fn(f64, usize) -> String + Send + Sync + 'static) -> Self {
+        Self {
+            format: Box::new(format),
+        }
+    }
+
+    /// Format numbers with thousands separated by `group` and the decimal point replaced by `decimal`.
+    pub fn grouped(group: &str, decimal: &str) -> Self {
+        let (group, decimal) = (group.to_string(), decimal.to_string());
+        Self::new(move |value, decimals| {
+            let formatted = format!("{:.*}", decimals, value.abs());
+            let (integer, fraction) = formatted
+                .split_once('.')
+                .unwrap_or((formatted.as_str(), ""));
+
+            let mut out = String::new();
+            if value.is_sign_negative()
+                && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0')
+            {
+                out.push('-');
+            }
+            for (i, c) in integer.chars().enumerate() {
+                if i > 0 && (integer.len() - i) % 3 == 0 {
+                    out.push_str(&group);
+                }
+                out.push(c);
+            }
+            if !fraction.is_empty() {
+                out.push_str(&decimal);
+                out.push_str(fraction);
+            }
+            out
+        })
+    }
+
+    /// Format the value with the given number of decimals.
+    pub fn format(&self, value: f64, decimals: usize) -> String {
+        (self.format)(value, decimals)
+    }
+}
+
+impl Default for ShapeNumberFormat {
+    fn default() -> Self {
+        Self::new(|value, decimals| format!("{value:.decimals$}"))
+    }
+}
+
+/// Component containing the data for drawing a single glyph of text, requires the `text` feature.
+///
+/// Text is generally drawn with [`TextPainter::text`], which lays out a glyph for each character.
+/// The glyph is drawn from the texture of it's [`ShapeMaterial`] which should be the atlas of a [`ShapeFonts`] font.
+#[derive(Component, Reflect)]
+pub struct GlyphComponent {
+    pub alignment: Alignment,
+
+    /// Bounds of the glyph relative to the origin of it's text.
+    pub rect: Rect,
+    /// Region of the font atlas containing the glyph in uv coordinates.
+    pub atlas_rect: Rect,
+}
+
+impl ShapeComponent for GlyphComponent {
+    type Data = GlyphData;
+
+    fn get_data(&self, tf: &GlobalTransform, fill: &ShapeFill) -> GlyphData {
+        let mut flags = Flags(0);
+        flags.set_alignment(self.alignment);
+
+        GlyphData {
+            transform: tf.compute_matrix().to_cols_array_2d(),
+
+            color: fill.color.to_linear().to_f32_array(),
+            thickness: 0.0,
+            flags: flags.0,
+
+            padding: default(),
+
+            rect: [
+                self.rect.min.x,
+                self.rect.min.y,
+                self.rect.max.x,
+                self.rect.max.y,
+            ],
+            atlas_rect: [
+                self.atlas_rect.min.x,
+                self.atlas_rect.min.y,
+                self.atlas_rect.max.x,
+                self.atlas_rect.max.y,
+            ],
+        }
+    }
+}
+
+impl Default for GlyphComponent {
+    fn default() -> Self {
+        Self {
+            alignment: default(),
+
+            rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            atlas_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// Raw data sent to the glyph shader to draw a single glyph of text
+#[derive(Clone, Copy, Reflect, Default, Debug, ShaderType)]
+#[repr(C)]
+pub struct GlyphData {
+    transform: [[f32; 4]; 4],
+
+    color: [f32; 4],
+    thickness: f32,
+    flags: u32,
+
+    padding: [f32; 2],
+
+    rect: [f32; 4],
+    atlas_rect: [f32; 4],
+}
+
+impl GlyphData {
+    pub fn new(config: &ShapeConfig, rect: Rect, atlas_rect: Rect) -> GlyphData {
+        let mut flags = Flags(0);
+        flags.set_alignment(config.alignment);
+
+        GlyphData {
+            transform: config.transform.compute_matrix().to_cols_array_2d(),
+
+            color: config.color.to_linear().to_f32_array(),
+            thickness: 0.0,
+            flags: flags.0,
+
+            padding: default(),
+
+            rect: [rect.min.x, rect.min.y, rect.max.x, rect.max.y],
+            atlas_rect: [
+                atlas_rect.min.x,
+                atlas_rect.min.y,
+                atlas_rect.max.x,
+                atlas_rect.max.y,
+            ],
+        }
+    }
+}
+
+impl ShapeData for GlyphData {
+    type Component = GlyphComponent;
+
+    fn vertex_layout() -> Vec<wgpu::VertexAttribute> {
+        vertex_attr_array![
+            0 => Float32x4,
+            1 => Float32x4,
+            2 => Float32x4,
+            3 => Float32x4,
+
+            4 => Float32x4,
+            5 => Float32,
+            6 => Uint32,
+
+            7 => Float32x4,
+            8 => Float32x4,
+        ]
+        .to_vec()
+    }
+
+    fn shader() -> ShaderRef {
+        GLYPH_HANDLE.into()
+    }
+
+    fn transform(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.transform)
+    }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn bounding_radius(&self) -> Option<f32> {
+        let [min_x, min_y, max_x, max_y] = self.rect;
+        Some(Vec2::new(min_x.abs().max(max_x.abs()), min_y.abs().max(max_y.abs())).length())
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Extension trait for [`ShapePainter`] to enable it to draw text, requires the `text` feature.
+pub trait TextPainter {
+    /// Draws text with the font, size and anchor of the painter's config, see [`ShapeConfig::font`].
+    ///
+    /// Lines are separated by `\n`, characters missing from the font are skipped.
+    /// Text is always filled and is drawn with the font's atlas in place of the painter's texture.
+    fn text(&mut self, text: &str) -> &mut Self;
+
+    /// Draws a number formatted by the [`ShapeNumberFormat`] resource with the painter's [`ShapeConfig::decimals`].
+    fn number(&mut self, value: f64) -> &mut Self;
+
+    /// Draws text curved along a path through the given points in the painter's local xy plane, e.g. a sampled bezier.
+    ///
+    /// Each glyph is rotated to follow the path at it's center, beyond the ends of the path glyphs continue along the first
+    /// and last segments. The horizontal text anchor sets where along the path the text is placed, from it's start to it's end,
+    /// and the vertical anchor sets whether the text sits above, on or below the path.
+    fn text_path(&mut self, text: &str, points: &[Vec2]) -> &mut Self;
+
+    /// Draws text curved around a circle of the given radius, with it's anchor at `angle` counter clockwise from the x axis.
+    ///
+    /// Text runs clockwise with the tops of the glyphs facing away from the center, e.g. labels around the top of a gauge.
+    /// With a negative radius the text runs counter clockwise with the tops of the glyphs facing the center instead,
+    /// so labels around the bottom of a gauge read left to right.
+    fn text_arc(&mut self, text: &str, radius: f32, angle: f32) -> &mut Self;
+}
+
+impl<'w, 's> TextPainter for ShapePainter<'w, 's> {
+    fn text(&mut self, text: &str) -> &mut Self {
+        self.text_along(text, |distance| (Vec2::X * distance, 0.0))
+    }
+
+    fn number(&mut self, value: f64) -> &mut Self {
+        let text = self.number_format().format(value, self.decimals);
+        self.text(&text)
+    }
+
+    fn text_path(&mut self, text: &str, points: &[Vec2]) -> &mut Self {
+        if points.len() < 2 {
+            return self;
+        }
+        let mut lengths = vec![0.0];
+        for segment in points.windows(2) {
+            lengths.push(lengths[lengths.len() - 1] + segment[0].distance(segment[1]));
+        }
+        let length = lengths[lengths.len() - 1];
+        let start = length * (self.text_anchor.as_vec().x + 0.5);
+
+        self.text_along(text, |distance| {
+            let distance = start + distance;
+            // Find the segment containing the distance, extending the first and last segments beyond the ends
+            let i = lengths[1..lengths.len() - 1]
+                .iter()
+                .take_while(|l| **l < distance)
+                .count();
+            let (a, b) = (points[i], points[i + 1]);
+            let direction = (b - a).normalize_or(Vec2::X);
+            let position = a + direction * (distance - lengths[i]);
+            (position, direction.to_angle())
+        })
+    }
+
+    fn text_arc(&mut self, text: &str, radius: f32, angle: f32) -> &mut Self {
+        if radius == 0.0 {
+            return self;
+        }
+        let side = radius.signum();
+        self.text_along(text, |distance| {
+            let angle = angle - distance / radius;
+            let position = Vec2::from_angle(angle) * radius.abs();
+            (position, angle - side * FRAC_PI_2)
+        })
+    }
+}
+
+impl<'w, 's> ShapePainter<'w, 's> {
+    /// Draws text with each glyph placed and rotated by `place` from the distance of it's center from the text's anchor.
+    fn text_along(&mut self, text: &str, place: impl Fn(f32) -> (Vec2, f32)) -> &mut Self {
+        let font = self.font.unwrap_or_default();
+        let Some((image, glyphs)) =
+            self.fonts()
+                .layout(font, text, self.font_size, self.text_anchor.as_vec())
+        else {
+            warn_once!("Text drawn before any font was registered with ShapeFonts");
+            return self;
+        };
+
+        let mut config = self.config().clone();
+        config.texture = Some(image);
+        let transform = config.transform;
+        for (rect, atlas_rect) in glyphs {
+            let center = rect.center().x;
+            let (position, rotation) = place(center);
+            config.transform = transform.mul_transform(
+                Transform::from_translation(position.extend(0.0))
+                    .with_rotation(Quat::from_rotation_z(rotation)),
+            );
+            let rect = Rect::from_corners(rect.min - Vec2::X * center, rect.max - Vec2::X * center);
+            self.send_with_config(&config, GlyphData::new(&config, rect, atlas_rect));
+        }
+        self
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
 };
 
 /// Component containing the data for drawing a triangle.
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, PartialEq, Reflect)]
 pub struct TriangleComponent {
     pub color: Color,
     pub thickness: f32,
@@ -21,6 +21,8 @@ pub struct TriangleComponent {
     pub hollow: bool,
     pub vertices: [Vec2; 3],
     pub roundness: f32,
+    /// Colors at each vertex, interpolated across the triangle and multiplied with the shape's color.
+    pub vertex_colors: Option<[Color; 3]>,
 }
 
 impl TriangleComponent {
@@ -33,6 +35,7 @@ impl TriangleComponent {
             hollow: config.hollow,
             vertices: [v_a, v_b, v_c],
             roundness: config.roundness,
+            vertex_colors: config.vertex_colors,
         }
     }
 }
@@ -65,8 +68,11 @@ impl ShapeComponent for TriangleComponent {
                 self.vertices[2].into(),
             ],
             roundness: self.roundness,
+            dash_pattern: fill.dash.map_or(0, DashPattern::packed),
 
             padding: default(),
+
+            vertex_colors: TriangleData::pack_vertex_colors(self.vertex_colors),
         }
     }
 }
@@ -82,6 +88,7 @@ impl Default for TriangleComponent {
 
             vertices: [vec2(0.5, 0.0), vec2(0.0, 0.7), vec2(-0.5, 0.0)],
             roundness: 0.0,
+            vertex_colors: None,
         }
     }
 }
@@ -98,8 +105,11 @@ pub struct TriangleData {
 
     vertices: [[f32; 2]; 3],
     roundness: f32,
+    dash_pattern: u32,
+
+    padding: [f32; 2],
 
-    padding: [f32; 3],
+    vertex_colors: [[f32; 4]; 3],
 }
 
 impl TriangleData {
@@ -117,10 +127,27 @@ impl TriangleData {
             flags: flags.0,
             vertices: [v_a.into(), v_b.into(), v_c.into()],
             roundness: config.roundness,
+            dash_pattern: config.dash.map_or(0, DashPattern::packed),
 
             padding: default(),
+
+            vertex_colors: Self::pack_vertex_colors(config.vertex_colors),
         }
     }
+
+    /// Mark edges shared with neighbouring triangles to be drawn without anti-aliasing, bit `i` being the edge from vertex `i` to the next.
+    pub(crate) fn with_interior_edges(mut self, edges: u32) -> Self {
+        let mut flags = Flags(self.flags);
+        flags.set_interior_edges(edges);
+        self.flags = flags.0;
+        self
+    }
+
+    fn pack_vertex_colors(colors: Option<[Color; 3]>) -> [[f32; 4]; 3] {
+        colors.map_or([[1.0; 4]; 3], |colors| {
+            colors.map(|color| color.to_linear().to_f32_array())
+        })
+    }
 }
 
 impl ShapeData for TriangleData {
@@ -142,6 +169,11 @@ impl ShapeData for TriangleData {
             8 => Float32x2,
             9 => Float32x2,
             10 => Float32,
+            11 => Uint32,
+
+            12 => Float32x4,
+            13 => Float32x4,
+            14 => Float32x4,
         ]
         .to_vec()
     }
@@ -153,37 +185,98 @@ impl ShapeData for TriangleData {
     fn transform(&self) -> Mat4 {
         Mat4::from_cols_array_2d(&self.transform)
     }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform.to_cols_array_2d();
+    }
+
+    fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn set_color(&mut self, color: [f32; 4]) {
+        self.color = color;
+    }
+}
+
+/// Find a transform whose local xy plane contains the given vertices, along with the vertices within that plane.
+fn triangle_plane(v_a: Vec3, v_b: Vec3, v_c: Vec3) -> (Transform, [Vec2; 3]) {
+    // Keep the plane facing the same way as the local xy plane where possible
+    let normal = (v_b - v_a).cross(v_c - v_a).normalize_or(Vec3::Z);
+    let z = if normal.z < 0.0 { -normal } else { normal };
+    let x = (v_b - v_a)
+        .try_normalize()
+        .unwrap_or_else(|| z.any_orthonormal_vector());
+
+    let rotation = Quat::from_mat3(&Mat3::from_cols(x, z.cross(x), z));
+    let to_plane = |v: Vec3| (rotation.inverse() * (v - v_a)).truncate();
+
+    (
+        Transform::from_translation(v_a).with_rotation(rotation),
+        [to_plane(v_a), to_plane(v_b), to_plane(v_c)],
+    )
 }
 
 /// Extension trait for [`ShapePainter`] to enable it to draw triangles.
 pub trait TrianglePainter {
     fn triangle(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> &mut Self;
+
+    /// Draws a triangle between vertices anywhere in the painter's local space, rather than within it's xy plane.
+    ///
+    /// The triangle is drawn flat in the plane containing it's vertices, useful for mesh wireframes and debug geometry.
+    fn triangle_3d(&mut self, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> &mut Self;
 }
 
 impl<'w, 's> TrianglePainter for ShapePainter<'w, 's> {
     fn triangle(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> &mut Self {
-        self.send(TriangleData::new(self.config(), v_a, v_b, v_c))
+        self.send_filled(|config| TriangleData::new(config, v_a, v_b, v_c))
+    }
+
+    fn triangle_3d(&mut self, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> &mut Self {
+        let (plane, [v_a, v_b, v_c]) = triangle_plane(v_a, v_b, v_c);
+
+        let transform = self.transform;
+        self.transform = transform * plane;
+        self.triangle(v_a, v_b, v_c);
+        self.transform = transform;
+        self
     }
 }
 
 /// Extension trait for [`ShapeBundle`] to enable creation of triangle bundles.
 pub trait TriangleBundle {
     fn triangle(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> Self;
+
+    /// Creates a triangle bundle whose transform is rotated into the plane containing the given vertices.
+    fn triangle_3d(config: &ShapeConfig, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> Self;
 }
 
 impl TriangleBundle for ShapeBundle<TriangleComponent> {
     fn triangle(config: &ShapeConfig, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> Self {
         Self::new(config, TriangleComponent::new(config, v_a, v_b, v_c))
     }
+
+    fn triangle_3d(config: &ShapeConfig, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> Self {
+        let (plane, [v_a, v_b, v_c]) = triangle_plane(v_a, v_b, v_c);
+        let mut bundle = Self::triangle(config, v_a, v_b, v_c);
+        bundle.transform = config.transform * plane;
+        bundle
+    }
 }
 
 /// Extension trait for [`ShapeSpawner`] to enable spawning of triangle entities.
 pub trait TriangleSpawner<'w> {
     fn triangle(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> ShapeEntityCommands;
+
+    fn triangle_3d(&mut self, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> ShapeEntityCommands;
 }
 
 impl<'w, T: ShapeSpawner<'w>> TriangleSpawner<'w> for T {
     fn triangle(&mut self, v_a: Vec2, v_b: Vec2, v_c: Vec2) -> ShapeEntityCommands {
         self.spawn_shape(ShapeBundle::triangle(self.config(), v_a, v_b, v_c))
     }
+
+    fn triangle_3d(&mut self, v_a: Vec3, v_b: Vec3, v_c: Vec3) -> ShapeEntityCommands {
+        self.spawn_shape(ShapeBundle::triangle_3d(self.config(), v_a, v_b, v_c))
+    }
 }